@@ -1,6 +1,6 @@
 use quote::quote;
 
-use crate::utils::string_to_ident;
+use crate::utils::{checked_div_down, string_to_ident};
 use crate::DecimalCharacteristics;
 
 pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
@@ -8,6 +8,7 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
         struct_name,
         big_type,
         underlying_type,
+        signed,
         ..
     } = characteristics;
 
@@ -16,6 +17,85 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
     let big_str = &big_type.to_string();
 
     let module_name = string_to_ident("tests_big_ops_", &name_str);
+    let div = checked_div_down(signed);
+
+    let big_div_up = match signed {
+        true => quote!(
+            fn big_div_up(self, rhs: T) -> Self {
+                Self::new(
+                    #big_type::try_from(self.get())
+                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_div_up()", #big_str, #name_str))
+                        .checked_mul(
+                            T::one()
+                        )
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
+                        .checked_div_ceil(
+                            rhs.get()
+                                .try_into()
+                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_div_up()", #big_str, #name_str))
+                        )
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
+                        .try_into()
+                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_div_up()", #underlying_str, #name_str))
+                )
+            }
+        ),
+        false => quote!(
+            fn big_div_up(self, rhs: T) -> Self {
+                Self::new(
+                    #big_type::try_from(self.get())
+                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_div_up()", #big_str, #name_str))
+                        .checked_mul(
+                            T::one()
+                        )
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
+                        .checked_add(
+                            rhs.get()
+                                .try_into()
+                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_div_up()", #big_str, #name_str))
+                                .checked_sub(#big_type::from(1u128)).unwrap()
+                        )
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
+                        .checked_div(
+                            rhs.get()
+                                .try_into().unwrap_or_else(|_| std::panic!("rhs value could not be converted to big type in `big_div_up`")),
+                        )
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
+                        .try_into()
+                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_div_up()", #underlying_str, #name_str))
+                )
+            }
+        ),
+    };
+
+    let checked_big_div_up = match signed {
+        true => quote!(
+            fn checked_big_div_up(self, rhs: T) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div_up: can't convert self to big_type")?
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_big_div_up: (self * rhs::one()) multiplication overflow")?
+                    .checked_div_ceil(
+                        rhs.get().try_into().map_err(|_| "checked_big_div_up: can't convert rhs to big_type")?
+                    ).ok_or_else(|| "checked_big_div_up: ((self * rhs::one()) / rhs) division overflow or division by zero")?
+                    .try_into().map_err(|_| "checked_big_div_up: can't convert to result")?
+                ))
+            }
+        ),
+        false => quote!(
+            fn checked_big_div_up(self, rhs: T) -> std::result::Result<Self, String> {
+                let rhs: #big_type = rhs.get().try_into().map_err(|_| "checked_big_div_up: can't convert rhs to big_type")?;
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div_up: can't convert self to big_type")?
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_big_div_up: (self * rhs::one()) multiplication overflow")?
+                    .checked_add(
+                        rhs.checked_sub(#big_type::from(1u128)).ok_or_else(|| "checked_big_div_up: division by zero")?
+                    ).ok_or_else(|| "checked_big_div_up: ((self * rhs::one()) + (rhs - 1)) addition overflow")?
+                    .checked_div(rhs).ok_or_else(|| "checked_big_div_up: (((self * rhs::one()) + (rhs - 1)) / rhs) division overflow")?
+                    .try_into().map_err(|_| "checked_big_div_up: can't convert to result")?
+                ))
+            }
+        ),
+    };
 
     proc_macro::TokenStream::from(quote!(
         impl<T: Decimal> BigOps<T> for #struct_name
@@ -32,7 +112,7 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
                                 .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_mul()", #big_str, #name_str))
                         )
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul()", #name_str))
-                        .checked_div(
+                        .#div(
                             T::one()
                         )
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul()", #name_str))
@@ -54,7 +134,7 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul_up()", #name_str))
                         .checked_add(T::almost_one())
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul_up()", #name_str))
-                        .checked_div(
+                        .#div(
                             T::one()
                         )
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul_up()", #name_str))
@@ -71,7 +151,7 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
                             T::one()
                         )
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div()", #name_str))
-                        .checked_div(
+                        .#div(
                             rhs.get()
                                 .try_into()
                                 .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_div()", #big_str, #name_str))
@@ -82,30 +162,102 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
                 )
             }
 
-            fn big_div_up(self, rhs: T) -> Self {
+            #big_div_up
+
+            fn checked_big_mul(self, rhs: T) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_mul: can't convert self to big_type")?
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_big_mul: can't convert rhs to big_type")?
+                    ).ok_or_else(|| "checked_big_mul: (self * rhs) multiplication overflow")?
+                    .#div(T::checked_one()?).ok_or_else(|| "checked_big_mul: ((self * rhs) / rhs::one()) division overflow")?
+                    .try_into().map_err(|_| "checked_big_mul: can't convert to result")?
+                ))
+            }
+
+            fn checked_big_mul_up(self, rhs: T) -> std::result::Result<Self, String> {
+                let one: #big_type = T::checked_one()?;
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_mul_up: can't convert self to big_type")?
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_big_mul_up: can't convert rhs to big_type")?
+                    ).ok_or_else(|| "checked_big_mul_up: (self * rhs) multiplication overflow")?
+                    .checked_add(T::almost_one()).ok_or_else(|| "checked_big_mul_up: ((self * rhs) + rhs::almost_one()) addition overflow")?
+                    .#div(one).ok_or_else(|| "checked_big_mul_up: (((self * rhs) + rhs::almost_one()) / rhs::one()) division overflow")?
+                    .try_into().map_err(|_| "checked_big_mul_up: can't convert to result")?
+                ))
+            }
+
+            fn checked_big_div(self, rhs: T) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div: can't convert self to big_type")?
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_big_div: (self * rhs::one()) multiplication overflow")?
+                    .#div(
+                        rhs.get().try_into().map_err(|_| "checked_big_div: can't convert rhs to big_type")?
+                    ).ok_or_else(|| "checked_big_div: ((self * rhs::one()) / rhs) division overflow or division by zero")?
+                    .try_into().map_err(|_| "checked_big_div: can't convert to result")?
+                ))
+            }
+
+            #checked_big_div_up
+
+            fn big_mul_rounded(self, rhs: T, rounding: Rounding) -> Self {
                 Self::new(
                     #big_type::try_from(self.get())
-                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_div_up()", #big_str, #name_str))
+                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_mul_rounded()", #big_str, #name_str))
                         .checked_mul(
-                            T::one()
-                        )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
-                        .checked_add(
                             rhs.get()
                                 .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_div_up()", #big_str, #name_str))
-                                .checked_sub(#big_type::from(1u128)).unwrap()
+                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_mul_rounded()", #big_str, #name_str))
                         )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
-                        .checked_div(
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul_rounded()", #name_str))
+                        .checked_div_rounded(T::one(), rounding)
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_mul_rounded()", #name_str))
+                        .try_into()
+                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_mul_rounded()", #underlying_str, #name_str))
+                )
+            }
+
+            fn big_div_rounded(self, rhs: T, rounding: Rounding) -> Self {
+                Self::new(
+                    #big_type::try_from(self.get())
+                        .unwrap_or_else(|_| std::panic!("decimal: lhs value can't fit into `{}` type in {}::big_div_rounded()", #big_str, #name_str))
+                        .checked_mul(T::one())
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_rounded()", #name_str))
+                        .checked_div_rounded(
                             rhs.get()
-                                .try_into().unwrap_or_else(|_| std::panic!("rhs value could not be converted to big type in `big_div_up`")),
+                                .try_into()
+                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::big_div_rounded()", #big_str, #name_str)),
+                            rounding
                         )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_up()", #name_str))
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::big_div_rounded()", #name_str))
                         .try_into()
-                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_div_up()", #underlying_str, #name_str))
+                        .unwrap_or_else(|_| std::panic!("decimal: overflow casting result to `{}` type in method {}::big_div_rounded()", #underlying_str, #name_str))
                 )
             }
+
+            fn checked_big_mul_rounded(self, rhs: T, rounding: Rounding) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_mul_rounded: can't convert self to big_type")?
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_big_mul_rounded: can't convert rhs to big_type")?
+                    ).ok_or_else(|| "checked_big_mul_rounded: (self * rhs) multiplication overflow")?
+                    .checked_div_rounded(T::checked_one()?, rounding).ok_or_else(|| "checked_big_mul_rounded: ((self * rhs) / rhs::one()) division overflow")?
+                    .try_into().map_err(|_| "checked_big_mul_rounded: can't convert to result")?
+                ))
+            }
+
+            fn checked_big_div_rounded(self, rhs: T, rounding: Rounding) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div_rounded: can't convert self to big_type")?
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_big_div_rounded: (self * rhs::one()) multiplication overflow")?
+                    .checked_div_rounded(
+                        rhs.get().try_into().map_err(|_| "checked_big_div_rounded: can't convert rhs to big_type")?,
+                        rounding
+                    ).ok_or_else(|| "checked_big_div_rounded: ((self * rhs::one()) / rhs) division overflow or division by zero")?
+                    .try_into().map_err(|_| "checked_big_div_rounded: can't convert to result")?
+                ))
+            }
         }
 
         #[cfg(test)]
@@ -139,6 +291,54 @@ pub fn generate_big_ops(characteristics: DecimalCharacteristics) -> proc_macro::
                 let b = #struct_name::new(#struct_name::one());
                 assert_eq!(a.big_div_up(b), #struct_name::new(2));
             }
+
+            #[test]
+            fn test_checked_big_mul() {
+                let a = #struct_name::new(2);
+                let b = #struct_name::new(#struct_name::one());
+                assert_eq!(a.checked_big_mul(b), Ok(#struct_name::new(2)));
+                assert_eq!(a.checked_big_mul_up(b), Ok(#struct_name::new(2)));
+
+                let max = #struct_name::max_instance();
+                let two = #struct_name::from_integer(2);
+                assert_eq!(
+                    max.checked_big_mul(two),
+                    Err("checked_big_mul: can't convert to result".to_string())
+                );
+                assert_eq!(
+                    max.checked_big_mul_up(two),
+                    Err("checked_big_mul_up: can't convert to result".to_string())
+                );
+            }
+
+            #[test]
+            fn test_checked_big_div() {
+                let a = #struct_name::new(2);
+                let b = #struct_name::new(#struct_name::one());
+                assert_eq!(a.checked_big_div(b), Ok(#struct_name::new(2)));
+                assert_eq!(a.checked_big_div_up(b), Ok(#struct_name::new(2)));
+
+                let zero = #struct_name::new(0);
+                assert!(a.checked_big_div(zero).is_err());
+                assert!(a.checked_big_div_up(zero).is_err());
+            }
+
+            #[test]
+            fn test_big_ops_rounded() {
+                let a = #struct_name::new(2);
+                let b = #struct_name::new(#struct_name::one());
+                for rounding in [Rounding::Down, Rounding::Up, Rounding::HalfUp, Rounding::HalfEven] {
+                    assert_eq!(a.big_mul_rounded(b, rounding), a.big_mul(b));
+                    assert_eq!(a.big_div_rounded(b, rounding), a.big_div(b));
+                    assert_eq!(a.checked_big_mul_rounded(b, rounding), Ok(a));
+                    assert_eq!(a.checked_big_div_rounded(b, rounding), Ok(a));
+                }
+
+                let max = #struct_name::max_instance();
+                let two = #struct_name::from_integer(2);
+                assert!(max.checked_big_mul_rounded(two, Rounding::HalfUp).is_err());
+                assert!(a.checked_big_div_rounded(#struct_name::new(0), Rounding::HalfUp).is_err());
+            }
         }
     ))
 }
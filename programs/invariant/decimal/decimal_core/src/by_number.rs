@@ -1,41 +1,35 @@
 use quote::quote;
 
-use crate::utils::string_to_ident;
+use crate::utils::{checked_div_down, string_to_ident};
 use crate::DecimalCharacteristics;
 
 pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
     let DecimalCharacteristics {
         struct_name,
         big_type,
+        signed,
         ..
     } = characteristics;
 
     let name_str = &struct_name.to_string();
 
     let module_name = string_to_ident("tests_by_number_", &name_str);
+    let div = checked_div_down(signed);
 
-    proc_macro::TokenStream::from(quote!(
-        impl ByNumber<#big_type> for #struct_name {
-            fn big_div_by_number(self, rhs: #big_type) -> Self {
+    let big_div_by_number_up = match signed {
+        true => quote!(
+            fn big_div_by_number_up(self, rhs: #big_type) -> Self {
                 Self::new(
                     #big_type::try_from(self.get()).unwrap()
                         .checked_mul(
                             Self::one()
                         ).unwrap()
-                        .checked_div(rhs).unwrap()
+                        .checked_div_ceil(rhs).unwrap()
                         .try_into().unwrap()
                 )
             }
-
-            fn checked_big_div_by_number(self, rhs: #big_type) -> std::result::Result<Self, String> {
-                Ok(Self::new(
-                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div_by_number: can't convert self to big_type")?
-                    .checked_mul(Self::checked_one()?).ok_or_else(|| "checked_big_div_by_number: (self * Self::one()) multiplication overflow")?
-                    .checked_div(rhs).ok_or_else(|| "checked_big_div_by_number: ((self * Self::one()) / rhs) division overflow")?
-                    .try_into().map_err(|_| "checked_big_div_by_number: can't convert to result")?
-                ))
-            }
-
+        ),
+        false => quote!(
             fn big_div_by_number_up(self, rhs: #big_type) -> Self {
                 Self::new(
                     #big_type::try_from(self.get()).unwrap()
@@ -49,7 +43,21 @@ pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro
                         .try_into().unwrap()
                 )
             }
+        ),
+    };
 
+    let checked_big_div_by_number_up = match signed {
+        true => quote!(
+            fn checked_big_div_by_number_up(self, rhs: #big_type) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div_by_number_up: can't convert self to big_type")?
+                    .checked_mul(Self::checked_one()?).ok_or_else(|| "checked_big_div_by_number_up: (self * Self::one()) multiplication overflow")?
+                    .checked_div_ceil(rhs).ok_or_else(|| "checked_big_div_by_number_up: ((self * Self::one()) / rhs) division overflow")?
+                    .try_into().map_err(|_| "checked_big_div_by_number_up: can't convert to result")?
+                ))
+            }
+        ),
+        false => quote!(
             fn checked_big_div_by_number_up(self, rhs: #big_type) -> std::result::Result<Self, String> {
                 Ok(Self::new(
                     #big_type::try_from(self.get()).map_err(|_| "checked_big_div_by_number_up: can't convert self to big_type")?
@@ -61,6 +69,34 @@ pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro
                     .try_into().map_err(|_| "checked_big_div_by_number_up: can't convert to result")?
                 ))
             }
+        ),
+    };
+
+    proc_macro::TokenStream::from(quote!(
+        impl ByNumber<#big_type> for #struct_name {
+            fn big_div_by_number(self, rhs: #big_type) -> Self {
+                Self::new(
+                    #big_type::try_from(self.get()).unwrap()
+                        .checked_mul(
+                            Self::one()
+                        ).unwrap()
+                        .#div(rhs).unwrap()
+                        .try_into().unwrap()
+                )
+            }
+
+            fn checked_big_div_by_number(self, rhs: #big_type) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    #big_type::try_from(self.get()).map_err(|_| "checked_big_div_by_number: can't convert self to big_type")?
+                    .checked_mul(Self::checked_one()?).ok_or_else(|| "checked_big_div_by_number: (self * Self::one()) multiplication overflow")?
+                    .#div(rhs).ok_or_else(|| "checked_big_div_by_number: ((self * Self::one()) / rhs) division overflow")?
+                    .try_into().map_err(|_| "checked_big_div_by_number: can't convert to result")?
+                ))
+            }
+
+            #big_div_by_number_up
+
+            #checked_big_div_by_number_up
         }
 
         impl<T: Decimal> ToValue<T, #big_type> for #struct_name
@@ -74,7 +110,7 @@ pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro
                         rhs.get()
                             .try_into().unwrap_or_else(|_| std::panic!("rhs value could not be converted to big type in `big_mul`")),
                     ).unwrap()
-                    .checked_div(
+                    .#div(
                         T::one()
                     ).unwrap()
             }
@@ -86,7 +122,7 @@ pub fn generate_by_number(characteristics: DecimalCharacteristics) -> proc_macro
                             .try_into().unwrap_or_else(|_| std::panic!("rhs value could not be converted to big type in `big_mul_up`")),
                     ).unwrap()
                     .checked_add(T::almost_one()).unwrap()
-                    .checked_div(
+                    .#div(
                         T::one()
                     ).unwrap()
             }
@@ -1,13 +1,19 @@
 use quote::quote;
 
-use crate::utils::string_to_ident;
+use crate::utils::{checked_div_down, string_to_ident};
 use crate::DecimalCharacteristics;
 
 pub fn generate_checked_ops(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
-    let DecimalCharacteristics { struct_name, .. } = characteristics;
+    let DecimalCharacteristics {
+        struct_name,
+        underlying_type,
+        signed,
+        ..
+    } = characteristics;
 
     let name_str = &struct_name.to_string();
     let module_name = string_to_ident("tests_checked_ops_", &name_str);
+    let div = checked_div_down(signed);
 
     proc_macro::TokenStream::from(quote!(
         impl CheckedOps for #struct_name {
@@ -26,6 +32,31 @@ pub fn generate_checked_ops(characteristics: DecimalCharacteristics) -> proc_mac
             }
         }
 
+        impl<T: Decimal> CheckedMulDiv<T> for #struct_name
+        where
+            T::U: TryInto<#underlying_type>,
+        {
+            fn checked_mul(self, rhs: T) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    self.get()
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_mul: can't convert rhs to underlying type")?
+                    ).ok_or_else(|| "checked_mul: (self * rhs) multiplication overflow")?
+                    .#div(T::checked_one()?).ok_or_else(|| "checked_mul: ((self * rhs) / rhs::one()) division overflow")?
+                ))
+            }
+
+            fn checked_div(self, rhs: T) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    self.get()
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_div: (self * rhs::one()) multiplication overflow")?
+                    .#div(
+                        rhs.get().try_into().map_err(|_| "checked_div: can't convert rhs to underlying type")?
+                    ).ok_or_else(|| "checked_div: ((self * rhs::one()) / rhs) division overflow or division by zero")?
+                ))
+            }
+        }
+
         #[cfg(test)]
         pub mod #module_name {
             use super::*;
@@ -56,11 +87,36 @@ pub fn generate_checked_ops(characteristics: DecimalCharacteristics) -> proc_mac
 
             #[test]
             fn test_underflow_checked_sub() {
-                let min = #struct_name::new(0);
+                let min = #struct_name::new(#underlying_type::MIN);
                 let result = min.checked_sub(#struct_name::new(1));
 
                 assert_eq!(result, Err("checked_sub: (self - rhs) subtraction underflow".to_string()));
             }
+
+            #[test]
+            fn test_checked_mul() {
+                let a = #struct_name::new(2);
+                let b = #struct_name::new(#struct_name::one());
+                assert_eq!(a.checked_mul(b), Ok(#struct_name::new(2)));
+
+                let max = #struct_name::max_instance();
+                assert_eq!(
+                    max.checked_mul(#struct_name::new(2)),
+                    Err("checked_mul: (self * rhs) multiplication overflow".to_string())
+                );
+            }
+
+            #[test]
+            fn test_checked_div() {
+                let a = #struct_name::new(2);
+                let b = #struct_name::new(#struct_name::one());
+                assert_eq!(a.checked_div(b), Ok(#struct_name::new(2)));
+
+                assert_eq!(
+                    a.checked_div(#struct_name::new(0)),
+                    Err("checked_div: ((self * rhs::one()) / rhs) division overflow or division by zero".to_string())
+                );
+            }
         }
     ))
 }
@@ -8,6 +8,7 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
         struct_name,
         underlying_type,
         scale,
+        signed,
         ..
     } = characteristics;
 
@@ -16,6 +17,13 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
 
     let module_name = string_to_ident("tests_factories_", &name_str);
 
+    // values of signed decimals are rounded down toward negative infinity by RoundingDiv,
+    // num_traits division used for unsigned ones takes the divisor by reference
+    let (div, div_rhs) = match signed {
+        true => (quote!(checked_div_floor), quote!()),
+        false => (quote!(checked_div), quote!(&)),
+    };
+
     proc_macro::TokenStream::from(quote!(
 
         impl<T> Factories<T> for #struct_name
@@ -24,6 +32,7 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
             T: TryFrom<u128>,
             T: TryInto<#underlying_type>,
             T: From<u8>,
+            T: RoundingDiv,
             T: num_traits::ops::checked::CheckedDiv,
             T: num_traits::ops::checked::CheckedAdd,
             T: num_traits::ops::checked::CheckedSub
@@ -46,8 +55,8 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
                         base.checked_mul(multiplier.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))).unwrap()
                     } else {
                         let denominator: u128 = 10u128.checked_pow((scale - #scale) as u32).unwrap();
-                         val.checked_div(
-                            &denominator.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))
+                         val.#div(
+                            #div_rhs denominator.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))
                         ).unwrap().try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))
                     }
                 )
@@ -61,8 +70,8 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
                         base.checked_mul(multiplier.try_into().map_err(|_| "checked_from_scale: can't convert to multiplier")?).ok_or_else(|| "checked_from_scale: (multiplier * base) overflow")?
                     } else {
                         let denominator: u128 = 10u128.checked_pow((scale - #scale) as u32).ok_or_else(|| "checked_from_scale: denominator overflow")?;
-                         val.checked_div(
-                            &denominator.try_into().map_err(|_| "checked_from_scale: can't convert to denominator")?
+                         val.#div(
+                            #div_rhs denominator.try_into().map_err(|_| "checked_from_scale: can't convert to denominator")?
                         ).ok_or_else(|| "checked_from_scale: (base / denominator) overflow")?
                         .try_into().map_err(|_| "checked_from_scale: can't convert to result")?
                     }
@@ -82,13 +91,66 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
                         .checked_add(
                             &denominator.checked_sub(&T::from(1u8)).unwrap()
                         ).unwrap()
-                        .checked_div(
-                            &denominator
+                        .#div(
+                            #div_rhs denominator
                         ).unwrap()
                         .try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))
                     }
                 )
             }
+
+            fn checked_from_scale_up(val: T, scale: u8) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    if #scale > scale {
+                        let base: #underlying_type = val.try_into().map_err(|_| "checked_from_scale_up: can't convert to base")?;
+                        let multiplier: u128 = 10u128.checked_pow((#scale - scale) as u32).ok_or_else(|| "checked_from_scale_up: multiplier overflow")?;
+                        base.checked_mul(multiplier.try_into().map_err(|_| "checked_from_scale_up: can't convert to multiplier")?).ok_or_else(|| "checked_from_scale_up: (multiplier * base) overflow")?
+                    } else {
+                        let multiplier: u128 = 10u128.checked_pow((scale - #scale) as u32).ok_or_else(|| "checked_from_scale_up: denominator overflow")?;
+                        let denominator: T = multiplier.try_into().map_err(|_| "checked_from_scale_up: can't convert to denominator")?;
+                        val.checked_add(
+                            &denominator.checked_sub(&T::from(1u8)).ok_or_else(|| "checked_from_scale_up: (denominator - 1) subtraction underflow")?
+                        ).ok_or_else(|| "checked_from_scale_up: (base + (denominator - 1)) addition overflow")?
+                        .#div(
+                            #div_rhs denominator
+                        ).ok_or_else(|| "checked_from_scale_up: ((base + (denominator - 1)) / denominator) overflow")?
+                        .try_into().map_err(|_| "checked_from_scale_up: can't convert to result")?
+                    }
+                ))
+            }
+
+            fn from_scale_rounded(val: T, scale: u8, rounding: Rounding) -> Self {
+                Self::new(
+                    if #scale > scale {
+                        let base: #underlying_type = val.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"));
+                        let multiplier: u128 = 10u128.checked_pow((#scale - scale) as u32).unwrap();
+                        base.checked_mul(multiplier.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))).unwrap()
+                    } else {
+                        let denominator: u128 = 10u128.checked_pow((scale - #scale) as u32).unwrap();
+                        val.checked_div_rounded(
+                            denominator.try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value")),
+                            rounding
+                        ).unwrap().try_into().unwrap_or_else(|_| std::panic!("decimal: can't convert value"))
+                    }
+                )
+            }
+
+            fn checked_from_scale_rounded(val: T, scale: u8, rounding: Rounding) -> std::result::Result<Self, String> {
+                Ok(Self::new(
+                    if #scale > scale {
+                        let base: #underlying_type = val.try_into().map_err(|_| "checked_from_scale_rounded: can't convert to base")?;
+                        let multiplier: u128 = 10u128.checked_pow((#scale - scale) as u32).ok_or_else(|| "checked_from_scale_rounded: multiplier overflow")?;
+                        base.checked_mul(multiplier.try_into().map_err(|_| "checked_from_scale_rounded: can't convert to multiplier")?).ok_or_else(|| "checked_from_scale_rounded: (multiplier * base) overflow")?
+                    } else {
+                        let denominator: u128 = 10u128.checked_pow((scale - #scale) as u32).ok_or_else(|| "checked_from_scale_rounded: denominator overflow")?;
+                        val.checked_div_rounded(
+                            denominator.try_into().map_err(|_| "checked_from_scale_rounded: can't convert to denominator")?,
+                            rounding
+                        ).ok_or_else(|| "checked_from_scale_rounded: (base / denominator) overflow")?
+                        .try_into().map_err(|_| "checked_from_scale_rounded: can't convert to result")?
+                    }
+                ))
+            }
         }
 
         impl<T: Decimal> BetweenDecimals<T> for #struct_name
@@ -106,6 +168,18 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
             fn from_decimal_up(other: T) -> Self {
                 Self::from_scale_up(other.get(), T::scale())
             }
+
+            fn checked_from_decimal_up(other: T) -> std::result::Result<Self, String> {
+                Self::checked_from_scale_up(other.get(), T::scale())
+            }
+
+            fn from_decimal_rounded(other: T, rounding: Rounding) -> Self {
+                Self::from_scale_rounded(other.get(), T::scale(), rounding)
+            }
+
+            fn checked_from_decimal_rounded(other: T, rounding: Rounding) -> std::result::Result<Self, String> {
+                Self::checked_from_scale_rounded(other.get(), T::scale(), rounding)
+            }
         }
 
 
@@ -190,6 +264,55 @@ pub fn generate_factories(characteristics: DecimalCharacteristics) -> proc_macro
                 );
 
             }
+
+            #[test]
+            fn test_from_scale_rounded() {
+                for rounding in [Rounding::Down, Rounding::Up, Rounding::HalfUp, Rounding::HalfEven] {
+                    assert_eq!(
+                        #struct_name::from_scale_rounded(42, #scale, rounding),
+                        #struct_name::new(42)
+                    );
+                    assert_eq!(
+                        #struct_name::checked_from_scale_rounded(42, #scale, rounding).unwrap(),
+                        #struct_name::new(42)
+                    );
+                }
+
+                assert_eq!(#struct_name::from_scale_rounded(45, #scale + 1, Rounding::Down), #struct_name::new(4));
+                assert_eq!(#struct_name::from_scale_rounded(45, #scale + 1, Rounding::Up), #struct_name::new(5));
+                assert_eq!(#struct_name::from_scale_rounded(45, #scale + 1, Rounding::HalfUp), #struct_name::new(5));
+                assert_eq!(#struct_name::from_scale_rounded(45, #scale + 1, Rounding::HalfEven), #struct_name::new(4));
+                assert_eq!(#struct_name::from_scale_rounded(46, #scale + 1, Rounding::HalfEven), #struct_name::new(5));
+            }
+
+            #[test]
+            fn test_checked_from_scale_up() {
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(0, 0).unwrap(),
+                    #struct_name::new(0)
+                );
+
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(0, 3).unwrap(),
+                    #struct_name::new(0)
+                );
+
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(42, #scale).unwrap(),
+                    #struct_name::new(42)
+                );
+
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(42, #scale + 1).unwrap(),
+                    #struct_name::new(5)
+                );
+
+                let max_val = #struct_name::max_value();
+                assert_eq!(
+                    #struct_name::checked_from_scale_up(max_val, #scale + 1).is_err(),
+                    true
+                );
+            }
         }
     ))
 }
@@ -0,0 +1,112 @@
+use quote::quote;
+
+use crate::utils::string_to_ident;
+use crate::DecimalCharacteristics;
+
+pub fn generate_float(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
+    let DecimalCharacteristics {
+        struct_name,
+        underlying_type,
+        scale,
+        signed,
+        ..
+    } = characteristics;
+
+    let name_str = &struct_name.to_string();
+
+    let module_name = string_to_ident("tests_float_", name_str);
+
+    // a negative value would saturate to zero when cast to an unsigned type
+    let check_sign = match signed {
+        true => quote!(),
+        false => quote!(if value < 0.0 {
+            return Err(
+                "checked_from_f64: negative value can't fit into unsigned type".to_string(),
+            );
+        }),
+    };
+
+    let test_sign = match signed {
+        true => quote!(
+            assert_eq!(#struct_name::from_f64(-42f64), #struct_name::from_integer(-42));
+        ),
+        false => quote!(
+            assert!(#struct_name::checked_from_f64(-0.4).is_err());
+            assert!(#struct_name::checked_from_f64(-1f64).is_err());
+        ),
+    };
+
+    // 1.5 can't be represented without decimal places
+    let test_fractional = match scale {
+        0 => quote!(),
+        _ => quote!(
+            assert_eq!(#struct_name::from_scale(15, 1).to_f64(), 1.5f64);
+            assert_eq!(#struct_name::from_f64(1.5f64), #struct_name::from_scale(15, 1));
+        ),
+    };
+
+    proc_macro::TokenStream::from(quote!(
+        impl FloatConversions for #struct_name {
+            fn to_f64(self) -> f64 {
+                // parts are converted separately, so big values keep their fractional digits
+                self.get().checked_div(Self::one()).unwrap() as f64
+                    + self.get().checked_rem(Self::one()).unwrap() as f64 / Self::one::<u128>() as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                Self::checked_from_f64(value)
+                    .unwrap_or_else(|_| std::panic!("decimal: value {} can't fit into `{}` type in {}::from_f64()", value, stringify!(#underlying_type), #name_str))
+            }
+
+            fn checked_from_f64(value: f64) -> std::result::Result<Self, String> {
+                if !value.is_finite() {
+                    return Err("checked_from_f64: value is not finite".to_string());
+                }
+                #check_sign
+                // integer part is scaled exactly, only the fraction is rounded
+                let integer = <#underlying_type as num_traits::cast::FromPrimitive>::from_f64(value.trunc())
+                    .ok_or_else(|| "checked_from_f64: value can't fit into underlying type".to_string())?;
+                // f64 keeps about f64::DIGITS decimal digits, the rest of the scale is applied exactly
+                let digits = u32::from(Self::scale()).min(f64::DIGITS);
+                let multiplier: #underlying_type = 10u128.pow(u32::from(Self::scale()) - digits).try_into()
+                    .map_err(|_| "checked_from_f64: can't convert multiplier to underlying type".to_string())?;
+                let fractional = ((value.fract() * 10f64.powi(digits as i32)).round() as #underlying_type)
+                    .checked_mul(multiplier)
+                    .ok_or_else(|| "checked_from_f64: (fractional * multiplier) multiplication overflow".to_string())?;
+
+                Ok(Self::new(
+                    integer
+                        .checked_mul(Self::one())
+                        .ok_or_else(|| "checked_from_f64: (integer * one) multiplication overflow".to_string())?
+                        .checked_add(fractional)
+                        .ok_or_else(|| "checked_from_f64: (integer + fractional) addition overflow".to_string())?
+                ))
+            }
+        }
+
+        #[cfg(test)]
+        pub mod #module_name {
+            use super::*;
+
+            #[test]
+            fn test_to_f64() {
+                assert_eq!(#struct_name::new(0).to_f64(), 0f64);
+                assert_eq!(#struct_name::from_integer(1).to_f64(), 1f64);
+                assert_eq!(#struct_name::from_integer(42).to_f64(), 42f64);
+                #test_fractional
+            }
+
+            #[test]
+            fn test_from_f64() {
+                assert_eq!(#struct_name::from_f64(0f64), #struct_name::new(0));
+                assert_eq!(#struct_name::from_f64(1f64), #struct_name::from_integer(1));
+                assert_eq!(#struct_name::from_f64(42f64), #struct_name::from_integer(42));
+
+                assert!(#struct_name::checked_from_f64(f64::NAN).is_err());
+                assert!(#struct_name::checked_from_f64(f64::INFINITY).is_err());
+                assert!(#struct_name::checked_from_f64(f64::MAX).is_err());
+                #test_sign
+            }
+        }
+    ))
+}
@@ -6,8 +6,12 @@ mod big_ops;
 mod by_number;
 mod checked_ops;
 mod factories;
+mod float;
+mod num;
 mod ops;
 mod others;
+#[cfg(feature = "serde")]
+mod serde;
 mod structs;
 mod utils;
 
@@ -28,12 +32,6 @@ pub fn decimal(
         Err(_) => 0,
     };
 
-    let big_type = match args.len() {
-        1 => string_to_ident("", "U256"),
-        2 => string_to_ident("", args[1].trim()),
-        _ => std::panic!("decimal: invalid number of parameters"),
-    };
-
     assert!(parsed_scale <= 38, "scale too big");
 
     let k = item.clone();
@@ -42,8 +40,16 @@ pub fn decimal(
     let fields = decimal_struct.fields;
     let first_field = fields.iter().next().unwrap();
 
-    let underlying_type =
-        string_to_ident("", first_field.ty.to_token_stream().to_string().as_str());
+    let underlying_str = first_field.ty.to_token_stream().to_string();
+    let underlying_type = string_to_ident("", underlying_str.as_str());
+    let signed = underlying_str.starts_with('i');
+
+    let big_type = match args.len() {
+        1 if signed => string_to_ident("", "I256"),
+        1 => string_to_ident("", "U256"),
+        2 => string_to_ident("", args[1].trim()),
+        _ => std::panic!("decimal: invalid number of parameters"),
+    };
 
     let field_name = match first_field.ident.clone() {
         Some(ident) => quote! {#ident},
@@ -58,6 +64,7 @@ pub fn decimal(
         underlying_type: underlying_type.clone(),
         big_type: big_type.clone(),
         scale: parsed_scale,
+        signed,
     };
 
     let mut result = proc_macro::TokenStream::from(quote! {
@@ -72,7 +79,11 @@ pub fn decimal(
     result.extend(by_number::generate_by_number(characteristics.clone()));
     result.extend(others::generate_others(characteristics.clone()));
     result.extend(factories::generate_factories(characteristics.clone()));
+    result.extend(float::generate_float(characteristics.clone()));
     result.extend(checked_ops::generate_checked_ops(characteristics.clone()));
+    result.extend(num::generate_num(characteristics.clone()));
+    #[cfg(feature = "serde")]
+    result.extend(serde::generate_serde(characteristics.clone()));
 
     result.extend(proc_macro::TokenStream::from(quote! {
         impl #struct_name {
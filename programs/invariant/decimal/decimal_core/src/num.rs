@@ -0,0 +1,167 @@
+use quote::quote;
+
+use crate::utils::string_to_ident;
+use crate::DecimalCharacteristics;
+
+pub fn generate_num(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
+    let DecimalCharacteristics {
+        struct_name,
+        underlying_type,
+        ..
+    } = characteristics;
+
+    let name_str = &struct_name.to_string();
+
+    let module_name = string_to_ident("tests_num_", name_str);
+
+    proc_macro::TokenStream::from(quote!(
+        impl num_traits::identities::Zero for #struct_name {
+            fn zero() -> Self {
+                Self::new(0)
+            }
+
+            fn is_zero(&self) -> bool {
+                self.get() == 0
+            }
+        }
+
+        impl num_traits::identities::One for #struct_name {
+            fn one() -> Self {
+                Self::from_integer(1)
+            }
+        }
+
+        impl num_traits::bounds::Bounded for #struct_name {
+            fn min_value() -> Self {
+                Self::new(#underlying_type::MIN)
+            }
+
+            fn max_value() -> Self {
+                Self::new(#underlying_type::MAX)
+            }
+        }
+
+        // num_traits methods take arguments by reference, so they delegate to the by value ones
+        impl num_traits::ops::checked::CheckedAdd for #struct_name {
+            fn checked_add(&self, v: &Self) -> Option<Self> {
+                CheckedOps::checked_add(*self, *v).ok()
+            }
+        }
+
+        impl num_traits::ops::checked::CheckedSub for #struct_name {
+            fn checked_sub(&self, v: &Self) -> Option<Self> {
+                CheckedOps::checked_sub(*self, *v).ok()
+            }
+        }
+
+        // same arithmetic as `Mul` and `Div` on the underlying type, so for high scales even
+        // `one * one` overflows, generic code multiplying such values should use the big ops instead
+        impl num_traits::ops::checked::CheckedMul for #struct_name {
+            fn checked_mul(&self, v: &Self) -> Option<Self> {
+                CheckedMulDiv::checked_mul(*self, *v).ok()
+            }
+        }
+
+        impl num_traits::ops::checked::CheckedDiv for #struct_name {
+            fn checked_div(&self, v: &Self) -> Option<Self> {
+                CheckedMulDiv::checked_div(*self, *v).ok()
+            }
+        }
+
+        impl num_traits::ops::saturating::SaturatingAdd for #struct_name {
+            fn saturating_add(&self, v: &Self) -> Self {
+                Self::new(self.get().saturating_add(v.get()))
+            }
+        }
+
+        impl num_traits::ops::saturating::SaturatingSub for #struct_name {
+            fn saturating_sub(&self, v: &Self) -> Self {
+                Self::new(self.get().saturating_sub(v.get()))
+            }
+        }
+
+        impl std::iter::Sum for #struct_name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::new(0), |acc, x| acc + x)
+            }
+        }
+
+        impl<'a> std::iter::Sum<&'a #struct_name> for #struct_name {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::new(0), |acc, x| acc + *x)
+            }
+        }
+
+        // multiplies with `Mul` like CheckedMul above
+        impl std::iter::Product for #struct_name {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::from_integer(1), |acc, x| acc * x)
+            }
+        }
+
+        impl<'a> std::iter::Product<&'a #struct_name> for #struct_name {
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::from_integer(1), |acc, x| acc * *x)
+            }
+        }
+
+        #[cfg(test)]
+        pub mod #module_name {
+            use super::*;
+
+            #[test]
+            fn test_identities() {
+                let zero: #struct_name = num_traits::identities::Zero::zero();
+                let one: #struct_name = num_traits::identities::One::one();
+
+                assert_eq!(zero, #struct_name::new(0));
+                assert!(num_traits::identities::Zero::is_zero(&zero));
+                assert!(!num_traits::identities::Zero::is_zero(&one));
+                assert_eq!(one, #struct_name::from_integer(1));
+                assert_eq!(one.big_mul(one), one);
+            }
+
+            #[test]
+            fn test_bounded() {
+                let min: #struct_name = num_traits::bounds::Bounded::min_value();
+                let max: #struct_name = num_traits::bounds::Bounded::max_value();
+
+                assert_eq!(min, #struct_name::new(#underlying_type::MIN));
+                assert_eq!(max, #struct_name::max_instance());
+            }
+
+            #[test]
+            fn test_checked_and_saturating() {
+                let max = #struct_name::max_instance();
+                let min = #struct_name::new(#underlying_type::MIN);
+                let one = #struct_name::from_integer(1);
+
+                assert_eq!(num_traits::ops::checked::CheckedAdd::checked_add(&one, &one), Some(#struct_name::from_integer(2)));
+                assert_eq!(num_traits::ops::checked::CheckedAdd::checked_add(&max, &one), None);
+                assert_eq!(num_traits::ops::checked::CheckedSub::checked_sub(&min, &one), None);
+                // small values, so the products fit into the underlying type at any scale
+                let two = #struct_name::new(2);
+                assert_eq!(num_traits::ops::checked::CheckedMul::checked_mul(&two, &one), Some(two * one));
+                assert_eq!(num_traits::ops::checked::CheckedMul::checked_mul(&max, &#struct_name::from_integer(2)), None);
+                assert_eq!(num_traits::ops::checked::CheckedDiv::checked_div(&two, &one), Some(two / one));
+                assert_eq!(num_traits::ops::checked::CheckedDiv::checked_div(&two, &#struct_name::new(0)), None);
+
+                assert_eq!(num_traits::ops::saturating::SaturatingAdd::saturating_add(&max, &one), max);
+                assert_eq!(num_traits::ops::saturating::SaturatingSub::saturating_sub(&min, &one), min);
+            }
+
+            #[test]
+            fn test_sum_and_product() {
+                let values = [#struct_name::new(2), #struct_name::from_integer(1)];
+                let sum = #struct_name::new(2) + #struct_name::from_integer(1);
+
+                assert_eq!(values.iter().sum::<#struct_name>(), sum);
+                assert_eq!(values.into_iter().sum::<#struct_name>(), sum);
+                assert_eq!(values.iter().product::<#struct_name>(), #struct_name::new(2));
+                assert_eq!(values.into_iter().product::<#struct_name>(), #struct_name::new(2));
+                assert_eq!(std::iter::empty::<#struct_name>().sum::<#struct_name>(), #struct_name::new(0));
+                assert_eq!(std::iter::empty::<#struct_name>().product::<#struct_name>(), #struct_name::from_integer(1));
+            }
+        }
+    ))
+}
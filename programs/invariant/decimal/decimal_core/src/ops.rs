@@ -1,12 +1,13 @@
 use quote::quote;
 
-use crate::utils::string_to_ident;
+use crate::utils::{checked_div_down, string_to_ident};
 use crate::DecimalCharacteristics;
 
 pub fn generate_ops(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
     let DecimalCharacteristics {
         struct_name,
         underlying_type,
+        signed,
         ..
     } = characteristics;
 
@@ -14,6 +15,7 @@ pub fn generate_ops(characteristics: DecimalCharacteristics) -> proc_macro::Toke
     let underlying_str = &underlying_type.to_string();
 
     let module_name = string_to_ident("tests_", &name_str);
+    let div = checked_div_down(signed);
 
     proc_macro::TokenStream::from(quote!(
         impl std::ops::Add for #struct_name {
@@ -52,7 +54,7 @@ pub fn generate_ops(characteristics: DecimalCharacteristics) -> proc_macro::Toke
                                 .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::mul()", #underlying_str, #name_str))
                         )
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul()", #name_str))
-                        .checked_div(T::one())
+                        .#div(T::one())
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul()", #name_str))
                 )
             }
@@ -69,7 +71,7 @@ pub fn generate_ops(characteristics: DecimalCharacteristics) -> proc_macro::Toke
                     self.get()
                         .checked_mul(T::one())
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div()", #name_str))
-                        .checked_div(
+                        .#div(
                             rhs.get()
                                 .try_into()
                                 .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::div()", #underlying_str, #name_str))
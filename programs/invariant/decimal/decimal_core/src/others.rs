@@ -1,12 +1,13 @@
 use quote::quote;
 
-use crate::utils::string_to_ident;
+use crate::utils::{checked_div_down, string_to_ident};
 use crate::DecimalCharacteristics;
 
 pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::TokenStream {
     let DecimalCharacteristics {
         struct_name,
         underlying_type,
+        signed,
         ..
     } = characteristics;
 
@@ -14,28 +15,25 @@ pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::T
     let underlying_str = &underlying_type.to_string();
 
     let module_name = string_to_ident("tests_others_", &name_str);
+    let div = checked_div_down(signed);
 
-    proc_macro::TokenStream::from(quote!(
-        impl<T: Decimal> Others<T> for #struct_name
-        where
-            T::U: TryInto<#underlying_type>,
-        {
-            fn mul_up(self, rhs: T) -> Self {
+    let div_up = match signed {
+        true => quote!(
+            fn div_up(self, rhs: T) -> Self {
                 Self::new(
                     self.get()
-                        .checked_mul(
+                        .checked_mul(T::one())
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str))
+                        .checked_div_ceil(
                             rhs.get()
                                 .try_into()
-                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::mul_up()", #underlying_str, #name_str))
+                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::div_up()", #underlying_str, #name_str))
                         )
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
-                        .checked_add(T::almost_one())
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
-                        .checked_div(T::one())
-                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str))
                 )
             }
-
+        ),
+        false => quote!(
             fn div_up(self, rhs: T) -> Self {
                 Self::new(
                     self.get()
@@ -57,6 +55,93 @@ pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::T
                         .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::div_up()", #name_str))
                 )
             }
+        ),
+    };
+
+    let checked_div_up = match signed {
+        true => quote!(
+            fn checked_div_up(self, rhs: T) -> std::result::Result<Self, String> {
+                let rhs: #underlying_type = rhs.get().try_into().map_err(|_| "checked_div_up: can't convert rhs to underlying type")?;
+                if rhs == 0 {
+                    return Err("checked_div_up: division by zero".to_string());
+                }
+                Ok(Self::new(
+                    self.get()
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_div_up: (self * rhs::one()) multiplication overflow")?
+                    .checked_div_ceil(rhs).ok_or_else(|| "checked_div_up: ((self * rhs::one()) / rhs) division overflow")?
+                ))
+            }
+        ),
+        false => quote!(
+            fn checked_div_up(self, rhs: T) -> std::result::Result<Self, String> {
+                let rhs: #underlying_type = rhs.get().try_into().map_err(|_| "checked_div_up: can't convert rhs to underlying type")?;
+                Ok(Self::new(
+                    self.get()
+                    .checked_mul(T::checked_one()?).ok_or_else(|| "checked_div_up: (self * rhs::one()) multiplication overflow")?
+                    .checked_add(
+                        rhs.checked_sub(#underlying_type::try_from(1u128).unwrap()).ok_or_else(|| "checked_div_up: division by zero")?
+                    ).ok_or_else(|| "checked_div_up: ((self * rhs::one()) + (rhs - 1)) addition overflow")?
+                    .checked_div(rhs).ok_or_else(|| "checked_div_up: (((self * rhs::one()) + (rhs - 1)) / rhs) division overflow")?
+                ))
+            }
+        ),
+    };
+
+    // magnitude and sign are formatted and parsed separately
+    let (magnitude, sign) = match signed {
+        true => (
+            quote!(self.get().unsigned_abs()),
+            quote!(if self.get() < 0 { "-" } else { "" }),
+        ),
+        false => (quote!(self.get()), quote!("")),
+    };
+
+    let strip_sign = match signed {
+        true => quote!(
+            let (negative, s) = match s.strip_prefix('-') {
+                Some(s) => (true, s),
+                None => (false, s),
+            };
+        ),
+        false => quote!(let negative = false;),
+    };
+
+    proc_macro::TokenStream::from(quote!(
+        impl<T: Decimal> Others<T> for #struct_name
+        where
+            T::U: TryInto<#underlying_type>,
+        {
+            fn mul_up(self, rhs: T) -> Self {
+                Self::new(
+                    self.get()
+                        .checked_mul(
+                            rhs.get()
+                                .try_into()
+                                .unwrap_or_else(|_| std::panic!("decimal: rhs value can't fit into `{}` type in {}::mul_up()", #underlying_str, #name_str))
+                        )
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
+                        .checked_add(T::almost_one())
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
+                        .#div(T::one())
+                        .unwrap_or_else(|| std::panic!("decimal: overflow in method {}::mul_up()", #name_str))
+                )
+            }
+
+            #div_up
+
+            fn checked_mul_up(self, rhs: T) -> std::result::Result<Self, String> {
+                let one: #underlying_type = T::checked_one()?;
+                Ok(Self::new(
+                    self.get()
+                    .checked_mul(
+                        rhs.get().try_into().map_err(|_| "checked_mul_up: can't convert rhs to underlying type")?
+                    ).ok_or_else(|| "checked_mul_up: (self * rhs) multiplication overflow")?
+                    .checked_add(T::almost_one()).ok_or_else(|| "checked_mul_up: ((self * rhs) + rhs::almost_one()) addition overflow")?
+                    .#div(one).ok_or_else(|| "checked_mul_up: (((self * rhs) + rhs::almost_one()) / rhs::one()) division overflow")?
+                ))
+            }
+
+            #checked_div_up
         }
 
         impl OthersSameType for #struct_name {
@@ -67,32 +152,93 @@ pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::T
                     rhs - self
                 }
             }
+
+            // parts have the sign of the value, same as f64::trunc and f64::fract
+            fn integer_part(self) -> Self {
+                Self::new(
+                    self.get()
+                        .checked_div(Self::one())
+                        .unwrap()
+                        .checked_mul(Self::one())
+                        .unwrap()
+                )
+            }
+
+            fn fractional_part(self) -> Self {
+                Self::new(self.get().checked_rem(Self::one()).unwrap())
+            }
         }
 
         impl std::fmt::Display for #struct_name {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let value = #magnitude;
                 if Self::scale() > 0 {
-                    let mut decimal_places = self.get().checked_rem(Self::one()).unwrap();
-                    let mut non_zero_tail = 0;
-
-                    while decimal_places > 0 {
-                        non_zero_tail += 1;
-                        decimal_places /= 10;
-                    }
-
                     write!(
                         f,
-                        "{}.{}{}",
-                        self.get().checked_div(Self::one()).unwrap(),
-                        "0".repeat((Self::scale() - non_zero_tail).into()),
-                        self.get().checked_rem(Self::one()).unwrap()
+                        "{}{}.{:0>width$}",
+                        #sign,
+                        value.checked_div(Self::one()).unwrap(),
+                        value.checked_rem(Self::one()).unwrap(),
+                        width = Self::scale() as usize
                     )
                 } else {
-                    write!(f, "{}", self.get())
+                    write!(f, "{}{}", #sign, value)
                 }
             }
         }
 
+        impl std::str::FromStr for #struct_name {
+            type Err = String;
+
+            fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+                let s = value;
+                #strip_sign
+                let (integer, fraction) = match s.split_once('.') {
+                    Some((integer, fraction)) => (integer, Some(fraction)),
+                    None => (s, None),
+                };
+
+                // only plain digits, integer parsing alone would also accept signs
+                let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+                if !is_number(integer) || !fraction.map_or(true, is_number) {
+                    return Err(format!("decimal: can't parse `{}` into {}", value, #name_str));
+                }
+
+                let fraction = fraction.unwrap_or("");
+                if fraction.len() > Self::scale().into() {
+                    return Err(format!(
+                        "decimal: `{}` has more than {} decimal places allowed in {}",
+                        value,
+                        Self::scale(),
+                        #name_str
+                    ));
+                }
+
+                let overflow = || format!("decimal: value `{}` can't fit into {}", value, #name_str);
+                let integer = integer.parse::<u128>().map_err(|_| overflow())?;
+                let fraction = match fraction.is_empty() {
+                    true => 0u128,
+                    // at most 38 digits, always fits into u128
+                    false => fraction.parse::<u128>().unwrap()
+                        * 10u128.pow(Self::scale() as u32 - fraction.len() as u32),
+                };
+
+                let magnitude = integer
+                    .checked_mul(Self::one())
+                    .and_then(|magnitude| magnitude.checked_add(fraction))
+                    .ok_or_else(overflow)?;
+
+                let result = match negative {
+                    // magnitude of the minimal value is greater than the maximal one
+                    true if magnitude > 0 => i128::try_from(magnitude - 1)
+                        .ok()
+                        .and_then(|magnitude| #underlying_type::try_from(-magnitude - 1).ok()),
+                    _ => #underlying_type::try_from(magnitude).ok(),
+                };
+
+                Ok(Self::new(result.ok_or_else(overflow)?))
+            }
+        }
 
         #[cfg(test)]
         pub mod #module_name {
@@ -112,6 +258,52 @@ pub fn generate_others(characteristics: DecimalCharacteristics) -> proc_macro::T
                 assert_eq!(a.div_up(b), a);
             }
 
+            #[test]
+            fn test_checked_mul_up() {
+                let a = #struct_name::new(1);
+                let b = #struct_name::new(#struct_name::one());
+                assert_eq!(a.checked_mul_up(b), Ok(a));
+
+                let max = #struct_name::max_instance();
+                assert_eq!(
+                    max.checked_mul_up(#struct_name::new(2)),
+                    Err("checked_mul_up: (self * rhs) multiplication overflow".to_string())
+                );
+            }
+
+            #[test]
+            fn test_checked_div_up() {
+                let a = #struct_name::new(1);
+                let b = #struct_name::new(#struct_name::one());
+                assert_eq!(a.checked_div_up(b), Ok(a));
+
+                assert_eq!(
+                    a.checked_div_up(#struct_name::new(0)),
+                    Err("checked_div_up: division by zero".to_string())
+                );
+            }
+
+            #[test]
+            fn test_from_str() {
+                let one = #struct_name::new(#struct_name::one());
+                assert_eq!(one.to_string().parse::<#struct_name>(), Ok(one));
+
+                let max = #struct_name::max_instance();
+                assert_eq!(max.to_string().parse::<#struct_name>(), Ok(max));
+
+                assert!(format!("{}0", max).parse::<#struct_name>().is_err());
+                assert!("".parse::<#struct_name>().is_err());
+                assert!("+1".parse::<#struct_name>().is_err());
+            }
+
+            #[test]
+            fn test_integer_and_fractional_part() {
+                let a = #struct_name::new(#struct_name::max_value());
+                assert_eq!(a.integer_part() + a.fractional_part(), a);
+                assert_eq!(#struct_name::from_integer(3).integer_part(), #struct_name::from_integer(3));
+                assert_eq!(#struct_name::from_integer(3).fractional_part(), #struct_name::new(0));
+            }
+
             #[test]
             fn test_sub_abs() {
                 let a = #struct_name::new(1);
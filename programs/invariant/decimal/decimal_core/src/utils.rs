@@ -1,4 +1,5 @@
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
 use syn::Ident;
 
 pub fn string_to_ident(prefix: &str, name: &str) -> Ident {
@@ -6,3 +7,11 @@ pub fn string_to_ident(prefix: &str, name: &str) -> Ident {
     denominator_const_name.push_str(name);
     Ident::new(denominator_const_name.as_str(), Span::call_site())
 }
+
+// method dividing with rounding down, `/` on signed values rounds toward zero instead
+pub fn checked_div_down(signed: bool) -> TokenStream {
+    match signed {
+        true => quote!(checked_div_floor),
+        false => quote!(checked_div),
+    }
+}
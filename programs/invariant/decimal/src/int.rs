@@ -0,0 +1,200 @@
+//! Large signed int types
+
+use core::convert::TryFrom;
+
+use crate::uint::U256;
+
+// sign and magnitude over U256, it is the default big type of signed decimals since products of
+// i128 values and their denominators don't fit into i128
+// zero is never negative, so derived equality compares values
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct I256 {
+    negative: bool,
+    magnitude: U256,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromI256Error;
+
+impl std::fmt::Display for TryFromI256Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "out of range integral type conversion attempted")
+    }
+}
+
+impl I256 {
+    pub fn from_magnitude(negative: bool, magnitude: U256) -> Self {
+        Self {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.negative
+    }
+
+    pub fn unsigned_abs(self) -> U256 {
+        self.magnitude
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self::from_magnitude(!self.negative, self.magnitude))
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        if self.negative == rhs.negative {
+            return Some(Self::from_magnitude(
+                self.negative,
+                self.magnitude.checked_add(rhs.magnitude)?,
+            ));
+        }
+        // signs differ, the result takes the sign of the bigger magnitude
+        Some(match self.magnitude >= rhs.magnitude {
+            true => Self::from_magnitude(self.negative, self.magnitude - rhs.magnitude),
+            false => Self::from_magnitude(rhs.negative, rhs.magnitude - self.magnitude),
+        })
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Some(Self::from_magnitude(
+            self.negative != rhs.negative,
+            self.magnitude.checked_mul(rhs.magnitude)?,
+        ))
+    }
+
+    // rounds toward zero like `/` on primitive signed integers
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        Some(Self::from_magnitude(
+            self.negative != rhs.negative,
+            self.magnitude.checked_div(rhs.magnitude)?,
+        ))
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for I256 {
+            fn from(value: $t) -> Self {
+                Self::from_magnitude(false, U256::from(value))
+            }
+        }
+
+        impl TryFrom<I256> for $t {
+            type Error = TryFromI256Error;
+
+            fn try_from(value: I256) -> Result<Self, Self::Error> {
+                if value.negative || value.magnitude > U256::from(<$t>::MAX) {
+                    return Err(TryFromI256Error);
+                }
+                Ok(value.magnitude.as_u128() as $t)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for I256 {
+            fn from(value: $t) -> Self {
+                Self::from_magnitude(value < 0, U256::from(value.unsigned_abs()))
+            }
+        }
+
+        impl TryFrom<I256> for $t {
+            type Error = TryFromI256Error;
+
+            fn try_from(value: I256) -> Result<Self, Self::Error> {
+                // the minimum has a magnitude one above the maximum
+                let limit = match value.negative {
+                    true => U256::from(<$t>::MIN.unsigned_abs()),
+                    false => U256::from(<$t>::MAX),
+                };
+                if value.magnitude > limit {
+                    return Err(TryFromI256Error);
+                }
+                let magnitude = value.magnitude.as_u128() as $t;
+                Ok(match value.negative {
+                    true => magnitude.wrapping_neg(),
+                    false => magnitude,
+                })
+            }
+        }
+    )*};
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl From<U256> for I256 {
+    fn from(value: U256) -> Self {
+        Self::from_magnitude(false, value)
+    }
+}
+
+impl TryFrom<I256> for U256 {
+    type Error = TryFromI256Error;
+
+    fn try_from(value: I256) -> Result<Self, Self::Error> {
+        match value.negative {
+            true => Err(TryFromI256Error),
+            false => Ok(value.magnitude),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(I256::from(0i8), I256::from(0u8));
+        assert_eq!(I256::from(-0i64), I256::zero());
+        assert_eq!(i128::try_from(I256::from(i128::MIN)), Ok(i128::MIN));
+        assert_eq!(i128::try_from(I256::from(i128::MAX)), Ok(i128::MAX));
+        assert_eq!(i8::try_from(I256::from(-128)), Ok(-128i8));
+        assert_eq!(i8::try_from(I256::from(-129)), Err(TryFromI256Error));
+        assert_eq!(i8::try_from(I256::from(128)), Err(TryFromI256Error));
+        assert_eq!(u128::try_from(I256::from(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(u128::try_from(I256::from(-1)), Err(TryFromI256Error));
+        assert_eq!(U256::try_from(I256::from(U256::MAX)), Ok(U256::MAX));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (a, b) = (I256::from(-7), I256::from(2));
+
+        assert_eq!(a.checked_add(b), Some(I256::from(-5)));
+        assert_eq!(b.checked_add(a), Some(I256::from(-5)));
+        assert_eq!(a.checked_sub(b), Some(I256::from(-9)));
+        assert_eq!(b.checked_sub(a), Some(I256::from(9)));
+        assert_eq!(a.checked_add(I256::from(7)), Some(I256::zero()));
+        assert_eq!(a.checked_mul(b), Some(I256::from(-14)));
+        assert_eq!(a.checked_mul(a), Some(I256::from(49)));
+        assert_eq!(a.checked_mul(I256::zero()), Some(I256::zero()));
+        assert_eq!(a.checked_div(b), Some(I256::from(-3)));
+        assert_eq!(a.checked_div(I256::zero()), None);
+
+        let max = I256::from(U256::MAX);
+        assert_eq!(max.checked_add(I256::from(1)), None);
+        assert_eq!(max.checked_neg().unwrap().checked_sub(I256::from(1)), None);
+        assert_eq!(max.checked_mul(I256::from(-2)), None);
+        // products of i128 values and 10^38 fit
+        let one = I256::from(10u128.pow(38));
+        let product = I256::from(i128::MIN).checked_mul(one).unwrap();
+        assert_eq!(product.checked_div(one), Some(I256::from(i128::MIN)));
+    }
+}
@@ -1,10 +1,16 @@
+mod int;
+mod rounding;
 mod traits;
 mod uint;
 
+pub use crate::int::{TryFromI256Error, I256};
 pub use crate::uint::U256;
 
 pub use decimal_core::decimal;
 pub use num_traits;
+pub use rounding::*;
+#[cfg(feature = "serde")]
+pub use serde;
 pub use traits::*;
 
 #[cfg(test)]
@@ -24,6 +30,23 @@ struct Q {
 #[derive(Default, std::fmt::Debug, Clone, Copy, PartialEq)]
 struct N(u8);
 
+#[cfg(test)]
+#[decimal(3)]
+#[derive(Default, std::fmt::Debug, Clone, Copy, PartialEq)]
+struct S(i64);
+
+#[cfg(test)]
+#[decimal(2)]
+#[derive(Default, std::fmt::Debug, Clone, Copy, PartialEq)]
+struct I {
+    v: i128,
+}
+
+#[cfg(test)]
+#[decimal(24)]
+#[derive(Default, std::fmt::Debug, Clone, Copy, PartialEq)]
+struct L(i128);
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -275,4 +298,450 @@ pub mod tests {
             assert_eq!(a.div_up(b), Q::new(5));
         }
     }
+
+    #[test]
+    fn test_checked_mul_div() {
+        assert_eq!(R(2000).checked_mul(Q { v: 15 }), Ok(R(3000)));
+        assert_eq!(Q { v: 15 }.checked_mul(N(2)), Ok(Q { v: 30 }));
+        assert_eq!(R(3000).checked_div(Q { v: 15 }), Ok(R(2000)));
+        assert_eq!(N(6).checked_div(Q { v: 20 }), Ok(N(3)));
+
+        // one of rhs doesn't fit into the underlying type
+        assert!(N(1).checked_mul(R(1000)).is_err());
+        assert!(N(1).checked_div(R(1000)).is_err());
+        // rhs doesn't fit into the underlying type
+        assert!(N(1).checked_mul(Q { v: 256 }).is_err());
+        assert!(Q { v: 1 }.checked_div(R(u32::MAX)).is_err());
+        // overflow
+        assert!(N(128).checked_mul(N(2)).is_err());
+        assert!(Q { v: 6554 }.checked_div(Q { v: 10 }).is_err());
+        // division by zero
+        assert!(R(1).checked_div(N(0)).is_err());
+    }
+
+    #[test]
+    fn test_checked_big_ops() {
+        assert_eq!(R(1).checked_big_mul(Q { v: 5 }), Ok(R(0)));
+        assert_eq!(R(1).checked_big_mul_up(Q { v: 5 }), Ok(R(1)));
+        assert_eq!(Q { v: 1 }.checked_big_div(R(3000)), Ok(Q { v: 0 }));
+        assert_eq!(Q { v: 1 }.checked_big_div_up(R(3000)), Ok(Q { v: 1 }));
+
+        // intermediate value exceeds the underlying type but not the big one
+        assert_eq!(N(200).checked_big_mul(Q { v: 11 }), Ok(N(220)));
+        assert_eq!(N(200).checked_big_div(Q { v: 20 }), Ok(N(100)));
+
+        assert_eq!(
+            N(200).checked_big_mul(N(2)),
+            Err("checked_big_mul: can't convert to result".to_string())
+        );
+        assert_eq!(
+            N(200).checked_big_div_up(Q { v: 5 }),
+            Err("checked_big_div_up: can't convert to result".to_string())
+        );
+        assert!(N(1).checked_big_div_up(N(0)).is_err());
+    }
+
+    #[test]
+    fn test_checked_mul_div_up() {
+        assert_eq!(R(1).checked_mul_up(Q { v: 5 }), Ok(R(1)));
+        assert_eq!(R(201).checked_div_up(R(2000)), Ok(R(101)));
+        assert_eq!(Q { v: 42 }.checked_div_up(R(10000)), Ok(Q { v: 5 }));
+
+        assert!(N(128).checked_mul_up(N(2)).is_err());
+        assert!(N(1).checked_mul_up(R(1000)).is_err());
+        assert!(Q { v: 6554 }.checked_div_up(Q { v: 10 }).is_err());
+    }
+
+    #[test]
+    fn test_checked_from_decimal_up() {
+        assert_eq!(R::checked_from_decimal_up(Q { v: 144 }), Ok(R(14400)));
+        assert_eq!(Q::checked_from_decimal_up(R(42)), Ok(Q { v: 1 }));
+        assert_eq!(N::checked_from_decimal_up(Q { v: 144 }), Ok(N(15)));
+
+        assert!(N::checked_from_decimal_up(R(u32::MAX)).is_err());
+        assert!(Q::checked_from_decimal_up(N(255)).is_ok());
+        assert!(R::checked_from_decimal_up(Q { v: u16::MAX }).is_ok());
+    }
+
+    #[test]
+    fn test_signed_rounding() {
+        // -0.001 * 0.5 = -0.0005
+        assert_eq!(S(-1) * Q { v: 5 }, S(-1));
+        assert_eq!(S(-1).mul_up(Q { v: 5 }), S(0));
+        assert_eq!(S(-1).big_mul(Q { v: 5 }), S(-1));
+        assert_eq!(S(-1).big_mul_up(Q { v: 5 }), S(0));
+        assert_eq!(S(-1).checked_mul(Q { v: 5 }), Ok(S(-1)));
+        assert_eq!(S(-1).checked_mul_up(Q { v: 5 }), Ok(S(0)));
+        assert_eq!(S(-1).checked_big_mul(Q { v: 5 }), Ok(S(-1)));
+        assert_eq!(S(-1).checked_big_mul_up(Q { v: 5 }), Ok(S(0)));
+        assert_eq!(S(-1).big_mul_to_value(Q { v: 5 }), I256::from(-1));
+        assert_eq!(S(-1).big_mul_to_value_up(Q { v: 5 }), I256::from(0));
+
+        // 0.001 / -2 = -0.0005
+        let rhs = S(-2000);
+        assert_eq!(S(1) / rhs, S(-1));
+        assert_eq!(S(1).div_up(rhs), S(0));
+        assert_eq!(S(1).big_div(rhs), S(-1));
+        assert_eq!(S(1).big_div_up(rhs), S(0));
+        assert_eq!(S(1).checked_div(rhs), Ok(S(-1)));
+        assert_eq!(S(1).checked_div_up(rhs), Ok(S(0)));
+        assert_eq!(S(1).checked_big_div(rhs), Ok(S(-1)));
+        assert_eq!(S(1).checked_big_div_up(rhs), Ok(S(0)));
+
+        // -0.001 / -2 = 0.0005
+        assert_eq!(S(-1) / rhs, S(0));
+        assert_eq!(S(-1).div_up(rhs), S(1));
+        assert_eq!(S(-1).big_div(rhs), S(0));
+        assert_eq!(S(-1).big_div_up(rhs), S(1));
+
+        // -0.001 / 3 = -0.000333..
+        assert_eq!(S(-1).big_div_by_number(I256::from(3000)), S(-1));
+        assert_eq!(S(-1).big_div_by_number_up(I256::from(3000)), S(0));
+        assert_eq!(S(-1).checked_big_div_by_number(I256::from(3000)), Ok(S(-1)));
+        assert_eq!(
+            S(-1).checked_big_div_by_number_up(I256::from(3000)),
+            Ok(S(0))
+        );
+        assert_eq!(
+            S(1).checked_big_div_by_number_up(I256::from(-3000)),
+            Ok(S(0))
+        );
+
+        // exact values are not rounded
+        assert_eq!(S(-1500) * Q { v: 5 }, S(-750));
+        assert_eq!(S(-1500).mul_up(Q { v: 5 }), S(-750));
+        assert_eq!(S(-1500).div_up(S(-500)), S(3000));
+    }
+
+    #[test]
+    fn test_signed_between_decimals() {
+        assert_eq!(S::from_decimal(I { v: -15 }), S(-150));
+        assert_eq!(I::from_decimal(S(-1555)), I { v: -156 });
+        assert_eq!(I::from_decimal_up(S(-1555)), I { v: -155 });
+        assert_eq!(I::checked_from_decimal(S(-1555)), Ok(I { v: -156 }));
+        assert_eq!(I::checked_from_decimal_up(S(-1555)), Ok(I { v: -155 }));
+        assert_eq!(I::from_decimal(S(1555)), I { v: 155 });
+        assert_eq!(I::from_decimal_up(S(1555)), I { v: 156 });
+
+        assert_eq!(S::from_integer(-2), S(-2000));
+        assert_eq!(S::from_decimal(R(1500)), S(1500));
+        assert!(R::checked_from_decimal(S(-1500)).is_err());
+        assert!(S::checked_from_decimal(I { v: i128::MIN }).is_err());
+    }
+
+    #[test]
+    fn test_signed_big_scale() {
+        // products of scale 24 values overflow i128, big ops go through I256
+        let a = L::from_integer(-3);
+        let b = L::from_integer(2);
+        assert_eq!(a.big_mul(b), L::from_integer(-6));
+        assert_eq!(a.big_mul_up(b), L::from_integer(-6));
+        assert_eq!(a.big_div(b), L::from_scale(-15, 1));
+        assert_eq!(a.big_div_up(b), L::from_scale(-15, 1));
+        assert_eq!(a.checked_big_mul(b), Ok(L::from_integer(-6)));
+        assert_eq!(a.checked_big_div(b), Ok(L::from_scale(-15, 1)));
+
+        // -1 / 3 at scale 24
+        let third = L::from_integer(3);
+        assert_eq!(L::new(-1).big_div(third), L::new(-1));
+        assert_eq!(L::new(-1).big_div_up(third), L::new(0));
+        assert_eq!(L(-1).big_mul_up(L::from_scale(5, 1)), L(0));
+        assert_eq!(L(-1).big_mul(L::from_scale(5, 1)), L(-1));
+
+        assert_eq!(
+            L(i128::MIN).checked_big_mul(b),
+            Err("checked_big_mul: can't convert to result".to_string())
+        );
+    }
+
+    #[test]
+    fn test_signed_ops() {
+        assert_eq!(S(-1000) + S(1500), S(500));
+        assert_eq!(S(500) - S(1500), S(-1000));
+        assert_eq!(S(-1).sub_abs(S(2)), S(3));
+        assert_eq!(S(2).sub_abs(S(-1)), S(3));
+
+        assert!(S(i64::MIN).checked_sub(S(1)).is_err());
+        assert!(S(i64::MAX).checked_add(S(1)).is_err());
+        assert!(S(1).checked_div(S(0)).is_err());
+        assert!(S(1).checked_div_up(S(0)).is_err());
+        assert!(S(1).checked_big_div_up(S(0)).is_err());
+        assert!(S(1).checked_big_div_by_number_up(I256::from(0)).is_err());
+        assert!(S(i64::MIN).checked_mul(S(-1000)).is_err());
+    }
+
+    #[test]
+    fn test_signed_display_and_parse() {
+        assert_eq!(S(-1500).to_string(), "-1.500");
+        assert_eq!(S(-1).to_string(), "-0.001");
+        assert_eq!(S(0).to_string(), "0.000");
+        assert_eq!(S(i64::MIN).to_string(), "-9223372036854775.808");
+        assert_eq!(
+            I { v: i128::MIN }.to_string(),
+            "-1701411834604692317316873037158841057.28"
+        );
+
+        assert_eq!("-1.5".parse::<S>(), Ok(S(-1500)));
+        assert_eq!("-0.001".parse::<S>(), Ok(S(-1)));
+        assert_eq!("-0".parse::<S>(), Ok(S(0)));
+        assert_eq!("-9223372036854775.808".parse::<S>(), Ok(S(i64::MIN)));
+        assert_eq!(
+            "-1701411834604692317316873037158841057.28".parse::<I>(),
+            Ok(I { v: i128::MIN })
+        );
+
+        assert!("-9223372036854775.809".parse::<S>().is_err());
+        assert!("-1701411834604692317316873037158841057.29"
+            .parse::<I>()
+            .is_err());
+        assert!("-".parse::<S>().is_err());
+        assert!("--1".parse::<S>().is_err());
+        assert!("+1".parse::<S>().is_err());
+        assert!("-0.0001".parse::<S>().is_err());
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        // 0.015, 0.025, 0.026 and 1.05 to one decimal place
+        let values = [R(15), R(25), R(26), R(1050)];
+        let expected = [
+            (Rounding::Down, [0, 0, 0, 10]),
+            (Rounding::Up, [1, 1, 1, 11]),
+            (Rounding::HalfUp, [0, 0, 0, 11]),
+            (Rounding::HalfEven, [0, 0, 0, 10]),
+        ];
+        for (rounding, results) in expected {
+            for (value, result) in values.iter().zip(results) {
+                assert_eq!(Q::from_decimal_rounded(*value, rounding), Q { v: result });
+                assert_eq!(
+                    Q::checked_from_decimal_rounded(*value, rounding),
+                    Ok(Q { v: result })
+                );
+            }
+        }
+
+        assert_eq!(N::from_decimal_rounded(Q { v: 25 }, Rounding::HalfUp), N(3));
+        assert_eq!(
+            N::from_decimal_rounded(Q { v: 25 }, Rounding::HalfEven),
+            N(2)
+        );
+        assert_eq!(
+            N::from_decimal_rounded(Q { v: 35 }, Rounding::HalfEven),
+            N(4)
+        );
+        assert_eq!(
+            N::from_decimal_rounded(Q { v: 144 }, Rounding::Down),
+            N::from_decimal(Q { v: 144 })
+        );
+        assert_eq!(
+            N::from_decimal_rounded(Q { v: 144 }, Rounding::Up),
+            N::from_decimal_up(Q { v: 144 })
+        );
+
+        // signed values round toward infinities and settle ties away from zero or to even
+        assert_eq!(
+            I::from_decimal_rounded(S(-1555), Rounding::Down),
+            I { v: -156 }
+        );
+        assert_eq!(
+            I::from_decimal_rounded(S(-1555), Rounding::Up),
+            I { v: -155 }
+        );
+        assert_eq!(
+            I::from_decimal_rounded(S(-1555), Rounding::HalfUp),
+            I { v: -156 }
+        );
+        assert_eq!(
+            I::from_decimal_rounded(S(-1555), Rounding::HalfEven),
+            I { v: -156 }
+        );
+        assert_eq!(
+            I::from_decimal_rounded(S(-1556), Rounding::HalfUp),
+            I { v: -156 }
+        );
+        assert_eq!(
+            I::from_decimal_rounded(S(-1554), Rounding::HalfUp),
+            I { v: -155 }
+        );
+        assert_eq!(
+            I::from_decimal_rounded(S(1555), Rounding::HalfUp),
+            I { v: 156 }
+        );
+
+        assert!(N::checked_from_decimal_rounded(R(256_000), Rounding::HalfUp).is_err());
+        assert!(R::checked_from_decimal_rounded(N(255), Rounding::HalfUp).is_ok());
+        assert!(Q::checked_from_decimal_rounded(R(u32::MAX), Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn test_big_ops_rounded() {
+        // 0.001 * 0.5 = 0.0005, 0.003 * 0.5 = 0.0015
+        assert_eq!(R(1).big_mul_rounded(Q { v: 5 }, Rounding::Down), R(0));
+        assert_eq!(R(1).big_mul_rounded(Q { v: 5 }, Rounding::Up), R(1));
+        assert_eq!(R(1).big_mul_rounded(Q { v: 5 }, Rounding::HalfUp), R(1));
+        assert_eq!(R(1).big_mul_rounded(Q { v: 5 }, Rounding::HalfEven), R(0));
+        assert_eq!(R(3).big_mul_rounded(Q { v: 5 }, Rounding::HalfEven), R(2));
+        assert_eq!(S(-3).big_mul_rounded(Q { v: 5 }, Rounding::HalfUp), S(-2));
+        assert_eq!(S(-1).big_mul_rounded(Q { v: 5 }, Rounding::HalfUp), S(-1));
+        assert_eq!(S(-3).big_mul_rounded(Q { v: 5 }, Rounding::HalfEven), S(-2));
+
+        // 0.001 / 3 = 0.00033.., 0.002 / 3 = 0.00066..
+        assert_eq!(R(1).big_div_rounded(R(3000), Rounding::HalfUp), R(0));
+        assert_eq!(R(2).big_div_rounded(R(3000), Rounding::HalfUp), R(1));
+        assert_eq!(R(2).big_div_rounded(R(3000), Rounding::Down), R(0));
+        assert_eq!(S(2).big_div_rounded(S(-3000), Rounding::HalfEven), S(-1));
+        assert_eq!(S(2).big_div_rounded(S(-3000), Rounding::Up), S(0));
+
+        // intermediate value exceeds the underlying type
+        assert_eq!(
+            N(200).checked_big_mul_rounded(Q { v: 11 }, Rounding::HalfUp),
+            Ok(N(220))
+        );
+        assert_eq!(
+            N(200).checked_big_mul_rounded(N(2), Rounding::HalfUp),
+            Err("checked_big_mul_rounded: can't convert to result".to_string())
+        );
+        assert!(N(1).checked_big_div_rounded(N(0), Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(R::new(0).to_string(), "0.000");
+        assert_eq!(R::new(1).to_string(), "0.001");
+        assert_eq!(R::new(1000).to_string(), "1.000");
+        assert_eq!(R::new(12034).to_string(), "12.034");
+        assert_eq!(Q { v: 15 }.to_string(), "1.5");
+        assert_eq!(N(3).to_string(), "3");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("12.034".parse::<R>(), Ok(R::new(12034)));
+        assert_eq!("12.03".parse::<R>(), Ok(R::new(12030)));
+        assert_eq!("12".parse::<R>(), Ok(R::new(12000)));
+        assert_eq!("0.001".parse::<R>(), Ok(R::new(1)));
+        assert_eq!("1.5".parse::<Q>(), Ok(Q { v: 15 }));
+        assert_eq!("255".parse::<N>(), Ok(N(255)));
+
+        // more decimal places than scale
+        assert!("0.0001".parse::<R>().is_err());
+        assert!("1.0".parse::<N>().is_err());
+        // overflow
+        assert!("4294967.296".parse::<R>().is_err());
+        assert!("6553.6".parse::<Q>().is_err());
+        assert!("256".parse::<N>().is_err());
+        assert!("340282366920938463463374607431768211456"
+            .parse::<N>()
+            .is_err());
+        // invalid format
+        assert!("".parse::<R>().is_err());
+        assert!(".5".parse::<R>().is_err());
+        assert!("1.".parse::<R>().is_err());
+        assert!("+1".parse::<R>().is_err());
+        assert!("-1".parse::<R>().is_err());
+        assert!(" 1".parse::<R>().is_err());
+        assert!("1.2.3".parse::<R>().is_err());
+    }
+
+    #[test]
+    fn test_f64_conversions() {
+        assert_eq!(R(12034).to_f64(), 12.034);
+        assert_eq!(S(-1500).to_f64(), -1.5);
+        assert_eq!(Q { v: 15 }.to_f64(), 1.5);
+        assert_eq!(R(u32::MAX).to_f64(), 4294967.295);
+
+        assert_eq!(R::from_f64(12.034), R(12034));
+        assert_eq!(S::from_f64(-1.5), S(-1500));
+        // rounded to the nearest representable value
+        assert_eq!(R::from_f64(0.0004), R(0));
+        assert_eq!(R::from_f64(0.0006), R(1));
+        assert_eq!(Q::from_f64(0.05), Q { v: 1 });
+        assert_eq!(S::from_f64(-0.0006), S(-1));
+
+        assert!(R::checked_from_f64(-1.0).is_err());
+        // would round to zero, but unsigned types reject any negative value
+        assert_eq!(
+            R::checked_from_f64(-0.0004),
+            Err("checked_from_f64: negative value can't fit into unsigned type".to_string())
+        );
+        assert_eq!(R::checked_from_f64(-0.0), Ok(R(0)));
+        assert!(R::checked_from_f64(4294967.296).is_err());
+        assert!(N::checked_from_f64(256.0).is_err());
+        assert!(S::checked_from_f64(f64::NEG_INFINITY).is_err());
+        assert_eq!(N::checked_from_f64(255.0), Ok(N(255)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_f64_overflow() {
+        N::from_f64(-1.0);
+    }
+
+    #[test]
+    fn test_integer_and_fractional_part() {
+        assert_eq!(R(12034).integer_part(), R(12000));
+        assert_eq!(R(12034).fractional_part(), R(34));
+        assert_eq!(R(999).integer_part(), R(0));
+        assert_eq!(N(7).fractional_part(), N(0));
+
+        // parts keep the sign of the value
+        assert_eq!(S(-1500).integer_part(), S(-1000));
+        assert_eq!(S(-1500).fractional_part(), S(-500));
+        assert_eq!(S(-500).integer_part(), S(0));
+        assert_eq!(
+            S(i64::MIN).integer_part() + S(i64::MIN).fractional_part(),
+            S(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn test_num_traits() {
+        use num_traits::{Bounded, CheckedAdd, CheckedMul, SaturatingSub, Zero};
+
+        fn checked_total<T: Zero + CheckedAdd>(values: &[T]) -> Option<T> {
+            values
+                .iter()
+                .try_fold(T::zero(), |acc, value| acc.checked_add(value))
+        }
+
+        assert_eq!(checked_total(&[R(1500), R(2500)]), Some(R(4000)));
+        assert_eq!(checked_total(&[R(u32::MAX), R(1)]), None);
+        assert_eq!(checked_total::<S>(&[]), Some(S(0)));
+        assert_eq!(checked_total(&[S(-1500), S(500)]), Some(S(-1000)));
+
+        assert_eq!(<S as Bounded>::min_value(), S(i64::MIN));
+        assert_eq!(SaturatingSub::saturating_sub(&R(1), &R(2)), R(0));
+        assert_eq!(SaturatingSub::saturating_sub(&S(1), &S(2)), S(-1));
+
+        assert_eq!([R(1500), R(2000)].iter().product::<R>(), R(3000));
+        assert_eq!([S(-1500), S(2000)].into_iter().sum::<S>(), S(500));
+        // product is rounded down at every step
+        assert_eq!(
+            [Q { v: 15 }, Q { v: 15 }].iter().product::<Q>(),
+            Q { v: 22 }
+        );
+
+        // CheckedMul matches `Mul`, at scale 24 `one * one` doesn't fit while big ops do
+        let one = L::from_integer(1);
+        assert_eq!(CheckedMul::checked_mul(&one, &one), None);
+        assert_eq!(one.checked_big_mul(one), Ok(one));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let r = R::new(12034);
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, "\"12.034\"");
+        assert_eq!(serde_json::from_str::<R>(&json).unwrap(), r);
+
+        assert_eq!(serde_json::from_str::<N>("\"3\"").unwrap(), N(3));
+        assert!(serde_json::from_str::<Q>("\"0.01\"").is_err());
+        assert!(serde_json::from_str::<Q>("15").is_err());
+
+        let s = S(-1500);
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, "\"-1.500\"");
+        assert_eq!(serde_json::from_str::<S>(&json).unwrap(), s);
+    }
 }
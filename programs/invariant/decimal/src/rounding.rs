@@ -0,0 +1,244 @@
+use crate::int::I256;
+use crate::uint::U256;
+
+// Down and Up round toward negative and positive infinity, half modes round to the nearest value
+// and settle ties away from zero (HalfUp) or to the even result (HalfEven)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+    HalfUp,
+    HalfEven,
+}
+
+// `/` on signed integers rounds toward zero, the generated code of decimals divides through this trait instead
+pub trait RoundingDiv: Sized {
+    fn checked_div_rounded(self, rhs: Self, rounding: Rounding) -> Option<Self>;
+
+    fn checked_div_floor(self, rhs: Self) -> Option<Self> {
+        self.checked_div_rounded(rhs, Rounding::Down)
+    }
+
+    fn checked_div_ceil(self, rhs: Self) -> Option<Self> {
+        self.checked_div_rounded(rhs, Rounding::Up)
+    }
+}
+
+// whether the quotient rounded down has to be increased, remainder is always in [0, divisor)
+fn rounds_up<T>(remainder: T, divisor: T, is_odd: bool, negative: bool, rounding: Rounding) -> bool
+where
+    T: Copy + Default + PartialOrd + std::ops::Sub<Output = T>,
+{
+    let rest = divisor - remainder;
+    match rounding {
+        Rounding::Down => false,
+        Rounding::Up => remainder != T::default(),
+        Rounding::HalfUp => remainder > rest || (remainder == rest && !negative),
+        Rounding::HalfEven => remainder > rest || (remainder == rest && is_odd),
+    }
+}
+
+macro_rules! impl_rounding_div_unsigned {
+    ($($t:ty),*) => {$(
+        impl RoundingDiv for $t {
+            fn checked_div_rounded(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+                let one = <$t>::from(1u8);
+                let quotient = self.checked_div(rhs)?;
+                let is_odd = quotient % (one + one) == one;
+
+                match rounds_up(self % rhs, rhs, is_odd, false, rounding) {
+                    true => quotient.checked_add(one),
+                    false => Some(quotient),
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_rounding_div_signed {
+    ($($t:ty),*) => {$(
+        impl RoundingDiv for $t {
+            fn checked_div_rounded(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+                // checked_div fails on zero and MIN / -1, so neither the remainder nor the floor can overflow
+                let truncated = self.checked_div(rhs)?;
+                let quotient = match self % rhs != 0 && (self < 0) != (rhs < 0) {
+                    true => truncated - 1,
+                    false => truncated,
+                };
+                // exact result fits, intermediate product may not
+                let remainder = self.wrapping_sub(quotient.wrapping_mul(rhs));
+
+                match rounds_up(remainder.unsigned_abs(), rhs.unsigned_abs(), quotient % 2 != 0, quotient < 0, rounding) {
+                    true => quotient.checked_add(1),
+                    false => Some(quotient),
+                }
+            }
+        }
+    )*};
+}
+
+impl_rounding_div_unsigned!(u8, u16, u32, u64, u128, usize, U256);
+impl_rounding_div_signed!(i8, i16, i32, i64, i128, isize);
+
+impl RoundingDiv for I256 {
+    fn checked_div_rounded(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+        let (magnitude, divisor) = (self.unsigned_abs(), rhs.unsigned_abs());
+        let truncated = magnitude.checked_div(divisor)?;
+        let remainder = magnitude % divisor;
+        let negative = self.is_negative() != rhs.is_negative();
+
+        // floor of an inexact negative quotient is one further from zero
+        let (quotient, remainder) = match negative && !remainder.is_zero() {
+            true => (
+                I256::from_magnitude(true, truncated.checked_add(U256::one())?),
+                divisor - remainder,
+            ),
+            false => (I256::from_magnitude(negative, truncated), remainder),
+        };
+
+        let is_odd = quotient.unsigned_abs().bit(0);
+        match rounds_up(remainder, divisor, is_odd, quotient.is_negative(), rounding) {
+            true => quotient.checked_add(I256::from(1u8)),
+            false => Some(quotient),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding_div_unsigned() {
+        assert_eq!(7u8.checked_div_floor(2), Some(3));
+        assert_eq!(7u8.checked_div_ceil(2), Some(4));
+        assert_eq!(8u8.checked_div_ceil(2), Some(4));
+        assert_eq!(0u8.checked_div_ceil(2), Some(0));
+        assert_eq!(u8::MAX.checked_div_ceil(2), Some(128));
+        assert_eq!(1u8.checked_div_floor(0), None);
+        assert_eq!(1u8.checked_div_ceil(0), None);
+    }
+
+    #[test]
+    fn test_rounding_div_signed() {
+        assert_eq!(7i8.checked_div_floor(2), Some(3));
+        assert_eq!(7i8.checked_div_ceil(2), Some(4));
+        assert_eq!((-7i8).checked_div_floor(2), Some(-4));
+        assert_eq!((-7i8).checked_div_ceil(2), Some(-3));
+        assert_eq!(7i8.checked_div_floor(-2), Some(-4));
+        assert_eq!(7i8.checked_div_ceil(-2), Some(-3));
+        assert_eq!((-7i8).checked_div_floor(-2), Some(3));
+        assert_eq!((-7i8).checked_div_ceil(-2), Some(4));
+        assert_eq!((-8i8).checked_div_floor(2), Some(-4));
+        assert_eq!((-8i8).checked_div_ceil(2), Some(-4));
+
+        assert_eq!(i8::MIN.checked_div_floor(2), Some(-64));
+        assert_eq!(i8::MIN.checked_div_ceil(-2), Some(64));
+        assert_eq!(i8::MIN.checked_div_ceil(-1), None);
+        assert_eq!(i8::MIN.checked_div_floor(-1), None);
+        assert_eq!(1i8.checked_div_floor(0), None);
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let div = |a: i8, b: i8, rounding| a.checked_div_rounded(b, rounding).unwrap();
+        // 2.5, -2.5, 3.5, -3.5, 2.4, -2.6
+        let cases = [(5, 2), (-5, 2), (7, 2), (7, -2), (12, 5), (-13, 5)];
+        let expected = [
+            (Rounding::Down, [2, -3, 3, -4, 2, -3]),
+            (Rounding::Up, [3, -2, 4, -3, 3, -2]),
+            (Rounding::HalfUp, [3, -3, 4, -4, 2, -3]),
+            (Rounding::HalfEven, [2, -2, 4, -4, 2, -3]),
+        ];
+        for (rounding, results) in expected {
+            for ((a, b), result) in cases.iter().zip(results) {
+                assert_eq!(
+                    div(*a, *b, rounding),
+                    result,
+                    "{} / {} {:?}",
+                    a,
+                    b,
+                    rounding
+                );
+            }
+        }
+
+        assert_eq!(
+            i8::MAX.checked_div_rounded(-2, Rounding::HalfEven),
+            Some(-64)
+        );
+        assert_eq!(i8::MIN.checked_div_rounded(3, Rounding::HalfUp), Some(-43));
+        assert_eq!(i8::MAX.checked_div_rounded(1, Rounding::Up), Some(i8::MAX));
+    }
+
+    #[test]
+    fn test_rounding_modes_big_signed() {
+        let div = |a: i8, b: i8, rounding| {
+            I256::from(a)
+                .checked_div_rounded(I256::from(b), rounding)
+                .unwrap()
+        };
+        let cases = [
+            (5, 2),
+            (-5, 2),
+            (7, 2),
+            (7, -2),
+            (12, 5),
+            (-13, 5),
+            (-8, -2),
+        ];
+        for rounding in [
+            Rounding::Down,
+            Rounding::Up,
+            Rounding::HalfUp,
+            Rounding::HalfEven,
+        ] {
+            for (a, b) in cases {
+                let expected = a.checked_div_rounded(b, rounding).unwrap();
+                assert_eq!(div(a, b, rounding), I256::from(expected));
+            }
+        }
+
+        assert_eq!(I256::from(1).checked_div_floor(I256::zero()), None);
+        assert_eq!(
+            I256::from(-1).checked_div_floor(I256::from(U256::MAX)),
+            Some(I256::from(-1))
+        );
+        assert_eq!(
+            I256::from(-1).checked_div_ceil(I256::from(U256::MAX)),
+            Some(I256::zero())
+        );
+    }
+
+    #[test]
+    fn test_rounding_modes_unsigned() {
+        assert_eq!(5u8.checked_div_rounded(2, Rounding::HalfUp), Some(3));
+        assert_eq!(5u8.checked_div_rounded(2, Rounding::HalfEven), Some(2));
+        assert_eq!(7u8.checked_div_rounded(2, Rounding::HalfEven), Some(4));
+        assert_eq!(12u8.checked_div_rounded(5, Rounding::HalfUp), Some(2));
+        assert_eq!(13u8.checked_div_rounded(5, Rounding::HalfUp), Some(3));
+        assert_eq!(
+            u8::MAX.checked_div_rounded(1, Rounding::HalfUp),
+            Some(u8::MAX)
+        );
+        assert_eq!(1u8.checked_div_rounded(0, Rounding::HalfUp), None);
+
+        let big = |n: u128| U256::from(n);
+        assert_eq!(
+            big(5).checked_div_rounded(big(2), Rounding::HalfEven),
+            Some(big(2))
+        );
+        assert_eq!(
+            big(7).checked_div_rounded(big(2), Rounding::HalfEven),
+            Some(big(4))
+        );
+        assert_eq!(
+            big(7).checked_div_rounded(big(3), Rounding::Up),
+            Some(big(3))
+        );
+        assert_eq!(
+            U256::MAX.checked_div_rounded(big(2), Rounding::HalfUp),
+            Some(U256::MAX / big(2) + big(1))
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use crate::Rounding;
+
 pub trait Decimal {
     type U: Debug + Default;
 
@@ -16,20 +18,40 @@ pub trait Decimal {
     fn almost_one<T: TryFrom<u128>>() -> T;
 }
 
-pub trait BigOps<T> {
+pub trait BigOps<T>: Sized {
     fn big_mul(self, rhs: T) -> Self;
     fn big_mul_up(self, rhs: T) -> Self;
     fn big_div(self, rhs: T) -> Self;
     fn big_div_up(self, rhs: T) -> Self;
+    fn checked_big_mul(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_big_mul_up(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_big_div(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_big_div_up(self, rhs: T) -> std::result::Result<Self, String>;
+    fn big_mul_rounded(self, rhs: T, rounding: Rounding) -> Self;
+    fn big_div_rounded(self, rhs: T, rounding: Rounding) -> Self;
+    fn checked_big_mul_rounded(
+        self,
+        rhs: T,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
+    fn checked_big_div_rounded(
+        self,
+        rhs: T,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
 }
 
-pub trait Others<T> {
+pub trait Others<T>: Sized {
     fn mul_up(self, rhs: T) -> Self;
     fn div_up(self, rhs: T) -> Self;
+    fn checked_mul_up(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_div_up(self, rhs: T) -> std::result::Result<Self, String>;
 }
 
 pub trait OthersSameType {
     fn sub_abs(self, rhs: Self) -> Self;
+    fn integer_part(self) -> Self;
+    fn fractional_part(self) -> Self;
 }
 
 pub trait Factories<T>: Sized {
@@ -37,12 +59,25 @@ pub trait Factories<T>: Sized {
     fn from_scale(integer: T, scale: u8) -> Self;
     fn checked_from_scale(integer: T, scale: u8) -> std::result::Result<Self, String>;
     fn from_scale_up(integer: T, scale: u8) -> Self;
+    fn checked_from_scale_up(integer: T, scale: u8) -> std::result::Result<Self, String>;
+    fn from_scale_rounded(integer: T, scale: u8, rounding: Rounding) -> Self;
+    fn checked_from_scale_rounded(
+        integer: T,
+        scale: u8,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
 }
 
 pub trait BetweenDecimals<T>: Sized {
     fn from_decimal(other: T) -> Self;
     fn checked_from_decimal(other: T) -> std::result::Result<Self, String>;
     fn from_decimal_up(other: T) -> Self;
+    fn checked_from_decimal_up(other: T) -> std::result::Result<Self, String>;
+    fn from_decimal_rounded(other: T, rounding: Rounding) -> Self;
+    fn checked_from_decimal_rounded(
+        other: T,
+        rounding: Rounding,
+    ) -> std::result::Result<Self, String>;
 }
 
 pub trait ToValue<T, B> {
@@ -61,3 +96,14 @@ pub trait CheckedOps: Sized {
     fn checked_add(self, rhs: Self) -> std::result::Result<Self, String>;
     fn checked_sub(self, rhs: Self) -> std::result::Result<Self, String>;
 }
+
+pub trait CheckedMulDiv<T>: Sized {
+    fn checked_mul(self, rhs: T) -> std::result::Result<Self, String>;
+    fn checked_div(self, rhs: T) -> std::result::Result<Self, String>;
+}
+
+pub trait FloatConversions: Sized {
+    fn to_f64(self) -> f64;
+    fn from_f64(value: f64) -> Self;
+    fn checked_from_f64(value: f64) -> std::result::Result<Self, String>;
+}
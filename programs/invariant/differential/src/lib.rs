@@ -0,0 +1,14 @@
+// Feeds the same inputs to the math of the program and to its copy in invariant-types.
+// Quotes computed off-chain are only valid while both versions agree.
+
+#[cfg(test)]
+mod math;
+#[cfg(test)]
+mod tickmap;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// the program panics where invariant-types returns an error, so both are treated as the same failure
+pub fn outcome<T>(f: impl FnOnce() -> Option<T>) -> Option<T> {
+    catch_unwind(AssertUnwindSafe(f)).ok().flatten()
+}
@@ -0,0 +1,230 @@
+use crate::outcome;
+use invariant::decimals::{self as program, Decimal};
+use invariant::log::get_tick_at_sqrt_price;
+use invariant::math::{calculate_price_sqrt, compute_swap_step, MAX_SQRT_PRICE, MIN_SQRT_PRICE};
+use invariant::structs::MAX_TICK;
+use invariant_types::decimals as types;
+use invariant_types::{log as types_log, math as types_math};
+use proptest::prelude::*;
+
+// next price, amount in, amount out and fee of a swap step
+type SwapStep = (u128, u64, u64, u64);
+
+fn program_swap_step(
+    price: u128,
+    target: u128,
+    liquidity: u128,
+    amount: u64,
+    by_amount_in: bool,
+    fee: u128,
+) -> Option<SwapStep> {
+    outcome(|| {
+        let result = compute_swap_step(
+            program::Price::new(price),
+            program::Price::new(target),
+            program::Liquidity::new(liquidity),
+            program::TokenAmount(amount),
+            by_amount_in,
+            program::FixedPoint::new(fee),
+        )
+        .ok()?;
+        Some((
+            result.next_price_sqrt.v,
+            result.amount_in.0,
+            result.amount_out.0,
+            result.fee_amount.0,
+        ))
+    })
+}
+
+fn types_swap_step(
+    price: u128,
+    target: u128,
+    liquidity: u128,
+    amount: u64,
+    by_amount_in: bool,
+    fee: u128,
+) -> Option<SwapStep> {
+    outcome(|| {
+        let result = types_math::compute_swap_step(
+            types::Price::new(price),
+            types::Price::new(target),
+            types::Liquidity::new(liquidity),
+            types::TokenAmount(amount),
+            by_amount_in,
+            types::FixedPoint::new(fee),
+        )
+        .ok()?;
+        Some((
+            result.next_price_sqrt.v,
+            result.amount_in.0,
+            result.amount_out.0,
+            result.fee_amount.0,
+        ))
+    })
+}
+
+fn assert_swap_step(
+    price: u128,
+    target: u128,
+    liquidity: u128,
+    amount: u64,
+    by_amount_in: bool,
+    fee: u128,
+) {
+    assert_eq!(
+        program_swap_step(price, target, liquidity, amount, by_amount_in, fee),
+        types_swap_step(price, target, liquidity, amount, by_amount_in, fee),
+        "price: {}, target: {}, liquidity: {}, amount: {}, by_amount_in: {}, fee: {}",
+        price,
+        target,
+        liquidity,
+        amount,
+        by_amount_in,
+        fee
+    );
+}
+
+fn program_price_sqrt(tick: i32) -> Option<u128> {
+    outcome(|| Some(calculate_price_sqrt(tick).ok()?.v))
+}
+
+fn types_price_sqrt(tick: i32) -> Option<u128> {
+    outcome(|| Some(types_math::calculate_price_sqrt(tick).v))
+}
+
+fn assert_tick_at_sqrt_price(price: u128, tick_spacing: u16) {
+    assert_eq!(
+        outcome(|| get_tick_at_sqrt_price(program::Price::new(price), tick_spacing).ok()),
+        outcome(|| Some(types_log::get_tick_at_sqrt_price(
+            types::Price::new(price),
+            tick_spacing
+        ))),
+        "price: {}, tick_spacing: {}",
+        price,
+        tick_spacing
+    );
+}
+
+#[test]
+fn test_compute_swap_step_edge_cases() {
+    let one = 10u128.pow(24);
+    let prices = [
+        MIN_SQRT_PRICE,
+        MIN_SQRT_PRICE + 1,
+        calculate_price_sqrt(-1).unwrap().v,
+        one,
+        calculate_price_sqrt(1).unwrap().v,
+        MAX_SQRT_PRICE - 1,
+        MAX_SQRT_PRICE,
+    ];
+    let liquidities = [
+        0,
+        1,
+        10u128.pow(6),
+        u64::MAX as u128,
+        10u128.pow(30),
+        u128::MAX,
+    ];
+    let amounts = [0, 1, 1_000_000, u32::MAX as u64, u64::MAX];
+    let fees = [0, 1, 10u128.pow(9), 10u128.pow(12)];
+
+    for &price in prices.iter() {
+        for &target in prices.iter() {
+            for &liquidity in liquidities.iter() {
+                for &amount in amounts.iter() {
+                    for &fee in fees.iter() {
+                        assert_swap_step(price, target, liquidity, amount, true, fee);
+                        assert_swap_step(price, target, liquidity, amount, false, fee);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_calculate_price_sqrt_all_ticks() {
+    for tick in -MAX_TICK..=MAX_TICK {
+        assert_eq!(
+            program_price_sqrt(tick),
+            types_price_sqrt(tick),
+            "tick: {}",
+            tick
+        );
+    }
+    // out of bounds
+    for &tick in [-MAX_TICK - 1, MAX_TICK + 1, i32::MIN + 1, i32::MAX].iter() {
+        assert_eq!(program_price_sqrt(tick), None);
+        assert_eq!(types_price_sqrt(tick), None);
+    }
+}
+
+#[test]
+fn test_get_tick_at_sqrt_price_around_ticks() {
+    let spacings = [1, 2, 5, 10, 100];
+    for tick in (-MAX_TICK..=MAX_TICK).step_by(7) {
+        let price = calculate_price_sqrt(tick).unwrap().v;
+        for &spacing in spacings.iter() {
+            assert_tick_at_sqrt_price(price.saturating_sub(1), spacing);
+            assert_tick_at_sqrt_price(price, spacing);
+            assert_tick_at_sqrt_price(price + 1, spacing);
+        }
+    }
+    for &price in [MIN_SQRT_PRICE, MAX_SQRT_PRICE].iter() {
+        for &spacing in spacings.iter() {
+            assert_tick_at_sqrt_price(price, spacing);
+        }
+    }
+}
+
+fn sqrt_price() -> impl Strategy<Value = u128> {
+    prop_oneof![
+        Just(MIN_SQRT_PRICE),
+        Just(MAX_SQRT_PRICE),
+        (-MAX_TICK..=MAX_TICK).prop_map(|tick| calculate_price_sqrt(tick).unwrap().v),
+        MIN_SQRT_PRICE..=MAX_SQRT_PRICE,
+    ]
+}
+
+fn liquidity() -> impl Strategy<Value = u128> {
+    prop_oneof![
+        0u128..=u64::MAX as u128,
+        0u128..=10u128.pow(30),
+        any::<u128>(),
+    ]
+}
+
+fn amount() -> impl Strategy<Value = u64> {
+    prop_oneof![0u64..=1_000_000, any::<u64>()]
+}
+
+fn fee() -> impl Strategy<Value = u128> {
+    prop_oneof![Just(0), 0u128..=10u128.pow(12)]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+    #[test]
+    fn compute_swap_step_matches(
+        price in sqrt_price(),
+        target in sqrt_price(),
+        liquidity in liquidity(),
+        amount in amount(),
+        by_amount_in in any::<bool>(),
+        fee in fee(),
+    ) {
+        assert_swap_step(price, target, liquidity, amount, by_amount_in, fee);
+    }
+
+    #[test]
+    fn calculate_price_sqrt_matches(tick in any::<i32>()) {
+        prop_assert_eq!(program_price_sqrt(tick), types_price_sqrt(tick));
+    }
+
+    #[test]
+    fn get_tick_at_sqrt_price_matches(price in sqrt_price(), tick_spacing in 1u16..=1000) {
+        assert_tick_at_sqrt_price(price, tick_spacing);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::outcome;
+use invariant::decimals::{Decimal, Price};
+use invariant::math::calculate_price_sqrt;
+use invariant::structs::{
+    flip_tick, get_search_limit, Tickmap, TickmapExtension, TickmapView, MAX_TICK,
+};
+use invariant::util::get_closer_limit;
+use invariant_types::decimals as types;
+use invariant_types::math as types_math;
+use invariant_types::structs as types_structs;
+use proptest::prelude::*;
+
+// closer limit reduced to the price and the tick it stops at
+type CloserLimit = (u128, Option<(i32, bool)>);
+
+struct Tickmaps {
+    program: Box<Tickmap>,
+    program_extension: Box<TickmapExtension>,
+    types: Box<types_structs::Tickmap>,
+    types_extension: Box<types_structs::TickmapExtension>,
+}
+
+impl Tickmaps {
+    fn new(ticks: &[i32], tick_spacing: u16) -> Self {
+        let mut tickmaps = Tickmaps {
+            program: Box::new(Tickmap::default()),
+            program_extension: Box::new(TickmapExtension::default()),
+            types: Box::new(types_structs::Tickmap::default()),
+            types_extension: Box::new(types_structs::TickmapExtension::default()),
+        };
+        for &tick in ticks {
+            if !tickmaps.program_view().get(tick, tick_spacing).unwrap() {
+                flip_tick(
+                    &mut tickmaps.program,
+                    Some(&mut tickmaps.program_extension),
+                    true,
+                    tick,
+                    tick_spacing,
+                )
+                .unwrap();
+                types_structs::flip_tick(
+                    &mut tickmaps.types,
+                    Some(&mut tickmaps.types_extension),
+                    true,
+                    tick,
+                    tick_spacing,
+                );
+            }
+        }
+        tickmaps
+    }
+
+    fn program_view(&self) -> TickmapView {
+        TickmapView::new(&self.program, Some(&self.program_extension))
+    }
+
+    fn types_view(&self) -> types_structs::TickmapView {
+        types_structs::TickmapView::new(&self.types, Some(&self.types_extension))
+    }
+
+    fn program_closer_limit(
+        &self,
+        price_limit: u128,
+        x_to_y: bool,
+        tick: i32,
+        tick_spacing: u16,
+    ) -> Option<CloserLimit> {
+        outcome(|| {
+            let (price, limit) = get_closer_limit(
+                Price::new(price_limit),
+                x_to_y,
+                tick,
+                tick_spacing,
+                &self.program_view(),
+            )
+            .ok()?;
+            Some((price.v, limit))
+        })
+    }
+
+    fn types_closer_limit(
+        &self,
+        price_limit: u128,
+        x_to_y: bool,
+        tick: i32,
+        tick_spacing: u16,
+    ) -> Option<CloserLimit> {
+        outcome(|| {
+            let (price, limit) = types_math::get_closer_limit(
+                types::Price::new(price_limit),
+                x_to_y,
+                tick,
+                tick_spacing,
+                &self.types_view(),
+            )
+            .ok()?;
+            Some((price.v, limit))
+        })
+    }
+
+    fn assert_search(&self, tick: i32, tick_spacing: u16, price_limit: u128) {
+        let context = format!(
+            "tick: {}, tick_spacing: {}, price_limit: {}",
+            tick, tick_spacing, price_limit
+        );
+        let program = self.program_view();
+        let types = self.types_view();
+
+        assert_eq!(
+            outcome(|| program.next_initialized(tick, tick_spacing).ok()),
+            outcome(|| Some(types.next_initialized(tick, tick_spacing))),
+            "next_initialized, {}",
+            context
+        );
+        assert_eq!(
+            outcome(|| program.prev_initialized(tick, tick_spacing).ok()),
+            outcome(|| Some(types.prev_initialized(tick, tick_spacing))),
+            "prev_initialized, {}",
+            context
+        );
+        for &up in [true, false].iter() {
+            assert_eq!(
+                outcome(|| get_search_limit(tick, tick_spacing, up).ok()),
+                outcome(|| Some(types_structs::get_search_limit(tick, tick_spacing, up))),
+                "get_search_limit up: {}, {}",
+                up,
+                context
+            );
+            assert_eq!(
+                outcome(|| program.search_limit(tick, tick_spacing, up).ok()),
+                outcome(|| Some(types.search_limit(tick, tick_spacing, up))),
+                "search_limit with extension up: {}, {}",
+                up,
+                context
+            );
+        }
+        for &x_to_y in [true, false].iter() {
+            assert_eq!(
+                self.program_closer_limit(price_limit, x_to_y, tick, tick_spacing),
+                self.types_closer_limit(price_limit, x_to_y, tick, tick_spacing),
+                "get_closer_limit x_to_y: {}, {}",
+                x_to_y,
+                context
+            );
+        }
+    }
+}
+
+#[test]
+fn test_search_edge_cases() {
+    for &tick_spacing in [1u16, 2, 5, 10, 100].iter() {
+        let max_tick = MAX_TICK / tick_spacing as i32 * tick_spacing as i32;
+        let step = tick_spacing as i32;
+        let queries = [
+            -max_tick,
+            -max_tick + step,
+            -step,
+            0,
+            step,
+            max_tick - step,
+            max_tick,
+        ];
+        let limits = [
+            calculate_price_sqrt(-max_tick).unwrap().v,
+            calculate_price_sqrt(0).unwrap().v,
+            calculate_price_sqrt(max_tick).unwrap().v,
+        ];
+
+        // empty tickmap, ticks at the edges and ticks next to the queries
+        let mut neighbours = vec![];
+        for &tick in queries.iter() {
+            neighbours.extend_from_slice(&[tick - step, tick + step, tick + 256 * step]);
+        }
+        neighbours.retain(|tick| tick.abs() <= max_tick);
+
+        for ticks in [vec![], vec![-max_tick, max_tick], neighbours].iter() {
+            let tickmaps = Tickmaps::new(ticks, tick_spacing);
+            for &tick in queries.iter() {
+                for &price_limit in limits.iter() {
+                    tickmaps.assert_search(tick, tick_spacing, price_limit);
+                }
+            }
+        }
+    }
+}
+
+// initialized ticks clustered around the queried tick so the search range is hit both ways
+fn tickmap_case() -> impl Strategy<Value = (u16, Vec<i32>, i32)> {
+    prop::sample::select(vec![1u16, 2, 5, 10, 50, 100]).prop_flat_map(|tick_spacing| {
+        let max_index = MAX_TICK / tick_spacing as i32;
+        (-max_index..=max_index).prop_flat_map(move |center| {
+            (
+                Just(tick_spacing),
+                prop::collection::vec(-300i32..=300, 0..32).prop_map(move |offsets| {
+                    offsets
+                        .into_iter()
+                        .map(|offset| (center + offset).max(-max_index).min(max_index))
+                        .map(|index| index * tick_spacing as i32)
+                        .collect()
+                }),
+                Just(center * tick_spacing as i32),
+            )
+        })
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2_000))]
+
+    #[test]
+    fn tickmap_search_matches(
+        (tick_spacing, ticks, center) in tickmap_case(),
+        price_limit in invariant::math::MIN_SQRT_PRICE..=invariant::math::MAX_SQRT_PRICE,
+    ) {
+        let tickmaps = Tickmaps::new(&ticks, tick_spacing);
+        let step = tick_spacing as i32;
+
+        tickmaps.assert_search(center, tick_spacing, price_limit);
+        for &tick in ticks.iter() {
+            for &query in [tick - step, tick, tick + step].iter() {
+                if query.abs() <= MAX_TICK {
+                    tickmaps.assert_search(query, tick_spacing, price_limit);
+                }
+            }
+        }
+    }
+}
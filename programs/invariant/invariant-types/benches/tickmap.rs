@@ -0,0 +1,38 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use invariant_types::structs::{Tickmap, TICK_SEARCH_RANGE};
+
+// tickmap with every n-th tick (scaled by spacing) initialized
+fn init_tickmap(tick_spacing: u16, step: i32) -> Tickmap {
+    let mut map = Tickmap::default();
+    if step == 0 {
+        return map;
+    }
+
+    let mut index = -10 * TICK_SEARCH_RANGE;
+    while index <= 10 * TICK_SEARCH_RANGE {
+        map.flip(true, index * tick_spacing as i32, tick_spacing);
+        index += step;
+    }
+    map
+}
+
+fn bench_search(c: &mut Criterion) {
+    let tick_spacing = 10;
+
+    // empty - whole search range is scanned
+    // sparse - initialized tick found after a few words
+    // dense - initialized tick found in the first word
+    for (name, step) in [("empty", 0), ("sparse", 200), ("dense", 3)] {
+        let map = init_tickmap(tick_spacing, step);
+
+        c.bench_function(&format!("next_initialized_{}", name), |b| {
+            b.iter(|| map.next_initialized(black_box(10), black_box(tick_spacing)))
+        });
+        c.bench_function(&format!("prev_initialized_{}", name), |b| {
+            b.iter(|| map.prev_initialized(black_box(-10), black_box(tick_spacing)))
+        });
+    }
+}
+
+criterion_group!(benches, bench_search);
+criterion_main!(benches);
@@ -4,7 +4,7 @@ pub use decimal::*;
 
 use anchor_lang::prelude::*;
 
-use crate::utils::{TrackableError, TrackableResult};
+use crate::utils::{ErrorCause, TrackableError, TrackableResult};
 use crate::{err, function, location};
 
 pub const PRICE_LIQUIDITY_DENOMINATOR: u128 = 1__0000_0000__0000_0000__00u128;
@@ -141,17 +141,17 @@ impl Price {
         Ok(Price::new(
             nominator
                 .checked_mul(Self::one::<U256>())
-                .ok_or_else(|| err!(TrackableError::MUL))?
+                .ok_or_else(|| err!(ErrorCause::Mul))?
                 .checked_add(
                     denominator
                         .checked_sub(U256::from(1u32))
-                        .ok_or_else(|| err!(TrackableError::SUB))?,
+                        .ok_or_else(|| err!(ErrorCause::Sub))?,
                 )
-                .ok_or_else(|| err!(TrackableError::ADD))?
+                .ok_or_else(|| err!(ErrorCause::Add))?
                 .checked_div(denominator)
-                .ok_or_else(|| err!(TrackableError::DIV))?
+                .ok_or_else(|| err!(ErrorCause::Div))?
                 .try_into()
-                .map_err(|_| err!(TrackableError::cast::<Self>().as_str()))?,
+                .map_err(|_| err!(ErrorCause::cast::<Self>()))?,
         ))
     }
 }
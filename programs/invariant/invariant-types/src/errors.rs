@@ -64,4 +64,32 @@ pub enum InvariantErrorCode {
     InvalidListOwner = 29, // 178d
     #[msg("Invalid tick spacing")]
     InvalidTickSpacing = 30, // 178e
+    #[msg("Remaining accounts should be triples of position, lower tick and upper tick")]
+    InvalidRemainingAccounts = 31, // 178f
+    #[msg("Signer is neither the owner nor the operator of the position")]
+    InvalidSigner = 32, // 1790
+    #[msg("Invalid reward index")]
+    InvalidRewardIndex = 33, // 1791
+    #[msg("Reward is already initialized")]
+    RewardAlreadyInitialized = 34, // 1792
+    #[msg("Account is not in a layout that can be migrated")]
+    InvalidAccountVersion = 35, // 1793
+    #[msg("Provided account owner is different than program ID")]
+    InvalidAccountOwner = 36, // 1794
+    #[msg("Initial sqrt price is out of range")]
+    InvalidInitSqrtPrice = 37, // 1795
+    #[msg("Pool does not belong to the fee tier")]
+    InvalidFeeTier = 38, // 1796
+    #[msg("Action has to be proposed and wait out the governance delay")]
+    TimelockActive = 39, // 1797
+    #[msg("Governance delay of the proposal has not passed yet")]
+    ProposalNotReady = 40, // 1798
+    #[msg("Proposal action is invalid")]
+    InvalidProposal = 41, // 1799
+    #[msg("Tick is already set to this state in the tickmap")]
+    InvalidTickmapFlip = 42, // 179a
+    #[msg("Arithmetic overflow")]
+    MathOverflow = 43, // 179b
+    #[msg("Sqrt price is zero or out of range")]
+    InvalidSqrtPrice = 44, // 179c
 }
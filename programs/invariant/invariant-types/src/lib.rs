@@ -4,6 +4,7 @@ pub mod log;
 pub mod macros;
 pub mod math;
 pub mod structs;
+pub mod swap;
 pub mod utils;
 
 use anchor_lang::prelude::*;
@@ -6,8 +6,8 @@ use anchor_lang::*;
 use crate::{
     decimals::*,
     errors::InvariantErrorCode,
-    structs::{get_search_limit, Pool, Tick, Tickmap, MAX_TICK, TICK_LIMIT},
-    utils::{TrackableError, TrackableResult},
+    structs::{Pool, Tick, TickmapView, MAX_TICK, TICK_LIMIT},
+    utils::{ErrorCause, TrackableError, TrackableResult},
 };
 
 #[derive(PartialEq, Debug)]
@@ -96,7 +96,7 @@ pub fn get_closer_limit(
     x_to_y: bool,
     current_tick: i32, // tick already scaled by tick_spacing
     tick_spacing: u16,
-    tickmap: &Tickmap,
+    tickmap: &TickmapView,
 ) -> Result<(Price, Option<(i32, bool)>)> {
     // find initalized tick (None also for virtual tick limiated by search scope)
     let closes_tick_index = if x_to_y {
@@ -118,10 +118,14 @@ pub fn get_closer_limit(
             }
         }
         None => {
-            let index = get_search_limit(current_tick, tick_spacing, !x_to_y);
+            let index = tickmap.search_limit(current_tick, tick_spacing, !x_to_y);
             let price = calculate_price_sqrt(index);
 
-            require!(current_tick != index, InvariantErrorCode::LimitReached);
+            // below the lowest tick aligned to spacing the limit would end up above the current tick
+            require!(
+                (x_to_y && index < current_tick) || (!x_to_y && index > current_tick),
+                InvariantErrorCode::LimitReached
+            );
 
             // trunk-ignore(clippy/if_same_then_else)
             if x_to_y && price > sqrt_price_limit {
@@ -158,15 +162,17 @@ pub fn compute_swap_step(
     let mut amount_in = TokenAmount(0);
     let mut amount_out = TokenAmount(0);
 
+    let mut amount_after_fee = TokenAmount(0);
+
     if by_amount_in {
         // take fee in input_amount
         // U256(2^64) * U256(1e12) - no overflow in intermediate operations
         // no overflow in token_amount result
-        let amount_after_fee = amount.big_mul(
+        amount_after_fee = from_result!(amount.checked_big_mul(
             FixedPoint::from_integer(1u8)
                 .checked_sub(fee)
-                .map_err(|_| err!("sub underflow"))?,
-        );
+                .map_err(|_| err!(ErrorCause::Sub))?,
+        ))?;
 
         amount_in = if x_to_y {
             get_delta_x(target_price_sqrt, current_price_sqrt, liquidity, true)
@@ -215,23 +221,28 @@ pub fn compute_swap_step(
     if x_to_y {
         if not_max || !by_amount_in {
             amount_in = get_delta_x(next_price_sqrt, current_price_sqrt, liquidity, true)
-                .ok_or_else(|| err!("get_delta_x overflow"))?;
+                .ok_or_else(|| err!(ErrorCause::AmountOverflow))?;
         };
         if not_max || by_amount_in {
             amount_out = get_delta_y(next_price_sqrt, current_price_sqrt, liquidity, false)
-                .ok_or_else(|| err!("get_delta_y overflow"))?;
+                .ok_or_else(|| err!(ErrorCause::AmountOverflow))?;
         }
     } else {
         if not_max || !by_amount_in {
             amount_in = get_delta_y(current_price_sqrt, next_price_sqrt, liquidity, true)
-                .ok_or_else(|| err!("get_delta_y overflow"))?;
+                .ok_or_else(|| err!(ErrorCause::AmountOverflow))?;
         };
         if not_max || by_amount_in {
             amount_out = get_delta_x(current_price_sqrt, next_price_sqrt, liquidity, false)
-                .ok_or_else(|| err!("get_delta_x overflow"))?;
+                .ok_or_else(|| err!(ErrorCause::AmountOverflow))?;
         };
     }
 
+    // Amount in rounded up from the next price can exceed the amount the price was calculated from
+    if by_amount_in && not_max && amount_in > amount_after_fee {
+        amount_in = amount_after_fee;
+    }
+
     // Amount out can not exceed amount
     if !by_amount_in && amount_out > amount {
         amount_out = amount;
@@ -242,11 +253,11 @@ pub fn compute_swap_step(
         // edge case occurs when the next_price is target_price (minimal distance to target)
         amount
             .checked_sub(amount_in)
-            .map_err(|_| err!("sub underflow"))?
+            .map_err(|_| err!(ErrorCause::Sub))?
     } else {
         // no possible to overflow in intermediate operations
         // edge case when amount_in is maximum and fee is maximum
-        amount_in.big_mul_up(fee)
+        from_result!(amount_in.checked_big_mul_up(fee))?
     };
 
     Ok(SwapResult {
@@ -322,10 +333,10 @@ fn get_next_sqrt_price_from_input(
     x_to_y: bool,
 ) -> TrackableResult<Price> {
     if liquidity.is_zero() {
-        return Err(err!("getting next price from input with zero liquidity"));
+        return Err(err!(ErrorCause::ZeroLiquidity));
     }
     if price_sqrt.is_zero() {
-        return Err(err!("getting next price from input with zero price"));
+        return Err(err!(ErrorCause::ZeroPrice));
     }
     // DOMAIN:
     // price_sqrt <sqrt_price_at_min_tick, sqrt_price_at_max_tick>
@@ -354,10 +365,10 @@ fn get_next_sqrt_price_from_output(
     // amount <1, u64::MAX>
 
     if liquidity.is_zero() {
-        return Err(err!("getting next price from output with zero liquidity"));
+        return Err(err!(ErrorCause::ZeroLiquidity));
     }
     if price_sqrt.is_zero() {
-        return Err(err!("getting next price from output with zero price"));
+        return Err(err!(ErrorCause::ZeroPrice));
     }
 
     let result = if x_to_y {
@@ -391,19 +402,23 @@ fn get_next_sqrt_price_x_up(
     let big_liquidity = liquidity
         .here::<U256>()
         .checked_mul(U256::from(PRICE_LIQUIDITY_DENOMINATOR)) // extends liquidity precision (operation on U256, so there is no dividing by denominator)
-        .ok_or_else(|| err!("mul overflow"))?;
+        .ok_or_else(|| err!(ErrorCause::Mul))?;
 
     // max(price * amount)
     // ceil(log2(max_price * 2^64))= 160
     // U256::from(max_price) * U256::from(2^64) / U256::(1)
     // so not possible to overflow here
-    let denominator = from_result!(match add {
+    let denominator = match add {
         // max_denominator = L + amount * price [maximize all parameters]
         // max_denominator 2^128 + 2^64 * 2^96 = 2^161 <- no possible to overflow
-        true => big_liquidity.checked_add(price_sqrt.big_mul_to_value(amount)),
-        false => big_liquidity.checked_sub(price_sqrt.big_mul_to_value(amount)),
-    }
-    .ok_or_else(|| "big_liquidity -/+ price_sqrt * amount"))?; // never should be triggered
+        true => big_liquidity
+            .checked_add(price_sqrt.big_mul_to_value(amount))
+            .ok_or_else(|| err!(ErrorCause::Add))?,
+        // never should be triggered
+        false => big_liquidity
+            .checked_sub(price_sqrt.big_mul_to_value(amount))
+            .ok_or_else(|| err!(ErrorCause::Sub))?,
+    };
 
     // max_nominator = (U256::from(max_price) * U256::from(max_liquidity) + 10^6) / 10^6
     // max_nominator = (2^96 * 2^128 + 10^6) / 10^6
@@ -453,12 +468,11 @@ fn get_next_sqrt_price_y_down(
 
         // max_quotient = max_nominator / min_denominator
         // max_quotient = 2^128 * 10^24 / 10^18 ~ 2^148 so possible to overflow in max_quote
-        let quotient = from_result!(Price::checked_from_decimal(amount)
-            .map_err(|err| err!(&err))? // TODO: add util macro to map str -> TrackableError
+        let quotient = from_result!(from_result!(Price::checked_from_decimal(amount))?
             .checked_big_div_by_number(
                 U256::from(liquidity.get())
                     .checked_mul(U256::from(PRICE_LIQUIDITY_DENOMINATOR))
-                    .ok_or_else(|| err!("mul overflow"))?,
+                    .ok_or_else(|| err!(ErrorCause::Mul))?,
             ))?;
         // max_quotient = 2^128
         // price_sqrt = 2^96
@@ -466,12 +480,11 @@ fn get_next_sqrt_price_y_down(
         from_result!(price_sqrt.checked_add(quotient))
     } else {
         // Price::from_scale - same as case above
-        let quotient = from_result!(Price::checked_from_decimal(amount)
-            .map_err(|err| err!(&err))? // TODO: add util macro to map str -> TrackableError
+        let quotient = from_result!(from_result!(Price::checked_from_decimal(amount))?
             .checked_big_div_by_number_up(
                 U256::from(liquidity.get())
                     .checked_mul(U256::from(PRICE_LIQUIDITY_DENOMINATOR))
-                    .ok_or_else(|| err!("mul overflow"))?,
+                    .ok_or_else(|| err!(ErrorCause::Mul))?,
             ))?;
         from_result!(price_sqrt.checked_sub(quotient))
     }
@@ -490,14 +503,18 @@ pub fn is_enough_amount_to_push_price(
     }
 
     let next_price_sqrt = ok_or_mark_trace!(if by_amount_in {
-        let amount_after_fee = amount.big_mul(
+        let amount_after_fee = from_result!(amount.checked_big_mul(
             FixedPoint::from_integer(1)
                 .checked_sub(fee)
-                .map_err(|_| err!("sub underflow"))?,
-        );
+                .map_err(|_| err!(ErrorCause::Sub))?,
+        ))?;
         get_next_sqrt_price_from_input(current_price_sqrt, liquidity, amount_after_fee, x_to_y)
     } else {
-        get_next_sqrt_price_from_output(current_price_sqrt, liquidity, amount, x_to_y)
+        // the next price overflows only for outputs above what the liquidity holds, those move it anyway
+        match get_next_sqrt_price_from_output(current_price_sqrt, liquidity, amount, x_to_y) {
+            Err(_) => return Ok(true),
+            next_price_sqrt => next_price_sqrt,
+        }
     })?;
 
     Ok(current_price_sqrt.ne(&next_price_sqrt))
@@ -525,18 +542,18 @@ pub fn cross_tick(tick: &mut RefMut<Tick>, pool: &mut Pool) -> Result<()> {
 pub fn get_max_tick(tick_spacing: u16) -> TrackableResult<i32> {
     let limit_by_space = TICK_LIMIT
         .checked_sub(1)
-        .ok_or_else(|| err!("sub underflow"))?
+        .ok_or_else(|| err!(ErrorCause::Sub))?
         .checked_mul(tick_spacing.into())
-        .ok_or_else(|| err!("mul overflow"))?;
+        .ok_or_else(|| err!(ErrorCause::Mul))?;
     Ok(limit_by_space.min(MAX_TICK))
 }
 
 pub fn get_min_tick(tick_spacing: u16) -> TrackableResult<i32> {
     let limit_by_space = (-TICK_LIMIT)
         .checked_add(1)
-        .ok_or_else(|| err!("add overflow"))?
+        .ok_or_else(|| err!(ErrorCause::Add))?
         .checked_mul(tick_spacing.into())
-        .ok_or_else(|| err!("mul overflow"))?;
+        .ok_or_else(|| err!(ErrorCause::Mul))?;
     Ok(limit_by_space.max(-MAX_TICK))
 }
 
@@ -558,6 +575,7 @@ mod tests {
 
     use crate::{
         decimals::{FixedPoint, Liquidity, Price, TokenAmount},
+        errors::InvariantErrorCode,
         math::{
             compute_swap_step, cross_tick, get_delta_x, get_delta_y, get_max_sqrt_price,
             get_max_tick, get_min_sqrt_price, get_min_tick, get_next_sqrt_price_from_input,
@@ -565,7 +583,7 @@ mod tests {
             SwapResult,
         },
         structs::{Pool, Tick, MAX_TICK},
-        utils::TrackableError,
+        utils::ErrorCause,
         MAX_SQRT_PRICE, MIN_SQRT_PRICE,
     };
 
@@ -972,7 +990,7 @@ mod tests {
             .unwrap_err()
             .get();
 
-            assert_eq!(cause, "get_delta_x overflow");
+            assert_eq!(cause, "token amount overflow");
             assert_eq!(stack.len(), 1);
         }
         // by_amount_in == true || close to target_price but not reached
@@ -1164,7 +1182,7 @@ mod tests {
                 .unwrap_err()
                 .get();
 
-                assert_eq!(cause, "sub underflow");
+                assert_eq!(cause, "subtraction underflow");
             }
             //max fee that fits within u64 && by_amount_in == false
             {
@@ -1189,6 +1207,27 @@ mod tests {
                 );
             }
         }
+        // amount in rounded up over the whole amount without fee
+        {
+            let current_price_sqrt = Price::new(791925200290000000000000);
+            let target_price_sqrt = Price::new(34906499059000000000000);
+            let liquidity = Liquidity::new(7556323959758483140);
+            let amount = TokenAmount(16834688482519);
+            let fee = FixedPoint::new(0);
+
+            let result = compute_swap_step(
+                current_price_sqrt,
+                target_price_sqrt,
+                liquidity,
+                amount,
+                true,
+                fee,
+            )
+            .unwrap();
+            assert_eq!(result.next_price_sqrt, Price::new(286480459051362175036776));
+            assert_eq!(result.amount_in, amount);
+            assert_eq!(result.fee_amount, TokenAmount(0));
+        }
     }
 
     #[test]
@@ -1715,23 +1754,22 @@ mod tests {
             let result = get_next_sqrt_price_x_up(max_price_sqrt, max_liquidity, max_amount, true)
                 .unwrap_err();
 
-            let (_, cause, stack) = result.get();
-            assert_eq!(stack.len(), 2);
-            assert_eq!(cause, TrackableError::MUL);
+            assert_eq!(result.stack.len(), 2);
+            assert_eq!(result.cause, ErrorCause::Mul);
         }
         // subtraction underflow (not possible from upper-level function)
         {
-            let (_, cause, stack) = get_next_sqrt_price_x_up(
+            let result = get_next_sqrt_price_x_up(
                 max_price_sqrt,
                 min_liquidity,
                 TokenAmount(u64::MAX),
                 false,
             )
-            .unwrap_err()
-            .get();
+            .unwrap_err();
 
-            assert_eq!(cause, "big_liquidity -/+ price_sqrt * amount");
-            assert_eq!(stack.len(), 1);
+            assert_eq!(result.cause, ErrorCause::Sub);
+            assert!(matches!(result.code(), InvariantErrorCode::MathOverflow));
+            assert_eq!(result.stack.len(), 1);
         }
         // max_liquidity
         {
@@ -1758,7 +1796,7 @@ mod tests {
             .unwrap_err()
             .get();
 
-            assert_eq!(cause, "getting next price from input with zero liquidity")
+            assert_eq!(cause, "zero liquidity")
         }
         {
             let (_, cause, _) = get_next_sqrt_price_from_input(
@@ -1770,7 +1808,7 @@ mod tests {
             .unwrap_err()
             .get();
 
-            assert_eq!(cause, "getting next price from input with zero price")
+            assert_eq!(cause, "zero sqrt price")
         }
     }
 
@@ -1786,7 +1824,7 @@ mod tests {
             .unwrap_err()
             .get();
 
-            assert_eq!(cause, "getting next price from output with zero liquidity")
+            assert_eq!(cause, "zero liquidity")
         }
         {
             let (_, cause, _) = get_next_sqrt_price_from_output(
@@ -1798,7 +1836,7 @@ mod tests {
             .unwrap_err()
             .get();
 
-            assert_eq!(cause, "getting next price from output with zero price")
+            assert_eq!(cause, "zero sqrt price")
         }
     }
     #[test]
@@ -1813,15 +1851,28 @@ mod tests {
                 max_price_sqrt,
                 min_liquidity,
                 min_fee,
-                false,
+                true,
                 false,
             )
             .unwrap_err()
             .get();
 
-            assert_eq!(cause, "big_liquidity -/+ price_sqrt * amount");
+            assert_eq!(cause, "checked_from_scale: (multiplier * base) overflow");
             assert_eq!(stack.len(), 3);
         }
+        // output above the liquidity
+        {
+            let result = is_enough_amount_to_push_price(
+                TokenAmount(u64::MAX),
+                max_price_sqrt,
+                min_liquidity,
+                min_fee,
+                false,
+                false,
+            )
+            .unwrap();
+            assert!(result);
+        }
         let fee_over_one = FixedPoint::from_integer(1) + FixedPoint::new(1);
 
         let (_, cause, _) = is_enough_amount_to_push_price(
@@ -1835,7 +1886,7 @@ mod tests {
         .unwrap_err()
         .get();
 
-        assert_eq!(cause, "sub underflow")
+        assert_eq!(cause, "subtraction underflow")
     }
 
     #[test]
@@ -2114,3 +2165,221 @@ mod tests {
         }
     }
 }
+
+#[cfg(test)]
+mod prop_tests {
+    use super::*;
+    use crate::log::get_tick_at_sqrt_price;
+    use crate::{MAX_SQRT_PRICE, MIN_SQRT_PRICE};
+    use decimal::Decimal;
+    use proptest::prelude::*;
+
+    // larger amounts overflow when scaled to a price in `get_next_sqrt_price_y_down`
+    const MAX_AMOUNT: u64 = (u128::MAX / 10u128.pow(24)) as u64;
+
+    fn sqrt_price() -> impl Strategy<Value = Price> {
+        prop_oneof![
+            Just(Price::new(MIN_SQRT_PRICE)),
+            Just(Price::new(MAX_SQRT_PRICE)),
+            (-MAX_TICK..=MAX_TICK).prop_map(calculate_price_sqrt),
+            (MIN_SQRT_PRICE..=MAX_SQRT_PRICE).prop_map(Price::new),
+        ]
+    }
+
+    fn liquidity() -> impl Strategy<Value = Liquidity> {
+        prop_oneof![
+            (1u128..=u64::MAX as u128).prop_map(Liquidity::new),
+            (1u128..=10u128.pow(30)).prop_map(Liquidity::new),
+        ]
+    }
+
+    fn amount() -> impl Strategy<Value = TokenAmount> {
+        prop_oneof![Just(MAX_AMOUNT), 1u64..=MAX_AMOUNT, 1u64..=1_000_000].prop_map(TokenAmount)
+    }
+
+    fn fee() -> impl Strategy<Value = FixedPoint> {
+        prop_oneof![
+            Just(FixedPoint::new(0)),
+            (0u128..=FixedPoint::one()).prop_map(FixedPoint::new)
+        ]
+    }
+
+    fn fits_into_token_amount(
+        sqrt_price_a: Price,
+        sqrt_price_b: Price,
+        liquidity: Liquidity,
+    ) -> bool {
+        get_delta_x(sqrt_price_a, sqrt_price_b, liquidity, true).is_some()
+            && get_delta_y(sqrt_price_a, sqrt_price_b, liquidity, true).is_some()
+    }
+
+    proptest! {
+        // out of domain inputs are rejected often
+        #![proptest_config(ProptestConfig {
+            max_global_rejects: 1 << 16,
+            ..ProptestConfig::default()
+        })]
+
+        #[test]
+        fn proptest_compute_swap_step(
+            current_price_sqrt in sqrt_price(),
+            target_price_sqrt in sqrt_price(),
+            liquidity in liquidity(),
+            amount in amount(),
+            by_amount_in in any::<bool>(),
+            fee in fee(),
+        ) {
+            // amounts over the whole step have to fit into a token amount
+            prop_assume!(fits_into_token_amount(current_price_sqrt, target_price_sqrt, liquidity));
+
+            let result = compute_swap_step(
+                current_price_sqrt,
+                target_price_sqrt,
+                liquidity,
+                amount,
+                by_amount_in,
+                fee,
+            )
+            .unwrap();
+            let next_price_sqrt = result.next_price_sqrt;
+
+            // price moves only towards the target, without passing it
+            if current_price_sqrt >= target_price_sqrt {
+                prop_assert!(next_price_sqrt <= current_price_sqrt);
+                prop_assert!(next_price_sqrt >= target_price_sqrt);
+            } else {
+                prop_assert!(next_price_sqrt >= current_price_sqrt);
+                prop_assert!(next_price_sqrt <= target_price_sqrt);
+            }
+
+            if by_amount_in {
+                prop_assert!(result.amount_in.0 as u128 + result.fee_amount.0 as u128 <= amount.0 as u128);
+            } else {
+                prop_assert!(result.amount_out <= amount);
+            }
+
+            // amounts are rounded in favour of the pool
+            if current_price_sqrt >= target_price_sqrt {
+                let amount_out = get_delta_y(next_price_sqrt, current_price_sqrt, liquidity, false).unwrap();
+                let amount_in = get_delta_x(next_price_sqrt, current_price_sqrt, liquidity, false).unwrap();
+                prop_assert!(result.amount_out <= amount_out);
+                prop_assert!(result.amount_in >= amount_in);
+            } else {
+                let amount_out = get_delta_x(current_price_sqrt, next_price_sqrt, liquidity, false).unwrap();
+                let amount_in = get_delta_y(current_price_sqrt, next_price_sqrt, liquidity, false).unwrap();
+                prop_assert!(result.amount_out <= amount_out);
+                prop_assert!(result.amount_in >= amount_in);
+            }
+        }
+
+        #[test]
+        fn proptest_swap_round_trip(
+            current_price_sqrt in sqrt_price(),
+            target_price_sqrt in sqrt_price(),
+            liquidity in liquidity(),
+            amount in amount(),
+        ) {
+            prop_assume!(fits_into_token_amount(current_price_sqrt, target_price_sqrt, liquidity));
+
+            // swapping there and back can't give back more than was put in
+            let there = compute_swap_step(
+                current_price_sqrt,
+                target_price_sqrt,
+                liquidity,
+                amount,
+                true,
+                FixedPoint::new(0),
+            )
+            .unwrap();
+            prop_assume!(there.amount_out.0 <= MAX_AMOUNT);
+            let back = compute_swap_step(
+                there.next_price_sqrt,
+                current_price_sqrt,
+                liquidity,
+                there.amount_out,
+                true,
+                FixedPoint::new(0),
+            )
+            .unwrap();
+            prop_assert!(back.amount_out <= there.amount_in);
+        }
+
+        #[test]
+        fn proptest_get_delta_rounding(
+            sqrt_price_a in sqrt_price(),
+            sqrt_price_b in sqrt_price(),
+            liquidity in liquidity(),
+        ) {
+            if let (Some(up), Some(down)) = (
+                get_delta_x(sqrt_price_a, sqrt_price_b, liquidity, true),
+                get_delta_x(sqrt_price_a, sqrt_price_b, liquidity, false),
+            ) {
+                prop_assert!(up >= down);
+                prop_assert!(up.0 - down.0 <= 1);
+            }
+            if let (Some(up), Some(down)) = (
+                get_delta_y(sqrt_price_a, sqrt_price_b, liquidity, true),
+                get_delta_y(sqrt_price_a, sqrt_price_b, liquidity, false),
+            ) {
+                prop_assert!(up >= down);
+                prop_assert!(up.0 - down.0 <= 1);
+            }
+        }
+
+        #[test]
+        fn proptest_get_next_sqrt_price(
+            price_sqrt in sqrt_price(),
+            liquidity in liquidity(),
+            amount in amount(),
+        ) {
+            // price can't be pushed out of range
+            let max_amount_x = get_delta_x(Price::new(MIN_SQRT_PRICE), price_sqrt, liquidity, false);
+            let max_amount_y = get_delta_y(price_sqrt, Price::new(MAX_SQRT_PRICE), liquidity, false);
+            prop_assume!(amount <= max_amount_x.unwrap_or(TokenAmount(u64::MAX)));
+            prop_assume!(amount <= max_amount_y.unwrap_or(TokenAmount(u64::MAX)));
+
+            // adding x lowers the price, adding y raises it, both in favour of the pool
+            let price_after_x = get_next_sqrt_price_from_input(price_sqrt, liquidity, amount, true).unwrap();
+            prop_assert!(price_after_x <= price_sqrt);
+            if let Some(amount_x) = get_delta_x(price_after_x, price_sqrt, liquidity, false) {
+                prop_assert!(amount_x <= amount);
+            }
+
+            let price_after_y = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true).unwrap();
+            prop_assert!(price_after_y >= price_sqrt);
+            if let Some(amount_y) = get_delta_y(price_sqrt, price_after_y, liquidity, false) {
+                prop_assert!(amount_y <= amount);
+            }
+        }
+
+        #[test]
+        fn proptest_calculate_price_sqrt(tick in -MAX_TICK..MAX_TICK) {
+            let price_sqrt = calculate_price_sqrt(tick);
+            prop_assert!(price_sqrt < calculate_price_sqrt(tick + 1));
+            prop_assert!(price_sqrt >= Price::new(MIN_SQRT_PRICE));
+            prop_assert!(price_sqrt <= Price::new(MAX_SQRT_PRICE));
+            prop_assert_eq!(get_tick_at_sqrt_price(price_sqrt, 1), tick);
+        }
+
+        #[test]
+        fn proptest_get_tick_at_sqrt_price_with_spacing(
+            tick in -MAX_TICK..=MAX_TICK,
+            tick_spacing in prop_oneof![Just(1u16), Just(10u16), Just(100u16), 1u16..=100],
+        ) {
+            let tick = tick / tick_spacing as i32 * tick_spacing as i32;
+            prop_assert_eq!(get_tick_at_sqrt_price(calculate_price_sqrt(tick), tick_spacing), tick);
+        }
+    }
+
+    #[test]
+    fn test_tick_at_sqrt_price_extremes() {
+        assert_eq!(
+            get_tick_at_sqrt_price(calculate_price_sqrt(MAX_TICK), 1),
+            MAX_TICK
+        );
+        assert_eq!(
+            get_tick_at_sqrt_price(calculate_price_sqrt(-MAX_TICK), 1),
+            -MAX_TICK
+        );
+    }
+}
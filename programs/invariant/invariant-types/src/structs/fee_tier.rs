@@ -8,5 +8,8 @@ pub struct FeeTier {
     pub fee: FixedPoint,
     pub tick_spacing: u16,
     pub bump: u8,
+    pub version: u8,
+    pub default_protocol_fee: FixedPoint,
+    pub reserved: [u64; 2],
 }
 size!(FeeTier);
@@ -2,6 +2,19 @@ use anchor_lang::prelude::*;
 
 use crate::{decimals::*, size};
 
+pub const REWARDS_PER_POOL: usize = 3;
+
+#[zero_copy]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug, AnchorDeserialize)]
+pub struct PoolReward {
+    pub mint: Pubkey, // default if reward is not initialized
+    pub vault: Pubkey,
+    pub emissions_per_second: u64,
+    pub end_timestamp: u64,
+    pub growth_global: FeeGrowth,
+}
+
 #[account(zero_copy)]
 #[repr(packed)]
 #[derive(PartialEq, Default, Debug, AnchorDeserialize)]
@@ -28,6 +41,18 @@ pub struct Pool {
     pub fee_receiver: Pubkey,
     pub oracle_address: Pubkey,
     pub oracle_initialized: bool,
+    pub rewards: [PoolReward; REWARDS_PER_POOL],
+    pub rewards_last_timestamp: u64,
     pub bump: u8,
+    pub version: u8,
+    pub tickmap_extension: Pubkey, // default until the pool needs ticks outside of the tickmap
+    pub reserved: [u64; 12],
 }
 size!(Pool);
+
+impl Pool {
+    pub fn has_tickmap_extension(&self) -> bool {
+        let extension = self.tickmap_extension;
+        extension != Pubkey::default()
+    }
+}
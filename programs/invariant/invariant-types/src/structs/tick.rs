@@ -1,4 +1,4 @@
-use crate::{decimals::*, size};
+use crate::{decimals::*, size, structs::REWARDS_PER_POOL};
 use anchor_lang::prelude::*;
 
 #[account(zero_copy)]
@@ -15,6 +15,9 @@ pub struct Tick {
     pub fee_growth_outside_y: FeeGrowth,
     pub seconds_per_liquidity_outside: FixedPoint,
     pub seconds_outside: u64,
+    pub reward_growths_outside: [FeeGrowth; REWARDS_PER_POOL],
     pub bump: u8,
+    pub version: u8,
+    pub reserved: [u64; 8],
 }
 size!(Tick);
@@ -18,13 +18,38 @@ impl Default for Tickmap {
 
 size!(Tickmap);
 
+// Ticks outside of the tickmap, created only for pools that need the whole price range
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(AnchorDeserialize)]
+pub struct TickmapExtension {
+    pub lower: [u8; 22182], // (Extended tick limit - Tick limit) / 8, ticks below the tickmap
+    pub upper: [u8; 22182], // ticks above the tickmap
+}
+
+impl Default for TickmapExtension {
+    fn default() -> Self {
+        TickmapExtension {
+            lower: [0; 22182],
+            upper: [0; 22182],
+        }
+    }
+}
+
+size!(TickmapExtension);
+
 pub const TICK_LIMIT: i32 = 44_364; // If you change it update length of array as well!
 pub const TICK_SEARCH_RANGE: i32 = 256;
 pub const MAX_TICK: i32 = 221_818; // log(1.0001, sqrt(2^64-1))
+pub const EXTENDED_TICK_LIMIT: i32 = MAX_TICK + 1; // tick limit of a tickmap with an extension
 pub const TICK_CROSSES_PER_IX: usize = 19;
 pub const TICKMAP_SIZE: i32 = 2 * TICK_LIMIT - 1;
+const EXTENSION_SIDE_BITS: usize = (EXTENDED_TICK_LIMIT - TICK_LIMIT) as usize; // bits below and above the tickmap
+const TICKMAP_WORD_BYTES: usize = 8;
+const TICKMAP_WORD_BITS: usize = 64;
 
-fn tick_to_position(tick: i32, tick_spacing: u16) -> (usize, u8) {
+// index of the tick in a bitmap holding 2 * tick_limit ticks
+fn tick_to_index(tick: i32, tick_spacing: u16, tick_limit: i32) -> usize {
     assert_eq!(
         (tick % tick_spacing as i32),
         0,
@@ -34,23 +59,38 @@ fn tick_to_position(tick: i32, tick_spacing: u16) -> (usize, u8) {
     let bitmap_index = tick
         .checked_div(tick_spacing.try_into().unwrap())
         .unwrap()
-        .checked_add(TICK_LIMIT)
+        .checked_add(tick_limit)
         .unwrap();
+    assert!(
+        bitmap_index >= 0 && bitmap_index < 2 * tick_limit,
+        "tick outside of the tickmap"
+    );
 
-    let byte: usize = (bitmap_index.checked_div(8).unwrap()).try_into().unwrap();
-    let bit: u8 = (bitmap_index % 8).abs().try_into().unwrap();
+    bitmap_index.try_into().unwrap()
+}
+
+fn index_to_tick(index: usize, tick_spacing: u16, tick_limit: i32) -> i32 {
+    let index: i32 = index.try_into().unwrap();
 
-    (byte, bit)
+    index
+        .checked_sub(tick_limit)
+        .unwrap()
+        .checked_mul(tick_spacing.try_into().unwrap())
+        .unwrap()
 }
 
 // tick_spacing - spacing already scaled by tick_spacing
 pub fn get_search_limit(tick: i32, tick_spacing: u16, up: bool) -> i32 {
+    search_limit(tick, tick_spacing, up, TICK_LIMIT)
+}
+
+fn search_limit(tick: i32, tick_spacing: u16, up: bool, tick_limit: i32) -> i32 {
     let index = tick / tick_spacing as i32;
 
     // limit unsclaed
     let limit = if up {
         // ticks are limited by amount of space in the bitmap...
-        let array_limit = TICK_LIMIT.checked_sub(1).unwrap();
+        let array_limit = tick_limit.checked_sub(1).unwrap();
         // ...search range is limited to 256 at the time ...
         let range_limit = index.checked_add(TICK_SEARCH_RANGE).unwrap();
         // ...also ticks for prices over 2^64 aren't needed
@@ -58,7 +98,7 @@ pub fn get_search_limit(tick: i32, tick_spacing: u16, up: bool) -> i32 {
 
         array_limit.min(range_limit).min(price_limit)
     } else {
-        let array_limit = (-TICK_LIMIT).checked_add(1).unwrap();
+        let array_limit = (-tick_limit).checked_add(1).unwrap();
         let range_limit = index.checked_sub(TICK_SEARCH_RANGE).unwrap();
         let price_limit = -MAX_TICK.checked_div(tick_spacing as i32).unwrap();
 
@@ -69,18 +109,319 @@ pub fn get_search_limit(tick: i32, tick_spacing: u16, up: bool) -> i32 {
     limit.checked_mul(tick_spacing as i32).unwrap()
 }
 
+enum ExtendedPart {
+    Lower,
+    Tickmap,
+    Upper,
+}
+
+// splits index of the extended bitmap into the part holding it and index inside of that part
+fn split_extended_index(index: usize) -> (ExtendedPart, usize) {
+    let upper_start = EXTENSION_SIDE_BITS + 2 * TICK_LIMIT as usize;
+
+    if index < EXTENSION_SIDE_BITS {
+        (ExtendedPart::Lower, index)
+    } else if index < upper_start {
+        (ExtendedPart::Tickmap, index - EXTENSION_SIDE_BITS)
+    } else {
+        (ExtendedPart::Upper, index - upper_start)
+    }
+}
+
+// 64 bits of the bitmap starting at the given bit (bits outside of the bitmap are zeros)
+fn read_word(bitmap: &[u8], start: isize) -> u64 {
+    let bits = (bitmap.len() * 8) as isize;
+    if start <= -(TICKMAP_WORD_BITS as isize) || start >= bits {
+        return 0;
+    }
+    if start < 0 {
+        return read_word(bitmap, 0) << -start;
+    }
+
+    let byte = start as usize / 8;
+    let end = bitmap.len().min(byte + TICKMAP_WORD_BYTES + 1);
+
+    let mut bytes = [0u8; 2 * TICKMAP_WORD_BYTES];
+    bytes[..end - byte].copy_from_slice(&bitmap[byte..end]);
+
+    (u128::from_le_bytes(bytes) >> (start as usize % 8)) as u64
+}
+
+// flips the tick in the tickmap or in its extension, depending on which of them holds the tick
+pub fn flip_tick(
+    tickmap: &mut Tickmap,
+    extension: Option<&mut TickmapExtension>,
+    value: bool,
+    tick: i32,
+    tick_spacing: u16,
+) {
+    let view = TickmapView::new(tickmap, extension.as_deref());
+    assert!(
+        view.get(tick, tick_spacing) != value,
+        "tick initialize tick again"
+    );
+
+    let index = tick_to_index(tick, tick_spacing, view.tick_limit());
+    let (bitmap, index) = match extension {
+        None => (&mut tickmap.bitmap[..], index),
+        Some(extension) => match split_extended_index(index) {
+            (ExtendedPart::Lower, index) => (&mut extension.lower[..], index),
+            (ExtendedPart::Tickmap, index) => (&mut tickmap.bitmap[..], index),
+            (ExtendedPart::Upper, index) => (&mut extension.upper[..], index),
+        },
+    };
+
+    bitmap[index / 8] ^= 1 << (index % 8);
+}
+
 impl Tickmap {
     pub fn next_initialized(&self, tick: i32, tick_spacing: u16) -> Option<i32> {
+        TickmapView::new(self, None).next_initialized(tick, tick_spacing)
+    }
+
+    // tick_spacing - spacing already scaled by tick_spacing
+    pub fn prev_initialized(&self, tick: i32, tick_spacing: u16) -> Option<i32> {
+        TickmapView::new(self, None).prev_initialized(tick, tick_spacing)
+    }
+
+    pub fn get(&self, tick: i32, tick_spacing: u16) -> bool {
+        TickmapView::new(self, None).get(tick, tick_spacing)
+    }
+
+    pub fn flip(&mut self, value: bool, tick: i32, tick_spacing: u16) {
+        flip_tick(self, None, value, tick, tick_spacing)
+    }
+}
+
+// Tickmap of a pool together with its extension, if the pool has one
+#[derive(Clone, Copy)]
+pub struct TickmapView<'a> {
+    pub tickmap: &'a Tickmap,
+    pub extension: Option<&'a TickmapExtension>,
+}
+
+impl<'a> TickmapView<'a> {
+    pub fn new(tickmap: &'a Tickmap, extension: Option<&'a TickmapExtension>) -> Self {
+        TickmapView { tickmap, extension }
+    }
+
+    pub fn tick_limit(&self) -> i32 {
+        match self.extension {
+            Some(_) => EXTENDED_TICK_LIMIT,
+            None => TICK_LIMIT,
+        }
+    }
+
+    pub fn search_limit(&self, tick: i32, tick_spacing: u16, up: bool) -> i32 {
+        search_limit(tick, tick_spacing, up, self.tick_limit())
+    }
+
+    // lowest tick aligned to spacing the tickmap can hold
+    pub fn min_tick(&self, tick_spacing: u16) -> i32 {
+        self.search_limit(-MAX_TICK, tick_spacing, false)
+    }
+
+    // highest tick aligned to spacing the tickmap can hold
+    pub fn max_tick(&self, tick_spacing: u16) -> i32 {
+        self.search_limit(MAX_TICK, tick_spacing, true)
+    }
+
+    pub fn next_initialized(&self, tick: i32, tick_spacing: u16) -> Option<i32> {
+        let tick_limit = self.tick_limit();
+        let limit = self.search_limit(tick, tick_spacing, true);
+
+        // add 1 to not check current tick
+        let next_tick = tick.checked_add(tick_spacing as i32).unwrap();
+        if next_tick > limit {
+            return None;
+        }
+
+        let start = tick_to_index(next_tick, tick_spacing, tick_limit);
+        let limiting_index = tick_to_index(limit, tick_spacing, tick_limit);
+
+        let mut word_index = start / TICKMAP_WORD_BITS;
+        // ignore bits below the start on first word
+        let mut word = self.get_word(word_index) & (u64::MAX << (start % TICKMAP_WORD_BITS));
+
+        loop {
+            if word != 0 {
+                let index = word_index
+                    .checked_mul(TICKMAP_WORD_BITS)
+                    .unwrap()
+                    .checked_add(word.trailing_zeros() as usize)
+                    .unwrap();
+
+                // return first initalized tick if limiit is not exceeded, otherswise return None
+                return match index <= limiting_index {
+                    true => Some(index_to_tick(index, tick_spacing, tick_limit)),
+                    false => None,
+                };
+            }
+
+            // go to the next word
+            word_index = word_index.checked_add(1).unwrap();
+            if word_index.checked_mul(TICKMAP_WORD_BITS).unwrap() > limiting_index {
+                return None;
+            }
+            word = self.get_word(word_index);
+        }
+    }
+
+    // tick_spacing - spacing already scaled by tick_spacing
+    pub fn prev_initialized(&self, tick: i32, tick_spacing: u16) -> Option<i32> {
+        let tick_limit = self.tick_limit();
+        // don't subtract 1 to check the current tick
+        let limit = self.search_limit(tick, tick_spacing, false); // limit scaled by tick_spacing
+        if tick < limit {
+            return None;
+        }
+
+        let start = tick_to_index(tick, tick_spacing, tick_limit);
+        let limiting_index = tick_to_index(limit, tick_spacing, tick_limit);
+
+        let mut word_index = start / TICKMAP_WORD_BITS;
+        // ignore bits above the start on first word
+        let mut word = self.get_word(word_index)
+            & (u64::MAX >> (TICKMAP_WORD_BITS - 1 - start % TICKMAP_WORD_BITS));
+
+        loop {
+            if word != 0 {
+                let index = word_index
+                    .checked_mul(TICKMAP_WORD_BITS)
+                    .unwrap()
+                    .checked_add(TICKMAP_WORD_BITS - 1 - word.leading_zeros() as usize)
+                    .unwrap();
+
+                // return first initalized tick if limiit is not exceeded, otherswise return None
+                return match index >= limiting_index {
+                    true => Some(index_to_tick(index, tick_spacing, tick_limit)),
+                    false => None,
+                };
+            }
+
+            // limit is inside of the current word
+            if word_index.checked_mul(TICKMAP_WORD_BITS).unwrap() <= limiting_index {
+                return None;
+            }
+
+            // go to the previous word
+            word_index = word_index.checked_sub(1).unwrap();
+            word = self.get_word(word_index);
+        }
+    }
+
+    // 64 bits of the bitmap starting at word_index * 64, the tickmap sits between both parts of the extension
+    fn get_word(&self, word_index: usize) -> u64 {
+        let start = word_index.checked_mul(TICKMAP_WORD_BITS).unwrap() as isize;
+
+        match self.extension {
+            None => read_word(&self.tickmap.bitmap, start),
+            Some(extension) => {
+                let tickmap_start = EXTENSION_SIDE_BITS as isize;
+                let upper_start = tickmap_start + 2 * TICK_LIMIT as isize;
+
+                read_word(&extension.lower, start)
+                    | read_word(&self.tickmap.bitmap, start - tickmap_start)
+                    | read_word(&extension.upper, start - upper_start)
+            }
+        }
+    }
+
+    pub fn get(&self, tick: i32, tick_spacing: u16) -> bool {
+        let index = tick_to_index(tick, tick_spacing, self.tick_limit());
+        let (bitmap, index) = match self.extension {
+            None => (&self.tickmap.bitmap[..], index),
+            Some(extension) => match split_extended_index(index) {
+                (ExtendedPart::Lower, index) => (&extension.lower[..], index),
+                (ExtendedPart::Tickmap, index) => (&self.tickmap.bitmap[..], index),
+                (ExtendedPart::Upper, index) => (&extension.upper[..], index),
+            },
+        };
+
+        (bitmap[index / 8] >> (index % 8)) % 2 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // byte and bit of the tick in the tickmap
+    fn tick_to_position(tick: i32, tick_spacing: u16) -> (usize, u8) {
+        let index = tick_to_index(tick, tick_spacing, TICK_LIMIT);
+
+        (index / 8, (index % 8) as u8)
+    }
+
+    #[test]
+    fn test_next_and_prev_initialized() {
+        // initalized edges
+        {
+            for spacing in 1..=10 {
+                println!("spacing = {}", spacing);
+                let mut map = Tickmap::default();
+                let max_index = match spacing < 5 {
+                    true => TICK_LIMIT - spacing,
+                    false => (MAX_TICK / spacing) * spacing,
+                };
+                let min_index = -max_index;
+                println!("max_index = {}", max_index);
+                println!("min_index = {}", min_index);
+
+                map.flip(true, max_index, spacing as u16);
+                map.flip(true, min_index, spacing as u16);
+
+                let tick_edge_diff = TICK_SEARCH_RANGE / spacing * spacing;
+
+                let prev = map.prev_initialized(min_index + tick_edge_diff, spacing as u16);
+                let next = map.next_initialized(max_index - tick_edge_diff, spacing as u16);
+
+                if prev.is_some() {
+                    println!("found prev = {}", prev.unwrap());
+                }
+                if next.is_some() {
+                    println!("found next = {}", next.unwrap());
+                }
+            }
+        }
+        // unintalized edges
+        for spacing in 1..=1000 {
+            let map = Tickmap::default();
+
+            let max_index = match spacing < 5 {
+                true => TICK_LIMIT - spacing,
+                false => (MAX_TICK / spacing) * spacing,
+            };
+            let min_index = -max_index;
+            let tick_edge_diff = TICK_SEARCH_RANGE / spacing * spacing;
+
+            let prev = map.prev_initialized(min_index + tick_edge_diff, spacing as u16);
+            let next = map.next_initialized(max_index - tick_edge_diff, spacing as u16);
+
+            if prev.is_some() {
+                println!("found prev = {}", prev.unwrap());
+            }
+            if next.is_some() {
+                println!("found next = {}", next.unwrap());
+            }
+        }
+    }
+
+    // byte by byte search used before word level search, kept as a reference
+    fn next_initialized_bytewise(map: &Tickmap, tick: i32, tick_spacing: u16) -> Option<i32> {
         let limit = get_search_limit(tick, tick_spacing, true);
 
         // add 1 to not check current tick
-        let (mut byte, mut bit) =
-            tick_to_position(tick.checked_add(tick_spacing as i32).unwrap(), tick_spacing);
+        let next_tick = tick.checked_add(tick_spacing as i32).unwrap();
+        if next_tick > limit {
+            return None;
+        }
+        let (mut byte, mut bit) = tick_to_position(next_tick, tick_spacing);
         let (limiting_byte, limiting_bit) = tick_to_position(limit, tick_spacing);
 
         while byte < limiting_byte || (byte == limiting_byte && bit <= limiting_bit) {
             // ignore some bits on first loop
-            let mut shifted = self.bitmap[byte] >> bit;
+            let mut shifted = map.bitmap[byte] >> bit;
 
             // go through all bits in byte until it is zero
             if shifted != 0 {
@@ -122,7 +463,7 @@ impl Tickmap {
     }
 
     // tick_spacing - spacing already scaled by tick_spacing
-    pub fn prev_initialized(&self, tick: i32, tick_spacing: u16) -> Option<i32> {
+    fn prev_initialized_bytewise(map: &Tickmap, tick: i32, tick_spacing: u16) -> Option<i32> {
         // don't subtract 1 to check the current tick
         let limit = get_search_limit(tick, tick_spacing, false); // limit scaled by tick_spacing
         let (mut byte, mut bit) = tick_to_position(tick as i32, tick_spacing);
@@ -131,7 +472,7 @@ impl Tickmap {
         while byte > limiting_byte || (byte == limiting_byte && bit >= limiting_bit) {
             // always safe due to limitated domain of bit variable
             let mut mask = 1u16.checked_shl(bit.try_into().unwrap()).unwrap(); // left = MSB direction (increase value)
-            let value = self.bitmap[byte] as u16;
+            let value = map.bitmap[byte] as u16;
 
             // enter if some of previous bits are initialized in current byte
             if value.checked_rem(mask.checked_shl(1).unwrap()).unwrap() > 0 {
@@ -176,79 +517,132 @@ impl Tickmap {
         None
     }
 
-    pub fn get(&self, tick: i32, tick_spacing: u16) -> bool {
-        let (byte, bit) = tick_to_position(tick, tick_spacing);
-        let value = (self.bitmap[byte] >> bit) % 2;
-
-        (value) == 1
-    }
-
-    pub fn flip(&mut self, value: bool, tick: i32, tick_spacing: u16) {
-        assert!(
-            self.get(tick, tick_spacing) != value,
-            "tick initialize tick again"
-        );
+    #[test]
+    fn test_word_search_matches_bytewise_search() {
+        // xorshift, deterministic pseudo random numbers
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut random = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for spacing in [1u16, 2, 3, 4, 5, 10, 60, 100, 1000] {
+            let max_index = match spacing < 5 {
+                true => TICK_LIMIT - spacing as i32,
+                false => (MAX_TICK / spacing as i32) * spacing as i32,
+            };
+            let max_position = max_index / spacing as i32;
 
-        let (byte, bit) = tick_to_position(tick, tick_spacing);
+            // empty, sparse and dense tickmaps
+            for density in [0u64, 1, 16, 512] {
+                let mut map = Tickmap::default();
+                for _ in 0..density {
+                    let position = (random() % (2 * max_position as u64 + 1)) as i32 - max_position;
+                    let tick = position * spacing as i32;
+                    if !map.get(tick, spacing) {
+                        map.flip(true, tick, spacing);
+                    }
+                }
 
-        self.bitmap[byte] ^= 1 << bit;
+                let mut ticks = vec![-max_index, max_index, 0];
+                ticks.extend((0..200).map(|_| {
+                    ((random() % (2 * max_position as u64 + 1)) as i32 - max_position)
+                        * spacing as i32
+                }));
+
+                for tick in ticks {
+                    assert_eq!(
+                        map.next_initialized(tick, spacing),
+                        next_initialized_bytewise(&map, tick, spacing)
+                    );
+                    assert_eq!(
+                        map.prev_initialized(tick, spacing),
+                        prev_initialized_bytewise(&map, tick, spacing)
+                    );
+                }
+            }
+        }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_next_and_prev_initialized() {
-        // initalized edges
-        {
-            for spacing in 1..=10 {
-                println!("spacing = {}", spacing);
-                let mut map = Tickmap::default();
-                let max_index = match spacing < 5 {
-                    true => TICK_LIMIT - spacing,
-                    false => (MAX_TICK / spacing) * spacing,
-                };
-                let min_index = -max_index;
-                println!("max_index = {}", max_index);
-                println!("min_index = {}", min_index);
-
-                map.flip(true, max_index, spacing as u16);
-                map.flip(true, min_index, spacing as u16);
-
-                let tick_edge_diff = TICK_SEARCH_RANGE / spacing * spacing;
-
-                let prev = map.prev_initialized(min_index + tick_edge_diff, spacing as u16);
-                let next = map.next_initialized(max_index - tick_edge_diff, spacing as u16);
-
-                if prev.is_some() {
-                    println!("found prev = {}", prev.unwrap());
-                }
-                if next.is_some() {
-                    println!("found next = {}", next.unwrap());
+    fn test_extended_search() {
+        // xorshift, deterministic pseudo random numbers
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut random = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for spacing in [1u16, 2, 3, 4] {
+            let max_position = MAX_TICK / spacing as i32;
+            let borders = [
+                -max_position,
+                -TICK_LIMIT - 1,
+                -TICK_LIMIT,
+                TICK_LIMIT - 1,
+                TICK_LIMIT,
+                max_position,
+            ];
+
+            let mut tickmap = Tickmap::default();
+            let mut extension = TickmapExtension::default();
+            for position in borders {
+                flip_tick(
+                    &mut tickmap,
+                    Some(&mut extension),
+                    true,
+                    position * spacing as i32,
+                    spacing,
+                );
+            }
+            for _ in 0..2048 {
+                let position = (random() % (2 * max_position as u64 + 1)) as i32 - max_position;
+                let tick = position * spacing as i32;
+                let view = TickmapView::new(&tickmap, Some(&extension));
+                if !view.get(tick, spacing) {
+                    flip_tick(&mut tickmap, Some(&mut extension), true, tick, spacing);
                 }
             }
-        }
-        // unintalized edges
-        for spacing in 1..=1000 {
-            let map = Tickmap::default();
 
-            let max_index = match spacing < 5 {
-                true => TICK_LIMIT - spacing,
-                false => (MAX_TICK / spacing) * spacing,
+            let view = TickmapView::new(&tickmap, Some(&extension));
+            // searches checking every tick in the range one by one
+            let next_tick_by_tick = |tick: i32| {
+                let limit = view.search_limit(tick, spacing, true);
+                (tick + spacing as i32..=limit)
+                    .step_by(spacing as usize)
+                    .find(|tick| view.get(*tick, spacing))
+            };
+            let prev_tick_by_tick = |tick: i32| {
+                let limit = view.search_limit(tick, spacing, false);
+                (limit..=tick)
+                    .rev()
+                    .step_by(spacing as usize)
+                    .find(|tick| view.get(*tick, spacing))
             };
-            let min_index = -max_index;
-            let tick_edge_diff = TICK_SEARCH_RANGE / spacing * spacing;
-
-            let prev = map.prev_initialized(min_index + tick_edge_diff, spacing as u16);
-            let next = map.next_initialized(max_index - tick_edge_diff, spacing as u16);
 
-            if prev.is_some() {
-                println!("found prev = {}", prev.unwrap());
-            }
-            if next.is_some() {
-                println!("found next = {}", next.unwrap());
+            let mut ticks: Vec<i32> = borders
+                .iter()
+                .flat_map(|position| [position - 1, *position, position + 1])
+                .filter(|position| position.abs() <= max_position)
+                .map(|position| position * spacing as i32)
+                .collect();
+            ticks.extend((0..500).map(|_| {
+                ((random() % (2 * max_position as u64 + 1)) as i32 - max_position) * spacing as i32
+            }));
+
+            for tick in ticks {
+                assert_eq!(
+                    view.next_initialized(tick, spacing),
+                    next_tick_by_tick(tick)
+                );
+                assert_eq!(
+                    view.prev_initialized(tick, spacing),
+                    prev_tick_by_tick(tick)
+                );
             }
         }
     }
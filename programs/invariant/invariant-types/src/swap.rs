@@ -0,0 +1,339 @@
+use std::cell::RefCell;
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::{
+    decimals::*,
+    err, function, location,
+    log::get_tick_at_sqrt_price,
+    math::{compute_swap_step, cross_tick, get_closer_limit, is_enough_amount_to_push_price},
+    ok_or_mark_trace,
+    structs::{Pool, Tick, TickmapView, TICK_CROSSES_PER_IX},
+    trace,
+    utils::{get_tick_address, ErrorCause, TrackableError, TrackableResult},
+};
+
+// Simulates the swap loop of the program and collects indexes of initialized ticks
+// the swap will reach, in the order they are reached (at most TICK_CROSSES_PER_IX)
+pub fn get_swap_tick_indexes(
+    pool: &Pool,
+    tickmap: &TickmapView, // tickmap of the pool together with its extension if it has one
+    ticks: &[Tick], // initialized ticks of the pool, only ticks in swap direction are required
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: Price,
+) -> TrackableResult<Vec<i32>> {
+    let mut pool = *pool;
+    let mut remaining_amount = amount;
+    let mut indexes = Vec::new();
+
+    let min_tick = tickmap.min_tick(pool.tick_spacing);
+    let max_tick = tickmap.max_tick(pool.tick_spacing);
+
+    while !remaining_amount.is_zero() && indexes.len() < TICK_CROSSES_PER_IX {
+        // swap can't go past the last tick of the tickmap, so no more ticks are needed
+        if (x_to_y && pool.current_tick_index < min_tick)
+            || (!x_to_y && pool.current_tick_index >= max_tick)
+        {
+            break;
+        }
+
+        let (swap_limit, limiting_tick) = get_closer_limit(
+            sqrt_price_limit,
+            x_to_y,
+            pool.current_tick_index,
+            pool.tick_spacing,
+            tickmap,
+        )
+        .map_err(|_| err!(ErrorCause::LimitReached))?;
+
+        let result = ok_or_mark_trace!(compute_swap_step(
+            pool.sqrt_price,
+            swap_limit,
+            pool.liquidity,
+            remaining_amount,
+            by_amount_in,
+            pool.fee,
+        ))?;
+
+        remaining_amount = if by_amount_in {
+            remaining_amount.checked_sub(result.amount_in + result.fee_amount)
+        } else {
+            remaining_amount.checked_sub(result.amount_out)
+        }
+        .map_err(|_| err!(ErrorCause::Sub))?;
+
+        pool.sqrt_price = result.next_price_sqrt;
+
+        // swap would stop at the price limit
+        if { pool.sqrt_price } == sqrt_price_limit && !remaining_amount.is_zero() {
+            break;
+        }
+
+        match limiting_tick {
+            Some((tick_index, initialized)) if result.next_price_sqrt == swap_limit => {
+                let is_enough_amount_to_cross = ok_or_mark_trace!(is_enough_amount_to_push_price(
+                    remaining_amount,
+                    result.next_price_sqrt,
+                    pool.liquidity,
+                    pool.fee,
+                    by_amount_in,
+                    x_to_y,
+                ))?;
+
+                if initialized {
+                    // tick account is loaded by the program whenever its price is reached
+                    indexes.push(tick_index);
+
+                    let tick = ticks
+                        .iter()
+                        .find(|tick| { tick.index } == tick_index)
+                        .ok_or_else(|| err!(ErrorCause::TickNotProvided))?;
+
+                    if !x_to_y || is_enough_amount_to_cross {
+                        let tick = RefCell::new(*tick);
+                        cross_tick(&mut tick.borrow_mut(), &mut pool)
+                            .map_err(|_| err!(ErrorCause::InvalidPoolLiquidity))?;
+                    } else {
+                        remaining_amount = TokenAmount(0);
+                    }
+                }
+
+                pool.current_tick_index = if x_to_y && is_enough_amount_to_cross {
+                    tick_index
+                        .checked_sub(pool.tick_spacing as i32)
+                        .ok_or_else(|| err!(ErrorCause::Sub))?
+                } else {
+                    tick_index
+                };
+            }
+            _ => {
+                pool.current_tick_index =
+                    get_tick_at_sqrt_price(result.next_price_sqrt, pool.tick_spacing);
+            }
+        }
+    }
+
+    Ok(indexes)
+}
+
+// Addresses of tick accounts that should be passed as remaining accounts of the swap
+#[allow(clippy::too_many_arguments)]
+pub fn get_swap_tick_addresses(
+    pool_address: Pubkey,
+    pool: &Pool,
+    tickmap: &TickmapView,
+    ticks: &[Tick],
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: Price,
+) -> TrackableResult<Vec<Pubkey>> {
+    let indexes = ok_or_mark_trace!(get_swap_tick_indexes(
+        pool,
+        tickmap,
+        ticks,
+        x_to_y,
+        amount,
+        by_amount_in,
+        sqrt_price_limit,
+    ))?;
+
+    Ok(indexes
+        .into_iter()
+        .map(|index| get_tick_address(pool_address, index))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{math::calculate_price_sqrt, structs::Tickmap, MAX_SQRT_PRICE, MIN_SQRT_PRICE};
+
+    use super::*;
+
+    fn init_pool(tick_spacing: u16, liquidity: Liquidity) -> Pool {
+        Pool {
+            tick_spacing,
+            fee: FixedPoint::from_scale(6, 4),
+            liquidity,
+            sqrt_price: calculate_price_sqrt(0),
+            current_tick_index: 0,
+            ..Default::default()
+        }
+    }
+
+    // position with given liquidity in <lower, upper> range
+    fn init_ticks(
+        tickmap: &mut Tickmap,
+        ticks: &mut Vec<Tick>,
+        tick_spacing: u16,
+        lower: i32,
+        upper: i32,
+        liquidity: Liquidity,
+    ) {
+        for (index, sign) in [(lower, true), (upper, false)] {
+            tickmap.flip(true, index, tick_spacing);
+            ticks.push(Tick {
+                index,
+                sign,
+                liquidity_change: liquidity,
+                liquidity_gross: liquidity,
+                sqrt_price: calculate_price_sqrt(index),
+                ..Default::default()
+            });
+        }
+    }
+
+    #[test]
+    fn test_get_swap_tick_indexes() {
+        let tick_spacing = 10;
+        let liquidity = Liquidity::from_integer(1_000_000);
+        let mut tickmap = Tickmap::default();
+        let mut ticks = vec![];
+        init_ticks(&mut tickmap, &mut ticks, tick_spacing, -20, 20, liquidity);
+        init_ticks(&mut tickmap, &mut ticks, tick_spacing, -100, 100, liquidity);
+        let pool = init_pool(tick_spacing, liquidity + liquidity);
+        let min_price = Price::new(MIN_SQRT_PRICE);
+        let max_price = Price::new(MAX_SQRT_PRICE);
+
+        // small swap does not reach any tick
+        {
+            let indexes = get_swap_tick_indexes(
+                &pool,
+                &TickmapView::new(&tickmap, None),
+                &ticks,
+                true,
+                TokenAmount(10),
+                true,
+                min_price,
+            )
+            .unwrap();
+            assert_eq!(indexes, Vec::<i32>::new());
+        }
+        // x to y crosses ticks in descending order
+        {
+            let indexes = get_swap_tick_indexes(
+                &pool,
+                &TickmapView::new(&tickmap, None),
+                &ticks,
+                true,
+                TokenAmount(10_000),
+                true,
+                min_price,
+            )
+            .unwrap();
+            assert_eq!(indexes, vec![-20, -100]);
+        }
+        // y to x crosses ticks in ascending order
+        {
+            let indexes = get_swap_tick_indexes(
+                &pool,
+                &TickmapView::new(&tickmap, None),
+                &ticks,
+                false,
+                TokenAmount(10_000),
+                true,
+                max_price,
+            )
+            .unwrap();
+            assert_eq!(indexes, vec![20, 100]);
+        }
+        // ticks behind price limit are not needed
+        {
+            let indexes = get_swap_tick_indexes(
+                &pool,
+                &TickmapView::new(&tickmap, None),
+                &ticks,
+                false,
+                TokenAmount(10_000),
+                true,
+                calculate_price_sqrt(50),
+            )
+            .unwrap();
+            assert_eq!(indexes, vec![20]);
+        }
+        // missing tick
+        {
+            let result = get_swap_tick_indexes(
+                &pool,
+                &TickmapView::new(&tickmap, None),
+                &ticks[..2],
+                false,
+                TokenAmount(10_000),
+                true,
+                max_price,
+            );
+            assert_eq!(result.unwrap_err().cause, ErrorCause::TickNotProvided);
+        }
+    }
+
+    #[test]
+    fn test_get_swap_tick_indexes_stops_at_tickmap_limit() {
+        let tick_spacing = 1;
+        let tickmap = Tickmap::default();
+        let tickmap = TickmapView::new(&tickmap, None);
+        let max_tick = tickmap.max_tick(tick_spacing);
+        let current_tick_index = max_tick - 300;
+        let pool = Pool {
+            sqrt_price: calculate_price_sqrt(current_tick_index),
+            current_tick_index,
+            ..init_pool(tick_spacing, Liquidity::from_integer(1_000))
+        };
+
+        let indexes = get_swap_tick_indexes(
+            &pool,
+            &tickmap,
+            &[],
+            false,
+            TokenAmount(1_000_000_000),
+            true,
+            Price::new(MAX_SQRT_PRICE),
+        )
+        .unwrap();
+        assert_eq!(indexes, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_get_swap_tick_indexes_crosses_limit() {
+        let tick_spacing = 1;
+        let liquidity = Liquidity::from_integer(1_000);
+        let mut tickmap = Tickmap::default();
+        let mut ticks = vec![];
+        for i in 1..=30 {
+            init_ticks(&mut tickmap, &mut ticks, tick_spacing, -i, i, liquidity);
+        }
+        let pool = init_pool(tick_spacing, Liquidity::from_integer(30_000));
+
+        let indexes = get_swap_tick_indexes(
+            &pool,
+            &TickmapView::new(&tickmap, None),
+            &ticks,
+            true,
+            TokenAmount(u64::MAX),
+            true,
+            Price::new(MIN_SQRT_PRICE),
+        )
+        .unwrap();
+        assert_eq!(indexes.len(), TICK_CROSSES_PER_IX);
+        assert_eq!(indexes[0], -1);
+        assert_eq!(
+            indexes[TICK_CROSSES_PER_IX - 1],
+            -(TICK_CROSSES_PER_IX as i32)
+        );
+
+        let pool_address = Pubkey::new_unique();
+        let addresses = get_swap_tick_addresses(
+            pool_address,
+            &pool,
+            &TickmapView::new(&tickmap, None),
+            &ticks,
+            true,
+            TokenAmount(u64::MAX),
+            true,
+            Price::new(MIN_SQRT_PRICE),
+        )
+        .unwrap();
+        assert_eq!(addresses[0], get_tick_address(pool_address, -1));
+    }
+}
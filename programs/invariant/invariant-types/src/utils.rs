@@ -1,32 +1,82 @@
 use std::cmp::Ordering;
+use std::fmt;
 
-use anchor_lang::prelude::Pubkey;
+use anchor_lang::prelude::{Error, Pubkey};
 
-use crate::ID;
+use crate::{errors::InvariantErrorCode, ID, TICK_SEED};
 
 pub type TrackableResult<T> = Result<T, TrackableError>;
 
-#[derive(Debug)]
-pub struct TrackableError {
-    pub cause: String,
-    pub stack: Vec<String>,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorCause {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Cast(&'static str), // name of the target type
+    AmountOverflow,     // token amount doesn't fit into u64
+    Decimal(String),    // failed checked operation of the decimal crate
+    ZeroLiquidity,
+    ZeroPrice,
+    TickNotProvided,
+    InvalidPoolLiquidity,
+    LimitReached,
 }
 
-// static error causes
-impl TrackableError {
-    pub const ADD: &'static str = "addition overflow";
-    pub const SUB: &'static str = "subtraction underflow";
-    pub const MUL: &'static str = "multiplication overflow";
-    pub const DIV: &'static str = "division overflow or division by zero";
-    pub fn cast<T: ?Sized>() -> String {
-        format!("conversion to {} type failed", std::any::type_name::<T>())
+impl ErrorCause {
+    pub fn cast<T: ?Sized>() -> Self {
+        ErrorCause::Cast(std::any::type_name::<T>())
+    }
+
+    // program error returned in the same situation
+    pub fn code(&self) -> InvariantErrorCode {
+        match self {
+            ErrorCause::Add
+            | ErrorCause::Sub
+            | ErrorCause::Mul
+            | ErrorCause::Div
+            | ErrorCause::Cast(_)
+            | ErrorCause::AmountOverflow
+            | ErrorCause::Decimal(_) => InvariantErrorCode::MathOverflow,
+            ErrorCause::ZeroLiquidity | ErrorCause::InvalidPoolLiquidity => {
+                InvariantErrorCode::InvalidPoolLiquidity
+            }
+            ErrorCause::ZeroPrice => InvariantErrorCode::InvalidSqrtPrice,
+            ErrorCause::TickNotProvided => InvariantErrorCode::TickNotFound,
+            ErrorCause::LimitReached => InvariantErrorCode::LimitReached,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorCause::Add => write!(f, "addition overflow"),
+            ErrorCause::Sub => write!(f, "subtraction underflow"),
+            ErrorCause::Mul => write!(f, "multiplication overflow"),
+            ErrorCause::Div => write!(f, "division overflow or division by zero"),
+            ErrorCause::Cast(type_name) => write!(f, "conversion to {} type failed", type_name),
+            ErrorCause::AmountOverflow => write!(f, "token amount overflow"),
+            ErrorCause::Decimal(message) => write!(f, "{}", message),
+            ErrorCause::ZeroLiquidity => write!(f, "zero liquidity"),
+            ErrorCause::ZeroPrice => write!(f, "zero sqrt price"),
+            ErrorCause::TickNotProvided => write!(f, "initialized tick not provided"),
+            ErrorCause::InvalidPoolLiquidity => write!(f, "invalid pool liquidity"),
+            ErrorCause::LimitReached => write!(f, "absolute price limit reached"),
+        }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackableError {
+    pub cause: ErrorCause,
+    pub stack: Vec<String>,
+}
+
 impl TrackableError {
-    pub fn new(cause: &str, location: &str) -> Self {
+    pub fn new(cause: ErrorCause, location: &str) -> Self {
         Self {
-            cause: cause.to_string(),
+            cause,
             stack: vec![location.to_string()],
         }
     }
@@ -35,21 +85,33 @@ impl TrackableError {
         self.stack.push(location.to_string());
     }
 
-    pub fn to_string(&self) -> String {
+    pub fn code(&self) -> InvariantErrorCode {
+        self.cause.code()
+    }
+
+    pub fn get(&self) -> (String, String, Vec<String>) {
+        (self.to_string(), self.cause.to_string(), self.stack.clone())
+    }
+}
+
+impl fmt::Display for TrackableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let stack_trace = self.stack.join("\n-> ");
 
-        format!(
+        write!(
+            f,
             "ERROR CAUSED BY: {}\nINVARIANT STACK TRACE:\n-> {}",
             self.cause, stack_trace
         )
     }
+}
 
-    pub fn get(&self) -> (String, String, Vec<String>) {
-        (
-            self.to_string().clone(),
-            self.cause.clone(),
-            self.stack.clone(),
-        )
+impl std::error::Error for TrackableError {}
+
+// the stack trace is dropped, the code and its message are kept
+impl From<TrackableError> for Error {
+    fn from(error: TrackableError) -> Self {
+        error.code().into()
     }
 }
 
@@ -78,6 +140,14 @@ pub fn get_pool_address(
     pool_address
 }
 
+pub fn get_tick_address(pool: Pubkey, index: i32) -> Pubkey {
+    let (tick_address, _) = Pubkey::find_program_address(
+        &[TICK_SEED.as_bytes(), pool.as_ref(), &index.to_le_bytes()],
+        &ID,
+    );
+    tick_address
+}
+
 #[macro_use]
 pub mod trackable_result {
     #[macro_export]
@@ -85,7 +155,7 @@ pub mod trackable_result {
         ($op:expr) => {
             match $op {
                 Ok(ok) => Ok(ok),
-                Err(err) => Err(err!(&err)),
+                Err(err) => Err(err!($crate::utils::ErrorCause::Decimal(err.to_string()))),
             }
         };
     }
@@ -153,7 +223,7 @@ mod trackable_error_tests {
 
     fn trigger_error() -> TrackableResult<u64> {
         let _ = ok_or_mark_trace!(outer_fun())?; // unwrap without propagate error
-        Err(err!("trigger error"))
+        Err(err!(ErrorCause::Div))
     }
 
     fn trigger_result_error() -> Result<u64, String> {
@@ -191,7 +261,9 @@ mod trackable_error_tests {
 
             println!("{}", format);
             assert_eq!(stack.len(), 3);
-            assert_eq!(cause, "trigger error");
+            assert_eq!(cause, "division overflow or division by zero");
+            assert_eq!(err.cause, ErrorCause::Div);
+            assert!(matches!(err.code(), InvariantErrorCode::MathOverflow));
         }
         // from_result
         {
@@ -200,6 +272,34 @@ mod trackable_error_tests {
             println!("{}", format);
             assert_eq!(stack.len(), 2);
             assert_eq!(cause, "trigger error [result])");
+            assert_eq!(
+                err.cause,
+                ErrorCause::Decimal("trigger error [result])".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_into_anchor_error() {
+        let err: Error = outer_fun_err().unwrap_err().into();
+        match err {
+            Error::AnchorError(err) => {
+                assert_eq!(
+                    err.error_code_number,
+                    u32::from(InvariantErrorCode::MathOverflow)
+                );
+                assert_eq!(err.error_name, "MathOverflow");
+            }
+            _ => panic!("expected anchor error"),
+        }
+
+        let err: Error = err!(ErrorCause::TickNotProvided).into();
+        match err {
+            Error::AnchorError(err) => assert_eq!(
+                err.error_code_number,
+                u32::from(InvariantErrorCode::TickNotFound)
+            ),
+            _ => panic!("expected anchor error"),
         }
     }
 }
@@ -2,6 +2,7 @@ use core::convert::TryFrom;
 use core::convert::TryInto;
 pub use decimal::*;
 
+use crate::errors::ErrorCode;
 use anchor_lang::prelude::*;
 
 pub const PRICE_LIQUIDITY_DENOMINATOR: u128 = 1__0000_0000__0000_0000__00u128;
@@ -54,33 +55,31 @@ impl FeeGrowth {
         FeeGrowth::new(self.get() - other.get())
     }
 
-    pub fn from_fee(liquidity: Liquidity, fee: TokenAmount) -> Self {
-        FeeGrowth::new(
-            U256::from(fee.get())
-                .checked_mul(FeeGrowth::one())
-                .unwrap()
-                .checked_mul(Liquidity::one())
-                .unwrap()
-                .checked_div(liquidity.here())
-                .unwrap()
-                .try_into()
-                .unwrap(),
-        )
+    pub fn from_fee(liquidity: Liquidity, fee: TokenAmount) -> Result<Self> {
+        let fee_growth = U256::from(fee.get())
+            .checked_mul(FeeGrowth::one())
+            .and_then(|value| value.checked_mul(Liquidity::one()))
+            .and_then(|value| value.checked_div(liquidity.here()))
+            .ok_or(ErrorCode::MathOverflow)?
+            .try_into()
+            .map_err(|_| ErrorCode::MathOverflow)?;
+
+        Ok(FeeGrowth::new(fee_growth))
     }
 
-    pub fn to_fee(self, liquidity: Liquidity) -> FixedPoint {
-        FixedPoint::new(
-            U256::try_from(self.get())
-                .unwrap()
-                .checked_mul(liquidity.here())
-                .unwrap()
-                .checked_div(U256::from(10).pow(U256::from(
+    pub fn to_fee(self, liquidity: Liquidity) -> Result<FixedPoint> {
+        let fee = U256::from(self.get())
+            .checked_mul(liquidity.here())
+            .and_then(|value| {
+                value.checked_div(U256::from(10).pow(U256::from(
                     FeeGrowth::scale() + Liquidity::scale() - FixedPoint::scale(),
                 )))
-                .unwrap()
-                .try_into()
-                .unwrap_or_else(|_| panic!("value too big to parse in `FeeGrowth::to_fee`")),
-        )
+            })
+            .ok_or(ErrorCode::MathOverflow)?
+            .try_into()
+            .map_err(|_| ErrorCode::MathOverflow)?;
+
+        Ok(FixedPoint::new(fee))
     }
 }
 
@@ -96,55 +95,35 @@ impl FixedPoint {
 
 impl Price {
     pub fn big_div_values_to_token(nominator: U256, denominator: U256) -> Option<TokenAmount> {
-        Some(TokenAmount::new(
-            match nominator
-                .checked_mul(Self::one::<U256>())
-                .unwrap()
-                .checked_div(denominator)
-                .unwrap()
-                .checked_div(Self::one::<U256>())
-                .unwrap()
-                .try_into()
-            {
-                Ok(v) => v,
-                Err(_) => return None,
-            },
-        ))
+        let token_amount = nominator
+            .checked_mul(Self::one::<U256>())?
+            .checked_div(denominator)?
+            .checked_div(Self::one::<U256>())?
+            .try_into()
+            .ok()?;
+        Some(TokenAmount::new(token_amount))
     }
 
     pub fn big_div_values_to_token_up(nominator: U256, denominator: U256) -> Option<TokenAmount> {
-        Some(TokenAmount::new({
-            match nominator
-                .checked_mul(Self::one::<U256>())
-                .unwrap()
-                .checked_add(denominator.checked_sub(U256::from(1u32)).unwrap())
-                .unwrap()
-                .checked_div(denominator)
-                .unwrap()
-                .checked_add(Self::almost_one::<U256>())
-                .unwrap()
-                .checked_div(Self::one::<U256>())
-                .unwrap()
-                .try_into()
-            {
-                Ok(v) => v,
-                Err(_) => return None,
-            }
-        }))
+        let token_amount = nominator
+            .checked_mul(Self::one::<U256>())?
+            .checked_add(denominator.checked_sub(U256::from(1u32))?)?
+            .checked_div(denominator)?
+            .checked_add(Self::almost_one::<U256>())?
+            .checked_div(Self::one::<U256>())?
+            .try_into()
+            .ok()?;
+        Some(TokenAmount::new(token_amount))
     }
 
-    pub fn big_div_values_up(nominator: U256, denominator: U256) -> Price {
-        Price::new({
-            nominator
-                .checked_mul(Self::one::<U256>())
-                .unwrap()
-                .checked_add(denominator.checked_sub(U256::from(1u32)).unwrap())
-                .unwrap()
-                .checked_div(denominator)
-                .unwrap()
-                .try_into()
-                .unwrap()
-        })
+    pub fn big_div_values_up(nominator: U256, denominator: U256) -> Option<Price> {
+        let price = nominator
+            .checked_mul(Self::one::<U256>())?
+            .checked_add(denominator.checked_sub(U256::from(1u32))?)?
+            .checked_div(denominator)?
+            .try_into()
+            .ok()?;
+        Some(Price::new(price))
     }
 }
 
@@ -173,17 +152,20 @@ pub mod tests {
     fn test_from_fee() {
         // One
         {
-            let fee_growth = FeeGrowth::from_fee(Liquidity::from_integer(1), TokenAmount(1));
+            let fee_growth =
+                FeeGrowth::from_fee(Liquidity::from_integer(1), TokenAmount(1)).unwrap();
             assert_eq!(fee_growth, FeeGrowth::from_integer(1));
         }
         // Half
         {
-            let fee_growth = FeeGrowth::from_fee(Liquidity::from_integer(2), TokenAmount(1));
+            let fee_growth =
+                FeeGrowth::from_fee(Liquidity::from_integer(2), TokenAmount(1)).unwrap();
             assert_eq!(fee_growth, FeeGrowth::from_scale(5, 1))
         }
         // Little
         {
-            let fee_growth = FeeGrowth::from_fee(Liquidity::from_integer(u64::MAX), TokenAmount(1));
+            let fee_growth =
+                FeeGrowth::from_fee(Liquidity::from_integer(u64::MAX), TokenAmount(1)).unwrap();
             // real    5.42101086242752217003726400434970855712890625 × 10^-20
             // expected 54210
             assert_eq!(fee_growth, FeeGrowth::new(54210))
@@ -191,7 +173,7 @@ pub mod tests {
         // Fairly big
         {
             let fee_growth =
-                FeeGrowth::from_fee(Liquidity::from_integer(100), TokenAmount(1_000_000));
+                FeeGrowth::from_fee(Liquidity::from_integer(100), TokenAmount(1_000_000)).unwrap();
             assert_eq!(fee_growth, FeeGrowth::from_integer(10000))
         }
     }
@@ -203,8 +185,8 @@ pub mod tests {
             let amount = TokenAmount(100);
             let liquidity = Liquidity::from_integer(1_000_000);
 
-            let fee_growth = FeeGrowth::from_fee(liquidity, amount);
-            let out = fee_growth.to_fee(liquidity);
+            let fee_growth = FeeGrowth::from_fee(liquidity, amount).unwrap();
+            let out = fee_growth.to_fee(liquidity).unwrap();
             assert_eq!(out, FixedPoint::from_decimal(amount));
         }
         // greater liquidity
@@ -213,8 +195,8 @@ pub mod tests {
             let liquidity_before = Liquidity::from_integer(1_000_000);
             let liquidity_after = Liquidity::from_integer(10_000_000);
 
-            let fee_growth = FeeGrowth::from_fee(liquidity_before, amount);
-            let out = fee_growth.to_fee(liquidity_after);
+            let fee_growth = FeeGrowth::from_fee(liquidity_before, amount).unwrap();
+            let out = fee_growth.to_fee(liquidity_after).unwrap();
             assert_eq!(out, FixedPoint::from_integer(1000))
         }
         // huge liquidity
@@ -222,12 +204,12 @@ pub mod tests {
             let amount = TokenAmount(100_000_000__000000);
             let liquidity = Liquidity::from_integer(2u128.pow(77));
 
-            let fee_growth = FeeGrowth::from_fee(liquidity, amount);
+            let fee_growth = FeeGrowth::from_fee(liquidity, amount).unwrap();
             // real    6.61744490042422139897126953655970282852649688720703125 × 10^-22
             // expected 661744490042422
             assert_eq!(fee_growth, FeeGrowth::new(661744490042422));
 
-            let out = fee_growth.to_fee(liquidity);
+            let out = fee_growth.to_fee(liquidity).unwrap();
             // real    9.9999999999999978859343891977453174784 × 10^25
             // expected 99999999999999978859343891
             assert_eq!(out, FixedPoint::new(99999999999999978859343891))
@@ -237,12 +219,12 @@ pub mod tests {
             let amount = TokenAmount(600000000000000000);
             let liquidity = Liquidity::from_integer(10000000000000000000u128);
 
-            let fee_growth = FeeGrowth::from_fee(liquidity, amount);
+            let fee_growth = FeeGrowth::from_fee(liquidity, amount).unwrap();
             // real     0.06
             // expected 0.06
             assert_eq!(fee_growth, FeeGrowth::new(60000000000000000000000));
 
-            let out = fee_growth.to_fee(liquidity);
+            let out = fee_growth.to_fee(liquidity).unwrap();
             // real     600000000000000000
             // expected 99999999999999978859343891
             assert_eq!(out, FixedPoint::from_integer(1) * amount)
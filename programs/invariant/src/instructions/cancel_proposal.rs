@@ -0,0 +1,28 @@
+use crate::structs::proposal::Proposal;
+use crate::structs::State;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+// Drops a pending proposal, either by its proposer or the admin
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut, close = proposer,
+        seeds = [b"proposalv1", target.key.as_ref(), &proposal.load()?.action.to_le_bytes()],
+        bump = proposal.load()?.bump
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    pub target: AccountInfo<'info>,
+    #[account(mut, constraint = proposer.key() == proposal.load()?.proposer @ InvalidProposal)]
+    pub proposer: AccountInfo<'info>,
+    #[account(constraint = signer.key() == proposer.key() || signer.key() == state.load()?.admin @ InvalidSigner)]
+    pub signer: Signer<'info>,
+}
+
+impl<'info> CancelProposal<'info> {
+    pub fn handler(&self) -> ProgramResult {
+        msg!("INVARIANT: CANCEL PROPOSAL");
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use crate::decimals::*;
+use crate::structs::fee_tier::FeeTier;
+use crate::structs::{Pool, State};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+// Changes protocol fee of pools created in a fee tier later on
+// pools of the tier passed in remaining accounts are switched to the new fee right away
+#[derive(Accounts)]
+pub struct ChangeDefaultProtocolFee<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [b"feetierv1", program_id.as_ref(), &fee_tier.load()?.fee.v.to_le_bytes(), &fee_tier.load()?.tick_spacing.to_le_bytes()],
+        bump = fee_tier.load()?.bump
+    )]
+    pub fee_tier: AccountLoader<'info, FeeTier>,
+    #[account(constraint = &state.load()?.admin == admin.key @ InvalidAdmin)]
+    pub admin: Signer<'info>,
+}
+
+impl<'info> ChangeDefaultProtocolFee<'info> {
+    pub fn handler(
+        ctx: Context<'_, '_, '_, 'info, ChangeDefaultProtocolFee<'info>>,
+        protocol_fee: FixedPoint,
+    ) -> ProgramResult {
+        msg!("INVARIANT: CHANGE DEFAULT PROTOCOL FEE");
+
+        require!(
+            protocol_fee <= FixedPoint::from_integer(1),
+            InvalidProtocolFee
+        );
+        let fee_tier = &mut ctx.accounts.fee_tier.load_mut()?;
+        apply_default_protocol_fee(fee_tier, protocol_fee, ctx.remaining_accounts)
+    }
+}
+
+// shared with the execution of a proposed change
+pub fn apply_default_protocol_fee(
+    fee_tier: &mut FeeTier,
+    protocol_fee: FixedPoint,
+    pools: &[AccountInfo],
+) -> ProgramResult {
+    fee_tier.default_protocol_fee = protocol_fee;
+
+    for account in pools {
+        let pool_loader = AccountLoader::<'_, Pool>::try_from(account)?;
+        let pool = &mut pool_loader.load_mut()?;
+
+        require!({ pool.fee } == { fee_tier.fee }, InvalidFeeTier);
+        require!(
+            { pool.tick_spacing } == { fee_tier.tick_spacing },
+            InvalidFeeTier
+        );
+        pool.protocol_fee = protocol_fee;
+    }
+
+    Ok(())
+}
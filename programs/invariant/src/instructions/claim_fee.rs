@@ -39,7 +39,8 @@ pub struct ClaimFee<'info> {
         constraint = upper_tick_index == position.load()?.upper_tick_index @ WrongTick
     )]
     pub upper_tick: AccountLoader<'info, Tick>,
-    pub owner: Signer<'info>,
+    // signs unless the operator of the position list signs in remaining accounts
+    pub owner: AccountInfo<'info>,
     #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
     pub token_x: Account<'info, Mint>,
     #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
@@ -97,28 +98,32 @@ impl<'info> interfaces::SendTokens<'info> for ClaimFee<'info> {
 }
 
 impl<'info> ClaimFee<'info> {
-    pub fn handler(&self) -> ProgramResult {
+    pub fn handler(&self, remaining_accounts: &[AccountInfo<'info>]) -> ProgramResult {
         msg!("INVARIANT: CLAIM FEE");
+        check_position_authority(&self.owner, None, remaining_accounts)?;
 
         let state = self.state.load()?;
         let pool = &mut self.pool.load_mut()?;
         let position = &mut self.position.load_mut()?;
         let lower_tick = &mut self.lower_tick.load_mut()?;
         let upper_tick = &mut self.upper_tick.load_mut()?;
-        let current_timestamp = get_current_timestamp();
+        let current_timestamp = get_current_timestamp()?;
 
-        check_ticks(lower_tick.index, upper_tick.index, pool.tick_spacing)?;
+        check_ticks(
+            lower_tick.index,
+            upper_tick.index,
+            pool.tick_spacing,
+            pool.tick_limit(),
+        )?;
 
-        position
-            .modify(
-                pool,
-                upper_tick,
-                lower_tick,
-                Liquidity::new(0),
-                true,
-                current_timestamp,
-            )
-            .unwrap();
+        position.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            Liquidity::new(0),
+            true,
+            current_timestamp,
+        )?;
 
         let fee_to_collect_x = TokenAmount::from_decimal(position.tokens_owed_x);
         let fee_to_collect_y = TokenAmount::from_decimal(position.tokens_owed_y);
@@ -0,0 +1,164 @@
+use crate::decimals::*;
+use crate::interfaces::send_tokens::SendTokens;
+use crate::structs::pool::Pool;
+use crate::structs::position::Position;
+use crate::structs::position_list::PositionList;
+use crate::structs::tick::Tick;
+use crate::util::*;
+use crate::ErrorCode::*;
+use crate::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount, Transfer};
+
+// Positions are passed in remaining accounts as [position, lower_tick, upper_tick] triples
+#[derive(Accounts)]
+pub struct ClaimFeesBatch<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(
+        seeds = [b"positionlistv1", owner.key().as_ref()],
+        bump = position_list.load()?.bump
+    )]
+    pub position_list: AccountLoader<'info, PositionList>,
+    pub owner: AccountInfo<'info>,
+    #[account(constraint = signer.key() == owner.key() || signer.key() == position_list.load()?.operator @ InvalidSigner)]
+    pub signer: Signer<'info>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Account<'info, Mint>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Account<'info, Mint>,
+    #[account(mut,
+        constraint = account_x.mint == token_x.key() @ InvalidMint,
+        constraint = &account_x.owner == owner.key @ InvalidOwner,
+    )]
+    pub account_x: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = account_y.mint == token_y.key() @ InvalidMint,
+        constraint = &account_y.owner == owner.key @ InvalidOwner
+    )]
+    pub account_y: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = reserve_x.mint == token_x.key() @ InvalidMint,
+        constraint = &reserve_x.owner == program_authority.key @ InvalidAuthority,
+        constraint = reserve_x.key() == pool.load()?.token_x_reserve @ InvalidTokenAccount
+    )]
+    pub reserve_x: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = reserve_y.mint == token_y.key() @ InvalidMint,
+        constraint = &reserve_y.owner == program_authority.key @ InvalidAuthority,
+        constraint = reserve_y.key() == pool.load()?.token_y_reserve @ InvalidTokenAccount
+    )]
+    pub reserve_y: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = &state.load()?.authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> interfaces::SendTokens<'info> for ClaimFeesBatch<'info> {
+    fn send_x(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reserve_x.to_account_info(),
+                to: self.account_x.to_account_info(),
+                authority: self.program_authority.clone(),
+            },
+        )
+    }
+
+    fn send_y(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reserve_y.to_account_info(),
+                to: self.account_y.to_account_info(),
+                authority: self.program_authority.clone(),
+            },
+        )
+    }
+}
+
+impl<'info> ClaimFeesBatch<'info> {
+    pub fn handler(ctx: Context<'_, '_, '_, 'info, ClaimFeesBatch<'info>>) -> ProgramResult {
+        msg!("INVARIANT: CLAIM FEES BATCH");
+
+        let remaining_accounts = ctx.remaining_accounts;
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 3 == 0,
+            InvalidRemainingAccounts
+        );
+
+        let state = ctx.accounts.state.load()?;
+        let pool = &mut ctx.accounts.pool.load_mut()?;
+        let pool_address = ctx.accounts.pool.key();
+        let owner = ctx.accounts.owner.key();
+        let current_timestamp = get_current_timestamp()?;
+
+        let mut fee_to_collect_x = TokenAmount(0);
+        let mut fee_to_collect_y = TokenAmount(0);
+
+        for accounts in remaining_accounts.chunks(3) {
+            let position_loader = AccountLoader::<'_, Position>::try_from(&accounts[0])?;
+            let lower_tick_loader = AccountLoader::<'_, Tick>::try_from(&accounts[1])?;
+            let upper_tick_loader = AccountLoader::<'_, Tick>::try_from(&accounts[2])?;
+
+            let position = &mut position_loader.load_mut()?;
+            let lower_tick = &mut lower_tick_loader.load_mut()?;
+            let upper_tick = &mut upper_tick_loader.load_mut()?;
+
+            require!({ position.owner } == owner, InvalidOwner);
+            require!({ position.pool } == pool_address, InvalidPositionIndex);
+            require!({ lower_tick.pool } == pool_address, WrongTick);
+            require!(
+                { lower_tick.index } == { position.lower_tick_index },
+                WrongTick
+            );
+            require!({ upper_tick.pool } == pool_address, WrongTick);
+            require!(
+                { upper_tick.index } == { position.upper_tick_index },
+                WrongTick
+            );
+
+            check_ticks(
+                lower_tick.index,
+                upper_tick.index,
+                pool.tick_spacing,
+                pool.tick_limit(),
+            )?;
+
+            position.modify(
+                pool,
+                upper_tick,
+                lower_tick,
+                Liquidity::new(0),
+                true,
+                current_timestamp,
+            )?;
+
+            let fee_x = TokenAmount::from_decimal(position.tokens_owed_x);
+            let fee_y = TokenAmount::from_decimal(position.tokens_owed_y);
+            position.tokens_owed_x = position.tokens_owed_x - FixedPoint::from_decimal(fee_x);
+            position.tokens_owed_y = position.tokens_owed_y - FixedPoint::from_decimal(fee_y);
+
+            fee_to_collect_x += fee_x;
+            fee_to_collect_y += fee_y;
+        }
+
+        let signer: &[&[&[u8]]] = get_signer!(state.nonce);
+
+        let cpi_ctx_x = ctx.accounts.send_x().with_signer(signer);
+        let cpi_ctx_y = ctx.accounts.send_y().with_signer(signer);
+
+        token::transfer(cpi_ctx_x, fee_to_collect_x.0)?;
+        token::transfer(cpi_ctx_y, fee_to_collect_y.0)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use crate::decimals::*;
+use crate::structs::pool::{Pool, REWARDS_PER_POOL};
+use crate::structs::position::Position;
+use crate::structs::position_list::PositionList;
+use crate::structs::tick::Tick;
+use crate::util::*;
+use crate::ErrorCode::*;
+use crate::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction( index: u32, lower_tick_index: i32, upper_tick_index: i32)]
+pub struct ClaimRewards<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut,
+        seeds = [b"positionv1",
+        owner.key().as_ref(),
+        &index.to_le_bytes()],
+        bump = position.load()?.bump,
+        constraint = position.load()?.pool == pool.key() @ InvalidPositionIndex
+    )]
+    pub position: AccountLoader<'info, Position>,
+    #[account(
+        seeds = [b"positionlistv1", owner.key().as_ref()],
+        bump = position_list.load()?.bump
+    )]
+    pub position_list: AccountLoader<'info, PositionList>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &lower_tick_index.to_le_bytes()],
+        bump = lower_tick.load()?.bump,
+        constraint = lower_tick_index == position.load()?.lower_tick_index @ WrongTick
+    )]
+    pub lower_tick: AccountLoader<'info, Tick>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &upper_tick_index.to_le_bytes()],
+        bump = upper_tick.load()?.bump,
+        constraint = upper_tick_index == position.load()?.upper_tick_index @ WrongTick
+    )]
+    pub upper_tick: AccountLoader<'info, Tick>,
+    pub owner: AccountInfo<'info>,
+    #[account(constraint = signer.key() == owner.key() || signer.key() == position_list.load()?.operator @ InvalidSigner)]
+    pub signer: Signer<'info>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Account<'info, Mint>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Account<'info, Mint>,
+    #[account(mut,
+        constraint = account_reward.mint == reward_vault.mint @ InvalidMint,
+        constraint = &account_reward.owner == owner.key @ InvalidOwner
+    )]
+    pub account_reward: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = &reward_vault.owner == program_authority.key @ InvalidAuthority
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = &state.load()?.authority == program_authority.key @ InvalidAuthority)]
+    pub program_authority: AccountInfo<'info>,
+    #[account(address = token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> ClaimRewards<'info> {
+    fn send_reward(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.reward_vault.to_account_info(),
+                to: self.account_reward.to_account_info(),
+                authority: self.program_authority.clone(),
+            },
+        )
+    }
+
+    pub fn handler(&self, reward_index: u8) -> ProgramResult {
+        msg!("INVARIANT: CLAIM REWARDS");
+
+        let state = self.state.load()?;
+        let pool = &mut self.pool.load_mut()?;
+        let position = &mut self.position.load_mut()?;
+        let lower_tick = &mut self.lower_tick.load_mut()?;
+        let upper_tick = &mut self.upper_tick.load_mut()?;
+        let current_timestamp = get_current_timestamp()?;
+        let index = reward_index as usize;
+
+        require!(index < REWARDS_PER_POOL, InvalidRewardIndex);
+        let reward = pool.rewards[index];
+        require!(reward.is_initialized(), InvalidRewardIndex);
+        require!(
+            { reward.vault } == self.reward_vault.key(),
+            InvalidTokenAccount
+        );
+
+        check_ticks(
+            lower_tick.index,
+            upper_tick.index,
+            pool.tick_spacing,
+            pool.tick_limit(),
+        )?;
+
+        // accrue rewards
+        position.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            Liquidity::new(0),
+            true,
+            current_timestamp,
+        )?;
+
+        // a vault short of the owed amount pays what it holds, the rest stays owed
+        let mut rewards_owed = position.rewards_owed;
+        let reward_to_collect = TokenAmount::from_decimal(rewards_owed[index])
+            .min(TokenAmount(self.reward_vault.amount));
+        rewards_owed[index] = rewards_owed[index] - FixedPoint::from_decimal(reward_to_collect);
+        position.rewards_owed = rewards_owed;
+
+        let signer: &[&[&[u8]]] = get_signer!(state.nonce);
+        token::transfer(self.send_reward().with_signer(signer), reward_to_collect.0)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use crate::decimals::*;
+use crate::math::calculate_max_liquidity_from_amounts;
+use crate::structs::pool::Pool;
+use crate::structs::position::Position;
+use crate::structs::tick::Tick;
+use crate::util::*;
+use crate::ErrorCode::*;
+use crate::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+#[derive(Accounts)]
+#[instruction( index: u32, lower_tick_index: i32, upper_tick_index: i32)]
+pub struct CompoundFees<'info> {
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(mut,
+        seeds = [b"positionv1",
+        owner.key().as_ref(),
+        &index.to_le_bytes()],
+        bump = position.load()?.bump,
+        constraint = position.load()?.pool == pool.key() @ InvalidPositionIndex
+    )]
+    pub position: AccountLoader<'info, Position>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &lower_tick_index.to_le_bytes()],
+        bump = lower_tick.load()?.bump,
+        constraint = lower_tick_index == position.load()?.lower_tick_index @ WrongTick
+    )]
+    pub lower_tick: AccountLoader<'info, Tick>,
+    #[account(mut,
+        seeds = [b"tickv1", pool.key().as_ref(), &upper_tick_index.to_le_bytes()],
+        bump = upper_tick.load()?.bump,
+        constraint = upper_tick_index == position.load()?.upper_tick_index @ WrongTick
+    )]
+    pub upper_tick: AccountLoader<'info, Tick>,
+    // signs unless the operator of the position list signs in remaining accounts
+    pub owner: AccountInfo<'info>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Account<'info, Mint>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Account<'info, Mint>,
+}
+
+impl<'info> CompoundFees<'info> {
+    pub fn handler(&self, remaining_accounts: &[AccountInfo<'info>]) -> ProgramResult {
+        msg!("INVARIANT: COMPOUND FEES");
+        check_position_authority(&self.owner, None, remaining_accounts)?;
+
+        let pool = &mut self.pool.load_mut()?;
+        let position = &mut self.position.load_mut()?;
+        let lower_tick = &mut self.lower_tick.load_mut()?;
+        let upper_tick = &mut self.upper_tick.load_mut()?;
+        let current_timestamp = get_current_timestamp()?;
+
+        check_ticks(
+            lower_tick.index,
+            upper_tick.index,
+            pool.tick_spacing,
+            pool.tick_limit(),
+        )?;
+
+        // accrue fees
+        position.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            Liquidity::new(0),
+            true,
+            current_timestamp,
+        )?;
+
+        // fees stay in reserves, so no transfer is needed to add them as liquidity
+        let liquidity_delta = calculate_max_liquidity_from_amounts(
+            pool.sqrt_price,
+            pool.current_tick_index,
+            lower_tick.index,
+            upper_tick.index,
+            TokenAmount::from_decimal(position.tokens_owed_x),
+            TokenAmount::from_decimal(position.tokens_owed_y),
+        )?;
+        require!(!liquidity_delta.is_zero(), ZeroAmount);
+
+        let (amount_x, amount_y) = position.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            liquidity_delta,
+            true,
+            current_timestamp,
+        )?;
+
+        // leftover stays claimable
+        position.tokens_owed_x = position.tokens_owed_x - FixedPoint::from_decimal(amount_x);
+        position.tokens_owed_y = position.tokens_owed_y - FixedPoint::from_decimal(amount_y);
+
+        Ok(())
+    }
+}
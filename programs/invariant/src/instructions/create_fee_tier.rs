@@ -1,5 +1,6 @@
 use crate::decimals::*;
 use crate::structs::fee_tier::FeeTier;
+use crate::structs::CURRENT_VERSION;
 use crate::ErrorCode::*;
 use crate::*;
 use anchor_lang::prelude::*;
@@ -34,6 +35,9 @@ impl<'info> CreateFeeTier<'info> {
             fee,
             tick_spacing,
             bump,
+            version: CURRENT_VERSION,
+            default_protocol_fee: FixedPoint::from_scale(1, 2),
+            reserved: Default::default(),
         };
 
         Ok(())
@@ -1,18 +1,17 @@
 use crate::decimals::*;
-use crate::math::calculate_price_sqrt;
 use crate::structs::fee_tier::FeeTier;
 use crate::structs::pool::Pool;
-use crate::structs::tickmap::Tickmap;
+use crate::structs::tickmap::{Tickmap, TickmapExtension};
 use crate::structs::State;
-use crate::util::check_tick;
 use crate::util::get_current_timestamp;
 use crate::ErrorCode::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_program;
 use anchor_spl::token::Token;
 use anchor_spl::token::{Mint, TokenAccount};
-use std::cmp::Ordering;
 
+// The tickmap extension can be passed as the first remaining account, the pool is then created with it
+// so it can start at a price outside of the tickmap. It has to be created by the client beforehand.
 #[derive(Accounts)]
 pub struct CreatePool<'info> {
     #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
@@ -54,51 +53,57 @@ pub struct CreatePool<'info> {
 }
 
 impl<'info> CreatePool<'info> {
-    pub fn handler(&self, init_tick: i32, bump: u8) -> ProgramResult {
+    pub fn handler(
+        &self,
+        init_sqrt_price: Price,
+        bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> ProgramResult {
         msg!("INVARIANT: CREATE POOL");
 
-        let token_x_address = &self.token_x.key();
-        let token_y_address = &self.token_y.key();
-        require!(
-            token_x_address
-                .to_string()
-                .cmp(&token_y_address.to_string())
-                == Ordering::Less,
-            InvalidPoolTokenAddresses
-        );
-
         let pool = &mut self.pool.load_init()?;
         let fee_tier = self.fee_tier.load()?;
-        let current_timestamp = get_current_timestamp();
+        let current_timestamp = get_current_timestamp()?;
 
-        check_tick(init_tick, fee_tier.tick_spacing)?;
+        let tickmap_extension = match remaining_accounts.first() {
+            Some(account) => Some(self.init_tickmap_extension(account)?),
+            None => None,
+        };
 
         **pool = Pool {
-            token_x: *token_x_address,
-            token_y: *token_y_address,
-            token_x_reserve: *self.token_x_reserve.to_account_info().key,
-            token_y_reserve: *self.token_y_reserve.to_account_info().key,
-            tick_spacing: fee_tier.tick_spacing,
-            fee: fee_tier.fee,
-            protocol_fee: FixedPoint::from_scale(1, 2),
-            liquidity: Liquidity::new(0),
-            sqrt_price: calculate_price_sqrt(init_tick),
-            current_tick_index: init_tick,
-            tickmap: *self.tickmap.to_account_info().key,
-            fee_growth_global_x: FeeGrowth::new(0),
-            fee_growth_global_y: FeeGrowth::new(0),
-            fee_protocol_token_x: 0,
-            fee_protocol_token_y: 0,
-            position_iterator: 0,
-            seconds_per_liquidity_global: FixedPoint::new(0),
-            start_timestamp: current_timestamp,
-            last_timestamp: current_timestamp,
+            token_x_reserve: self.token_x_reserve.key(),
+            token_y_reserve: self.token_y_reserve.key(),
+            tickmap: self.tickmap.key(),
             fee_receiver: self.state.load()?.admin,
-            oracle_address: Pubkey::default(),
-            oracle_initialized: false,
-            bump,
+            ..Pool::create(
+                self.token_x.key(),
+                self.token_y.key(),
+                &fee_tier,
+                init_sqrt_price,
+                tickmap_extension,
+                current_timestamp,
+                bump,
+            )?
         };
 
         Ok(())
     }
+
+    // same checks as the zero constraint of create_tickmap_extension
+    fn init_tickmap_extension(&self, account: &AccountInfo<'info>) -> Result<Pubkey> {
+        require!(account.key() != self.tickmap.key(), InvalidTickmapExtension);
+        require!(
+            Rent::get()?.is_exempt(account.lamports(), account.data_len()),
+            InvalidTickmapExtension
+        );
+
+        // checks the owner, load_init checks the account is writable and not initialized
+        let loader =
+            AccountLoader::<'_, TickmapExtension>::try_from_unchecked(&crate::ID, account)?;
+        **loader.load_init()? = TickmapExtension::default();
+        // writes the discriminator, anchor does it only for accounts of the context
+        loader.exit(&crate::ID)?;
+
+        Ok(account.key())
+    }
 }
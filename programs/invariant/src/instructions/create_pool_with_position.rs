@@ -0,0 +1,214 @@
+use crate::decimals::*;
+use crate::interfaces::take_tokens::TakeTokens;
+use crate::structs::fee_tier::FeeTier;
+use crate::structs::pool::Pool;
+use crate::structs::position::Position;
+use crate::structs::position_list::PositionList;
+use crate::structs::tick::Tick;
+use crate::structs::tickmap::Tickmap;
+use crate::structs::State;
+use crate::util::{check_ticks, get_current_slot, get_current_timestamp};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+
+// Creates a pool together with its first position, so the price can't be moved in between
+#[derive(Accounts)]
+#[instruction(init_sqrt_price: Price, lower_tick_index: i32, upper_tick_index: i32, position_index: u32)]
+pub struct CreatePoolWithPosition<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(init,
+        seeds = [b"poolv1", token_x.to_account_info().key.as_ref(), token_y.to_account_info().key.as_ref(), &fee_tier.load()?.fee.v.to_le_bytes(), &fee_tier.load()?.tick_spacing.to_le_bytes()],
+        bump, payer = payer
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(
+        seeds = [b"feetierv1", program_id.as_ref(), &fee_tier.load()?.fee.v.to_le_bytes(), &fee_tier.load()?.tick_spacing.to_le_bytes()],
+        bump = fee_tier.load()?.bump
+    )]
+    pub fee_tier: AccountLoader<'info, FeeTier>,
+    #[account(zero)]
+    pub tickmap: AccountLoader<'info, Tickmap>,
+    #[account(init,
+        seeds = [b"tickv1", pool.key().as_ref(), &lower_tick_index.to_le_bytes()],
+        bump, payer = payer
+    )]
+    pub lower_tick: AccountLoader<'info, Tick>,
+    #[account(init,
+        seeds = [b"tickv1", pool.key().as_ref(), &upper_tick_index.to_le_bytes()],
+        bump, payer = payer
+    )]
+    pub upper_tick: AccountLoader<'info, Tick>,
+    #[account(mut,
+        seeds = [b"positionlistv1", owner.key.as_ref()],
+        bump = position_list.load()?.bump
+    )]
+    pub position_list: AccountLoader<'info, PositionList>,
+    #[account(init,
+        seeds = [b"positionv1", owner.key.as_ref(), &position_index.to_le_bytes()],
+        bump, payer = payer
+    )]
+    pub position: AccountLoader<'info, Position>,
+    pub token_x: Box<Account<'info, Mint>>,
+    pub token_y: Box<Account<'info, Mint>>,
+    #[account(init,
+        token::mint = token_x,
+        token::authority = authority,
+        payer = payer,
+    )]
+    pub token_x_reserve: Box<Account<'info, TokenAccount>>,
+    #[account(init,
+        token::mint = token_y,
+        token::authority = authority,
+        payer = payer,
+    )]
+    pub token_y_reserve: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = account_x.mint == token_x.key() @ InvalidMint,
+        constraint = &account_x.owner == owner.key @ InvalidOwner,
+    )]
+    pub account_x: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = account_y.mint == token_y.key() @ InvalidMint,
+        constraint = &account_y.owner == owner.key @ InvalidOwner
+    )]
+    pub account_y: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(constraint = &state.load()?.authority == authority.key @ InvalidAuthority)]
+    pub authority: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> TakeTokens<'info> for CreatePoolWithPosition<'info> {
+    fn take_x(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.account_x.to_account_info(),
+                to: self.token_x_reserve.to_account_info(),
+                authority: self.owner.to_account_info().clone(),
+            },
+        )
+    }
+
+    fn take_y(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.account_y.to_account_info(),
+                to: self.token_y_reserve.to_account_info(),
+                authority: self.owner.to_account_info().clone(),
+            },
+        )
+    }
+}
+
+impl<'info> CreatePoolWithPosition<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn handler(
+        &self,
+        init_sqrt_price: Price,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        position_index: u32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: Price,
+        slippage_limit_upper: Price,
+        bumps: &std::collections::BTreeMap<String, u8>,
+    ) -> ProgramResult {
+        msg!("INVARIANT: CREATE POOL WITH POSITION");
+
+        let pool = &mut self.pool.load_init()?;
+        let fee_tier = self.fee_tier.load()?;
+        let tickmap = &mut self.tickmap.load_init()?;
+        let lower_tick = &mut self.lower_tick.load_init()?;
+        let upper_tick = &mut self.upper_tick.load_init()?;
+        let mut position_list = self.position_list.load_mut()?;
+        let position = &mut self.position.load_init()?;
+        let current_timestamp = get_current_timestamp()?;
+        let slot = get_current_slot()?;
+
+        **pool = Pool {
+            token_x_reserve: self.token_x_reserve.key(),
+            token_y_reserve: self.token_y_reserve.key(),
+            tickmap: self.tickmap.key(),
+            fee_receiver: self.state.load()?.admin,
+            ..Pool::create(
+                self.token_x.key(),
+                self.token_y.key(),
+                &fee_tier,
+                init_sqrt_price,
+                None,
+                current_timestamp,
+                *bumps.get("pool").unwrap(),
+            )?
+        };
+
+        // validate price
+        let price = pool.sqrt_price;
+        require!(price >= slippage_limit_lower, PriceLimitReached);
+        require!(price <= slippage_limit_upper, PriceLimitReached);
+
+        // validate ticks
+        check_ticks(
+            lower_tick_index,
+            upper_tick_index,
+            pool.tick_spacing,
+            pool.tick_limit(),
+        )?;
+        **lower_tick = Tick::create(
+            self.pool.key(),
+            pool,
+            lower_tick_index,
+            current_timestamp,
+            *bumps.get("lower_tick").unwrap(),
+        )?;
+        **upper_tick = Tick::create(
+            self.pool.key(),
+            pool,
+            upper_tick_index,
+            current_timestamp,
+            *bumps.get("upper_tick").unwrap(),
+        )?;
+        tickmap.flip(true, lower_tick_index, pool.tick_spacing)?;
+        tickmap.flip(true, upper_tick_index, pool.tick_spacing)?;
+
+        // update position_list head
+        require!(position_index == position_list.head, InvalidPositionIndex);
+        position_list.head = position_list
+            .head
+            .checked_add(1)
+            .ok_or(MathOverflow)?;
+
+        **position = Position::create(
+            self.owner.key(),
+            self.pool.key(),
+            pool,
+            lower_tick_index,
+            upper_tick_index,
+            slot,
+            *bumps.get("position").unwrap(),
+        )?;
+
+        let (amount_x, amount_y) = position.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            liquidity_delta,
+            true,
+            current_timestamp,
+        )?;
+
+        token::transfer(self.take_x(), amount_x.0)?;
+        token::transfer(self.take_y(), amount_y.0)?;
+        Ok(())
+    }
+}
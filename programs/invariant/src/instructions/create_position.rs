@@ -3,8 +3,8 @@ use crate::structs::pool::Pool;
 use crate::structs::position::Position;
 use crate::structs::position_list::PositionList;
 use crate::structs::tick::Tick;
-use crate::structs::Tickmap;
-use crate::util::check_ticks;
+use crate::structs::{flip_tick, Tickmap, TickmapView};
+use crate::util::{check_ticks, load_tickmap_extension};
 use crate::ErrorCode::*;
 use crate::*;
 use anchor_lang::prelude::*;
@@ -38,6 +38,7 @@ pub struct CreatePosition<'info> {
     pub position_list: AccountLoader<'info, PositionList>,
     #[account(mut)]
     pub payer: Signer<'info>,
+    // no operator path, tokens are taken from accounts of the owner
     pub owner: Signer<'info>,
     #[account(mut,
         seeds = [b"tickv1", pool.key().as_ref(), &lower_tick_index.to_le_bytes()],
@@ -120,17 +121,18 @@ impl<'info> CreatePosition<'info> {
         slippage_limit_lower: Price,
         slippage_limit_upper: Price,
         bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> ProgramResult {
         msg!("INVARIANT: CREATE POSITION");
 
         let mut position = self.position.load_init()?;
-        let mut pool = &mut self.pool.load_mut()?;
+        let pool = &mut self.pool.load_mut()?;
         let lower_tick = &mut self.lower_tick.load_mut()?;
         let upper_tick = &mut self.upper_tick.load_mut()?;
         let mut position_list = self.position_list.load_mut()?;
-        let current_timestamp = get_current_timestamp();
+        let current_timestamp = get_current_timestamp()?;
         let mut tickmap = self.tickmap.load_mut()?;
-        let slot = get_current_slot();
+        let slot = get_current_slot()?;
 
         // validate price
         let price = pool.sqrt_price;
@@ -138,35 +140,46 @@ impl<'info> CreatePosition<'info> {
         require!(price <= slippage_limit_upper, PriceLimitReached);
 
         // validate ticks
-        check_ticks(lower_tick.index, upper_tick.index, pool.tick_spacing)?;
+        check_ticks(
+            lower_tick.index,
+            upper_tick.index,
+            pool.tick_spacing,
+            pool.tick_limit(),
+        )?;
 
-        if !tickmap.get(lower_tick.index, pool.tick_spacing) {
-            tickmap.flip(true, lower_tick.index, pool.tick_spacing)
-        }
-        if !tickmap.get(upper_tick.index, pool.tick_spacing) {
-            tickmap.flip(true, upper_tick.index, pool.tick_spacing)
+        let tickmap_extension = load_tickmap_extension(pool, remaining_accounts)?;
+        let mut tickmap_extension = tickmap_extension
+            .as_ref()
+            .map(|extension| extension.load_mut())
+            .transpose()?;
+        for index in [lower_tick.index, upper_tick.index] {
+            if !TickmapView::new(&tickmap, tickmap_extension.as_deref())
+                .get(index, pool.tick_spacing)?
+            {
+                flip_tick(
+                    &mut tickmap,
+                    tickmap_extension.as_deref_mut(),
+                    true,
+                    index,
+                    pool.tick_spacing,
+                )?;
+            }
         }
 
         // update position_list head
-        position_list.head = position_list.head.checked_add(1).unwrap();
-        position.initialized_id(&mut pool);
-
-        // init position
-        *position = Position {
-            owner: *self.owner.to_account_info().key,
-            pool: *self.pool.to_account_info().key,
-            id: position.id,
-            liquidity: Liquidity::new(0),
-            lower_tick_index: lower_tick.index,
-            upper_tick_index: upper_tick.index,
-            fee_growth_inside_x: FeeGrowth::new(0),
-            fee_growth_inside_y: FeeGrowth::new(0),
-            seconds_per_liquidity_inside: FixedPoint::new(0),
-            last_slot: slot,
-            tokens_owed_x: FixedPoint::new(0),
-            tokens_owed_y: FixedPoint::new(0),
+        position_list.head = position_list
+            .head
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        *position = Position::create(
+            self.owner.key(),
+            self.pool.key(),
+            pool,
+            lower_tick.index,
+            upper_tick.index,
+            slot,
             bump,
-        };
+        )?;
 
         let (amount_x, amount_y) = position.modify(
             pool,
@@ -1,4 +1,5 @@
 use crate::structs::position_list::PositionList;
+use crate::structs::CURRENT_VERSION;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_program;
 
@@ -22,7 +23,13 @@ impl<'info> CreatePositionList<'info> {
     pub fn handler(&self, bump: u8) -> ProgramResult {
         msg!("INVARIANT: CREATE POSITION LIST");
         let mut position_list = self.position_list.load_init()?;
-        *position_list = PositionList { head: 0, bump };
+        *position_list = PositionList {
+            head: 0,
+            bump,
+            version: CURRENT_VERSION,
+            operator: Pubkey::default(),
+            reserved: Default::default(),
+        };
 
         Ok(())
     }
@@ -1,4 +1,5 @@
 use crate::structs::state::State;
+use crate::structs::CURRENT_VERSION;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_program;
 
@@ -25,6 +26,9 @@ pub fn handler(ctx: Context<CreateState>, nonce: u8) -> ProgramResult {
         authority: *ctx.accounts.program_authority.key,
         nonce,
         bump: *ctx.bumps.get("state").unwrap(),
+        version: CURRENT_VERSION,
+        governance_delay: 0,
+        reserved: Default::default(),
     };
     Ok(())
 }
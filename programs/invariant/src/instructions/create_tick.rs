@@ -1,9 +1,6 @@
-use crate::decimals::*;
-use crate::math::calculate_price_sqrt;
 use crate::structs::pool::Pool;
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::Tickmap;
-use crate::util::check_tick;
 use crate::util::get_current_timestamp;
 use crate::ErrorCode::*;
 use anchor_lang::prelude::*;
@@ -46,37 +43,9 @@ impl<'info> CreateTick<'info> {
 
         let mut tick = self.tick.load_init()?;
         let pool = self.pool.load()?;
-        let current_timestamp = get_current_timestamp();
+        let current_timestamp = get_current_timestamp()?;
 
-        check_tick(index, pool.tick_spacing)?;
-
-        // init tick
-        let below_current_tick = index <= pool.current_tick_index;
-        *tick = Tick {
-            pool: self.pool.key(),
-            index,
-            sign: true,
-            liquidity_change: Liquidity::new(0),
-            liquidity_gross: Liquidity::new(0),
-            sqrt_price: calculate_price_sqrt(index),
-            fee_growth_outside_x: match below_current_tick {
-                true => pool.fee_growth_global_x,
-                false => FeeGrowth::new(0),
-            },
-            fee_growth_outside_y: match below_current_tick {
-                true => pool.fee_growth_global_y,
-                false => FeeGrowth::new(0),
-            },
-            seconds_outside: match below_current_tick {
-                true => current_timestamp.checked_sub(pool.start_timestamp).unwrap(),
-                false => 0,
-            },
-            seconds_per_liquidity_outside: match below_current_tick {
-                true => pool.seconds_per_liquidity_global,
-                false => FixedPoint::new(0),
-            },
-            bump,
-        };
+        *tick = Tick::create(self.pool.key(), &pool, index, current_timestamp, bump)?;
 
         Ok(())
     }
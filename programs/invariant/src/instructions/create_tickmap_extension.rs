@@ -0,0 +1,45 @@
+use crate::structs::pool::Pool;
+use crate::structs::tickmap::TickmapExtension;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::Mint;
+
+// Extension has to be created by the client beforehand (it is too big to be initialized by the program)
+#[derive(Accounts)]
+pub struct CreateTickmapExtension<'info> {
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(zero)]
+    pub tickmap_extension: AccountLoader<'info, TickmapExtension>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Box<Account<'info, Mint>>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Box<Account<'info, Mint>>,
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> CreateTickmapExtension<'info> {
+    pub fn handler(&self) -> ProgramResult {
+        msg!("INVARIANT: CREATE TICKMAP EXTENSION");
+
+        let tickmap_extension = &mut self.tickmap_extension.load_init()?;
+        let pool = &mut self.pool.load_mut()?;
+
+        require!(
+            !pool.has_tickmap_extension(),
+            TickmapExtensionAlreadyCreated
+        );
+
+        **tickmap_extension = TickmapExtension::default();
+        pool.tickmap_extension = self.tickmap_extension.key();
+
+        Ok(())
+    }
+}
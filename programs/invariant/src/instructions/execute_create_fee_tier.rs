@@ -0,0 +1,59 @@
+use crate::decimals::*;
+use crate::structs::fee_tier::FeeTier;
+use crate::structs::proposal::*;
+use crate::structs::{State, CURRENT_VERSION};
+use crate::util::get_current_timestamp;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+#[derive(Accounts)]
+pub struct ExecuteCreateFeeTier<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut, close = proposer,
+        seeds = [b"proposalv1", fee_tier.key().as_ref(), &ACTION_CREATE_FEE_TIER.to_le_bytes()],
+        bump = proposal.load()?.bump,
+        constraint = proposal.load()?.action == ACTION_CREATE_FEE_TIER @ InvalidProposal
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    #[account(init,
+        seeds = [b"feetierv1", program_id.as_ref(), &proposal.load()?.value.to_le_bytes(), &proposal.load()?.tick_spacing.to_le_bytes()],
+        bump, payer = payer
+    )]
+    pub fee_tier: AccountLoader<'info, FeeTier>,
+    #[account(mut,
+        constraint = proposer.key() == proposal.load()?.proposer @ InvalidProposal,
+        constraint = proposer.key() == state.load()?.admin @ Unauthorized
+    )]
+    pub proposer: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> ExecuteCreateFeeTier<'info> {
+    pub fn handler(&self, bump: u8) -> ProgramResult {
+        msg!("INVARIANT: EXECUTE CREATE FEE TIER");
+
+        let proposal = self.proposal.load()?;
+        require!(
+            proposal.is_ready(get_current_timestamp()?),
+            ProposalNotReady
+        );
+
+        let fee_tier = &mut self.fee_tier.load_init()?;
+        **fee_tier = FeeTier {
+            fee: FixedPoint::new(proposal.value),
+            tick_spacing: proposal.tick_spacing,
+            bump,
+            version: CURRENT_VERSION,
+            default_protocol_fee: FixedPoint::from_scale(1, 2),
+            reserved: Default::default(),
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::decimals::*;
+use crate::instructions::change_default_protocol_fee::apply_default_protocol_fee;
+use crate::structs::proposal::*;
+use crate::structs::{FeeTier, Pool, State};
+use crate::util::get_current_timestamp;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+
+// Executes a proposal once its delay passed, anyone can do so
+// pools to apply a default protocol fee to are passed in remaining accounts
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut, seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut, close = proposer,
+        seeds = [b"proposalv1", target.key.as_ref(), &proposal.load()?.action.to_le_bytes()],
+        bump = proposal.load()?.bump
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    #[account(mut)]
+    pub target: AccountInfo<'info>,
+    #[account(mut, constraint = proposer.key() == proposal.load()?.proposer @ InvalidProposal)]
+    pub proposer: AccountInfo<'info>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    pub fn handler(ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>) -> ProgramResult {
+        msg!("INVARIANT: EXECUTE PROPOSAL");
+
+        let proposal = ctx.accounts.proposal.load()?;
+        require!(
+            proposal.is_ready(get_current_timestamp()?),
+            ProposalNotReady
+        );
+
+        // proposals lapse once the proposer loses the authority they were made with
+        if proposal.action != ACTION_CHANGE_PROTOCOL_FEE {
+            require!(
+                proposal.proposer == ctx.accounts.state.load()?.admin,
+                Unauthorized
+            );
+        }
+
+        let target = &ctx.accounts.target;
+        match proposal.action {
+            ACTION_CHANGE_PROTOCOL_FEE => {
+                let pool_loader = AccountLoader::<'_, Pool>::try_from(target)?;
+                let pool = &mut pool_loader.load_mut()?;
+                require!(proposal.proposer == { pool.fee_receiver }, Unauthorized);
+                pool.protocol_fee = FixedPoint::new(proposal.value);
+            }
+            ACTION_CHANGE_FEE_RECEIVER => {
+                let pool_loader = AccountLoader::<'_, Pool>::try_from(target)?;
+                pool_loader.load_mut()?.fee_receiver = proposal.fee_receiver;
+            }
+            ACTION_CHANGE_DEFAULT_PROTOCOL_FEE => {
+                let fee_tier_loader = AccountLoader::<'_, FeeTier>::try_from(target)?;
+                apply_default_protocol_fee(
+                    &mut fee_tier_loader.load_mut()?,
+                    FixedPoint::new(proposal.value),
+                    ctx.remaining_accounts,
+                )?;
+            }
+            ACTION_CHANGE_GOVERNANCE_DELAY => {
+                ctx.accounts.state.load_mut()?.governance_delay = proposal.value as u64;
+            }
+            // fee tier has to be initialized, so it is created with its own instruction
+            _ => return Err(InvalidProposal.into()),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use crate::structs::pool::{Pool, PoolReward, REWARDS_PER_POOL};
+use crate::structs::State;
+use crate::util::get_current_timestamp;
+use crate::ErrorCode::*;
+use crate::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct InitializeReward<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(mut,
+        seeds = [b"poolv1", token_x.key().as_ref(), token_y.key().as_ref(), &pool.load()?.fee.v.to_le_bytes(), &pool.load()?.tick_spacing.to_le_bytes()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
+    pub token_x: Account<'info, Mint>,
+    #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
+    pub token_y: Account<'info, Mint>,
+    pub reward_mint: Account<'info, Mint>,
+    #[account(init,
+        token::mint = reward_mint,
+        token::authority = authority,
+        payer = admin,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = admin_account.mint == reward_mint.key() @ InvalidMint,
+        constraint = &admin_account.owner == admin.key @ InvalidOwner
+    )]
+    pub admin_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = &state.load()?.admin == admin.key @ InvalidAdmin)]
+    pub admin: Signer<'info>,
+    #[account(constraint = &state.load()?.authority == authority.key @ InvalidAuthority)]
+    pub authority: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> InitializeReward<'info> {
+    fn sweep(
+        &self,
+        previous_vault: &AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: previous_vault.clone(),
+                to: self.reward_vault.to_account_info(),
+                authority: self.authority.clone(),
+            },
+        )
+    }
+
+    fn deposit(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.admin_account.to_account_info(),
+                to: self.reward_vault.to_account_info(),
+                authority: self.admin.to_account_info(),
+            },
+        )
+    }
+
+    pub fn handler(
+        &self,
+        reward_index: u8,
+        emissions_per_second: u64,
+        end_timestamp: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> ProgramResult {
+        msg!("INVARIANT: INITIALIZE REWARD");
+
+        let mut pool = self.pool.load_mut()?;
+        let current_timestamp = get_current_timestamp()?;
+        let index = reward_index as usize;
+
+        require!(index < REWARDS_PER_POOL, InvalidRewardIndex);
+        require!(end_timestamp > current_timestamp, NegativeTime);
+
+        // accrue already running rewards before a new one starts
+        pool.update_rewards_growth_global(current_timestamp)?;
+
+        // finished reward can be started again in the same mint, what is left in the previous vault
+        // (first remaining account) is moved to the new one, which then pays the unclaimed rewards
+        let mut rewards = pool.rewards;
+        let previous = rewards[index];
+        if previous.is_initialized() {
+            require!(
+                { previous.end_timestamp } <= current_timestamp,
+                RewardAlreadyInitialized
+            );
+            require!(previous.mint == self.reward_mint.key(), InvalidMint);
+
+            let previous_vault = remaining_accounts.first().ok_or(InvalidRemainingAccounts)?;
+            require!(
+                *previous_vault.key == previous.vault,
+                InvalidRemainingAccounts
+            );
+            let leftover = Account::<'_, TokenAccount>::try_from(previous_vault)?.amount;
+
+            let state = self.state.load()?;
+            let signer: &[&[&[u8]]] = get_signer!(state.nonce);
+            token::transfer(self.sweep(previous_vault).with_signer(signer), leftover)?;
+        }
+
+        rewards[index] = PoolReward {
+            mint: self.reward_mint.key(),
+            vault: self.reward_vault.key(),
+            emissions_per_second,
+            end_timestamp,
+            // growth keeps accumulating, so checkpoints of ticks and positions stay valid
+            growth_global: previous.growth_global,
+        };
+        pool.rewards = rewards;
+
+        // whole emission is deposited upfront
+        let amount = (end_timestamp - current_timestamp)
+            .checked_mul(emissions_per_second)
+            .ok_or(MathOverflow)?;
+        token::transfer(self.deposit(), amount)?;
+
+        Ok(())
+    }
+}
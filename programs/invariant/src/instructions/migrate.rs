@@ -0,0 +1,64 @@
+use crate::ErrorCode::*;
+use crate::*;
+use anchor_lang::__private::bytemuck::{self, Pod};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::{system_instruction, system_program};
+use anchor_lang::Discriminator;
+use std::mem::size_of;
+
+// Upgrades an account created with a previous layout in place, payer covers rent of the new size
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(mut, constraint = account.owner == program_id @ InvalidAccountOwner)]
+    pub account: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> Migrate<'info> {
+    pub fn handler<Old, New>(&self) -> ProgramResult
+    where
+        Old: Pod,
+        New: Pod + Discriminator + From<Old>,
+    {
+        msg!("INVARIANT: MIGRATE");
+
+        let old: Old = {
+            let data = self.account.try_borrow_data()?;
+            require!(
+                data.len() == 8 + size_of::<Old>() && data[..8] == New::discriminator(),
+                InvalidAccountVersion
+            );
+            *bytemuck::from_bytes(&data[8..])
+        };
+
+        self.resize(8 + size_of::<New>())?;
+
+        let mut data = self.account.try_borrow_mut_data()?;
+        data[8..].copy_from_slice(bytemuck::bytes_of(&New::from(old)));
+
+        Ok(())
+    }
+
+    fn resize(&self, new_len: usize) -> ProgramResult {
+        let missing_lamports = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(self.account.lamports());
+
+        if missing_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(self.payer.key, self.account.key, missing_lamports),
+                &[
+                    self.payer.to_account_info(),
+                    self.account.clone(),
+                    self.system_program.clone(),
+                ],
+            )?;
+        }
+
+        self.account.realloc(new_len, true)
+    }
+}
@@ -1,31 +1,59 @@
+pub mod cancel_proposal;
+pub mod change_default_protocol_fee;
 pub mod change_fee_receiver;
 pub mod change_protocol_fee;
 pub mod claim_fee;
+pub mod claim_fees_batch;
+pub mod claim_rewards;
+pub mod compound_fees;
 pub mod create_fee_tier;
 pub mod create_pool;
+pub mod create_pool_with_position;
 pub mod create_position;
 pub mod create_position_list;
 pub mod create_state;
 pub mod create_tick;
+pub mod create_tickmap_extension;
+pub mod execute_create_fee_tier;
+pub mod execute_proposal;
 pub mod initialize_oracle;
+pub mod initialize_reward;
+pub mod migrate;
+pub mod propose;
 pub mod remove_position;
+pub mod set_position_operator;
 pub mod swap;
 pub mod transfer_position_ownership;
 pub mod update_seconds_per_liquidity;
+pub mod verify_pool;
 pub mod withdraw_protocol_fee;
 
+pub use cancel_proposal::*;
+pub use change_default_protocol_fee::*;
 pub use change_fee_receiver::*;
 pub use change_protocol_fee::*;
 pub use claim_fee::*;
+pub use claim_fees_batch::*;
+pub use claim_rewards::*;
+pub use compound_fees::*;
 pub use create_fee_tier::*;
 pub use create_pool::*;
+pub use create_pool_with_position::*;
 pub use create_position::*;
 pub use create_position_list::*;
 pub use create_state::*;
 pub use create_tick::*;
+pub use create_tickmap_extension::*;
+pub use execute_create_fee_tier::*;
+pub use execute_proposal::*;
 pub use initialize_oracle::*;
+pub use initialize_reward::*;
+pub use migrate::*;
+pub use propose::*;
 pub use remove_position::*;
+pub use set_position_operator::*;
 pub use swap::*;
 pub use transfer_position_ownership::*;
 pub use update_seconds_per_liquidity::*;
+pub use verify_pool::*;
 pub use withdraw_protocol_fee::*;
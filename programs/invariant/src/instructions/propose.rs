@@ -0,0 +1,103 @@
+use crate::decimals::*;
+use crate::structs::proposal::*;
+use crate::structs::{FeeTier, Pool, State, CURRENT_VERSION};
+use crate::util::get_current_timestamp;
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+// Queues a sensitive admin action, it can be executed once the governance delay passes
+// there is one pending proposal per action and target, so a proposal can't block another action
+#[derive(Accounts)]
+#[instruction(action: u8)]
+pub struct Propose<'info> {
+    #[account(seeds = [b"statev1".as_ref()], bump = state.load()?.bump)]
+    pub state: AccountLoader<'info, State>,
+    #[account(init,
+        seeds = [b"proposalv1", target.key.as_ref(), &action.to_le_bytes()],
+        bump, payer = proposer
+    )]
+    pub proposal: AccountLoader<'info, Proposal>,
+    pub target: AccountInfo<'info>,
+    // only read by a fee receiver change
+    pub fee_receiver: AccountInfo<'info>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> Propose<'info> {
+    pub fn handler(&self, action: u8, value: u128, tick_spacing: u16, bump: u8) -> ProgramResult {
+        msg!("INVARIANT: PROPOSE");
+
+        let state = self.state.load()?;
+        let proposer = self.proposer.key();
+        let is_admin = proposer == state.admin;
+
+        match action {
+            ACTION_CHANGE_PROTOCOL_FEE => {
+                let pool_loader = AccountLoader::<'_, Pool>::try_from(&self.target)?;
+                require!(
+                    proposer == { pool_loader.load()?.fee_receiver },
+                    Unauthorized
+                );
+                require!(
+                    FixedPoint::new(value) <= FixedPoint::from_integer(1),
+                    InvalidProtocolFee
+                );
+            }
+            ACTION_CHANGE_FEE_RECEIVER => {
+                require!(is_admin, Unauthorized);
+                AccountLoader::<'_, Pool>::try_from(&self.target)?;
+            }
+            ACTION_CREATE_FEE_TIER => {
+                require!(is_admin, Unauthorized);
+                require!(tick_spacing > 0, InvalidTickSpacing);
+                let (fee_tier_address, _) = Pubkey::find_program_address(
+                    &[
+                        b"feetierv1",
+                        crate::ID.as_ref(),
+                        &value.to_le_bytes(),
+                        &tick_spacing.to_le_bytes(),
+                    ],
+                    &crate::ID,
+                );
+                require!(self.target.key() == fee_tier_address, InvalidProposal);
+            }
+            ACTION_CHANGE_DEFAULT_PROTOCOL_FEE => {
+                require!(is_admin, Unauthorized);
+                AccountLoader::<'_, FeeTier>::try_from(&self.target)?;
+                require!(
+                    FixedPoint::new(value) <= FixedPoint::from_integer(1),
+                    InvalidProtocolFee
+                );
+            }
+            ACTION_CHANGE_GOVERNANCE_DELAY => {
+                require!(is_admin, Unauthorized);
+                require!(self.target.key() == self.state.key(), InvalidProposal);
+                require!(value <= MAX_GOVERNANCE_DELAY as u128, InvalidProposal);
+            }
+            _ => return Err(InvalidProposal.into()),
+        }
+
+        let proposal = &mut self.proposal.load_init()?;
+        **proposal = Proposal {
+            action,
+            target: self.target.key(),
+            proposer,
+            value,
+            tick_spacing,
+            fee_receiver: self.fee_receiver.key(),
+            execute_after: get_current_timestamp()?
+                .checked_add(state.governance_delay)
+                .ok_or(MathOverflow)?,
+            bump,
+            version: CURRENT_VERSION,
+            reserved: Default::default(),
+        };
+
+        Ok(())
+    }
+}
@@ -4,8 +4,8 @@ use crate::structs::pool::Pool;
 use crate::structs::position::Position;
 use crate::structs::position_list::PositionList;
 use crate::structs::tick::Tick;
-use crate::structs::tickmap::Tickmap;
-use crate::util::{check_ticks, close};
+use crate::structs::tickmap::{flip_tick, Tickmap};
+use crate::util::{check_position_authority, check_ticks, close, load_tickmap_extension};
 use crate::ErrorCode::*;
 use crate::*;
 use anchor_lang::prelude::*;
@@ -60,7 +60,8 @@ pub struct RemovePosition<'info> {
     )]
     pub upper_tick: AccountLoader<'info, Tick>,
     #[account(mut)]
-    pub owner: Signer<'info>,
+    // signs unless the operator of the position list signs in remaining accounts
+    pub owner: AccountInfo<'info>,
     #[account(constraint = token_x.key() == pool.load()?.token_x @ InvalidTokenAccount)]
     pub token_x: Account<'info, Mint>,
     #[account(constraint = token_y.key() == pool.load()?.token_y @ InvalidTokenAccount)]
@@ -123,15 +124,22 @@ impl<'info> RemovePosition<'info> {
         index: u32,
         lower_tick_index: i32,
         upper_tick_index: i32,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> ProgramResult {
         msg!("INVARIANT: REMOVE POSITION");
+        check_position_authority(&self.owner, Some(&self.position_list), remaining_accounts)?;
 
         let state = self.state.load()?;
         let mut position_list = self.position_list.load_mut()?;
         let removed_position = &mut self.removed_position.load_mut()?;
         let pool = &mut self.pool.load_mut()?;
         let tickmap = &mut self.tickmap.load_mut()?;
-        let current_timestamp = get_current_timestamp();
+        let tickmap_extension = load_tickmap_extension(pool, remaining_accounts)?;
+        let mut tickmap_extension = tickmap_extension
+            .as_ref()
+            .map(|extension| extension.load_mut())
+            .transpose()?;
+        let current_timestamp = get_current_timestamp()?;
 
         // closing tick can't be in the same scope as loaded tick
         let close_lower;
@@ -142,7 +150,12 @@ impl<'info> RemovePosition<'info> {
             let upper_tick = &mut self.upper_tick.load_mut()?;
 
             // validate ticks
-            check_ticks(lower_tick.index, upper_tick.index, pool.tick_spacing)?;
+            check_ticks(
+                lower_tick.index,
+                upper_tick.index,
+                pool.tick_spacing,
+                pool.tick_limit(),
+            )?;
             let liquidity_delta = removed_position.liquidity;
             let (amount_x, amount_y) = removed_position.modify(
                 pool,
@@ -153,6 +166,20 @@ impl<'info> RemovePosition<'info> {
                 current_timestamp,
             )?;
 
+            // rewards are paid in other mints and claimed separately, anything left unclaimed is
+            // forfeited, so withdrawing liquidity never depends on the reward vaults
+            let rewards_owed = removed_position.rewards_owed;
+            for (reward_index, owed) in rewards_owed.iter().enumerate() {
+                let forfeited = TokenAmount::from_decimal(*owed);
+                if !forfeited.is_zero() {
+                    msg!(
+                        "INVARIANT: REWARD {} FORFEITED {}",
+                        reward_index,
+                        forfeited.0
+                    );
+                }
+            }
+
             let amount_x = amount_x + TokenAmount::from_decimal(removed_position.tokens_owed_x);
             let amount_y = amount_y + TokenAmount::from_decimal(removed_position.tokens_owed_y);
 
@@ -170,10 +197,15 @@ impl<'info> RemovePosition<'info> {
             close(
                 self.lower_tick.to_account_info(),
                 self.owner.to_account_info(),
-            )
-            .unwrap();
+            )?;
 
-            tickmap.flip(false, lower_tick_index, pool.tick_spacing);
+            flip_tick(
+                tickmap,
+                tickmap_extension.as_deref_mut(),
+                false,
+                lower_tick_index,
+                pool.tick_spacing,
+            )?;
         }
         if close_upper {
             {
@@ -183,14 +215,22 @@ impl<'info> RemovePosition<'info> {
             close(
                 self.upper_tick.to_account_info(),
                 self.owner.to_account_info(),
-            )
-            .unwrap();
+            )?;
 
-            tickmap.flip(false, upper_tick_index, pool.tick_spacing);
+            flip_tick(
+                tickmap,
+                tickmap_extension.as_deref_mut(),
+                false,
+                upper_tick_index,
+                pool.tick_spacing,
+            )?;
         }
 
         // Remove empty position
-        position_list.head = position_list.head.checked_sub(1).unwrap();
+        position_list.head = position_list
+            .head
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
 
         // when removed position is not the last one
         if position_list.head != index {
@@ -211,6 +251,10 @@ impl<'info> RemovePosition<'info> {
                 last_slot: last_position.last_slot,
                 tokens_owed_x: last_position.tokens_owed_x,
                 tokens_owed_y: last_position.tokens_owed_y,
+                reward_growths_inside: last_position.reward_growths_inside,
+                rewards_owed: last_position.rewards_owed,
+                version: last_position.version,
+                reserved: last_position.reserved,
             };
 
             *last_position = Default::default();
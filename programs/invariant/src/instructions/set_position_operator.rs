@@ -0,0 +1,26 @@
+use crate::structs::position_list::PositionList;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPositionOperator<'info> {
+    #[account(mut,
+        seeds = [b"positionlistv1", owner.key().as_ref()],
+        bump = position_list.load()?.bump
+    )]
+    pub position_list: AccountLoader<'info, PositionList>,
+    pub owner: Signer<'info>,
+}
+
+impl<'info> SetPositionOperator<'info> {
+    // operator can claim fees, compound them and remove positions of the owner
+    // it can't add liquidity, create_position takes tokens from the owner so the owner has to sign it
+    // operator equal to default pubkey revokes the approval
+    pub fn handler(&self, operator: Pubkey) -> ProgramResult {
+        msg!("INVARIANT: SET POSITION OPERATOR");
+
+        let mut position_list = self.position_list.load_mut()?;
+        position_list.operator = operator;
+
+        Ok(())
+    }
+}
@@ -1,16 +1,15 @@
 use crate::interfaces::send_tokens::SendTokens;
 use crate::interfaces::take_ref_tokens::TakeRefTokens;
 use crate::interfaces::take_tokens::TakeTokens;
-use crate::log::get_tick_at_sqrt_price;
-use crate::math::compute_swap_step;
 use crate::structs::pool::Pool;
 use crate::structs::tick::Tick;
-use crate::structs::tickmap::Tickmap;
-use crate::util::get_closer_limit;
+use crate::structs::tickmap::{Tickmap, TickmapView};
+use crate::util::{load_tickmap_extension, swap_loop, SwapTicks, SwapTotals};
 use crate::ErrorCode::*;
 use crate::*;
 use crate::{decimals::*, referral::whitelist::contains_owner};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 use anchor_spl::token::{TokenAccount, Transfer};
 
 #[derive(Accounts)]
@@ -132,6 +131,7 @@ impl<'info> Swap<'info> {
         amount: u64,
         by_amount_in: bool, // whether amount specifies input or output
         sqrt_price_limit: u128,
+        allow_partial_fill: bool, // stop at price limit or at the last provided tick instead of failing
     ) -> ProgramResult {
         msg!("INVARIANT: SWAP");
         require!(amount != 0, ZeroAmount);
@@ -139,6 +139,12 @@ impl<'info> Swap<'info> {
         let sqrt_price_limit = Price::new(sqrt_price_limit);
         let mut pool = ctx.accounts.pool.load_mut()?;
         let tickmap = ctx.accounts.tickmap.load()?;
+        let tickmap_extension = load_tickmap_extension(&pool, ctx.remaining_accounts)?;
+        let tickmap_extension = tickmap_extension
+            .as_ref()
+            .map(|extension| extension.load())
+            .transpose()?;
+        let tickmap = TickmapView::new(&tickmap, tickmap_extension.as_deref());
         let state = ctx.accounts.state.load()?;
 
         let ref_account = match ctx
@@ -164,137 +170,39 @@ impl<'info> Swap<'info> {
             None => None,
         };
 
-        // limit is on the right side of price
-        if x_to_y {
-            require!(
-                { pool.sqrt_price } > sqrt_price_limit
-                    && sqrt_price_limit <= Price::new(MAX_SQRT_PRICE),
-                WrongLimit
-            );
-        } else {
-            require!(
-                { pool.sqrt_price } < sqrt_price_limit
-                    && sqrt_price_limit >= Price::new(MIN_SQRT_PRICE),
-                WrongLimit
-            );
-        }
-
-        let mut remaining_amount = TokenAmount(amount);
-
-        let mut total_amount_in = TokenAmount(0);
-        let mut total_amount_out = TokenAmount(0);
-        let mut total_amount_referral = TokenAmount(0);
-
-        while !remaining_amount.is_zero() {
-            let (swap_limit, limiting_tick) = get_closer_limit(
-                sqrt_price_limit,
-                x_to_y,
-                pool.current_tick_index,
-                pool.tick_spacing,
-                &tickmap,
-            )?;
+        let mut ticks = TickAccounts {
+            pool: ctx.accounts.pool.key(),
+            program_id: ctx.program_id,
+            remaining_accounts: ctx.remaining_accounts,
+        };
+        let ref_percentage = match ref_account.is_some() {
+            true => FixedPoint::from_scale(2, 1),
+            false => FixedPoint::from_integer(0),
+        };
+        let SwapTotals {
+            amount_in: total_amount_in,
+            amount_out: total_amount_out,
+            amount_referral: total_amount_referral,
+            unfilled_amount,
+        } = swap_loop(
+            &mut pool,
+            &tickmap,
+            &mut ticks,
+            x_to_y,
+            TokenAmount(amount),
+            by_amount_in,
+            sqrt_price_limit,
+            allow_partial_fill,
+            ref_percentage,
+            get_current_timestamp()?,
+        )?;
 
-            let result = compute_swap_step(
-                pool.sqrt_price,
-                swap_limit,
-                pool.liquidity,
-                remaining_amount,
-                by_amount_in,
-                pool.fee,
+        if !unfilled_amount.is_zero() {
+            msg!(
+                "INVARIANT: SWAP PARTIALLY FILLED, UNFILLED {}",
+                unfilled_amount.0
             );
-            // make remaining amount smaller
-            if by_amount_in {
-                remaining_amount -= result.amount_in + result.fee_amount;
-            } else {
-                remaining_amount -= result.amount_out;
-            }
-
-            total_amount_referral += match ref_account.is_some() {
-                true => pool.add_fee(result.fee_amount, FixedPoint::from_scale(2, 1), x_to_y),
-                false => pool.add_fee(result.fee_amount, FixedPoint::from_integer(0), x_to_y),
-            };
-
-            pool.sqrt_price = result.next_price_sqrt;
-
-            total_amount_in += result.amount_in + result.fee_amount;
-            total_amount_out += result.amount_out;
-
-            // Fail if price would go over swap limit
-            if { pool.sqrt_price } == sqrt_price_limit && !remaining_amount.is_zero() {
-                return Err(ErrorCode::PriceLimitReached.into());
-            }
-
-            // crossing tick
-            // trunk-ignore(clippy/unnecessary_unwrap)
-            if result.next_price_sqrt == swap_limit && limiting_tick.is_some() {
-                let (tick_index, initialized) = limiting_tick.unwrap();
-
-                let is_enough_amount_to_cross = is_enough_amount_to_push_price(
-                    remaining_amount,
-                    result.next_price_sqrt,
-                    pool.liquidity,
-                    pool.fee,
-                    by_amount_in,
-                    x_to_y,
-                );
-
-                if initialized {
-                    // Calculating address of the crossed tick
-                    let (tick_address, _) = Pubkey::find_program_address(
-                        &[
-                            b"tickv1",
-                            ctx.accounts.pool.to_account_info().key.as_ref(),
-                            &tick_index.to_le_bytes(),
-                        ],
-                        ctx.program_id,
-                    );
-
-                    // Finding the correct tick in remaining accounts
-                    let loader = match ctx
-                        .remaining_accounts
-                        .iter()
-                        .find(|account| *account.key == tick_address)
-                    {
-                        Some(account) => AccountLoader::<'_, Tick>::try_from(account).unwrap(),
-                        None => return Err(ErrorCode::TickNotFound.into()),
-                    };
-                    let mut tick = loader.load_mut().unwrap();
-
-                    // crossing tick
-                    if !x_to_y || is_enough_amount_to_cross {
-                        msg!("INVARIANT: CROSSING TICK {} ", { tick.index });
-                        cross_tick(&mut tick, &mut pool, get_current_timestamp())?;
-                    } else if !remaining_amount.is_zero() {
-                        if by_amount_in {
-                            pool.add_fee(remaining_amount, FixedPoint::from_integer(0), x_to_y);
-                            total_amount_in += remaining_amount;
-                        }
-                        remaining_amount = TokenAmount(0);
-                    }
-                }
-                // set tick to limit (below if price is going down, because current tick should always be below price)
-                pool.current_tick_index = if x_to_y && is_enough_amount_to_cross {
-                    tick_index.checked_sub(pool.tick_spacing as i32).unwrap()
-                } else {
-                    tick_index
-                };
-            } else {
-                assert!(
-                    pool.current_tick_index
-                        .checked_rem(pool.tick_spacing.into())
-                        .unwrap()
-                        == 0,
-                    "tick not divisible by spacing"
-                );
-                pool.current_tick_index =
-                    get_tick_at_sqrt_price(result.next_price_sqrt, pool.tick_spacing);
-            }
-        }
-
-        if total_amount_out.0 == 0 {
-            return Err(ErrorCode::NoGainSwap.into());
         }
-
         // Execute swap
         let (take_ctx, send_ctx) = match x_to_y {
             true => (ctx.accounts.take_x(), ctx.accounts.send_y()),
@@ -304,20 +212,61 @@ impl<'info> Swap<'info> {
         let signer: &[&[&[u8]]] = get_signer!(state.nonce);
         token::transfer(send_ctx.with_signer(signer), total_amount_out.0)?;
 
-        match ref_account.is_some() && !total_amount_referral.is_zero() {
-            true => {
+        match ref_account {
+            Some(ref_account) if !total_amount_referral.is_zero() => {
                 let take_ref_ctx = match x_to_y {
-                    true => ctx.accounts.take_ref_x(ref_account.unwrap().clone()),
-                    false => ctx.accounts.take_ref_y(ref_account.unwrap().clone()),
+                    true => ctx.accounts.take_ref_x(ref_account.clone()),
+                    false => ctx.accounts.take_ref_y(ref_account.clone()),
                 };
-                token::transfer(take_ctx, total_amount_in.0 - total_amount_referral.0)?;
+                let amount_to_pool = total_amount_in
+                    .checked_sub(total_amount_referral)
+                    .map_err(|_| ErrorCode::MathOverflow)?;
+                token::transfer(take_ctx, amount_to_pool.0)?;
                 token::transfer(take_ref_ctx, total_amount_referral.0)?;
             }
-            false => {
+            _ => {
                 token::transfer(take_ctx, total_amount_in.0)?;
             }
         }
 
+        // unfilled part of the amount is returned so callers don't have to parse the logs,
+        // set after the transfers as every CPI clears the return data
+        if allow_partial_fill {
+            set_return_data(&unfilled_amount.0.to_le_bytes());
+        }
+
         Ok(())
     }
 }
+
+// Ticks to cross are passed in remaining accounts
+struct TickAccounts<'a, 'info> {
+    pool: Pubkey,
+    program_id: &'a Pubkey,
+    remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> SwapTicks for TickAccounts<'a, 'info> {
+    type Handle = AccountLoader<'info, Tick>;
+
+    fn find(&mut self, index: i32) -> Result<Option<Self::Handle>> {
+        // Calculating address of the crossed tick
+        let (tick_address, _) = Pubkey::find_program_address(
+            &[b"tickv1", self.pool.as_ref(), &index.to_le_bytes()],
+            self.program_id,
+        );
+
+        // Finding the correct tick in remaining accounts
+        self.remaining_accounts
+            .iter()
+            .find(|account| *account.key == tick_address)
+            .map(|account| AccountLoader::<'info, Tick>::try_from(account))
+            .transpose()
+    }
+
+    fn cross(&mut self, tick: Self::Handle, pool: &mut Pool, current_timestamp: u64) -> Result<()> {
+        let mut tick = tick.load_mut()?;
+        msg!("INVARIANT: CROSSING TICK {} ", { tick.index });
+        cross_tick(&mut tick, pool, current_timestamp)
+    }
+}
@@ -57,8 +57,14 @@ impl<'info> TransferPositionOwnership<'info> {
         let new_position = &mut self.new_position.load_init()?;
         let removed_position = &mut self.removed_position.load_mut()?;
 
-        owner_list.head = owner_list.head.checked_sub(1).unwrap();
-        recipient_list.head = recipient_list.head.checked_add(1).unwrap();
+        owner_list.head = owner_list
+            .head
+            .checked_sub(1)
+            .ok_or(MathOverflow)?;
+        recipient_list.head = recipient_list
+            .head
+            .checked_add(1)
+            .ok_or(MathOverflow)?;
 
         // reassign all fields in new_position
         {
@@ -75,6 +81,10 @@ impl<'info> TransferPositionOwnership<'info> {
                 tokens_owed_x: removed_position.tokens_owed_x,
                 tokens_owed_y: removed_position.tokens_owed_y,
                 last_slot: removed_position.last_slot,
+                reward_growths_inside: removed_position.reward_growths_inside,
+                rewards_owed: removed_position.rewards_owed,
+                version: removed_position.version,
+                reserved: removed_position.reserved,
                 bump, // assign new bump
             };
         }
@@ -96,6 +106,10 @@ impl<'info> TransferPositionOwnership<'info> {
                 tokens_owed_x: last_position.tokens_owed_x,
                 tokens_owed_y: last_position.tokens_owed_y,
                 last_slot: last_position.last_slot,
+                reward_growths_inside: last_position.reward_growths_inside,
+                rewards_owed: last_position.rewards_owed,
+                version: last_position.version,
+                reserved: last_position.reserved,
                 bump: removed_position.bump, // stay with the same bump
             };
             *last_position = Default::default();
@@ -55,11 +55,11 @@ impl<'info> UpdateSecondsPerLiquidity<'info> {
         let pool = &mut self.pool.load_mut()?;
         let lower_tick = *self.lower_tick.load()?;
         let upper_tick = *self.upper_tick.load()?;
-        let current_time = get_current_timestamp();
+        let current_time = get_current_timestamp()?;
         let position = &mut self.position.load_mut()?;
         position.seconds_per_liquidity_inside =
-            calculate_seconds_per_liquidity_inside(lower_tick, upper_tick, pool, current_time);
-        position.last_slot = get_current_slot();
+            calculate_seconds_per_liquidity_inside(lower_tick, upper_tick, pool, current_time)?;
+        position.last_slot = get_current_slot()?;
 
         Ok(())
     }
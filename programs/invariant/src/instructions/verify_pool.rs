@@ -0,0 +1,87 @@
+use crate::structs::pool::Pool;
+use crate::structs::position::Position;
+use crate::structs::tick::Tick;
+use crate::structs::tickmap::{Tickmap, TickmapView};
+use crate::util::{calculate_pool_report, load_tickmap_extension};
+use crate::ErrorCode::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::Discriminator;
+use anchor_spl::token::TokenAccount;
+
+// Checks pool invariants from chain data, one page of the pool at a time
+// remaining accounts are positions each followed by its lower and upper tick, and the initialized ticks
+// between lower and upper tick in ascending order, along with the tickmap extension if the pool has one
+// report of the page is logged and set as return data, reports of all pages are merged with PoolReport::merge
+#[derive(Accounts)]
+pub struct VerifyPool<'info> {
+    pub pool: AccountLoader<'info, Pool>,
+    #[account(constraint = tickmap.key() == pool.load()?.tickmap @ InvalidTickmap)]
+    pub tickmap: AccountLoader<'info, Tickmap>,
+    #[account(constraint = token_x_reserve.key() == pool.load()?.token_x_reserve @ InvalidTokenAccount)]
+    pub token_x_reserve: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = token_y_reserve.key() == pool.load()?.token_y_reserve @ InvalidTokenAccount)]
+    pub token_y_reserve: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> VerifyPool<'info> {
+    pub fn handler(
+        ctx: Context<'_, '_, '_, 'info, VerifyPool<'info>>,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> ProgramResult {
+        msg!("INVARIANT: VERIFY POOL");
+
+        let pool = ctx.accounts.pool.load()?;
+        let pool_address = ctx.accounts.pool.key();
+        let tickmap = ctx.accounts.tickmap.load()?;
+        let tickmap_extension = load_tickmap_extension(&pool, ctx.remaining_accounts)?;
+        let tickmap_extension = tickmap_extension
+            .as_ref()
+            .map(|extension| extension.load())
+            .transpose()?;
+        let tickmap = TickmapView::new(&tickmap, tickmap_extension.as_deref());
+
+        let load_tick = |account: Option<&AccountInfo<'info>>| -> Result<Tick> {
+            let account = account.ok_or(InvalidRemainingAccounts)?;
+            let tick = *AccountLoader::<'_, Tick>::try_from(account)?.load()?;
+            require!({ tick.pool } == pool_address, WrongTick);
+            Ok(tick)
+        };
+
+        let mut ticks: Vec<Tick> = Vec::new();
+        let mut positions: Vec<(Position, Tick, Tick)> = Vec::new();
+        let mut accounts = ctx.remaining_accounts.iter();
+        while let Some(account) = accounts.next() {
+            if pool.has_tickmap_extension() && *account.key == pool.tickmap_extension {
+                continue;
+            }
+            let is_position = account
+                .try_borrow_data()?
+                .starts_with(&Position::discriminator());
+
+            if is_position {
+                let position = *AccountLoader::<'_, Position>::try_from(account)?.load()?;
+                require!({ position.pool } == pool_address, InvalidPositionIndex);
+                let lower_tick = load_tick(accounts.next())?;
+                let upper_tick = load_tick(accounts.next())?;
+                positions.push((position, lower_tick, upper_tick));
+            } else {
+                ticks.push(load_tick(Some(account))?);
+            }
+        }
+
+        let mut report =
+            calculate_pool_report(&pool, &tickmap, lower_tick, upper_tick, &ticks, &positions)?;
+        report.reserve_x = ctx.accounts.token_x_reserve.amount;
+        report.reserve_y = ctx.accounts.token_y_reserve.amount;
+
+        msg!("{:?}", report);
+        // holds for the whole pool only if the page covers all of it
+        msg!("INVARIANT: POOL VALID {}", report.is_valid());
+        let data = report.try_to_vec().map_err(ProgramError::from)?;
+        set_return_data(&data);
+
+        Ok(())
+    }
+}
@@ -1,14 +1,16 @@
-mod decimals;
+pub mod decimals;
 mod errors;
 mod instructions;
 mod interfaces;
-mod log;
+pub mod log;
 mod macros;
-mod math;
+pub mod math;
 mod referral;
+#[cfg(test)]
+mod simulation;
 pub mod structs;
 mod uint;
-mod util;
+pub mod util;
 
 use anchor_lang::prelude::*;
 use anchor_spl::token;
@@ -18,7 +20,10 @@ use errors::ErrorCode;
 use errors::*;
 use instructions::*;
 use math::*;
-use structs::{Pool, State};
+use structs::{
+    FeeTier, FeeTierV0, Pool, PoolV0, Position, PositionList, PositionListV0, PositionV0, State,
+    StateV0, Tick, TickV0,
+};
 use util::*;
 
 use instructions::claim_fee::ClaimFee;
@@ -33,7 +38,10 @@ pub mod invariant {
     pub fn create_state(ctx: Context<CreateState>, nonce: u8) -> ProgramResult {
         instructions::create_state::handler(ctx, nonce)
     }
-    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    #[access_control(
+        admin(&ctx.accounts.state, &ctx.accounts.admin)
+        no_timelock(&ctx.accounts.state)
+    )]
     pub fn create_fee_tier(
         ctx: Context<CreateFeeTier>,
         fee: u128,
@@ -43,9 +51,38 @@ pub mod invariant {
             .handler(fee, tick_spacing, *ctx.bumps.get("fee_tier").unwrap())
     }
 
-    pub fn create_pool(ctx: Context<CreatePool>, init_tick: i32) -> ProgramResult {
-        ctx.accounts
-            .handler(init_tick, *ctx.bumps.get("pool").unwrap())
+    pub fn create_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePool<'info>>,
+        init_sqrt_price: Price,
+    ) -> ProgramResult {
+        ctx.accounts.handler(
+            init_sqrt_price,
+            *ctx.bumps.get("pool").unwrap(),
+            ctx.remaining_accounts,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_pool_with_position(
+        ctx: Context<CreatePoolWithPosition>,
+        init_sqrt_price: Price,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        position_index: u32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: Price,
+        slippage_limit_upper: Price,
+    ) -> ProgramResult {
+        ctx.accounts.handler(
+            init_sqrt_price,
+            lower_tick_index,
+            upper_tick_index,
+            position_index,
+            liquidity_delta,
+            slippage_limit_lower,
+            slippage_limit_upper,
+            &ctx.bumps,
+        )
     }
 
     pub fn swap<'info>(
@@ -55,7 +92,17 @@ pub mod invariant {
         by_amount_in: bool, // whether amount specifies input or output
         sqrt_price_limit: u128,
     ) -> ProgramResult {
-        Swap::handler(ctx, x_to_y, amount, by_amount_in, sqrt_price_limit)
+        Swap::handler(ctx, x_to_y, amount, by_amount_in, sqrt_price_limit, false)
+    }
+
+    pub fn swap_with_partial_fill<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        x_to_y: bool,
+        amount: u64,
+        by_amount_in: bool, // whether amount specifies input or output
+        sqrt_price_limit: u128,
+    ) -> ProgramResult {
+        Swap::handler(ctx, x_to_y, amount, by_amount_in, sqrt_price_limit, true)
     }
 
     pub fn initialize_oracle(ctx: Context<InitializeOracle>) -> ProgramResult {
@@ -66,13 +113,17 @@ pub mod invariant {
         ctx.accounts.handler(index, *ctx.bumps.get("tick").unwrap())
     }
 
+    pub fn create_tickmap_extension(ctx: Context<CreateTickmapExtension>) -> ProgramResult {
+        ctx.accounts.handler()
+    }
+
     pub fn create_position_list(ctx: Context<CreatePositionList>) -> ProgramResult {
         ctx.accounts
             .handler(*ctx.bumps.get("position_list").unwrap())
     }
 
-    pub fn create_position(
-        ctx: Context<CreatePosition>,
+    pub fn create_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePosition<'info>>,
         _lower_tick_index: i32,
         _upper_tick_index: i32,
         liquidity_delta: Liquidity,
@@ -84,17 +135,29 @@ pub mod invariant {
             slippage_limit_lower,
             slippage_limit_upper,
             *ctx.bumps.get("position").unwrap(),
+            ctx.remaining_accounts,
         )
     }
 
-    pub fn remove_position(
-        ctx: Context<RemovePosition>,
+    pub fn remove_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemovePosition<'info>>,
         index: u32,
         lower_tick_index: i32,
         upper_tick_index: i32,
     ) -> ProgramResult {
-        ctx.accounts
-            .handler(index, lower_tick_index, upper_tick_index)
+        ctx.accounts.handler(
+            index,
+            lower_tick_index,
+            upper_tick_index,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn set_position_operator(
+        ctx: Context<SetPositionOperator>,
+        operator: Pubkey,
+    ) -> ProgramResult {
+        ctx.accounts.handler(operator)
     }
 
     pub fn transfer_position_ownership(
@@ -105,13 +168,81 @@ pub mod invariant {
             .handler(index, *ctx.bumps.get("new_position").unwrap())
     }
 
-    pub fn claim_fee(
-        ctx: Context<ClaimFee>,
+    pub fn claim_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimFee<'info>>,
         _index: u32,
         _lower_tick_index: i32,
         _upper_tick_index: i32,
     ) -> ProgramResult {
-        ctx.accounts.handler()
+        ctx.accounts.handler(ctx.remaining_accounts)
+    }
+
+    pub fn claim_fees_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimFeesBatch<'info>>,
+    ) -> ProgramResult {
+        ClaimFeesBatch::handler(ctx)
+    }
+
+    pub fn compound_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompoundFees<'info>>,
+        _index: u32,
+        _lower_tick_index: i32,
+        _upper_tick_index: i32,
+    ) -> ProgramResult {
+        ctx.accounts.handler(ctx.remaining_accounts)
+    }
+
+    pub fn claim_rewards(
+        ctx: Context<ClaimRewards>,
+        _index: u32,
+        _lower_tick_index: i32,
+        _upper_tick_index: i32,
+        reward_index: u8,
+    ) -> ProgramResult {
+        ctx.accounts.handler(reward_index)
+    }
+
+    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    pub fn initialize_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeReward<'info>>,
+        reward_index: u8,
+        emissions_per_second: u64,
+        end_timestamp: u64,
+    ) -> ProgramResult {
+        ctx.accounts.handler(
+            reward_index,
+            emissions_per_second,
+            end_timestamp,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn migrate_state(ctx: Context<Migrate>) -> ProgramResult {
+        ctx.accounts.handler::<StateV0, State>()
+    }
+
+    pub fn migrate_fee_tier(ctx: Context<Migrate>) -> ProgramResult {
+        ctx.accounts.handler::<FeeTierV0, FeeTier>()
+    }
+
+    pub fn migrate_pool(ctx: Context<Migrate>) -> ProgramResult {
+        ctx.accounts.handler::<PoolV0, Pool>()
+    }
+
+    pub fn migrate_tick(ctx: Context<Migrate>) -> ProgramResult {
+        ctx.accounts.handler::<TickV0, Tick>()
+    }
+
+    pub fn migrate_position(ctx: Context<Migrate>) -> ProgramResult {
+        ctx.accounts.handler::<PositionV0, Position>()
+    }
+
+    pub fn migrate_position_list(ctx: Context<Migrate>) -> ProgramResult {
+        ctx.accounts.handler::<PositionListV0, PositionList>()
+    }
+
+    pub fn migrate_oracle(ctx: Context<Migrate>) -> ProgramResult {
+        ctx.accounts.handler::<OracleV0, Oracle>()
     }
 
     pub fn update_seconds_per_liquidity(
@@ -128,7 +259,10 @@ pub mod invariant {
         ctx.accounts.handler()
     }
 
-    #[access_control(receiver(&ctx.accounts.pool, &ctx.accounts.admin))]
+    #[access_control(
+        receiver(&ctx.accounts.pool, &ctx.accounts.admin)
+        no_timelock(&ctx.accounts.state)
+    )]
     pub fn change_protocol_fee(
         ctx: Context<ChangeProtocolFee>,
         protocol_fee: FixedPoint,
@@ -136,10 +270,61 @@ pub mod invariant {
         ctx.accounts.handler(protocol_fee)
     }
 
-    #[access_control(admin(&ctx.accounts.state, &ctx.accounts.admin))]
+    #[access_control(
+        admin(&ctx.accounts.state, &ctx.accounts.admin)
+        no_timelock(&ctx.accounts.state)
+    )]
+    pub fn change_default_protocol_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, ChangeDefaultProtocolFee<'info>>,
+        protocol_fee: FixedPoint,
+    ) -> ProgramResult {
+        ChangeDefaultProtocolFee::handler(ctx, protocol_fee)
+    }
+
+    #[access_control(
+        admin(&ctx.accounts.state, &ctx.accounts.admin)
+        no_timelock(&ctx.accounts.state)
+    )]
     pub fn change_fee_receiver(ctx: Context<ChangeFeeReceiver>) -> ProgramResult {
         ctx.accounts.handler()
     }
+
+    pub fn propose(
+        ctx: Context<Propose>,
+        action: u8,
+        value: u128,
+        tick_spacing: u16,
+    ) -> ProgramResult {
+        ctx.accounts.handler(
+            action,
+            value,
+            tick_spacing,
+            *ctx.bumps.get("proposal").unwrap(),
+        )
+    }
+
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> ProgramResult {
+        ExecuteProposal::handler(ctx)
+    }
+
+    pub fn execute_create_fee_tier(ctx: Context<ExecuteCreateFeeTier>) -> ProgramResult {
+        ctx.accounts
+            .handler(*ctx.bumps.get("fee_tier").unwrap())
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> ProgramResult {
+        ctx.accounts.handler()
+    }
+
+    pub fn verify_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, VerifyPool<'info>>,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> ProgramResult {
+        VerifyPool::handler(ctx, lower_tick, upper_tick)
+    }
 }
 
 fn admin(state_loader: &AccountLoader<State>, signer: &AccountInfo) -> Result<()> {
@@ -148,6 +333,13 @@ fn admin(state_loader: &AccountLoader<State>, signer: &AccountInfo) -> Result<()
     Ok(())
 }
 
+// sensitive actions are only immediate while there is no governance delay, otherwise they are proposed
+fn no_timelock(state_loader: &AccountLoader<State>) -> Result<()> {
+    let state = state_loader.load()?;
+    require!(state.governance_delay == 0, TimelockActive);
+    Ok(())
+}
+
 fn receiver(pool_loader: &AccountLoader<Pool>, signer: &AccountInfo) -> Result<()> {
     let pool = pool_loader.load()?;
     require!(signer.key.eq(&pool.fee_receiver), Unauthorized);
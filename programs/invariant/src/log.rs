@@ -1,5 +1,6 @@
 use crate::decimals::*;
 use crate::math::calculate_price_sqrt;
+use anchor_lang::prelude::*;
 
 const LOG2_SCALE: u8 = 32;
 const LOG2_DOUBLE_SCALE: u8 = 64;
@@ -85,7 +86,7 @@ fn log2_iterative_approximation_x32(mut sqrt_price_x32: u64) -> (bool, u64) {
     (sign, result)
 }
 
-pub fn get_tick_at_sqrt_price(sqrt_price_decimal: Price, tick_spacing: u16) -> i32 {
+pub fn get_tick_at_sqrt_price(sqrt_price_decimal: Price, tick_spacing: u16) -> Result<i32> {
     let sqrt_price_x32: u64 = price_to_x32(sqrt_price_decimal);
     let (log2_sign, log2_sqrt_price) = log2_iterative_approximation_x32(sqrt_price_x32);
 
@@ -105,19 +106,19 @@ pub fn get_tick_at_sqrt_price(sqrt_price_decimal: Price, tick_spacing: u16) -> i
     let farther_tick_with_spacing = align_tick_to_spacing(farther_tick, tick_spacing as i32);
     let nearer_tick_with_spacing = align_tick_to_spacing(nearer_tick, tick_spacing as i32);
     if farther_tick_with_spacing == nearer_tick_with_spacing {
-        return nearer_tick_with_spacing;
+        return Ok(nearer_tick_with_spacing);
     };
 
     let accurate_tick = match log2_sign {
         true => {
-            let farther_tick_sqrt_price_decimal = calculate_price_sqrt(farther_tick);
+            let farther_tick_sqrt_price_decimal = calculate_price_sqrt(farther_tick)?;
             match sqrt_price_decimal >= farther_tick_sqrt_price_decimal {
                 true => farther_tick_with_spacing,
                 false => nearer_tick_with_spacing,
             }
         }
         false => {
-            let nearer_tick_sqrt_price_decimal = calculate_price_sqrt(nearer_tick);
+            let nearer_tick_sqrt_price_decimal = calculate_price_sqrt(nearer_tick)?;
             match nearer_tick_sqrt_price_decimal <= sqrt_price_decimal {
                 true => nearer_tick_with_spacing,
                 false => farther_tick_with_spacing,
@@ -125,8 +126,8 @@ pub fn get_tick_at_sqrt_price(sqrt_price_decimal: Price, tick_spacing: u16) -> i
         }
     };
     match tick_spacing > 1 {
-        true => align_tick_to_spacing(accurate_tick, tick_spacing as i32),
-        false => accurate_tick,
+        true => Ok(align_tick_to_spacing(accurate_tick, tick_spacing as i32)),
+        false => Ok(accurate_tick),
     }
 }
 
@@ -140,7 +141,7 @@ mod tests {
     fn test_price_to_u64() {
         // min sqrt price -> sqrt(1.0001)^MIN_TICK
         {
-            let min_sqrt_price_decimal = calculate_price_sqrt(-MAX_TICK);
+            let min_sqrt_price_decimal = calculate_price_sqrt(-MAX_TICK).unwrap();
             let min_sqrt_price_x32 = price_to_x32(min_sqrt_price_decimal);
 
             let expected_min_sqrt_price_x32 = 65536;
@@ -148,7 +149,7 @@ mod tests {
         }
         // max sqrt price -> sqrt(1.0001)^MAX_TICK
         {
-            let max_sqrt_price_decimal = calculate_price_sqrt(MAX_TICK);
+            let max_sqrt_price_decimal = calculate_price_sqrt(MAX_TICK).unwrap();
             let max_sqrt_price_x32 = price_to_x32(max_sqrt_price_decimal);
 
             let expected_max_sqrt_price_x32 = 281472330729535;
@@ -184,7 +185,7 @@ mod tests {
         }
         // log2 of max sqrt price
         {
-            let max_sqrt_price = calculate_price_sqrt(MAX_TICK);
+            let max_sqrt_price = calculate_price_sqrt(MAX_TICK).unwrap();
             let sqrt_price_x32 = price_to_x32(max_sqrt_price);
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, true);
@@ -192,7 +193,7 @@ mod tests {
         }
         // log2 of min sqrt price
         {
-            let min_sqrt_price = calculate_price_sqrt(-MAX_TICK);
+            let min_sqrt_price = calculate_price_sqrt(-MAX_TICK).unwrap();
             let sqrt_price_x32 = price_to_x32(min_sqrt_price);
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
             assert_eq!(sign, false);
@@ -200,7 +201,7 @@ mod tests {
         }
         // log2 of sqrt(1.0001^(-19_999)) - 1
         {
-            let mut sqrt_price_decimal = calculate_price_sqrt(-19_999);
+            let mut sqrt_price_decimal = calculate_price_sqrt(-19_999).unwrap();
             sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
             let sqrt_price_x32 = price_to_x32(sqrt_price_decimal);
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
@@ -209,7 +210,7 @@ mod tests {
         }
         // log2 of sqrt(1.0001^(19_999)) + 1
         {
-            let mut sqrt_price_decimal = calculate_price_sqrt(19_999);
+            let mut sqrt_price_decimal = calculate_price_sqrt(19_999).unwrap();
             sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
             let sqrt_price_x32 = price_to_x32(sqrt_price_decimal);
             let (sign, value) = log2_iterative_approximation_x32(sqrt_price_x32);
@@ -225,104 +226,104 @@ mod tests {
             // get tick at 1
             {
                 let sqrt_price_decimal = Price::from_integer(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 0);
             }
             // get tick slightly below 1
             {
                 let sqrt_price_decimal = Price::from_integer(1) - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -1);
             }
             // get tick slightly above 1
             {
                 let sqrt_price_decimal = Price::from_integer(1) + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 0);
             }
         }
         // around 1 tick
         {
-            let sqrt_price_decimal = calculate_price_sqrt(1);
+            let sqrt_price_decimal = calculate_price_sqrt(1).unwrap();
             // get tick at sqrt(1.0001)
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 1);
             }
             // get tick slightly below sqrt(1.0001)
             {
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 0);
             }
             // get tick slightly above sqrt(1.0001)
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, 1);
             }
         }
         // around -1 tick
         {
-            let sqrt_price_decimal = calculate_price_sqrt(-1);
+            let sqrt_price_decimal = calculate_price_sqrt(-1).unwrap();
             // get tick at sqrt(1.0001^(-1))
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -1);
             }
             // get tick slightly below sqrt(1.0001^(-1))
             {
-                let sqrt_price_decimal = calculate_price_sqrt(-1) - Price::new(1);
+                let sqrt_price_decimal = calculate_price_sqrt(-1).unwrap() - Price::new(1);
 
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -2);
             }
             // get tick slightly above sqrt(1.0001^(-1))
             {
-                let sqrt_price_decimal = calculate_price_sqrt(-1) + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let sqrt_price_decimal = calculate_price_sqrt(-1).unwrap() + Price::new(1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -1);
             }
         }
         // around max - 1 tick
         {
-            let sqrt_price_decimal = calculate_price_sqrt(MAX_TICK - 1);
+            let sqrt_price_decimal = calculate_price_sqrt(MAX_TICK - 1).unwrap();
             // get tick at sqrt(1.0001^(MAX_TICK - 1))
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, MAX_TICK - 1);
             }
             // get tick slightly below sqrt(1.0001^(MAX_TICK - 1))
             {
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, MAX_TICK - 2);
             }
             // get tick slightly above sqrt(1.0001^(MAX_TICK - 1))
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, MAX_TICK - 1);
             }
         }
         // around min + 1 tick
         {
-            let sqrt_price_decimal = calculate_price_sqrt(-(MAX_TICK - 1));
+            let sqrt_price_decimal = calculate_price_sqrt(-(MAX_TICK - 1)).unwrap();
             // get tick at sqrt(1.0001^(-MAX_TICK + 1))
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -(MAX_TICK - 1));
             }
             // get tick slightly below sqrt(1.0001^(-MAX_TICK + 1))
             {
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -MAX_TICK);
             }
             // get tick slightly above sqrt(1.0001^(-MAX_TICK + 1))
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, -(MAX_TICK - 1));
             }
         }
@@ -330,60 +331,60 @@ mod tests {
         {
             let max_sqrt_price = Price::from_scale(655354, 1);
             let sqrt_price_decimal = max_sqrt_price - Price::new(1);
-            let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+            let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
             assert_eq!(tick, MAX_TICK);
         }
         // around 19_999 tick
         {
             let expected_tick = 19_999;
-            let sqrt_price_decimal = calculate_price_sqrt(expected_tick);
+            let sqrt_price_decimal = calculate_price_sqrt(expected_tick).unwrap();
             // get tick at sqrt(1.0001^19_999)
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick);
             }
             // get tick slightly below sqrt(1.0001^19_999)
             {
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
 
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick - 1);
             }
             // get tick slightly above sqrt(1.0001^19_999)
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick);
             }
         }
         // around -19_999 tick
         {
             let expected_tick = -19_999;
-            let sqrt_price_decimal = calculate_price_sqrt(expected_tick);
+            let sqrt_price_decimal = calculate_price_sqrt(expected_tick).unwrap();
             // get tick at sqrt(1.0001^(-19_999))
             {
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick);
             }
             // get tick slightly below sqrt(1.0001^(-19_999))
             {
                 // let sqrt_price_decimal = sqrt_price_decimal - Decimal::new(150);
                 let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick - 1);
             }
             // get tick slightly above sqrt(1.0001^(-19_999))
             {
                 let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                 assert_eq!(tick, expected_tick);
             }
         }
         //get tick slightly above at min tick
         {
-            let min_sqrt_price = calculate_price_sqrt(-MAX_TICK);
+            let min_sqrt_price = calculate_price_sqrt(-MAX_TICK).unwrap();
             let sqrt_price_decimal = min_sqrt_price + Price::new(1);
-            let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+            let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
             assert_eq!(tick, -MAX_TICK);
         }
     }
@@ -437,22 +438,22 @@ mod tests {
         for n in 0..MAX_TICK {
             {
                 let expected_tick = n;
-                let sqrt_price_decimal = calculate_price_sqrt(expected_tick);
+                let sqrt_price_decimal = calculate_price_sqrt(expected_tick).unwrap();
                 // get tick at sqrt(1.0001^(n))
                 {
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly below sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick - 1);
                 }
                 // get tick slightly above sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick);
                 }
             }
@@ -464,22 +465,22 @@ mod tests {
         for n in 0..MAX_TICK {
             {
                 let expected_tick = -n;
-                let sqrt_price_decimal = calculate_price_sqrt(expected_tick);
+                let sqrt_price_decimal = calculate_price_sqrt(expected_tick).unwrap();
                 // get tick at sqrt(1.0001^(n))
                 {
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly below sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick - 1);
                 }
                 // get tick slightly above sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1);
+                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, 1).unwrap();
                     assert_eq!(tick, expected_tick);
                 }
             }
@@ -492,24 +493,27 @@ mod tests {
         for n in 0..MAX_TICK {
             {
                 let input_tick = n;
-                let sqrt_price_decimal = calculate_price_sqrt(input_tick);
+                let sqrt_price_decimal = calculate_price_sqrt(input_tick).unwrap();
                 // get tick at sqrt(1.0001^(n))
                 {
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly below sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick - 1, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly above sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
@@ -523,24 +527,27 @@ mod tests {
         for n in 0..MAX_TICK {
             {
                 let input_tick = -n;
-                let sqrt_price_decimal = calculate_price_sqrt(input_tick);
+                let sqrt_price_decimal = calculate_price_sqrt(input_tick).unwrap();
                 // get tick at sqrt(1.0001^(n))
                 {
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly below sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal - Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick - 1, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
                 // get tick slightly above sqrt(1.0001^n)
                 {
                     let sqrt_price_decimal = sqrt_price_decimal + Price::new(1);
-                    let tick = get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16);
+                    let tick =
+                        get_tick_at_sqrt_price(sqrt_price_decimal, tick_spacing as u16).unwrap();
                     let expected_tick = align_tick_to_spacing(input_tick, tick_spacing);
                     assert_eq!(tick, expected_tick);
                 }
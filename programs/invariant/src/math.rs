@@ -4,7 +4,7 @@ use crate::decimals::*;
 use crate::structs::pool::Pool;
 use crate::structs::tick::Tick;
 use crate::structs::tickmap::MAX_TICK;
-use crate::structs::TICK_LIMIT;
+use crate::structs::REWARDS_PER_POOL;
 use crate::*;
 
 pub const MAX_SQRT_PRICE: u128 = 65535383934512647000000000000;
@@ -19,10 +19,10 @@ pub struct SwapResult {
 }
 
 // converts ticks to price with reduced precision
-pub fn calculate_price_sqrt(tick_index: i32) -> Price {
+pub fn calculate_price_sqrt(tick_index: i32) -> Result<Price> {
     // checking if tick be converted to price (overflows if more)
     let tick = tick_index.abs();
-    assert!(tick <= MAX_TICK, "tick over bounds");
+    require!(tick <= MAX_TICK, InvalidTickIndex);
 
     let mut price = FixedPoint::from_integer(1);
 
@@ -83,9 +83,11 @@ pub fn calculate_price_sqrt(tick_index: i32) -> Price {
 
     // Parsing to the Price type by the end by convention (should always have 12 zeros at the end)
     if tick_index >= 0 {
-        Price::from_decimal(price)
+        Ok(Price::from_decimal(price))
     } else {
-        Price::from_decimal(FixedPoint::from_integer(1).big_div(price))
+        Ok(Price::from_decimal(
+            FixedPoint::from_integer(1).big_div(price),
+        ))
     }
 }
 
@@ -96,14 +98,14 @@ pub fn compute_swap_step(
     amount: TokenAmount,
     by_amount_in: bool,
     fee: FixedPoint,
-) -> SwapResult {
+) -> Result<SwapResult> {
     if liquidity.is_zero() {
-        return SwapResult {
+        return Ok(SwapResult {
             next_price_sqrt: target_price_sqrt,
             amount_in: TokenAmount(0),
             amount_out: TokenAmount(0),
             fee_amount: TokenAmount(0),
-        };
+        });
     }
 
     let x_to_y = current_price_sqrt >= target_price_sqrt;
@@ -112,8 +114,10 @@ pub fn compute_swap_step(
     let mut amount_in = TokenAmount(0);
     let mut amount_out = TokenAmount(0);
 
+    let mut amount_after_fee = TokenAmount(0);
+
     if by_amount_in {
-        let amount_after_fee = amount.big_mul(FixedPoint::from_integer(1u8) - fee);
+        amount_after_fee = amount.big_mul(FixedPoint::from_integer(1u8) - fee);
 
         amount_in = if x_to_y {
             get_delta_x(target_price_sqrt, current_price_sqrt, liquidity, true)
@@ -131,7 +135,7 @@ pub fn compute_swap_step(
                 liquidity,
                 amount_after_fee,
                 x_to_y,
-            )
+            )?
         };
     } else {
         amount_out = if x_to_y {
@@ -145,7 +149,7 @@ pub fn compute_swap_step(
             next_price_sqrt = target_price_sqrt
         } else {
             next_price_sqrt =
-                get_next_sqrt_price_from_output(current_price_sqrt, liquidity, amount, x_to_y)
+                get_next_sqrt_price_from_output(current_price_sqrt, liquidity, amount, x_to_y)?
         }
     }
 
@@ -153,20 +157,29 @@ pub fn compute_swap_step(
 
     if x_to_y {
         if not_max || !by_amount_in {
-            amount_in = get_delta_x(next_price_sqrt, current_price_sqrt, liquidity, true).unwrap()
+            amount_in = get_delta_x(next_price_sqrt, current_price_sqrt, liquidity, true)
+                .ok_or(ErrorCode::MathOverflow)?
         };
         if not_max || by_amount_in {
-            amount_out = get_delta_y(next_price_sqrt, current_price_sqrt, liquidity, false).unwrap()
+            amount_out = get_delta_y(next_price_sqrt, current_price_sqrt, liquidity, false)
+                .ok_or(ErrorCode::MathOverflow)?
         }
     } else {
         if not_max || !by_amount_in {
-            amount_in = get_delta_y(current_price_sqrt, next_price_sqrt, liquidity, true).unwrap()
+            amount_in = get_delta_y(current_price_sqrt, next_price_sqrt, liquidity, true)
+                .ok_or(ErrorCode::MathOverflow)?
         };
         if not_max || by_amount_in {
-            amount_out = get_delta_x(current_price_sqrt, next_price_sqrt, liquidity, false).unwrap()
+            amount_out = get_delta_x(current_price_sqrt, next_price_sqrt, liquidity, false)
+                .ok_or(ErrorCode::MathOverflow)?
         }
     }
 
+    // Amount in rounded up from the next price can exceed the amount the price was calculated from
+    if by_amount_in && not_max && amount_in > amount_after_fee {
+        amount_in = amount_after_fee;
+    }
+
     // Amount out can not exceed amount
     if !by_amount_in && amount_out > amount {
         amount_out = amount;
@@ -178,12 +191,12 @@ pub fn compute_swap_step(
         amount_in.big_mul_up(fee)
     };
 
-    SwapResult {
+    Ok(SwapResult {
         next_price_sqrt,
         amount_in,
         amount_out,
         fee_amount,
-    }
+    })
 }
 
 // delta x = (L * delta_sqrt_price) / (lower_sqrt_price * higher_sqrt_price)
@@ -226,23 +239,17 @@ pub fn get_delta_y(
         sqrt_price_b - sqrt_price_a
     };
 
-    match match up {
+    let delta_y = match up {
         true => delta_price
             .big_mul_to_value_up(liquidity)
-            .checked_add(Price::almost_one())
-            .unwrap()
-            .checked_div(Price::one())
-            .unwrap()
-            .try_into(),
+            .checked_add(Price::almost_one())?
+            .checked_div(Price::one())?,
         false => delta_price
             .big_mul_to_value(liquidity)
-            .checked_div(Price::one())
-            .unwrap()
-            .try_into(),
-    } {
-        Ok(x) => Some(TokenAmount(x)),
-        Err(_) => None,
-    }
+            .checked_div(Price::one())?,
+    };
+
+    delta_y.try_into().ok().map(TokenAmount)
 }
 
 fn get_next_sqrt_price_from_input(
@@ -250,9 +257,9 @@ fn get_next_sqrt_price_from_input(
     liquidity: Liquidity,
     amount: TokenAmount,
     x_to_y: bool,
-) -> Price {
-    assert!(!price_sqrt.is_zero());
-    assert!(!liquidity.is_zero());
+) -> Result<Price> {
+    require!(!price_sqrt.is_zero(), InvalidSqrtPrice);
+    require!(!liquidity.is_zero(), InvalidPoolLiquidity);
 
     if x_to_y {
         get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true)
@@ -266,9 +273,9 @@ fn get_next_sqrt_price_from_output(
     liquidity: Liquidity,
     amount: TokenAmount,
     x_to_y: bool,
-) -> Price {
-    assert!(!price_sqrt.is_zero());
-    assert!(!liquidity.is_zero());
+) -> Result<Price> {
+    require!(!price_sqrt.is_zero(), InvalidSqrtPrice);
+    require!(!liquidity.is_zero(), InvalidPoolLiquidity);
 
     if x_to_y {
         get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, false)
@@ -283,23 +290,24 @@ fn get_next_sqrt_price_x_up(
     liquidity: Liquidity,
     amount: TokenAmount,
     add: bool,
-) -> Price {
+) -> Result<Price> {
     if amount.is_zero() {
-        return price_sqrt;
+        return Ok(price_sqrt);
     };
 
     let big_liquidity = liquidity
         .here::<U256>()
         .checked_mul(U256::from(PRICE_LIQUIDITY_DENOMINATOR))
-        .unwrap();
+        .ok_or(ErrorCode::MathOverflow)?;
 
     let denominator = match add {
         true => big_liquidity.checked_add(price_sqrt.big_mul_to_value(amount)),
         false => big_liquidity.checked_sub(price_sqrt.big_mul_to_value(amount)),
     }
-    .unwrap();
+    .ok_or(ErrorCode::MathOverflow)?;
 
     Price::big_div_values_up(price_sqrt.big_mul_to_value_up(liquidity), denominator)
+        .ok_or_else(|| ErrorCode::MathOverflow.into())
 }
 
 // price +- (amount / L)
@@ -308,22 +316,21 @@ fn get_next_sqrt_price_y_down(
     liquidity: Liquidity,
     amount: TokenAmount,
     add: bool,
-) -> Price {
-    if add {
-        let quotient = Price::from_decimal(amount).big_div_by_number(
-            U256::from(liquidity.get())
-                .checked_mul(U256::from(PRICE_LIQUIDITY_DENOMINATOR))
-                .unwrap(),
-        );
-        price_sqrt + quotient
+) -> Result<Price> {
+    let amount = Price::checked_from_decimal(amount).map_err(|_| ErrorCode::MathOverflow)?;
+    let big_liquidity = U256::from(liquidity.get())
+        .checked_mul(U256::from(PRICE_LIQUIDITY_DENOMINATOR))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let next_price_sqrt = if add {
+        let quotient = amount.checked_big_div_by_number(big_liquidity);
+        quotient.and_then(|quotient| price_sqrt.checked_add(quotient))
     } else {
-        let quotient = Price::from_decimal(amount).big_div_by_number_up(
-            U256::from(liquidity.get())
-                .checked_mul(U256::from(PRICE_LIQUIDITY_DENOMINATOR))
-                .unwrap(),
-        );
-        price_sqrt - quotient
-    }
+        let quotient = amount.checked_big_div_by_number_up(big_liquidity);
+        quotient.and_then(|quotient| price_sqrt.checked_sub(quotient))
+    };
+
+    next_price_sqrt.map_err(|_| ErrorCode::MathOverflow.into())
 }
 
 #[allow(unaligned_references)]
@@ -373,6 +380,38 @@ pub fn calculate_fee_growth_inside(
     (fee_growth_inside_x, fee_growth_inside_y)
 }
 
+pub fn calculate_reward_growths_inside(
+    tick_lower: Tick,
+    tick_upper: Tick,
+    tick_current: i32,
+    reward_growths_global: [FeeGrowth; REWARDS_PER_POOL],
+) -> [FeeGrowth; REWARDS_PER_POOL] {
+    let current_above_lower = tick_current >= tick_lower.index;
+    let current_below_upper = tick_current < tick_upper.index;
+    let outside_lower = tick_lower.reward_growths_outside;
+    let outside_upper = tick_upper.reward_growths_outside;
+
+    let mut reward_growths_inside = [FeeGrowth::new(0); REWARDS_PER_POOL];
+    for i in 0..REWARDS_PER_POOL {
+        let growth_below = if current_above_lower {
+            outside_lower[i]
+        } else {
+            reward_growths_global[i].unchecked_sub(outside_lower[i])
+        };
+        let growth_above = if current_below_upper {
+            outside_upper[i]
+        } else {
+            reward_growths_global[i].unchecked_sub(outside_upper[i])
+        };
+
+        reward_growths_inside[i] = reward_growths_global[i]
+            .unchecked_sub(growth_below)
+            .unchecked_sub(growth_above);
+    }
+
+    reward_growths_inside
+}
+
 pub fn calculate_amount_delta(
     pool: &mut Pool,
     liquidity_delta: Liquidity,
@@ -386,51 +425,158 @@ pub fn calculate_amount_delta(
 
     if pool.current_tick_index < lower_tick {
         amount_x = get_delta_x(
-            calculate_price_sqrt(lower_tick),
-            calculate_price_sqrt(upper_tick),
+            calculate_price_sqrt(lower_tick)?,
+            calculate_price_sqrt(upper_tick)?,
             liquidity_delta,
             liquidity_sign,
         )
-        .unwrap();
+        .ok_or(ErrorCode::MathOverflow)?;
     } else if pool.current_tick_index < upper_tick {
         // calculating price_sqrt of current_tick is not required - can by pass
         amount_x = get_delta_x(
             pool.sqrt_price,
-            calculate_price_sqrt(upper_tick),
+            calculate_price_sqrt(upper_tick)?,
             liquidity_delta,
             liquidity_sign,
         )
-        .unwrap();
+        .ok_or(ErrorCode::MathOverflow)?;
         amount_y = get_delta_y(
-            calculate_price_sqrt(lower_tick),
+            calculate_price_sqrt(lower_tick)?,
             pool.sqrt_price,
             liquidity_delta,
             liquidity_sign,
         )
-        .unwrap();
+        .ok_or(ErrorCode::MathOverflow)?;
 
         pool.update_liquidity_safely(liquidity_delta, liquidity_sign)?;
     } else {
         amount_y = get_delta_y(
-            calculate_price_sqrt(lower_tick),
-            calculate_price_sqrt(upper_tick),
+            calculate_price_sqrt(lower_tick)?,
+            calculate_price_sqrt(upper_tick)?,
             liquidity_delta,
             liquidity_sign,
         )
-        .unwrap()
+        .ok_or(ErrorCode::MathOverflow)?
     }
 
     Ok((amount_x, amount_y))
 }
 
+// Largest liquidity that can be added to <lower_tick, upper_tick> range using at most given amounts
+pub fn calculate_max_liquidity_from_amounts(
+    current_sqrt_price: Price,
+    current_tick_index: i32,
+    lower_tick: i32,
+    upper_tick: i32,
+    amount_x: TokenAmount,
+    amount_y: TokenAmount,
+) -> Result<Liquidity> {
+    let lower_sqrt_price = calculate_price_sqrt(lower_tick)?;
+    let upper_sqrt_price = calculate_price_sqrt(upper_tick)?;
+
+    // price ranges in which tokens are needed, same as in calculate_amount_delta
+    let (range_x, range_y) = if current_tick_index < lower_tick {
+        (Some((lower_sqrt_price, upper_sqrt_price)), None)
+    } else if current_tick_index < upper_tick {
+        (
+            Some((current_sqrt_price, upper_sqrt_price)),
+            Some((lower_sqrt_price, current_sqrt_price)),
+        )
+    } else {
+        (None, Some((lower_sqrt_price, upper_sqrt_price)))
+    };
+
+    let is_enough = |liquidity: Liquidity| {
+        let enough_x = range_x.map_or(true, |(a, b)| {
+            get_delta_x(a, b, liquidity, true).map_or(false, |delta_x| delta_x <= amount_x)
+        });
+        let enough_y = range_y.map_or(true, |(a, b)| {
+            get_delta_y(a, b, liquidity, true).map_or(false, |delta_y| delta_y <= amount_y)
+        });
+        enough_x && enough_y
+    };
+
+    // L = x * sqrt(pa) * sqrt(pb) / (sqrt(pb) - sqrt(pa))
+    let liquidity_by_x = range_x.and_then(|(a, b)| {
+        let delta_price = b.checked_sub(a).ok()?;
+        if delta_price.is_zero() {
+            return None;
+        }
+        a.big_mul_to_value(b)
+            .checked_div(U256::from(PRICE_LIQUIDITY_DENOMINATOR))?
+            .checked_mul(U256::from(amount_x.get()))?
+            .checked_div(U256::from(delta_price.get()))
+    });
+    // L = y / (sqrt(pb) - sqrt(pa))
+    let liquidity_by_y = range_y.and_then(|(a, b)| {
+        let delta_price = b.checked_sub(a).ok()?;
+        if delta_price.is_zero() {
+            return None;
+        }
+        U256::from(amount_y.get())
+            .checked_mul(Price::one::<U256>())?
+            .checked_mul(Liquidity::one::<U256>())?
+            .checked_div(U256::from(delta_price.get()))
+    });
+
+    let estimate = match (liquidity_by_x, liquidity_by_y) {
+        (Some(x), Some(y)) => x.min(y),
+        (Some(x), None) => x,
+        (None, Some(y)) => y,
+        (None, None) => U256::from(0),
+    };
+    let estimate = Liquidity::new(estimate.try_into().unwrap_or(u128::MAX));
+
+    // estimate is only approximate due to rounding of deltas, so move it by growing steps
+    // until the largest fitting liquidity is surrounded
+    let (mut liquidity, mut exceeding) = if is_enough(estimate) {
+        let mut liquidity = estimate;
+        let mut step = 1u128;
+        loop {
+            let next = Liquidity::new(liquidity.get().saturating_add(step));
+            if next == liquidity {
+                return Ok(liquidity);
+            }
+            if !is_enough(next) {
+                break (liquidity, next);
+            }
+            liquidity = next;
+            step = step.saturating_mul(2);
+        }
+    } else {
+        let mut exceeding = estimate;
+        let mut step = 1u128;
+        loop {
+            let next = Liquidity::new(exceeding.get().saturating_sub(step));
+            if is_enough(next) {
+                break (next, exceeding);
+            }
+            exceeding = next;
+            step = step.saturating_mul(2);
+        }
+    };
+
+    // largest fitting liquidity is in <liquidity, exceeding)
+    while exceeding.get() - liquidity.get() > 1 {
+        let middle = Liquidity::new(liquidity.get() + (exceeding.get() - liquidity.get()) / 2);
+        if is_enough(middle) {
+            liquidity = middle;
+        } else {
+            exceeding = middle;
+        }
+    }
+
+    Ok(liquidity)
+}
+
 pub fn calculate_seconds_per_liquidity_inside(
     tick_lower: Tick,
     tick_upper: Tick,
     pool: &mut Pool,
     current_timestamp: u64,
-) -> FixedPoint {
+) -> Result<FixedPoint> {
     if !pool.liquidity.is_zero() {
-        pool.update_seconds_per_liquidity_global(current_timestamp);
+        pool.update_seconds_per_liquidity_global(current_timestamp)?;
     } else {
         pool.last_timestamp = current_timestamp;
     }
@@ -454,9 +600,10 @@ pub fn calculate_seconds_per_liquidity_inside(
             .unchecked_sub(tick_upper.seconds_per_liquidity_outside)
     };
 
-    pool.seconds_per_liquidity_global
+    Ok(pool
+        .seconds_per_liquidity_global
         .unchecked_sub(seconds_per_liquidity_below)
-        .unchecked_sub(seconds_per_liquidity_above)
+        .unchecked_sub(seconds_per_liquidity_above))
 }
 
 pub fn is_enough_amount_to_push_price(
@@ -466,39 +613,67 @@ pub fn is_enough_amount_to_push_price(
     fee: FixedPoint,
     by_amount_in: bool,
     x_to_y: bool,
-) -> bool {
+) -> Result<bool> {
     if liquidity.is_zero() {
-        return true;
+        return Ok(true);
     }
 
     let next_price_sqrt = if by_amount_in {
         let amount_after_fee = amount.big_mul(FixedPoint::from_integer(1) - fee);
-        get_next_sqrt_price_from_input(current_price_sqrt, liquidity, amount_after_fee, x_to_y)
+        get_next_sqrt_price_from_input(current_price_sqrt, liquidity, amount_after_fee, x_to_y)?
     } else {
-        get_next_sqrt_price_from_output(current_price_sqrt, liquidity, amount, x_to_y)
+        // the next price overflows only for outputs above what the liquidity holds, those move it anyway
+        match get_next_sqrt_price_from_output(current_price_sqrt, liquidity, amount, x_to_y) {
+            Ok(next_price_sqrt) => next_price_sqrt,
+            Err(_) => return Ok(true),
+        }
     };
 
-    current_price_sqrt.ne(&next_price_sqrt)
+    Ok(current_price_sqrt.ne(&next_price_sqrt))
+}
+
+// highest tick usable at the spacing with a tickmap of the given limit
+pub fn get_max_tick(tick_spacing: u16, tick_limit: i32) -> i32 {
+    let tick_spacing = tick_spacing as i64;
+    let limited_by_price = MAX_TICK as i64 - MAX_TICK as i64 % tick_spacing;
+    let limited_by_tickmap = (tick_limit as i64 - 1) * tick_spacing;
+    limited_by_price.min(limited_by_tickmap) as i32
 }
 
-pub fn calculate_max_liquidity_per_tick(tick_spacing: u16) -> Liquidity {
-    const MAX_TICKS_AMOUNT_MEMORY_LIMITED: u128 = 2 * TICK_LIMIT as u128;
+// lowest tick usable at the spacing with a tickmap of the given limit
+pub fn get_min_tick(tick_spacing: u16, tick_limit: i32) -> i32 {
+    let tick_spacing = tick_spacing as i64;
+    let limited_by_price = -(MAX_TICK as i64) + MAX_TICK as i64 % tick_spacing;
+    let limited_by_tickmap = -(tick_limit as i64) * tick_spacing;
+    limited_by_price.max(limited_by_tickmap) as i32
+}
+
+pub fn get_max_sqrt_price(tick_spacing: u16, tick_limit: i32) -> Result<Price> {
+    calculate_price_sqrt(get_max_tick(tick_spacing, tick_limit))
+}
+
+pub fn get_min_sqrt_price(tick_spacing: u16, tick_limit: i32) -> Result<Price> {
+    calculate_price_sqrt(get_min_tick(tick_spacing, tick_limit))
+}
+
+// pools without a tickmap extension can use fewer ticks, so each of them can hold more liquidity
+pub fn calculate_max_liquidity_per_tick(tick_spacing: u16, tick_limit: i32) -> Result<Liquidity> {
     const MAX_TICKS_AMOUNT_PRICE_LIMITED: u128 = 2 * MAX_TICK as u128 + 1;
     const MAX_GLOBAL_LIQUIDITY: u128 = u128::MAX;
-    const MAX_LIQUIDITY_SIZE_LIMITED: u128 = MAX_GLOBAL_LIQUIDITY / MAX_TICKS_AMOUNT_MEMORY_LIMITED;
 
-    let ticks_amount_spacing_limited = MAX_TICKS_AMOUNT_PRICE_LIMITED
-        .checked_div(tick_spacing.try_into().unwrap())
-        .unwrap();
+    require!(tick_spacing != 0, InvalidTickSpacing);
+    let max_ticks_amount_memory_limited = 2 * tick_limit as u128;
+    // at least one tick fits in the price range for any spacing
+    let ticks_amount_spacing_limited = MAX_TICKS_AMOUNT_PRICE_LIMITED / tick_spacing as u128;
 
-    if MAX_TICKS_AMOUNT_MEMORY_LIMITED < ticks_amount_spacing_limited {
-        Liquidity::new(MAX_LIQUIDITY_SIZE_LIMITED)
+    if max_ticks_amount_memory_limited < ticks_amount_spacing_limited {
+        Ok(Liquidity::new(
+            MAX_GLOBAL_LIQUIDITY / max_ticks_amount_memory_limited,
+        ))
     } else {
-        Liquidity::new(
-            MAX_GLOBAL_LIQUIDITY
-                .checked_div(ticks_amount_spacing_limited)
-                .unwrap(),
-        )
+        Ok(Liquidity::new(
+            MAX_GLOBAL_LIQUIDITY / ticks_amount_spacing_limited,
+        ))
     }
 }
 
@@ -507,7 +682,7 @@ mod tests {
 
     use std::str::FromStr;
 
-    use crate::structs::TICK_LIMIT;
+    use crate::structs::{EXTENDED_TICK_LIMIT, TICK_LIMIT};
 
     use super::*;
 
@@ -521,7 +696,7 @@ mod tests {
             let amount = TokenAmount(1);
             let fee = FixedPoint::from_scale(6, 4);
 
-            let result = compute_swap_step(price, target, liquidity, amount, true, fee);
+            let result = compute_swap_step(price, target, liquidity, amount, true, fee).unwrap();
 
             let expected_result = SwapResult {
                 next_price_sqrt: price,
@@ -539,8 +714,9 @@ mod tests {
             let amount = TokenAmount(20);
             let fee = FixedPoint::from_scale(6, 4);
 
-            let result_in = compute_swap_step(price, target, liquidity, amount, true, fee);
-            let result_out = compute_swap_step(price, target, liquidity, amount, false, fee);
+            let result_in = compute_swap_step(price, target, liquidity, amount, true, fee).unwrap();
+            let result_out =
+                compute_swap_step(price, target, liquidity, amount, false, fee).unwrap();
 
             let expected_result = SwapResult {
                 next_price_sqrt: target,
@@ -559,7 +735,7 @@ mod tests {
             let amount = TokenAmount(1000000);
             let fee = FixedPoint::from_scale(6, 4);
 
-            let result = compute_swap_step(price, target, liquidity, amount, true, fee);
+            let result = compute_swap_step(price, target, liquidity, amount, true, fee).unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: Price::new(1013331333333_333333333333),
                 amount_in: TokenAmount(999400),
@@ -576,7 +752,7 @@ mod tests {
             let amount = TokenAmount(2000000);
             let fee = FixedPoint::from_scale(6, 4);
 
-            let result = compute_swap_step(price, target, liquidity, amount, false, fee);
+            let result = compute_swap_step(price, target, liquidity, amount, false, fee).unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: Price::new(100999999600000_000000000000),
                 amount_in: TokenAmount(197), // (5000000000000 * (101 - 100.9999996)) /  (101 * 100.9999996)
@@ -602,7 +778,8 @@ mod tests {
                 amount,
                 by_amount_in,
                 fee,
-            );
+            )
+            .unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: current_price_sqrt,
                 amount_in: TokenAmount(0),
@@ -627,7 +804,8 @@ mod tests {
                 amount,
                 by_amount_in,
                 fee,
-            );
+            )
+            .unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: Price::new(999500149965_000000000001),
                 amount_in: TokenAmount(341),
@@ -652,7 +830,8 @@ mod tests {
                 amount,
                 by_amount_in,
                 fee,
-            );
+            )
+            .unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: current_price_sqrt,
                 amount_in: TokenAmount(0),
@@ -677,7 +856,8 @@ mod tests {
                 amount,
                 by_amount_in,
                 fee,
-            );
+            )
+            .unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: current_price_sqrt,
                 amount_in: TokenAmount(0),
@@ -702,7 +882,8 @@ mod tests {
                 amount,
                 by_amount_in,
                 fee,
-            );
+            )
+            .unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: Price::new(1_000000000000_000000000003),
                 amount_in: TokenAmount(2),
@@ -727,7 +908,8 @@ mod tests {
                 amount,
                 by_amount_in,
                 fee,
-            );
+            )
+            .unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: target_price_sqrt,
                 amount_in: TokenAmount(0),
@@ -752,7 +934,8 @@ mod tests {
                 amount,
                 by_amount_in,
                 fee,
-            );
+            )
+            .unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: target_price_sqrt,
                 amount_in: TokenAmount(0),
@@ -777,7 +960,8 @@ mod tests {
                 amount,
                 by_amount_in,
                 fee,
-            );
+            )
+            .unwrap();
             let expected_result = SwapResult {
                 next_price_sqrt: Price::from_scale(99997, 5),
                 amount_in: TokenAmount(1000),
@@ -788,7 +972,7 @@ mod tests {
         }
         // by_amount_out and x_to_y edge cases
         {
-            let target_price_sqrt = calculate_price_sqrt(-10);
+            let target_price_sqrt = calculate_price_sqrt(-10).unwrap();
             let current_price_sqrt = target_price_sqrt + Price::from_integer(1);
             let liquidity = Liquidity::from_integer(340282366920938463463374607u128);
             let one_token = TokenAmount(1);
@@ -805,7 +989,8 @@ mod tests {
                 one_token,
                 by_amount_in,
                 max_fee,
-            );
+            )
+            .unwrap();
             let tokens_with_same_output_result = compute_swap_step(
                 current_price_sqrt,
                 target_price_sqrt,
@@ -813,7 +998,8 @@ mod tests {
                 tokens_with_same_output,
                 by_amount_in,
                 max_fee,
-            );
+            )
+            .unwrap();
             let zero_token_result = compute_swap_step(
                 current_price_sqrt,
                 target_price_sqrt,
@@ -821,7 +1007,8 @@ mod tests {
                 zero_token,
                 by_amount_in,
                 min_fee,
-            );
+            )
+            .unwrap();
             /*
                 86x -> [1, 85]y
                 rounding due to price accuracy
@@ -852,6 +1039,27 @@ mod tests {
             );
             assert_eq!(zero_token_result, expected_zero_token_result);
         }
+        // amount in rounded up over the whole amount without fee
+        {
+            let current_price_sqrt = Price::new(791925200290000000000000);
+            let target_price_sqrt = Price::new(34906499059000000000000);
+            let liquidity = Liquidity::new(7556323959758483140);
+            let amount = TokenAmount(16834688482519);
+            let fee = FixedPoint::new(0);
+
+            let result = compute_swap_step(
+                current_price_sqrt,
+                target_price_sqrt,
+                liquidity,
+                amount,
+                true,
+                fee,
+            )
+            .unwrap();
+            assert_eq!(result.next_price_sqrt, Price::new(286480459051362175036776));
+            assert_eq!(result.amount_in, amount);
+            assert_eq!(result.fee_amount, TokenAmount(0));
+        }
     }
 
     #[test]
@@ -1007,41 +1215,41 @@ mod tests {
     #[test]
     fn test_calculate_price_sqrt() {
         {
-            let price_sqrt = calculate_price_sqrt(20_000);
+            let price_sqrt = calculate_price_sqrt(20_000).unwrap();
             // expected 2.718145925979
             // real     2.718145926825...
             assert_eq!(price_sqrt, Price::from_scale(2718145925979u128, 12));
         }
         {
-            let price_sqrt = calculate_price_sqrt(200_000);
+            let price_sqrt = calculate_price_sqrt(200_000).unwrap();
             // expected 22015.455979766288
             // real     22015.456048527954...
             assert_eq!(price_sqrt, Price::from_scale(22015455979766288u128, 12));
         }
         {
-            let price_sqrt = calculate_price_sqrt(-20_000);
+            let price_sqrt = calculate_price_sqrt(-20_000).unwrap();
             // expected 0.367897834491
             // real     0.36789783437712...
             assert_eq!(price_sqrt, Price::from_scale(367897834491u128, 12));
         }
         {
-            let price_sqrt = calculate_price_sqrt(-200_000);
+            let price_sqrt = calculate_price_sqrt(-200_000).unwrap();
             // expected 0.000045422634
             // real     0.00004542263388...
             assert_eq!(price_sqrt, Price::from_scale(45422634u128, 12))
         }
         {
-            let price_sqrt = calculate_price_sqrt(0);
+            let price_sqrt = calculate_price_sqrt(0).unwrap();
             assert_eq!(price_sqrt, Price::from_integer(1));
         }
         {
-            let price_sqrt = calculate_price_sqrt(MAX_TICK);
+            let price_sqrt = calculate_price_sqrt(MAX_TICK).unwrap();
             // expected 65535.383934512647
             // real     65535.384161610681...
             assert_eq!(price_sqrt, Price::from_scale(65535383934512647u128, 12))
         }
         {
-            let price_sqrt = calculate_price_sqrt(-MAX_TICK);
+            let price_sqrt = calculate_price_sqrt(-MAX_TICK).unwrap();
             // expected 0.000015258932
             // real     0.0000152589324...
             assert_eq!(price_sqrt, Price::from_scale(15258932u128, 12))
@@ -1050,13 +1258,50 @@ mod tests {
 
     #[test]
     fn edge_prices_regression_test() {
-        let min_sqrt_price = calculate_price_sqrt(-MAX_TICK);
-        let max_sqrt_price = calculate_price_sqrt(MAX_TICK);
+        let min_sqrt_price = calculate_price_sqrt(-MAX_TICK).unwrap();
+        let max_sqrt_price = calculate_price_sqrt(MAX_TICK).unwrap();
 
         assert_eq!(min_sqrt_price, Price::new(MIN_SQRT_PRICE));
         assert_eq!(max_sqrt_price, Price::new(MAX_SQRT_PRICE));
     }
 
+    #[test]
+    fn test_get_min_max_tick() {
+        assert_eq!(get_max_tick(1, TICK_LIMIT), TICK_LIMIT - 1);
+        assert_eq!(get_min_tick(1, TICK_LIMIT), -TICK_LIMIT);
+        assert_eq!(get_max_tick(2, TICK_LIMIT), 2 * (TICK_LIMIT - 1));
+        assert_eq!(get_min_tick(2, TICK_LIMIT), -2 * TICK_LIMIT);
+        assert_eq!(get_max_tick(100, TICK_LIMIT), MAX_TICK - MAX_TICK % 100);
+        assert_eq!(get_min_tick(100, TICK_LIMIT), -MAX_TICK + MAX_TICK % 100);
+        // does not overflow for the widest spacing
+        assert_eq!(get_max_tick(u16::MAX, TICK_LIMIT), 3 * u16::MAX as i32);
+        assert_eq!(get_min_tick(u16::MAX, TICK_LIMIT), -3 * u16::MAX as i32);
+        // an extension covers the whole price range at any spacing
+        assert_eq!(get_max_tick(1, EXTENDED_TICK_LIMIT), MAX_TICK);
+        assert_eq!(get_min_tick(1, EXTENDED_TICK_LIMIT), -MAX_TICK);
+        assert_eq!(get_max_tick(2, EXTENDED_TICK_LIMIT), MAX_TICK);
+        assert_eq!(get_min_tick(2, EXTENDED_TICK_LIMIT), -MAX_TICK);
+
+        assert_eq!(
+            get_max_sqrt_price(1, TICK_LIMIT).unwrap(),
+            calculate_price_sqrt(TICK_LIMIT - 1).unwrap()
+        );
+        assert_eq!(
+            get_min_sqrt_price(1, TICK_LIMIT).unwrap(),
+            calculate_price_sqrt(-TICK_LIMIT).unwrap()
+        );
+        assert_eq!(
+            get_max_sqrt_price(1, EXTENDED_TICK_LIMIT).unwrap(),
+            Price::new(MAX_SQRT_PRICE)
+        );
+        assert_eq!(
+            get_min_sqrt_price(1, EXTENDED_TICK_LIMIT).unwrap(),
+            Price::new(MIN_SQRT_PRICE)
+        );
+        assert!(get_max_sqrt_price(100, TICK_LIMIT).unwrap() <= Price::new(MAX_SQRT_PRICE));
+        assert!(get_min_sqrt_price(100, TICK_LIMIT).unwrap() >= Price::new(MIN_SQRT_PRICE));
+    }
+
     #[test]
     fn test_get_next_sqrt_price_x_up() {
         // Add
@@ -1065,7 +1310,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(1);
             let amount = TokenAmount(1);
 
-            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true);
+            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true).unwrap();
 
             assert_eq!(result, Price::from_scale(5, 1));
         }
@@ -1074,7 +1319,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(2);
             let amount = TokenAmount(3);
 
-            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true);
+            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true).unwrap();
 
             assert_eq!(result, Price::from_scale(4, 1));
         }
@@ -1083,7 +1328,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(3);
             let amount = TokenAmount(5);
 
-            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true);
+            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true).unwrap();
 
             assert_eq!(
                 result,
@@ -1095,7 +1340,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(3000);
             let amount = TokenAmount(5000);
 
-            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true);
+            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, true).unwrap();
 
             assert_eq!(
                 result,
@@ -1108,7 +1353,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(2);
             let amount = TokenAmount(1);
 
-            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, false);
+            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, false).unwrap();
 
             assert_eq!(result, Price::from_integer(2));
         }
@@ -1117,7 +1362,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(500_000_000);
             let amount = TokenAmount(4_000);
 
-            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, false);
+            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, false).unwrap();
 
             assert_eq!(result, Price::from_integer(500_000));
         }
@@ -1128,7 +1373,7 @@ mod tests {
 
             // expected 7.490636713462104974072145
             // real     7.4906367134621049740721443...
-            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, false);
+            let result = get_next_sqrt_price_x_up(price_sqrt, liquidity, amount, false).unwrap();
 
             assert_eq!(result, Price::new(7490636713462104974072145));
         }
@@ -1141,7 +1386,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(1);
             let amount = TokenAmount(1);
 
-            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true);
+            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true).unwrap();
 
             assert_eq!(result, Price::from_integer(2));
         }
@@ -1150,7 +1395,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(2);
             let amount = TokenAmount(3);
 
-            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true);
+            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true).unwrap();
 
             assert_eq!(result, Price::from_scale(25, 1));
         }
@@ -1159,7 +1404,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(3);
             let amount = TokenAmount(5);
 
-            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true);
+            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true).unwrap();
 
             assert_eq!(
                 result,
@@ -1171,7 +1416,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(3000);
             let amount = TokenAmount(5000);
 
-            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true);
+            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true).unwrap();
 
             assert_eq!(
                 result,
@@ -1184,7 +1429,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(2);
             let amount = TokenAmount(1);
 
-            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, false);
+            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, false).unwrap();
 
             assert_eq!(result, Price::from_scale(5, 1));
         }
@@ -1193,7 +1438,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(500_000_000);
             let amount = TokenAmount(4_000);
 
-            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, false);
+            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, false).unwrap();
             assert_eq!(result, Price::new(99999999992000000_000000000000));
         }
         {
@@ -1201,7 +1446,7 @@ mod tests {
             let liquidity = Liquidity::from_integer(222);
             let amount = TokenAmount(37);
 
-            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, false);
+            let result = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, false).unwrap();
 
             // expected 2.833333333333
             // real     2.999999999999833...
@@ -1371,6 +1616,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_calculate_reward_growths_inside() {
+        let reward_growths_global = [
+            FeeGrowth::from_integer(15),
+            FeeGrowth::from_integer(7),
+            FeeGrowth::new(0),
+        ];
+        // second reward was only emitted below the range
+        let tick_lower = Tick {
+            index: -2,
+            reward_growths_outside: [
+                FeeGrowth::from_integer(2),
+                FeeGrowth::from_integer(7),
+                FeeGrowth::new(0),
+            ],
+            ..Default::default()
+        };
+        let tick_upper = Tick {
+            index: 2,
+            reward_growths_outside: [
+                FeeGrowth::from_integer(3),
+                FeeGrowth::new(0),
+                FeeGrowth::new(0),
+            ],
+            ..Default::default()
+        };
+        // current tick inside range
+        {
+            let reward_growths_inside =
+                calculate_reward_growths_inside(tick_lower, tick_upper, 0, reward_growths_global);
+
+            assert_eq!(
+                reward_growths_inside,
+                [
+                    FeeGrowth::from_integer(10),
+                    FeeGrowth::new(0),
+                    FeeGrowth::new(0)
+                ]
+            );
+        }
+        // current tick above range
+        {
+            let tick_upper = Tick {
+                reward_growths_outside: [
+                    FeeGrowth::from_integer(3),
+                    FeeGrowth::from_integer(7),
+                    FeeGrowth::new(0),
+                ],
+                ..tick_upper
+            };
+            let reward_growths_inside =
+                calculate_reward_growths_inside(tick_lower, tick_upper, 4, reward_growths_global);
+
+            assert_eq!(
+                reward_growths_inside,
+                [
+                    FeeGrowth::from_integer(1),
+                    FeeGrowth::new(0),
+                    FeeGrowth::new(0)
+                ]
+            );
+        }
+    }
+
     #[test]
     fn test_calculate_amount_delta() {
         // current tick between lower tick and upper tick
@@ -1450,6 +1759,115 @@ mod tests {
             assert_eq!(y, TokenAmount(1));
         }
     }
+    #[test]
+    fn test_calculate_max_liquidity_from_amounts() {
+        // amounts needed to add liquidity to <lower_tick, upper_tick> range
+        let amounts = |current_tick_index: i32, lower_tick: i32, upper_tick: i32, liquidity| {
+            let mut pool = Pool {
+                sqrt_price: calculate_price_sqrt(current_tick_index).unwrap(),
+                current_tick_index,
+                ..Default::default()
+            };
+            calculate_amount_delta(&mut pool, liquidity, true, upper_tick, lower_tick).unwrap()
+        };
+
+        // current tick between lower tick and upper tick
+        {
+            let (current_tick_index, lower_tick, upper_tick) = (2, -10, 20);
+            let amount_x = TokenAmount(1_000);
+            let amount_y = TokenAmount(5_000);
+
+            let liquidity = calculate_max_liquidity_from_amounts(
+                calculate_price_sqrt(current_tick_index).unwrap(),
+                current_tick_index,
+                lower_tick,
+                upper_tick,
+                amount_x,
+                amount_y,
+            )
+            .unwrap();
+            assert_eq!(liquidity, Liquidity::new(1111777907437));
+
+            let (x, y) = amounts(current_tick_index, lower_tick, upper_tick, liquidity);
+            assert!(x <= amount_x && y <= amount_y);
+            // x is the limiting token
+            let (x, _) = amounts(
+                current_tick_index,
+                lower_tick,
+                upper_tick,
+                liquidity + Liquidity::new(1),
+            );
+            assert!(x > amount_x);
+        }
+        // current tick smaller than lower tick
+        {
+            let (current_tick_index, lower_tick, upper_tick) = (0, 2, 4);
+            let amount_x = TokenAmount(100);
+
+            let liquidity = calculate_max_liquidity_from_amounts(
+                calculate_price_sqrt(current_tick_index).unwrap(),
+                current_tick_index,
+                lower_tick,
+                upper_tick,
+                amount_x,
+                TokenAmount(0),
+            )
+            .unwrap();
+            assert_eq!(liquidity, Liquidity::new(1000200010000));
+
+            let (x, y) = amounts(current_tick_index, lower_tick, upper_tick, liquidity);
+            assert!(x <= amount_x);
+            assert_eq!(y, TokenAmount(0));
+            let (x, _) = amounts(
+                current_tick_index,
+                lower_tick,
+                upper_tick,
+                liquidity + Liquidity::new(1),
+            );
+            assert!(x > amount_x);
+        }
+        // current tick greater than upper tick
+        {
+            let (current_tick_index, lower_tick, upper_tick) = (6, 2, 4);
+            let amount_y = TokenAmount(100);
+
+            let liquidity = calculate_max_liquidity_from_amounts(
+                calculate_price_sqrt(current_tick_index).unwrap(),
+                current_tick_index,
+                lower_tick,
+                upper_tick,
+                TokenAmount(0),
+                amount_y,
+            )
+            .unwrap();
+            assert_eq!(liquidity, Liquidity::new(999900009999));
+
+            let (x, y) = amounts(current_tick_index, lower_tick, upper_tick, liquidity);
+            assert_eq!(x, TokenAmount(0));
+            assert!(y <= amount_y);
+            let (_, y) = amounts(
+                current_tick_index,
+                lower_tick,
+                upper_tick,
+                liquidity + Liquidity::new(1),
+            );
+            assert!(y > amount_y);
+        }
+        // missing token
+        {
+            let liquidity = calculate_max_liquidity_from_amounts(
+                calculate_price_sqrt(2).unwrap(),
+                2,
+                -10,
+                20,
+                TokenAmount(1_000),
+                TokenAmount(0),
+            )
+            .unwrap();
+            assert_eq!(liquidity, Liquidity::new(0));
+        }
+    }
+
     #[test]
     fn test_update_seconds_per_liquidity_global() {
         let mut pool = Pool {
@@ -1461,7 +1879,8 @@ mod tests {
         };
 
         let current_timestamp = 100;
-        pool.update_seconds_per_liquidity_global(current_timestamp);
+        pool.update_seconds_per_liquidity_global(current_timestamp)
+            .unwrap();
         assert_eq!({ pool.seconds_per_liquidity_global }.get(), 100000000000);
     }
     #[test]
@@ -1492,7 +1911,8 @@ mod tests {
                 tick_upper,
                 &mut pool,
                 current_timestamp,
-            );
+            )
+            .unwrap();
             assert_eq!(seconds_per_liquidity_inside.get(), 981900000);
         }
 
@@ -1503,7 +1923,8 @@ mod tests {
                 tick_upper,
                 &mut pool,
                 current_timestamp,
-            );
+            )
+            .unwrap();
             assert_eq!(seconds_per_liquidity_inside.get(), 94957300000);
         }
 
@@ -1516,7 +1937,8 @@ mod tests {
                 tick_upper,
                 &mut pool,
                 current_timestamp,
-            );
+            )
+            .unwrap();
             assert_eq!(seconds_per_liquidity_inside.get(), 1000000110);
         }
 
@@ -1529,7 +1951,8 @@ mod tests {
                 tick_upper,
                 &mut pool,
                 current_timestamp,
-            );
+            )
+            .unwrap();
             assert_eq!(seconds_per_liquidity_inside.get(), 100000011000);
         }
         {
@@ -1541,7 +1964,8 @@ mod tests {
                 tick_upper,
                 &mut pool,
                 current_timestamp,
-            );
+            )
+            .unwrap();
             assert_eq!(
                 seconds_per_liquidity_inside.get(),
                 340282366920938463463374607331768200456
@@ -1550,7 +1974,7 @@ mod tests {
     }
     #[test]
     fn test_is_enough_amount_to_push_price() {
-        let current_price_sqrt = calculate_price_sqrt(-20); // at -20 tick
+        let current_price_sqrt = calculate_price_sqrt(-20).unwrap(); // at -20 tick
         let liquidity = Liquidity::new(20006000000000000000);
         let fee = FixedPoint::from_scale(6, 4); // 0.0006 -> 0.06%
 
@@ -1567,7 +1991,8 @@ mod tests {
                 fee,
                 by_amount_in,
                 x_to_y,
-            );
+            )
+            .unwrap();
             assert_eq!(result, false);
         }
         // -20 crossing tick with 1 token amount by amount out
@@ -1583,7 +2008,8 @@ mod tests {
                 fee,
                 by_amount_in,
                 x_to_y,
-            );
+            )
+            .unwrap();
             assert_eq!(result, true);
         }
         // -20 crossing tick with 2 token amount by amount in
@@ -1599,7 +2025,8 @@ mod tests {
                 fee,
                 by_amount_in,
                 x_to_y,
-            );
+            )
+            .unwrap();
             assert_eq!(result, true);
         }
         // zero amount
@@ -1614,7 +2041,8 @@ mod tests {
                 fee,
                 false,
                 true,
-            );
+            )
+            .unwrap();
             let result_by_amount_out_y_to_x = is_enough_amount_to_push_price(
                 zero_amount,
                 current_price_sqrt,
@@ -1622,7 +2050,8 @@ mod tests {
                 fee,
                 false,
                 false,
-            );
+            )
+            .unwrap();
             let result_by_amount_in_x_to_y = is_enough_amount_to_push_price(
                 zero_amount,
                 current_price_sqrt,
@@ -1630,7 +2059,8 @@ mod tests {
                 fee,
                 true,
                 true,
-            );
+            )
+            .unwrap();
             let result_by_amount_in_y_to_x = is_enough_amount_to_push_price(
                 zero_amount,
                 current_price_sqrt,
@@ -1638,12 +2068,37 @@ mod tests {
                 fee,
                 true,
                 false,
-            );
+            )
+            .unwrap();
             assert_eq!(result_by_amount_out_x_to_y, false);
             assert_eq!(result_by_amount_out_y_to_x, false);
             assert_eq!(result_by_amount_in_x_to_y, false);
             assert_eq!(result_by_amount_in_y_to_x, false);
         }
+        // output above the liquidity
+        {
+            let min_liquidity = Liquidity::new(1);
+            let amount = TokenAmount(1_000_000);
+
+            assert!(is_enough_amount_to_push_price(
+                amount,
+                current_price_sqrt,
+                min_liquidity,
+                fee,
+                false,
+                true,
+            )
+            .unwrap());
+            assert!(is_enough_amount_to_push_price(
+                amount,
+                current_price_sqrt,
+                min_liquidity,
+                fee,
+                false,
+                false,
+            )
+            .unwrap());
+        }
         // should always be enough amount to cross tick when pool liquidity is zero
         {
             let no_liquidity = Decimal::new(0);
@@ -1658,33 +2113,42 @@ mod tests {
                 fee,
                 by_amount_in,
                 x_to_y,
-            );
+            )
+            .unwrap();
             assert_eq!(result, true);
         }
     }
 
     #[test]
     fn test_calculate_max_liquidity_per_tick() {
-        let max_liquidity_per_tick_limited_by_space =
-            Liquidity::new(3835118191787693439087713094308090u128);
         // tick_spacing 1 [L_MAX / 88_728]
         {
-            let max_l = calculate_max_liquidity_per_tick(1);
-            assert_eq!(max_l, max_liquidity_per_tick_limited_by_space);
+            let max_l = calculate_max_liquidity_per_tick(1, TICK_LIMIT).unwrap();
+            assert_eq!(max_l, Liquidity::new(3835118191787693439087713094308090));
+        };
+        // tick_spacing 1 with an extension [L_MAX / 443_637]
+        {
+            let max_l = calculate_max_liquidity_per_tick(1, EXTENDED_TICK_LIMIT).unwrap();
+            assert_eq!(max_l, Liquidity::new(767028825190275976673213928125400));
         };
         // tick_spacing 2 [L_MAX / 88_728]
         {
-            let max_l = calculate_max_liquidity_per_tick(2);
-            assert_eq!(max_l, max_liquidity_per_tick_limited_by_space);
+            let max_l = calculate_max_liquidity_per_tick(2, TICK_LIMIT).unwrap();
+            assert_eq!(max_l, Liquidity::new(3835118191787693439087713094308090));
+        }
+        // tick_spacing 2 with an extension [L_MAX / 221_818]
+        {
+            let max_l = calculate_max_liquidity_per_tick(2, EXTENDED_TICK_LIMIT).unwrap();
+            assert_eq!(max_l, Liquidity::new(1534061108300221187926023169588438));
         }
         // tick_spacing 5 [L_MAX / 88_727]
         {
-            let max_l = calculate_max_liquidity_per_tick(5);
+            let max_l = calculate_max_liquidity_per_tick(5, TICK_LIMIT).unwrap();
             assert_eq!(max_l, Liquidity::new(3835161415588698631345301964810804));
         }
         // tick_spacing 100 [L_MAX / 4436]
         {
-            let max_l = calculate_max_liquidity_per_tick(100);
+            let max_l = calculate_max_liquidity_per_tick(100, TICK_LIMIT).unwrap();
             assert_eq!(max_l, Liquidity::new(76709280189571339824926647302021688));
         }
     }
@@ -1694,10 +2158,11 @@ mod tests {
         let liquidity_denominator = U256::from(Liquidity::from_integer(1).get());
         let price_denominator = U256::from(Price::from_integer(1).get());
         let max_token_amount: u64 = (10u128.pow(64) - 1) as u64;
-        let max_sqrt_price = calculate_price_sqrt(MAX_TICK);
-        let min_tick_spacing_reachable_max_price = (MAX_TICK + TICK_LIMIT - 1) / TICK_LIMIT; // 5
+        let max_sqrt_price = calculate_price_sqrt(MAX_TICK).unwrap();
+        let min_tick_spacing_reachable_max_price =
+            (MAX_TICK + EXTENDED_TICK_LIMIT - 1) / EXTENDED_TICK_LIMIT; // 1
         let almost_max_sqrt_price =
-            calculate_price_sqrt(MAX_TICK - min_tick_spacing_reachable_max_price);
+            calculate_price_sqrt(MAX_TICK - min_tick_spacing_reachable_max_price).unwrap();
         let max_u64 = u64::max_value() as u128;
         let max_u128 = u128::max_value();
 
@@ -1707,8 +2172,8 @@ mod tests {
         // 2^128 > L_MAX * ACCURACY > 2^64
         {
             let max_y: TokenAmount = TokenAmount::new(max_token_amount);
-            let upper_sqrt_price = calculate_price_sqrt(0);
-            let lower_sqrt_price = calculate_price_sqrt(-1);
+            let upper_sqrt_price = calculate_price_sqrt(0).unwrap();
+            let lower_sqrt_price = calculate_price_sqrt(-1).unwrap();
             let min_price_diff_between_tick = upper_sqrt_price - lower_sqrt_price;
 
             // MAX_LIQUIDITY = ~2^79 * 10^6 = ~2^99
@@ -1757,10 +2222,10 @@ mod tests {
                 .checked_sub(U256::from(almost_max_sqrt_price.v))
                 .unwrap();
 
-            //  ~2^29 * 10^6 = ~ 2^49
+            //  ~2^31 * 10^6 = ~ 2^51
             let multiplier = product.checked_div(diff).unwrap();
 
-            // ~2^93 * 10^6 = ~2^113
+            // ~2^95 * 10^6 = ~2^115
             let max_liquidity = U256::from(max_token_amount)
                 .checked_mul(multiplier)
                 .unwrap();
@@ -1768,11 +2233,11 @@ mod tests {
             assert!(max_liquidity.lt(&U256::from(u128::MAX)));
             assert_eq!(
                 max_liquidity,
-                U256::from(4835295146534425838005632517460130u128)
+                U256::from(24178892163540860941746712205152005u128)
             );
 
             // delta y (y amount require to fill position)
-            // delta y ~ 2^88
+            // delta y ~ 2^93
             // impossible to fully fill position with max liquidity
             let delta_y = max_liquidity
                 .checked_mul(price_denominator)
@@ -1783,7 +2248,7 @@ mod tests {
                 .unwrap();
 
             assert!(delta_y.gt(&U256::from(u64::MAX)));
-            assert_eq!(delta_y, U256::from(295177416098739345480985970u128));
+            assert_eq!(delta_y, U256::from(7379434659801330727385241603u128));
         }
         // position range below current price
         // L (by x) = x * sqrt(pu) * sqrt(pc)  / (sqrt(pu) - sqrt(pc))
@@ -1816,3 +2281,217 @@ mod tests {
         }
     }
 }
+
+#[cfg(test)]
+mod prop_tests {
+    use super::*;
+    use crate::log::get_tick_at_sqrt_price;
+    use crate::structs::MAX_TICK;
+    use proptest::prelude::*;
+
+    fn sqrt_price() -> impl Strategy<Value = Price> {
+        prop_oneof![
+            Just(Price::new(MIN_SQRT_PRICE)),
+            Just(Price::new(MAX_SQRT_PRICE)),
+            (-MAX_TICK..=MAX_TICK).prop_map(|tick| calculate_price_sqrt(tick).unwrap()),
+            (MIN_SQRT_PRICE..=MAX_SQRT_PRICE).prop_map(Price::new),
+        ]
+    }
+
+    fn liquidity() -> impl Strategy<Value = Liquidity> {
+        prop_oneof![
+            (1u128..=u64::MAX as u128).prop_map(Liquidity::new),
+            (1u128..=10u128.pow(30)).prop_map(Liquidity::new),
+        ]
+    }
+
+    // larger amounts overflow when scaled to a price in `get_next_sqrt_price_y_down`
+    const MAX_AMOUNT: u64 = (u128::MAX / 10u128.pow(24)) as u64;
+
+    fn amount() -> impl Strategy<Value = TokenAmount> {
+        prop_oneof![Just(MAX_AMOUNT), 1u64..=MAX_AMOUNT, 1u64..=1_000_000].prop_map(TokenAmount)
+    }
+
+    fn fee() -> impl Strategy<Value = FixedPoint> {
+        prop_oneof![
+            Just(FixedPoint::new(0)),
+            (0u128..=FixedPoint::one()).prop_map(FixedPoint::new)
+        ]
+    }
+
+    fn fits_into_token_amount(
+        sqrt_price_a: Price,
+        sqrt_price_b: Price,
+        liquidity: Liquidity,
+    ) -> bool {
+        get_delta_x(sqrt_price_a, sqrt_price_b, liquidity, true).is_some()
+            && get_delta_y(sqrt_price_a, sqrt_price_b, liquidity, true).is_some()
+    }
+
+    proptest! {
+        // out of domain inputs are rejected often
+        #![proptest_config(ProptestConfig {
+            max_global_rejects: 1 << 16,
+            ..ProptestConfig::default()
+        })]
+
+        #[test]
+        fn proptest_compute_swap_step(
+            current_price_sqrt in sqrt_price(),
+            target_price_sqrt in sqrt_price(),
+            liquidity in liquidity(),
+            amount in amount(),
+            by_amount_in in any::<bool>(),
+            fee in fee(),
+        ) {
+            // amounts over the whole step have to fit into a token amount
+            prop_assume!(fits_into_token_amount(current_price_sqrt, target_price_sqrt, liquidity));
+
+            let result = compute_swap_step(
+                current_price_sqrt,
+                target_price_sqrt,
+                liquidity,
+                amount,
+                by_amount_in,
+                fee,
+            ).unwrap();
+            let next_price_sqrt = result.next_price_sqrt;
+
+            // price moves only towards the target, without passing it
+            if current_price_sqrt >= target_price_sqrt {
+                prop_assert!(next_price_sqrt <= current_price_sqrt);
+                prop_assert!(next_price_sqrt >= target_price_sqrt);
+            } else {
+                prop_assert!(next_price_sqrt >= current_price_sqrt);
+                prop_assert!(next_price_sqrt <= target_price_sqrt);
+            }
+
+            if by_amount_in {
+                prop_assert!(result.amount_in.0 as u128 + result.fee_amount.0 as u128 <= amount.0 as u128);
+            } else {
+                prop_assert!(result.amount_out <= amount);
+            }
+
+            // amounts are rounded in favour of the pool
+            if current_price_sqrt >= target_price_sqrt {
+                let amount_out = get_delta_y(next_price_sqrt, current_price_sqrt, liquidity, false).unwrap();
+                let amount_in = get_delta_x(next_price_sqrt, current_price_sqrt, liquidity, false).unwrap();
+                prop_assert!(result.amount_out <= amount_out);
+                prop_assert!(result.amount_in >= amount_in);
+            } else {
+                let amount_out = get_delta_x(current_price_sqrt, next_price_sqrt, liquidity, false).unwrap();
+                let amount_in = get_delta_y(current_price_sqrt, next_price_sqrt, liquidity, false).unwrap();
+                prop_assert!(result.amount_out <= amount_out);
+                prop_assert!(result.amount_in >= amount_in);
+            }
+        }
+
+        #[test]
+        fn proptest_swap_round_trip(
+            current_price_sqrt in sqrt_price(),
+            target_price_sqrt in sqrt_price(),
+            liquidity in liquidity(),
+            amount in amount(),
+        ) {
+            prop_assume!(fits_into_token_amount(current_price_sqrt, target_price_sqrt, liquidity));
+
+            // swapping there and back can't give back more than was put in
+            let there = compute_swap_step(
+                current_price_sqrt,
+                target_price_sqrt,
+                liquidity,
+                amount,
+                true,
+                FixedPoint::new(0),
+            ).unwrap();
+            prop_assume!(there.amount_out.0 <= MAX_AMOUNT);
+            let back = compute_swap_step(
+                there.next_price_sqrt,
+                current_price_sqrt,
+                liquidity,
+                there.amount_out,
+                true,
+                FixedPoint::new(0),
+            ).unwrap();
+            prop_assert!(back.amount_out <= there.amount_in);
+        }
+
+        #[test]
+        fn proptest_get_delta_rounding(
+            sqrt_price_a in sqrt_price(),
+            sqrt_price_b in sqrt_price(),
+            liquidity in liquidity(),
+        ) {
+            if let (Some(up), Some(down)) = (
+                get_delta_x(sqrt_price_a, sqrt_price_b, liquidity, true),
+                get_delta_x(sqrt_price_a, sqrt_price_b, liquidity, false),
+            ) {
+                prop_assert!(up >= down);
+                prop_assert!(up.0 - down.0 <= 1);
+            }
+            if let (Some(up), Some(down)) = (
+                get_delta_y(sqrt_price_a, sqrt_price_b, liquidity, true),
+                get_delta_y(sqrt_price_a, sqrt_price_b, liquidity, false),
+            ) {
+                prop_assert!(up >= down);
+                prop_assert!(up.0 - down.0 <= 1);
+            }
+        }
+
+        #[test]
+        fn proptest_get_next_sqrt_price(
+            price_sqrt in sqrt_price(),
+            liquidity in liquidity(),
+            amount in amount(),
+        ) {
+            // price can't be pushed out of range
+            let max_amount_x = get_delta_x(Price::new(MIN_SQRT_PRICE), price_sqrt, liquidity, false);
+            let max_amount_y = get_delta_y(price_sqrt, Price::new(MAX_SQRT_PRICE), liquidity, false);
+            prop_assume!(amount <= max_amount_x.unwrap_or(TokenAmount(u64::MAX)));
+            prop_assume!(amount <= max_amount_y.unwrap_or(TokenAmount(u64::MAX)));
+
+            // adding x lowers the price, adding y raises it, both in favour of the pool
+            let price_after_x = get_next_sqrt_price_from_input(price_sqrt, liquidity, amount, true).unwrap();
+            prop_assert!(price_after_x <= price_sqrt);
+            if let Some(amount_x) = get_delta_x(price_after_x, price_sqrt, liquidity, false) {
+                prop_assert!(amount_x <= amount);
+            }
+
+            let price_after_y = get_next_sqrt_price_y_down(price_sqrt, liquidity, amount, true).unwrap();
+            prop_assert!(price_after_y >= price_sqrt);
+            if let Some(amount_y) = get_delta_y(price_sqrt, price_after_y, liquidity, false) {
+                prop_assert!(amount_y <= amount);
+            }
+        }
+
+        #[test]
+        fn proptest_calculate_price_sqrt(tick in -MAX_TICK..MAX_TICK) {
+            let price_sqrt = calculate_price_sqrt(tick).unwrap();
+            prop_assert!(price_sqrt < calculate_price_sqrt(tick + 1).unwrap());
+            prop_assert!(price_sqrt >= Price::new(MIN_SQRT_PRICE));
+            prop_assert!(price_sqrt <= Price::new(MAX_SQRT_PRICE));
+            prop_assert_eq!(get_tick_at_sqrt_price(price_sqrt, 1).unwrap(), tick);
+        }
+
+        #[test]
+        fn proptest_get_tick_at_sqrt_price_with_spacing(
+            tick in -MAX_TICK..=MAX_TICK,
+            tick_spacing in prop_oneof![Just(1u16), Just(10u16), Just(100u16), 1u16..=100],
+        ) {
+            let tick = tick / tick_spacing as i32 * tick_spacing as i32;
+            prop_assert_eq!(get_tick_at_sqrt_price(calculate_price_sqrt(tick).unwrap(), tick_spacing).unwrap(), tick);
+        }
+    }
+
+    #[test]
+    fn test_tick_at_sqrt_price_extremes() {
+        assert_eq!(
+            get_tick_at_sqrt_price(calculate_price_sqrt(MAX_TICK).unwrap(), 1).unwrap(),
+            MAX_TICK
+        );
+        assert_eq!(
+            get_tick_at_sqrt_price(calculate_price_sqrt(-MAX_TICK).unwrap(), 1).unwrap(),
+            -MAX_TICK
+        );
+    }
+}
@@ -0,0 +1,436 @@
+// Drives pool, ticks, tickmap and positions through random sequences of instructions
+// and checks invariants of the whole pool after every step.
+// Handlers are mirrored without accounts, swaps run the loop of the swap handler. Every failed instruction
+// is reverted as a transaction would be.
+use std::collections::BTreeMap;
+
+use proptest::prelude::*;
+
+use crate::decimals::*;
+use crate::math::*;
+use crate::structs::{Pool, Position, Tick, Tickmap, TickmapView, MAX_TICK};
+use crate::util::*;
+use crate::*;
+
+#[derive(Clone, Debug)]
+enum Op {
+    // ticks are given in multiples of tick spacing
+    CreatePosition {
+        lower: i32,
+        width: i32,
+        liquidity: u128,
+    },
+    RemovePosition {
+        index: usize,
+    },
+    ClaimFee {
+        index: usize,
+    },
+    Swap {
+        x_to_y: bool,
+        amount: u64,
+        by_amount_in: bool,
+    },
+    WithdrawProtocolFee,
+}
+
+#[derive(Clone)]
+struct Simulation {
+    pool: Pool,
+    tickmap: Tickmap,
+    ticks: BTreeMap<i32, Tick>,
+    positions: Vec<Position>,
+    reserve_x: u64,
+    reserve_y: u64,
+    timestamp: u64,
+}
+
+impl Simulation {
+    fn new(tick_spacing: u16, fee: FixedPoint, protocol_fee: FixedPoint, init_tick: i32) -> Self {
+        let timestamp = 1_000;
+        Simulation {
+            pool: Pool {
+                tick_spacing,
+                fee,
+                protocol_fee,
+                sqrt_price: calculate_price_sqrt(init_tick).unwrap(),
+                current_tick_index: init_tick,
+                start_timestamp: timestamp,
+                last_timestamp: timestamp,
+                rewards_last_timestamp: timestamp,
+                ..Default::default()
+            },
+            tickmap: Tickmap::default(),
+            ticks: BTreeMap::new(),
+            positions: vec![],
+            reserve_x: 0,
+            reserve_y: 0,
+            timestamp,
+        }
+    }
+
+    // a failed instruction leaves the state untouched
+    fn execute(&mut self, op: &Op) -> bool {
+        let snapshot = self.clone();
+        self.timestamp += 10;
+
+        let tick_spacing = self.pool.tick_spacing as i32;
+        let result = match *op {
+            Op::CreatePosition {
+                lower,
+                width,
+                liquidity,
+            } => self.create_position(
+                lower * tick_spacing,
+                (lower + width) * tick_spacing,
+                Liquidity::new(liquidity),
+            ),
+            Op::RemovePosition { index } => match self.positions.len() {
+                0 => Ok(()),
+                len => self.remove_position(index % len),
+            },
+            Op::ClaimFee { index } => match self.positions.len() {
+                0 => Ok(()),
+                len => self.claim_fee(index % len),
+            },
+            Op::Swap {
+                x_to_y,
+                amount,
+                by_amount_in,
+            } => self.swap(x_to_y, amount, by_amount_in),
+            Op::WithdrawProtocolFee => {
+                self.withdraw_protocol_fee();
+                Ok(())
+            }
+        };
+
+        if result.is_err() {
+            *self = snapshot;
+        }
+        result.is_ok()
+    }
+
+    fn send(reserve: &mut u64, amount: TokenAmount) {
+        assert!(*reserve >= amount.0, "reserve overdrawn");
+        *reserve -= amount.0;
+    }
+
+    fn take(reserve: &mut u64, amount: TokenAmount) {
+        *reserve = reserve.checked_add(amount.0).unwrap();
+    }
+
+    fn create_position(
+        &mut self,
+        lower: i32,
+        upper: i32,
+        liquidity_delta: Liquidity,
+    ) -> Result<()> {
+        check_ticks(lower, upper, self.pool.tick_spacing, self.pool.tick_limit())?;
+
+        let mut lower_tick = match self.ticks.get(&lower) {
+            Some(tick) => *tick,
+            None => Tick::create(Pubkey::default(), &self.pool, lower, self.timestamp, 0)?,
+        };
+        let mut upper_tick = match self.ticks.get(&upper) {
+            Some(tick) => *tick,
+            None => Tick::create(Pubkey::default(), &self.pool, upper, self.timestamp, 0)?,
+        };
+        for index in [lower, upper] {
+            if !self.tickmap.get(index, self.pool.tick_spacing)? {
+                self.tickmap.flip(true, index, self.pool.tick_spacing)?;
+            }
+        }
+
+        let mut position = Position {
+            lower_tick_index: lower,
+            upper_tick_index: upper,
+            ..Default::default()
+        };
+        position.initialized_id(&mut self.pool)?;
+        let (amount_x, amount_y) = position.modify(
+            &mut self.pool,
+            &mut upper_tick,
+            &mut lower_tick,
+            liquidity_delta,
+            true,
+            self.timestamp,
+        )?;
+
+        self.ticks.insert(lower, lower_tick);
+        self.ticks.insert(upper, upper_tick);
+        self.positions.push(position);
+        Self::take(&mut self.reserve_x, amount_x);
+        Self::take(&mut self.reserve_y, amount_y);
+        Ok(())
+    }
+
+    fn remove_position(&mut self, index: usize) -> Result<()> {
+        let mut position = self.positions[index];
+        let lower = position.lower_tick_index;
+        let upper = position.upper_tick_index;
+        let mut lower_tick = self.ticks[&lower];
+        let mut upper_tick = self.ticks[&upper];
+
+        let liquidity_delta = position.liquidity;
+        let (amount_x, amount_y) = position.modify(
+            &mut self.pool,
+            &mut upper_tick,
+            &mut lower_tick,
+            liquidity_delta,
+            false,
+            self.timestamp,
+        )?;
+        let amount_x = amount_x + TokenAmount::from_decimal(position.tokens_owed_x);
+        let amount_y = amount_y + TokenAmount::from_decimal(position.tokens_owed_y);
+
+        for (index, tick) in [(lower, lower_tick), (upper, upper_tick)] {
+            if tick.liquidity_gross.is_zero() {
+                self.ticks.remove(&index);
+                self.tickmap.flip(false, index, self.pool.tick_spacing)?;
+            } else {
+                self.ticks.insert(index, tick);
+            }
+        }
+
+        // last position takes place of the removed one
+        self.positions.swap_remove(index);
+        Self::send(&mut self.reserve_x, amount_x);
+        Self::send(&mut self.reserve_y, amount_y);
+        Ok(())
+    }
+
+    fn claim_fee(&mut self, index: usize) -> Result<()> {
+        let position = &mut self.positions[index];
+        let lower = position.lower_tick_index;
+        let upper = position.upper_tick_index;
+        let mut lower_tick = self.ticks[&lower];
+        let mut upper_tick = self.ticks[&upper];
+
+        position.modify(
+            &mut self.pool,
+            &mut upper_tick,
+            &mut lower_tick,
+            Liquidity::new(0),
+            true,
+            self.timestamp,
+        )?;
+
+        let fee_to_collect_x = TokenAmount::from_decimal(position.tokens_owed_x);
+        let fee_to_collect_y = TokenAmount::from_decimal(position.tokens_owed_y);
+        position.tokens_owed_x =
+            position.tokens_owed_x - FixedPoint::from_decimal(fee_to_collect_x);
+        position.tokens_owed_y =
+            position.tokens_owed_y - FixedPoint::from_decimal(fee_to_collect_y);
+
+        self.ticks.insert(lower, lower_tick);
+        self.ticks.insert(upper, upper_tick);
+        Self::send(&mut self.reserve_x, fee_to_collect_x);
+        Self::send(&mut self.reserve_y, fee_to_collect_y);
+        Ok(())
+    }
+
+    // same as the swap handler, without referral and partial fill
+    fn swap(&mut self, x_to_y: bool, amount: u64, by_amount_in: bool) -> Result<()> {
+        let sqrt_price_limit = match x_to_y {
+            true => Price::new(MIN_SQRT_PRICE),
+            false => Price::new(MAX_SQRT_PRICE),
+        };
+        let totals = swap_loop(
+            &mut self.pool,
+            &TickmapView::new(&self.tickmap, None),
+            &mut self.ticks,
+            x_to_y,
+            TokenAmount(amount),
+            by_amount_in,
+            sqrt_price_limit,
+            false,
+            FixedPoint::from_integer(0),
+            self.timestamp,
+        )?;
+
+        let (reserve_in, reserve_out) = match x_to_y {
+            true => (&mut self.reserve_x, &mut self.reserve_y),
+            false => (&mut self.reserve_y, &mut self.reserve_x),
+        };
+        Self::take(reserve_in, totals.amount_in);
+        Self::send(reserve_out, totals.amount_out);
+        Ok(())
+    }
+
+    fn withdraw_protocol_fee(&mut self) {
+        let pool = &mut self.pool;
+        Self::send(&mut self.reserve_x, TokenAmount(pool.fee_protocol_token_x));
+        Self::send(&mut self.reserve_y, TokenAmount(pool.fee_protocol_token_y));
+        pool.fee_protocol_token_x = 0;
+        pool.fee_protocol_token_y = 0;
+    }
+
+    fn check_invariants(&self) {
+        let pool = &self.pool;
+        let ticks: Vec<Tick> = self.ticks.values().copied().collect();
+        let positions: Vec<(Position, Tick, Tick)> = self
+            .positions
+            .iter()
+            .map(|position| {
+                (
+                    *position,
+                    self.ticks[&{ position.lower_tick_index }],
+                    self.ticks[&{ position.upper_tick_index }],
+                )
+            })
+            .collect();
+
+        let tickmap = TickmapView::new(&self.tickmap, None);
+        let mut report =
+            calculate_pool_report(pool, &tickmap, -MAX_TICK, MAX_TICK, &ticks, &positions).unwrap();
+        report.reserve_x = self.reserve_x;
+        report.reserve_y = self.reserve_y;
+        assert!(report.tickmap_valid, "tickmap out of sync with ticks");
+        assert!(
+            report.ticks_valid(),
+            "tick liquidity out of sync with positions"
+        );
+        assert!(
+            report.liquidity_valid(),
+            "pool liquidity out of sync with positions"
+        );
+        assert!(report.is_solvent(), "pool insolvent: {:?}", report);
+
+        // current tick is the nearest one below the price
+        let current_tick_index = pool.current_tick_index;
+        // it can be below the lowest tick aligned to spacing, so both are clamped to the price range
+        let next_tick_index = (current_tick_index + pool.tick_spacing as i32).min(MAX_TICK);
+        assert_eq!(current_tick_index % pool.tick_spacing as i32, 0);
+        let sqrt_price = pool.sqrt_price;
+        assert!(calculate_price_sqrt(current_tick_index.max(-MAX_TICK)).unwrap() <= sqrt_price);
+        assert!(sqrt_price <= calculate_price_sqrt(next_tick_index).unwrap());
+
+        // growth inside since the last update can't exceed the global one, so a negative one would wrap above it
+        let fee_growth_global_x = pool.fee_growth_global_x;
+        let fee_growth_global_y = pool.fee_growth_global_y;
+        for position in &self.positions {
+            let (fee_growth_inside_x, fee_growth_inside_y) = calculate_fee_growth_inside(
+                self.ticks[&{ position.lower_tick_index }],
+                self.ticks[&{ position.upper_tick_index }],
+                current_tick_index,
+                fee_growth_global_x,
+                fee_growth_global_y,
+            );
+            assert!(
+                fee_growth_inside_x.unchecked_sub(position.fee_growth_inside_x)
+                    <= fee_growth_global_x,
+                "negative fee growth inside x"
+            );
+            assert!(
+                fee_growth_inside_y.unchecked_sub(position.fee_growth_inside_y)
+                    <= fee_growth_global_y,
+                "negative fee growth inside y"
+            );
+        }
+    }
+}
+
+// ticks of the simulation are kept by index
+impl SwapTicks for BTreeMap<i32, Tick> {
+    type Handle = i32;
+
+    fn find(&mut self, index: i32) -> Result<Option<i32>> {
+        Ok(self.contains_key(&index).then(|| index))
+    }
+
+    fn cross(&mut self, index: i32, pool: &mut Pool, current_timestamp: u64) -> Result<()> {
+        cross_tick(self.get_mut(&index).unwrap(), pool, current_timestamp)
+    }
+}
+
+#[test]
+fn test_simulation_crosses_positions() {
+    let mut simulation = Box::new(Simulation::new(
+        10,
+        FixedPoint::from_scale(3, 3),
+        FixedPoint::from_scale(1, 2),
+        0,
+    ));
+    let liquidity = Liquidity::from_integer(1_000_000).v;
+    let ops = [
+        Op::CreatePosition {
+            lower: -10,
+            width: 20,
+            liquidity,
+        },
+        Op::CreatePosition {
+            lower: -20,
+            width: 15,
+            liquidity,
+        },
+        Op::CreatePosition {
+            lower: 5,
+            width: 10,
+            liquidity,
+        },
+        Op::Swap {
+            x_to_y: true,
+            amount: 5_000,
+            by_amount_in: true,
+        },
+        Op::Swap {
+            x_to_y: false,
+            amount: 10_000,
+            by_amount_in: false,
+        },
+        Op::ClaimFee { index: 0 },
+        Op::RemovePosition { index: 1 },
+        Op::WithdrawProtocolFee,
+        Op::RemovePosition { index: 0 },
+        Op::RemovePosition { index: 0 },
+    ];
+    let mut crossed = 0;
+    for op in ops.iter() {
+        let tick_before = simulation.pool.current_tick_index;
+        assert!(simulation.execute(op), "{:?} failed", op);
+        simulation.check_invariants();
+        if let Op::Swap { .. } = op {
+            crossed += (simulation.pool.current_tick_index - tick_before).abs() / 100;
+        }
+    }
+    assert!(crossed > 0);
+    assert!(simulation.positions.is_empty());
+    assert!(simulation.ticks.is_empty());
+    assert!(simulation.pool.liquidity.is_zero());
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => (-40i32..=40, 1i32..=40, prop_oneof![10u128.pow(6)..=10u128.pow(12), 10u128.pow(6)..=10u128.pow(18)])
+            .prop_map(|(lower, width, liquidity)| Op::CreatePosition { lower, width, liquidity }),
+        1 => any::<usize>().prop_map(|index| Op::RemovePosition { index }),
+        1 => any::<usize>().prop_map(|index| Op::ClaimFee { index }),
+        4 => (any::<bool>(), prop_oneof![1u64..=10_000, 1u64..=10u64.pow(10)], any::<bool>())
+            .prop_map(|(x_to_y, amount, by_amount_in)| Op::Swap { x_to_y, amount, by_amount_in }),
+        1 => Just(Op::WithdrawProtocolFee),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn pool_invariants_hold_after_every_step(
+        tick_spacing in prop::sample::select(vec![1u16, 2, 10, 100]),
+        fee in prop_oneof![Just(0u128), 0u128..=10u128.pow(10)],
+        protocol_fee in prop_oneof![Just(0u128), 0u128..=10u128.pow(12)],
+        init_tick in -20i32..=20,
+        ops in prop::collection::vec(op(), 1..64),
+    ) {
+        let mut simulation = Box::new(Simulation::new(
+            tick_spacing,
+            FixedPoint::new(fee),
+            FixedPoint::new(protocol_fee),
+            init_tick * tick_spacing as i32,
+        ));
+        for op in ops.iter() {
+            simulation.execute(op);
+            simulation.check_invariants();
+        }
+    }
+}
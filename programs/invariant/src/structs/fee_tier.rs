@@ -8,4 +8,7 @@ pub struct FeeTier {
     pub fee: FixedPoint,
     pub tick_spacing: u16,
     pub bump: u8,
+    pub version: u8,
+    pub default_protocol_fee: FixedPoint, // protocol fee of pools created in this tier
+    pub reserved: [u64; 2],
 }
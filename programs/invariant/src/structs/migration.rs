@@ -0,0 +1,322 @@
+use crate::decimals::*;
+use crate::structs::*;
+use anchor_lang::prelude::*;
+
+// Accounts created before versioning are treated as version 0. Every versioned account ends with
+// `version` and zeroed `reserved` space, so fields added later can be carved out of the reserved
+// space and upgraded in place, without reallocation.
+pub const CURRENT_VERSION: u8 = 1;
+
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct PoolV0 {
+    pub token_x: Pubkey,
+    pub token_y: Pubkey,
+    pub token_x_reserve: Pubkey,
+    pub token_y_reserve: Pubkey,
+    pub position_iterator: u128,
+    pub tick_spacing: u16,
+    pub fee: FixedPoint,
+    pub protocol_fee: FixedPoint,
+    pub liquidity: Liquidity,
+    pub sqrt_price: Price,
+    pub current_tick_index: i32,
+    pub tickmap: Pubkey,
+    pub fee_growth_global_x: FeeGrowth,
+    pub fee_growth_global_y: FeeGrowth,
+    pub fee_protocol_token_x: u64,
+    pub fee_protocol_token_y: u64,
+    pub seconds_per_liquidity_global: FixedPoint,
+    pub start_timestamp: u64,
+    pub last_timestamp: u64,
+    pub fee_receiver: Pubkey,
+    pub oracle_address: Pubkey,
+    pub oracle_initialized: bool,
+    pub bump: u8,
+}
+
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct TickV0 {
+    pub pool: Pubkey,
+    pub index: i32,
+    pub sign: bool,
+    pub liquidity_change: Liquidity,
+    pub liquidity_gross: Liquidity,
+    pub sqrt_price: Price,
+    pub fee_growth_outside_x: FeeGrowth,
+    pub fee_growth_outside_y: FeeGrowth,
+    pub seconds_per_liquidity_outside: FixedPoint,
+    pub seconds_outside: u64,
+    pub bump: u8,
+}
+
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct PositionV0 {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub id: u128,
+    pub liquidity: Liquidity,
+    pub lower_tick_index: i32,
+    pub upper_tick_index: i32,
+    pub fee_growth_inside_x: FeeGrowth,
+    pub fee_growth_inside_y: FeeGrowth,
+    pub seconds_per_liquidity_inside: FixedPoint,
+    pub last_slot: u64,
+    pub tokens_owed_x: FixedPoint,
+    pub tokens_owed_y: FixedPoint,
+    pub bump: u8,
+}
+
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct PositionListV0 {
+    pub head: u32,
+    pub bump: u8,
+}
+
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct StateV0 {
+    pub admin: Pubkey,
+    pub nonce: u8,
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct FeeTierV0 {
+    pub fee: FixedPoint,
+    pub tick_spacing: u16,
+    pub bump: u8,
+}
+
+#[account(zero_copy)]
+#[repr(packed)]
+pub struct OracleV0 {
+    pub data: [Record; 256],
+    pub head: u16,
+    pub amount: u16,
+    pub size: u16,
+}
+
+impl From<PoolV0> for Pool {
+    fn from(old: PoolV0) -> Self {
+        Pool {
+            token_x: old.token_x,
+            token_y: old.token_y,
+            token_x_reserve: old.token_x_reserve,
+            token_y_reserve: old.token_y_reserve,
+            position_iterator: old.position_iterator,
+            tick_spacing: old.tick_spacing,
+            fee: old.fee,
+            protocol_fee: old.protocol_fee,
+            liquidity: old.liquidity,
+            sqrt_price: old.sqrt_price,
+            current_tick_index: old.current_tick_index,
+            tickmap: old.tickmap,
+            fee_growth_global_x: old.fee_growth_global_x,
+            fee_growth_global_y: old.fee_growth_global_y,
+            fee_protocol_token_x: old.fee_protocol_token_x,
+            fee_protocol_token_y: old.fee_protocol_token_y,
+            seconds_per_liquidity_global: old.seconds_per_liquidity_global,
+            start_timestamp: old.start_timestamp,
+            last_timestamp: old.last_timestamp,
+            fee_receiver: old.fee_receiver,
+            oracle_address: old.oracle_address,
+            oracle_initialized: old.oracle_initialized,
+            // no reward is initialized, so rewards timestamp is set with the first one
+            rewards: Default::default(),
+            rewards_last_timestamp: 0,
+            bump: old.bump,
+            version: CURRENT_VERSION,
+            tickmap_extension: Pubkey::default(),
+            reserved: Default::default(),
+        }
+    }
+}
+
+impl From<TickV0> for Tick {
+    fn from(old: TickV0) -> Self {
+        Tick {
+            pool: old.pool,
+            index: old.index,
+            sign: old.sign,
+            liquidity_change: old.liquidity_change,
+            liquidity_gross: old.liquidity_gross,
+            sqrt_price: old.sqrt_price,
+            fee_growth_outside_x: old.fee_growth_outside_x,
+            fee_growth_outside_y: old.fee_growth_outside_y,
+            seconds_per_liquidity_outside: old.seconds_per_liquidity_outside,
+            seconds_outside: old.seconds_outside,
+            reward_growths_outside: [FeeGrowth::new(0); REWARDS_PER_POOL],
+            bump: old.bump,
+            version: CURRENT_VERSION,
+            reserved: Default::default(),
+        }
+    }
+}
+
+impl From<PositionV0> for Position {
+    fn from(old: PositionV0) -> Self {
+        Position {
+            owner: old.owner,
+            pool: old.pool,
+            id: old.id,
+            liquidity: old.liquidity,
+            lower_tick_index: old.lower_tick_index,
+            upper_tick_index: old.upper_tick_index,
+            fee_growth_inside_x: old.fee_growth_inside_x,
+            fee_growth_inside_y: old.fee_growth_inside_y,
+            seconds_per_liquidity_inside: old.seconds_per_liquidity_inside,
+            last_slot: old.last_slot,
+            tokens_owed_x: old.tokens_owed_x,
+            tokens_owed_y: old.tokens_owed_y,
+            reward_growths_inside: [FeeGrowth::new(0); REWARDS_PER_POOL],
+            rewards_owed: [FixedPoint::new(0); REWARDS_PER_POOL],
+            bump: old.bump,
+            version: CURRENT_VERSION,
+            reserved: Default::default(),
+        }
+    }
+}
+
+impl From<PositionListV0> for PositionList {
+    fn from(old: PositionListV0) -> Self {
+        PositionList {
+            head: old.head,
+            bump: old.bump,
+            version: CURRENT_VERSION,
+            operator: Pubkey::default(),
+            reserved: Default::default(),
+        }
+    }
+}
+
+impl From<StateV0> for State {
+    fn from(old: StateV0) -> Self {
+        State {
+            admin: old.admin,
+            nonce: old.nonce,
+            authority: old.authority,
+            bump: old.bump,
+            version: CURRENT_VERSION,
+            governance_delay: 0,
+            reserved: Default::default(),
+        }
+    }
+}
+
+impl From<FeeTierV0> for FeeTier {
+    fn from(old: FeeTierV0) -> Self {
+        FeeTier {
+            fee: old.fee,
+            tick_spacing: old.tick_spacing,
+            bump: old.bump,
+            version: CURRENT_VERSION,
+            // protocol fee that used to be hard-coded for new pools
+            default_protocol_fee: FixedPoint::from_scale(1, 2),
+            reserved: Default::default(),
+        }
+    }
+}
+
+impl From<OracleV0> for Oracle {
+    fn from(old: OracleV0) -> Self {
+        Oracle {
+            data: old.data,
+            head: old.head,
+            amount: old.amount,
+            size: old.size,
+            version: CURRENT_VERSION,
+            reserved: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_from_v0() {
+        let position = Position::from(PositionV0 {
+            owner: Pubkey::new_unique(),
+            id: 7,
+            liquidity: Liquidity::from_integer(5),
+            tokens_owed_x: FixedPoint::from_integer(3),
+            bump: 254,
+            ..Default::default()
+        });
+        assert_eq!({ position.id }, 7);
+        assert_eq!({ position.liquidity }, Liquidity::from_integer(5));
+        assert_eq!({ position.tokens_owed_x }, FixedPoint::from_integer(3));
+        assert_eq!(
+            { position.rewards_owed },
+            [FixedPoint::new(0); REWARDS_PER_POOL]
+        );
+        assert_eq!({ position.bump }, 254);
+        assert_eq!({ position.version }, CURRENT_VERSION);
+
+        let pool = Pool::from(PoolV0 {
+            current_tick_index: -20,
+            oracle_initialized: true,
+            bump: 253,
+            ..Default::default()
+        });
+        assert_eq!({ pool.current_tick_index }, -20);
+        assert!(pool.oracle_initialized);
+        assert!(pool
+            .rewards
+            .iter()
+            .all(|reward| *reward == PoolReward::default()));
+        assert_eq!({ pool.rewards_last_timestamp }, 0);
+        assert_eq!({ pool.bump }, 253);
+        assert_eq!({ pool.version }, CURRENT_VERSION);
+
+        let tick = Tick::from(TickV0 {
+            index: 30,
+            fee_growth_outside_x: FeeGrowth::from_integer(2),
+            bump: 252,
+            ..Default::default()
+        });
+        assert_eq!({ tick.index }, 30);
+        assert_eq!({ tick.fee_growth_outside_x }, FeeGrowth::from_integer(2));
+        assert_eq!(
+            { tick.reward_growths_outside },
+            [FeeGrowth::new(0); REWARDS_PER_POOL]
+        );
+        assert_eq!({ tick.bump }, 252);
+        assert_eq!({ tick.version }, CURRENT_VERSION);
+
+        let mut data = [Record {
+            timestamp: 0,
+            price: Price::new(0),
+        }; 256];
+        data[3] = Record {
+            timestamp: 11,
+            price: Price::from_integer(2),
+        };
+        let oracle = Oracle::from(OracleV0 {
+            data,
+            head: 3,
+            amount: 4,
+            size: 256,
+        });
+        assert_eq!({ oracle.data[3].timestamp }, 11);
+        assert_eq!({ oracle.data[3].price }, Price::from_integer(2));
+        assert_eq!({ oracle.head }, 3);
+        assert_eq!({ oracle.amount }, 4);
+        assert_eq!({ oracle.size }, 256);
+        assert_eq!({ oracle.version }, CURRENT_VERSION);
+        assert_eq!({ oracle.reserved }, [0; 4]);
+    }
+}
@@ -1,17 +1,21 @@
 pub mod fee_tier;
+pub mod migration;
 pub mod oracle;
 pub mod pool;
 pub mod position;
 pub mod position_list;
+pub mod proposal;
 pub mod state;
 pub mod tick;
 pub mod tickmap;
 
 pub use fee_tier::*;
+pub use migration::*;
 pub use oracle::*;
 pub use pool::*;
 pub use position::*;
 pub use position_list::*;
+pub use proposal::*;
 pub use state::*;
 pub use tick::*;
 pub use tickmap::*;
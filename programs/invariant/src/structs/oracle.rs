@@ -1,4 +1,5 @@
 use crate::decimals::*;
+use crate::structs::CURRENT_VERSION;
 use anchor_lang::prelude::*;
 
 const SIZE: u16 = 256; // UPDATE IN ARRAYS AS WELL!
@@ -10,6 +11,8 @@ pub struct Oracle {
     pub head: u16,
     pub amount: u16,
     pub size: u16,
+    pub version: u8,
+    pub reserved: [u64; 4],
 }
 
 #[zero_copy]
@@ -33,6 +36,7 @@ impl Oracle {
     pub fn init(&mut self) {
         self.size = SIZE;
         self.head = SIZE - 1;
+        self.version = CURRENT_VERSION;
     }
 }
 
@@ -51,6 +55,8 @@ mod tests {
             head: SIZE - 1,
             amount: 0,
             size: SIZE,
+            version: CURRENT_VERSION,
+            reserved: [0; 4],
         };
         assert_eq!({ oracle.size }, SIZE);
 
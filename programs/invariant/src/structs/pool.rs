@@ -1,6 +1,29 @@
+use crate::log::get_tick_at_sqrt_price;
+use crate::structs::{FeeTier, CURRENT_VERSION, EXTENDED_TICK_LIMIT, TICK_LIMIT};
 use crate::*;
 use anchor_lang::prelude::*;
 use decimals::*;
+use std::cmp::Ordering;
+
+pub const REWARDS_PER_POOL: usize = 3;
+
+#[zero_copy]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct PoolReward {
+    pub mint: Pubkey, // default if reward is not initialized
+    pub vault: Pubkey,
+    pub emissions_per_second: u64,
+    pub end_timestamp: u64,
+    pub growth_global: FeeGrowth,
+}
+
+impl PoolReward {
+    pub fn is_initialized(&self) -> bool {
+        let mint = self.mint;
+        mint != Pubkey::default()
+    }
+}
 
 #[account(zero_copy)]
 #[repr(packed)]
@@ -28,17 +51,82 @@ pub struct Pool {
     pub fee_receiver: Pubkey,
     pub oracle_address: Pubkey,
     pub oracle_initialized: bool,
+    pub rewards: [PoolReward; REWARDS_PER_POOL],
+    pub rewards_last_timestamp: u64,
     pub bump: u8,
+    pub version: u8,
+    pub tickmap_extension: Pubkey, // default until the pool needs ticks outside of the tickmap
+    pub reserved: [u64; 12],
 }
 
 impl Pool {
+    // state of a new pool, its reserves, tickmap and fee receiver are set by the caller
+    // the extension is created along with the pool when the initial price is outside of the tickmap
+    pub fn create(
+        token_x: Pubkey,
+        token_y: Pubkey,
+        fee_tier: &FeeTier,
+        init_sqrt_price: Price,
+        tickmap_extension: Option<Pubkey>,
+        current_timestamp: u64,
+        bump: u8,
+    ) -> Result<Pool> {
+        require!(
+            token_x.to_string().cmp(&token_y.to_string()) == Ordering::Less,
+            InvalidPoolTokenAddresses
+        );
+        let pool = Pool {
+            token_x,
+            token_y,
+            tick_spacing: fee_tier.tick_spacing,
+            fee: fee_tier.fee,
+            protocol_fee: fee_tier.default_protocol_fee,
+            sqrt_price: init_sqrt_price,
+            tickmap_extension: tickmap_extension.unwrap_or_default(),
+            start_timestamp: current_timestamp,
+            last_timestamp: current_timestamp,
+            rewards_last_timestamp: current_timestamp,
+            bump,
+            version: CURRENT_VERSION,
+            ..Default::default()
+        };
+
+        let tick_limit = pool.tick_limit();
+        require!(
+            init_sqrt_price >= get_min_sqrt_price(fee_tier.tick_spacing, tick_limit)?
+                && init_sqrt_price <= get_max_sqrt_price(fee_tier.tick_spacing, tick_limit)?,
+            InvalidInitSqrtPrice
+        );
+        // price does not have to be on a tick, current tick is the nearest one below it
+        let init_tick = get_tick_at_sqrt_price(init_sqrt_price, fee_tier.tick_spacing)?;
+        check_tick(init_tick, fee_tier.tick_spacing, tick_limit)?;
+
+        Ok(Pool {
+            current_tick_index: init_tick,
+            ..pool
+        })
+    }
+
+    pub fn has_tickmap_extension(&self) -> bool {
+        let extension = self.tickmap_extension;
+        extension != Pubkey::default()
+    }
+
+    // ticks outside of the tickmap can be used only once the pool has an extension
+    pub fn tick_limit(&self) -> i32 {
+        match self.has_tickmap_extension() {
+            true => EXTENDED_TICK_LIMIT,
+            false => TICK_LIMIT,
+        }
+    }
+
     #[allow(unaligned_references)]
     pub fn add_fee(
         &mut self,
         amount: TokenAmount,
         ref_percentage: FixedPoint,
         in_x: bool,
-    ) -> TokenAmount {
+    ) -> Result<TokenAmount> {
         let protocol_fee = TokenAmount::from_decimal_up(amount.big_mul_up(self.protocol_fee));
         let ref_fee = match ref_percentage.is_zero() {
             true => TokenAmount(0),
@@ -47,24 +135,24 @@ impl Pool {
         let pool_fee = amount - protocol_fee - ref_fee;
 
         if (pool_fee.is_zero() && protocol_fee.is_zero()) || self.liquidity.is_zero() {
-            return ref_fee;
+            return Ok(ref_fee);
         }
-        let fee_growth = FeeGrowth::from_fee(self.liquidity, pool_fee);
+        let fee_growth = FeeGrowth::from_fee(self.liquidity, pool_fee)?;
 
         if in_x {
             self.fee_growth_global_x = self.fee_growth_global_x.unchecked_add(fee_growth);
             self.fee_protocol_token_x = self
                 .fee_protocol_token_x
                 .checked_add(protocol_fee.0)
-                .unwrap();
+                .ok_or(ErrorCode::MathOverflow)?;
         } else {
             self.fee_growth_global_y = self.fee_growth_global_y.unchecked_add(fee_growth);
             self.fee_protocol_token_y = self
                 .fee_protocol_token_y
                 .checked_add(protocol_fee.0)
-                .unwrap();
+                .ok_or(ErrorCode::MathOverflow)?;
         }
-        ref_fee
+        Ok(ref_fee)
     }
 
     pub fn update_liquidity_safely(&mut self, liquidity_delta: Liquidity, add: bool) -> Result<()> {
@@ -81,13 +169,63 @@ impl Pool {
         Ok(())
     }
 
-    pub fn update_seconds_per_liquidity_global(&mut self, current_timestamp: u64) {
-        self.seconds_per_liquidity_global = self.seconds_per_liquidity_global.unchecked_add(
-            FixedPoint::from_integer(current_timestamp.checked_sub(self.last_timestamp).unwrap())
-                / self.liquidity,
-        );
+    pub fn update_seconds_per_liquidity_global(&mut self, current_timestamp: u64) -> Result<()> {
+        let seconds_passed = current_timestamp
+            .checked_sub(self.last_timestamp)
+            .ok_or(ErrorCode::NegativeTime)?;
+        self.seconds_per_liquidity_global = self
+            .seconds_per_liquidity_global
+            .unchecked_add(FixedPoint::from_integer(seconds_passed) / self.liquidity);
 
         self.last_timestamp = current_timestamp;
+        Ok(())
+    }
+
+    // distribute rewards emitted since the last update among liquidity in range
+    pub fn update_rewards_growth_global(&mut self, current_timestamp: u64) -> Result<()> {
+        let last_timestamp = self.rewards_last_timestamp;
+        if current_timestamp <= last_timestamp {
+            return Ok(());
+        }
+
+        let mut rewards = self.rewards;
+        for reward in rewards.iter_mut().filter(|reward| reward.is_initialized()) {
+            let end_timestamp = { reward.end_timestamp }.min(current_timestamp);
+            if end_timestamp <= last_timestamp {
+                continue;
+            }
+            let elapsed = end_timestamp - last_timestamp;
+
+            // nobody can earn the emission, so it is postponed instead of being lost in the vault
+            if self.liquidity.is_zero() {
+                reward.end_timestamp = { reward.end_timestamp }
+                    .checked_add(elapsed)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                continue;
+            }
+
+            let emitted = TokenAmount(
+                elapsed
+                    .checked_mul(reward.emissions_per_second)
+                    .ok_or(ErrorCode::MathOverflow)?,
+            );
+            reward.growth_global = reward
+                .growth_global
+                .unchecked_add(FeeGrowth::from_fee(self.liquidity, emitted)?);
+        }
+        self.rewards = rewards;
+
+        self.rewards_last_timestamp = current_timestamp;
+        Ok(())
+    }
+
+    pub fn reward_growths_global(&self) -> [FeeGrowth; REWARDS_PER_POOL] {
+        let rewards = self.rewards;
+        let mut growths = [FeeGrowth::new(0); REWARDS_PER_POOL];
+        for (growth, reward) in growths.iter_mut().zip(rewards.iter()) {
+            *growth = reward.growth_global;
+        }
+        growths
     }
 
     pub fn set_oracle(&mut self, address: Pubkey) {
@@ -101,6 +239,106 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_create() {
+        let (token_x, token_y) = {
+            let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+            match a.to_string() < b.to_string() {
+                true => (a, b),
+                false => (b, a),
+            }
+        };
+        let fee_tier = FeeTier {
+            fee: FixedPoint::from_scale(3, 3),
+            tick_spacing: 10,
+            default_protocol_fee: FixedPoint::from_scale(1, 2),
+            ..Default::default()
+        };
+        // price between ticks
+        {
+            let init_sqrt_price = calculate_price_sqrt(25).unwrap() + Price::new(1);
+            let pool =
+                Pool::create(token_x, token_y, &fee_tier, init_sqrt_price, None, 7, 254).unwrap();
+
+            assert_eq!({ pool.token_x }, token_x);
+            assert_eq!({ pool.token_y }, token_y);
+            assert_eq!({ pool.tick_spacing }, 10);
+            assert_eq!({ pool.fee }, FixedPoint::from_scale(3, 3));
+            assert_eq!({ pool.protocol_fee }, FixedPoint::from_scale(1, 2));
+            assert_eq!({ pool.sqrt_price }, init_sqrt_price);
+            assert_eq!({ pool.current_tick_index }, 20);
+            assert_eq!({ pool.start_timestamp }, 7);
+            assert_eq!({ pool.last_timestamp }, 7);
+            assert_eq!({ pool.rewards_last_timestamp }, 7);
+            assert_eq!({ pool.liquidity }, Liquidity::new(0));
+            assert_eq!({ pool.bump }, 254);
+            assert_eq!({ pool.version }, CURRENT_VERSION);
+        }
+        // tokens in wrong order
+        {
+            let result = Pool::create(
+                token_y,
+                token_x,
+                &fee_tier,
+                Price::from_integer(1),
+                None,
+                7,
+                254,
+            );
+            assert!(result.is_err());
+        }
+        // price at the edges of the tick range of the spacing
+        {
+            let max_sqrt_price = get_max_sqrt_price(10, TICK_LIMIT).unwrap();
+            let min_sqrt_price = get_min_sqrt_price(10, TICK_LIMIT).unwrap();
+            assert!(
+                Pool::create(token_x, token_y, &fee_tier, max_sqrt_price, None, 7, 254).is_ok()
+            );
+            assert!(
+                Pool::create(token_x, token_y, &fee_tier, min_sqrt_price, None, 7, 254).is_ok()
+            );
+
+            let above = max_sqrt_price + Price::new(1);
+            let below = min_sqrt_price - Price::new(1);
+            assert!(Pool::create(token_x, token_y, &fee_tier, above, None, 7, 254).is_err());
+            assert!(Pool::create(token_x, token_y, &fee_tier, below, None, 7, 254).is_err());
+        }
+        // price outside of the tickmap at spacing 1 needs an extension
+        {
+            let fee_tier = FeeTier {
+                tick_spacing: 1,
+                ..fee_tier
+            };
+            let extension = Pubkey::new_unique();
+            let init_sqrt_price = calculate_price_sqrt(-100_000).unwrap();
+            let result = Pool::create(token_x, token_y, &fee_tier, init_sqrt_price, None, 7, 254);
+            assert!(result.is_err());
+
+            let pool = Pool::create(
+                token_x,
+                token_y,
+                &fee_tier,
+                init_sqrt_price,
+                Some(extension),
+                7,
+                254,
+            )
+            .unwrap();
+            assert_eq!({ pool.tickmap_extension }, extension);
+            assert_eq!({ pool.current_tick_index }, -100_000);
+            assert_eq!(pool.tick_limit(), EXTENDED_TICK_LIMIT);
+
+            let max_sqrt_price = Price::new(MAX_SQRT_PRICE);
+            let min_sqrt_price = Price::new(MIN_SQRT_PRICE);
+            let with_extension =
+                |price| Pool::create(token_x, token_y, &fee_tier, price, Some(extension), 7, 254);
+            assert!(with_extension(max_sqrt_price).is_ok());
+            assert!(with_extension(min_sqrt_price).is_ok());
+            assert!(with_extension(max_sqrt_price + Price::new(1)).is_err());
+            assert!(with_extension(min_sqrt_price - Price::new(1)).is_err());
+        }
+    }
+
     #[test]
     fn test_update_liquidity_safely_pool() {
         // Invalid pool liquidity
@@ -155,7 +393,9 @@ mod tests {
         {
             let mut pool = pool.clone();
             let amount = TokenAmount::from_integer(6);
-            let ref_fee = pool.add_fee(amount, FixedPoint::from_integer(0), true);
+            let ref_fee = pool
+                .add_fee(amount, FixedPoint::from_integer(0), true)
+                .unwrap();
             assert_eq!({ pool.fee_growth_global_x }, FeeGrowth::from_scale(4, 1));
             assert_eq!({ pool.fee_growth_global_y }, FeeGrowth::from_integer(0));
             assert_eq!({ pool.fee_protocol_token_x }, 2);
@@ -166,7 +406,9 @@ mod tests {
         {
             let mut pool = pool.clone();
             let amount = TokenAmount::from_integer(200);
-            let ref_fee = pool.add_fee(amount, FixedPoint::from_scale(1, 2), false);
+            let ref_fee = pool
+                .add_fee(amount, FixedPoint::from_scale(1, 2), false)
+                .unwrap();
 
             assert_eq!({ pool.fee_growth_global_x }, FeeGrowth::from_integer(0));
             assert_eq!({ pool.fee_growth_global_y }, FeeGrowth::from_scale(158, 1));
@@ -178,7 +420,9 @@ mod tests {
         {
             let mut pool = pool.clone();
             let amount = TokenAmount::new(1);
-            let ref_fee = pool.add_fee(amount, FixedPoint::from_scale(2, 1), true);
+            let ref_fee = pool
+                .add_fee(amount, FixedPoint::from_scale(2, 1), true)
+                .unwrap();
 
             assert_eq!({ pool.fee_growth_global_x }, FeeGrowth::new(0));
             assert_eq!({ pool.fee_growth_global_y }, FeeGrowth::new(0));
@@ -200,7 +444,9 @@ mod tests {
                 ..Default::default()
             };
             let current_timestamp: u64 = 18446;
-            test_pool.update_seconds_per_liquidity_global(current_timestamp);
+            test_pool
+                .update_seconds_per_liquidity_global(current_timestamp)
+                .unwrap();
             let result = test_pool.seconds_per_liquidity_global;
             assert_eq!(
                 result,
@@ -219,7 +465,9 @@ mod tests {
                 ..Default::default()
             };
             let current_timestamp: u64 = u64::MAX;
-            test_pool.update_seconds_per_liquidity_global(current_timestamp);
+            test_pool
+                .update_seconds_per_liquidity_global(current_timestamp)
+                .unwrap();
             let result = test_pool.seconds_per_liquidity_global;
             assert_eq!(
                 result,
@@ -229,4 +477,80 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_update_rewards_growth_global() {
+        let reward = PoolReward {
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            emissions_per_second: 5,
+            end_timestamp: 100,
+            growth_global: FeeGrowth::new(0),
+        };
+        // emission is split among liquidity
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::from_integer(10),
+                rewards: [reward, PoolReward::default(), PoolReward::default()],
+                ..Default::default()
+            };
+            pool.update_rewards_growth_global(10).unwrap();
+
+            let growths = pool.reward_growths_global();
+            assert_eq!(growths[0], FeeGrowth::from_integer(5));
+            assert_eq!(growths[1], FeeGrowth::new(0));
+            assert_eq!(growths[2], FeeGrowth::new(0));
+            assert_eq!({ pool.rewards_last_timestamp }, 10);
+        }
+        // emission stops at end timestamp
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::from_integer(10),
+                rewards: [PoolReward::default(), reward, PoolReward::default()],
+                rewards_last_timestamp: 90,
+                ..Default::default()
+            };
+            pool.update_rewards_growth_global(110).unwrap();
+
+            let growths = pool.reward_growths_global();
+            assert_eq!(growths[1], FeeGrowth::from_integer(5));
+            assert_eq!({ pool.rewards_last_timestamp }, 110);
+
+            pool.update_rewards_growth_global(120).unwrap();
+            assert_eq!(pool.reward_growths_global()[1], FeeGrowth::from_integer(5));
+        }
+        // no liquidity in range
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::new(0),
+                rewards: [reward, PoolReward::default(), PoolReward::default()],
+                ..Default::default()
+            };
+            pool.update_rewards_growth_global(10).unwrap();
+
+            assert_eq!(pool.reward_growths_global()[0], FeeGrowth::new(0));
+            assert_eq!({ pool.rewards_last_timestamp }, 10);
+            // emission is postponed by the time without liquidity
+            assert_eq!({ pool.rewards[0].end_timestamp }, 110);
+
+            // whole deposit is still emitted once liquidity is back
+            pool.liquidity = Liquidity::from_integer(10);
+            pool.update_rewards_growth_global(200).unwrap();
+            assert_eq!(pool.reward_growths_global()[0], FeeGrowth::from_integer(50));
+            assert_eq!({ pool.rewards[0].end_timestamp }, 110);
+        }
+        // no liquidity after the reward ended
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::new(0),
+                rewards: [reward, PoolReward::default(), PoolReward::default()],
+                rewards_last_timestamp: 100,
+                ..Default::default()
+            };
+            pool.update_rewards_growth_global(150).unwrap();
+
+            assert_eq!({ pool.rewards[0].end_timestamp }, 100);
+            assert_eq!({ pool.rewards_last_timestamp }, 150);
+        }
+    }
 }
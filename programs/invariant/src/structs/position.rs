@@ -1,6 +1,7 @@
 use crate::decimals::*;
-use crate::structs::pool::Pool;
+use crate::structs::pool::{Pool, REWARDS_PER_POOL};
 use crate::structs::tick::Tick;
+use crate::structs::CURRENT_VERSION;
 use crate::*;
 use anchor_lang::prelude::*;
 
@@ -20,10 +21,47 @@ pub struct Position {
     pub last_slot: u64,
     pub tokens_owed_x: FixedPoint,
     pub tokens_owed_y: FixedPoint,
+    pub reward_growths_inside: [FeeGrowth; REWARDS_PER_POOL],
+    pub rewards_owed: [FixedPoint; REWARDS_PER_POOL],
     pub bump: u8,
+    pub version: u8,
+    pub reserved: [u64; 8],
 }
 
 impl Position {
+    // empty position, liquidity is added with modify
+    pub fn create(
+        owner: Pubkey,
+        pool_address: Pubkey,
+        pool: &mut Pool,
+        lower_tick_index: i32,
+        upper_tick_index: i32,
+        slot: u64,
+        bump: u8,
+    ) -> Result<Position> {
+        let mut position = Position {
+            owner,
+            pool: pool_address,
+            id: 0,
+            liquidity: Liquidity::new(0),
+            lower_tick_index,
+            upper_tick_index,
+            fee_growth_inside_x: FeeGrowth::new(0),
+            fee_growth_inside_y: FeeGrowth::new(0),
+            seconds_per_liquidity_inside: FixedPoint::new(0),
+            last_slot: slot,
+            tokens_owed_x: FixedPoint::new(0),
+            tokens_owed_y: FixedPoint::new(0),
+            reward_growths_inside: [FeeGrowth::new(0); REWARDS_PER_POOL],
+            rewards_owed: [FixedPoint::new(0); REWARDS_PER_POOL],
+            bump,
+            version: CURRENT_VERSION,
+            reserved: Default::default(),
+        };
+        position.initialized_id(pool)?;
+        Ok(position)
+    }
+
     pub fn modify(
         &mut self,
         pool: &mut Pool,
@@ -34,13 +72,15 @@ impl Position {
         current_timestamp: u64,
     ) -> Result<(TokenAmount, TokenAmount)> {
         if !pool.liquidity.is_zero() {
-            pool.update_seconds_per_liquidity_global(current_timestamp);
+            pool.update_seconds_per_liquidity_global(current_timestamp)?;
         } else {
             pool.last_timestamp = current_timestamp;
         }
+        pool.update_rewards_growth_global(current_timestamp)?;
 
         // calculate dynamically limit allows easy modification
-        let max_liquidity_per_tick = calculate_max_liquidity_per_tick(pool.tick_spacing);
+        let max_liquidity_per_tick =
+            calculate_max_liquidity_per_tick(pool.tick_spacing, pool.tick_limit())?;
 
         // update initialized tick
         lower_tick.update(liquidity_delta, max_liquidity_per_tick, false, add)?;
@@ -56,6 +96,14 @@ impl Position {
             pool.fee_growth_global_y,
         );
 
+        // rewards are accrued with liquidity from before the modification
+        self.update_rewards(calculate_reward_growths_inside(
+            *lower_tick,
+            *upper_tick,
+            pool.current_tick_index,
+            pool.reward_growths_global(),
+        ))?;
+
         self.update(
             add,
             liquidity_delta,
@@ -88,10 +136,10 @@ impl Position {
         // calculate accumulated fee
         let tokens_owed_x = fee_growth_inside_x
             .unchecked_sub(self.fee_growth_inside_x)
-            .to_fee(self.liquidity);
+            .to_fee(self.liquidity)?;
         let tokens_owed_y = fee_growth_inside_y
             .unchecked_sub(self.fee_growth_inside_y)
-            .to_fee(self.liquidity);
+            .to_fee(self.liquidity)?;
 
         self.liquidity = self.calculate_new_liquidity_safely(sign, liquidity_delta)?;
         self.fee_growth_inside_x = fee_growth_inside_x;
@@ -104,9 +152,33 @@ impl Position {
         Ok(())
     }
 
-    pub fn initialized_id(&mut self, pool: &mut Pool) {
+    pub fn update_rewards(
+        &mut self,
+        reward_growths_inside: [FeeGrowth; REWARDS_PER_POOL],
+    ) -> Result<()> {
+        let growths_before = self.reward_growths_inside;
+        let mut rewards_owed = self.rewards_owed;
+
+        for i in 0..REWARDS_PER_POOL {
+            rewards_owed[i] = rewards_owed[i].unchecked_add(
+                reward_growths_inside[i]
+                    .unchecked_sub(growths_before[i])
+                    .to_fee(self.liquidity)?,
+            );
+        }
+
+        self.reward_growths_inside = reward_growths_inside;
+        self.rewards_owed = rewards_owed;
+        Ok(())
+    }
+
+    pub fn initialized_id(&mut self, pool: &mut Pool) -> Result<()> {
         self.id = pool.position_iterator;
-        pool.position_iterator = pool.position_iterator.checked_add(1).unwrap();
+        pool.position_iterator = pool
+            .position_iterator
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
     }
 
     // for future use
@@ -137,6 +209,28 @@ impl Position {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_create() {
+        let owner = Pubkey::new_unique();
+        let pool_address = Pubkey::new_unique();
+        let mut pool = Pool {
+            position_iterator: 3,
+            ..Default::default()
+        };
+
+        let position = Position::create(owner, pool_address, &mut pool, -10, 10, 100, 254).unwrap();
+        assert_eq!({ position.owner }, owner);
+        assert_eq!({ position.pool }, pool_address);
+        assert_eq!({ position.id }, 3);
+        assert_eq!({ position.liquidity }, Liquidity::new(0));
+        assert_eq!({ position.lower_tick_index }, -10);
+        assert_eq!({ position.upper_tick_index }, 10);
+        assert_eq!({ position.last_slot }, 100);
+        assert_eq!({ position.bump }, 254);
+        assert_eq!({ position.version }, CURRENT_VERSION);
+        assert_eq!({ pool.position_iterator }, 4);
+    }
+
     #[test]
     fn test_calculate_new_liquidity_safely() {
         // negative liquidity error
@@ -358,4 +452,43 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_update_rewards() {
+        let mut position = Position {
+            liquidity: Liquidity::from_integer(4),
+            reward_growths_inside: [
+                FeeGrowth::from_integer(1),
+                FeeGrowth::new(0),
+                FeeGrowth::new(0),
+            ],
+            rewards_owed: [
+                FixedPoint::from_integer(2),
+                FixedPoint::new(0),
+                FixedPoint::new(0),
+            ],
+            ..Default::default()
+        };
+
+        position
+            .update_rewards([
+                FeeGrowth::from_integer(3),
+                FeeGrowth::from_integer(1),
+                FeeGrowth::new(0),
+            ])
+            .unwrap();
+
+        let rewards_owed = position.rewards_owed;
+        assert_eq!(rewards_owed[0], FixedPoint::from_integer(10));
+        assert_eq!(rewards_owed[1], FixedPoint::from_integer(4));
+        assert_eq!(rewards_owed[2], FixedPoint::new(0));
+        assert_eq!(
+            { position.reward_growths_inside },
+            [
+                FeeGrowth::from_integer(3),
+                FeeGrowth::from_integer(1),
+                FeeGrowth::new(0)
+            ]
+        );
+    }
 }
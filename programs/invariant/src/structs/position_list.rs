@@ -6,4 +6,7 @@ use anchor_lang::prelude::*;
 pub struct PositionList {
     pub head: u32,
     pub bump: u8,
+    pub version: u8,
+    pub operator: Pubkey, // can manage positions on behalf of the owner, default if not set
+    pub reserved: [u64; 4],
 }
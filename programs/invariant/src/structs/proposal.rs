@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+pub const ACTION_CHANGE_PROTOCOL_FEE: u8 = 0;
+pub const ACTION_CHANGE_FEE_RECEIVER: u8 = 1;
+pub const ACTION_CREATE_FEE_TIER: u8 = 2;
+pub const ACTION_CHANGE_DEFAULT_PROTOCOL_FEE: u8 = 3;
+pub const ACTION_CHANGE_GOVERNANCE_DELAY: u8 = 4;
+
+// 30 days, a longer delay could lock the admin out of the protocol
+pub const MAX_GOVERNANCE_DELAY: u64 = 30 * 24 * 60 * 60;
+
+// Admin action waiting out the governance delay, there is at most one pending action per target
+#[account(zero_copy)]
+#[repr(packed)]
+#[derive(PartialEq, Default, Debug)]
+pub struct Proposal {
+    pub action: u8,
+    pub target: Pubkey,       // pool, fee tier or state the action applies to
+    pub proposer: Pubkey,     // receives rent back once the proposal is closed
+    pub value: u128,          // fee, protocol fee or governance delay
+    pub tick_spacing: u16,    // tick spacing of the proposed fee tier
+    pub fee_receiver: Pubkey, // proposed fee receiver
+    pub execute_after: u64,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u64; 4],
+}
+
+impl Proposal {
+    pub fn is_ready(&self, current_timestamp: u64) -> bool {
+        current_timestamp >= self.execute_after
+    }
+}
@@ -8,4 +8,7 @@ pub struct State {
     pub nonce: u8,
     pub authority: Pubkey,
     pub bump: u8,
+    pub version: u8,
+    pub governance_delay: u64, // seconds between proposing and executing an admin action
+    pub reserved: [u64; 7],
 }
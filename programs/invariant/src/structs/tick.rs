@@ -1,3 +1,4 @@
+use crate::structs::{Pool, CURRENT_VERSION, REWARDS_PER_POOL};
 use crate::*;
 use anchor_lang::prelude::*;
 use decimals::*;
@@ -16,10 +17,59 @@ pub struct Tick {
     pub fee_growth_outside_y: FeeGrowth,
     pub seconds_per_liquidity_outside: FixedPoint,
     pub seconds_outside: u64,
+    pub reward_growths_outside: [FeeGrowth; REWARDS_PER_POOL],
     pub bump: u8,
+    pub version: u8,
+    pub reserved: [u64; 8],
 }
 
 impl Tick {
+    // everything accrued so far is outside of a tick below the current one
+    pub fn create(
+        pool_address: Pubkey,
+        pool: &Pool,
+        index: i32,
+        current_timestamp: u64,
+        bump: u8,
+    ) -> Result<Tick> {
+        check_tick(index, pool.tick_spacing, pool.tick_limit())?;
+
+        let below_current_tick = index <= pool.current_tick_index;
+        Ok(Tick {
+            pool: pool_address,
+            index,
+            sign: true,
+            liquidity_change: Liquidity::new(0),
+            liquidity_gross: Liquidity::new(0),
+            sqrt_price: calculate_price_sqrt(index)?,
+            fee_growth_outside_x: match below_current_tick {
+                true => pool.fee_growth_global_x,
+                false => FeeGrowth::new(0),
+            },
+            fee_growth_outside_y: match below_current_tick {
+                true => pool.fee_growth_global_y,
+                false => FeeGrowth::new(0),
+            },
+            seconds_outside: match below_current_tick {
+                true => current_timestamp
+                    .checked_sub(pool.start_timestamp)
+                    .ok_or(ErrorCode::NegativeTime)?,
+                false => 0,
+            },
+            seconds_per_liquidity_outside: match below_current_tick {
+                true => pool.seconds_per_liquidity_global,
+                false => FixedPoint::new(0),
+            },
+            reward_growths_outside: match below_current_tick {
+                true => pool.reward_growths_global(),
+                false => [FeeGrowth::new(0); REWARDS_PER_POOL],
+            },
+            bump,
+            version: CURRENT_VERSION,
+            reserved: Default::default(),
+        })
+    }
+
     pub fn update(
         &mut self,
         liquidity_delta: Liquidity,
@@ -75,8 +125,52 @@ impl Tick {
 
 #[cfg(test)]
 mod tests {
+    use crate::structs::TICK_LIMIT;
+
     use super::*;
 
+    #[test]
+    fn test_create() {
+        let pool_address = Pubkey::new_unique();
+        let pool = Pool {
+            tick_spacing: 10,
+            current_tick_index: 0,
+            fee_growth_global_x: FeeGrowth::from_integer(3),
+            fee_growth_global_y: FeeGrowth::from_integer(4),
+            seconds_per_liquidity_global: FixedPoint::from_integer(5),
+            start_timestamp: 10,
+            ..Default::default()
+        };
+        // everything accrued is outside of a tick below the current one
+        {
+            let tick = Tick::create(pool_address, &pool, -10, 30, 254).unwrap();
+            assert_eq!({ tick.pool }, pool_address);
+            assert_eq!({ tick.index }, -10);
+            assert_eq!({ tick.sqrt_price }, calculate_price_sqrt(-10).unwrap());
+            assert_eq!({ tick.fee_growth_outside_x }, FeeGrowth::from_integer(3));
+            assert_eq!({ tick.fee_growth_outside_y }, FeeGrowth::from_integer(4));
+            assert_eq!(
+                { tick.seconds_per_liquidity_outside },
+                FixedPoint::from_integer(5)
+            );
+            assert_eq!({ tick.seconds_outside }, 20);
+            assert_eq!({ tick.bump }, 254);
+            assert_eq!({ tick.version }, CURRENT_VERSION);
+        }
+        // nothing is outside of a tick above the current one
+        {
+            let tick = Tick::create(pool_address, &pool, 10, 30, 254).unwrap();
+            assert_eq!({ tick.fee_growth_outside_x }, FeeGrowth::new(0));
+            assert_eq!({ tick.fee_growth_outside_y }, FeeGrowth::new(0));
+            assert_eq!({ tick.seconds_per_liquidity_outside }, FixedPoint::new(0));
+            assert_eq!({ tick.seconds_outside }, 0);
+        }
+        // tick not on the spacing
+        {
+            assert!(Tick::create(pool_address, &pool, 5, 30, 254).is_err());
+        }
+    }
+
     #[test]
     fn test_update_liquidity_change() {
         // update when tick sign and sign of liquidity change are the same
@@ -196,7 +290,7 @@ mod tests {
                 ..Default::default()
             };
 
-            let max_liquidity_per_tick = calculate_max_liquidity_per_tick(1);
+            let max_liquidity_per_tick = calculate_max_liquidity_per_tick(1, TICK_LIMIT).unwrap();
             let liquidity_delta = max_liquidity_per_tick + Liquidity::new(1);
             let result = tick.update(liquidity_delta, max_liquidity_per_tick, false, true);
             assert!(result.is_err());
@@ -1,9 +1,14 @@
+use crate::errors::ErrorCode;
 use anchor_lang::prelude::*;
 use std::convert::TryInto;
 
 pub const TICK_LIMIT: i32 = 44_364; // If you change it update length of array as well!
 pub const TICK_SEARCH_RANGE: i32 = 256;
 pub const MAX_TICK: i32 = 221_818; // log(1.0001, sqrt(2^64-1))
+pub const EXTENDED_TICK_LIMIT: i32 = MAX_TICK + 1; // tick limit of a tickmap with an extension
+const EXTENSION_SIDE_BITS: usize = (EXTENDED_TICK_LIMIT - TICK_LIMIT) as usize; // bits below and above the tickmap
+const TICKMAP_WORD_BYTES: usize = 8;
+const TICKMAP_WORD_BITS: usize = 64;
 
 #[account(zero_copy)]
 #[repr(packed)]
@@ -17,156 +22,290 @@ impl Default for Tickmap {
     }
 }
 
-fn tick_to_position(tick: i32, tick_spacing: u16) -> (usize, u8) {
-    assert!(
-        (tick % tick_spacing as i32) == 0,
-        "tick not divisible by spacing"
+// Ticks that don't fit into the tickmap, so pools with small tick spacing can use the whole price range.
+// It is created only for pools that need it, the tickmap keeps its layout and covers the middle part.
+#[account(zero_copy)]
+#[repr(packed)]
+pub struct TickmapExtension {
+    pub lower: [u8; 22182], // (Extended tick limit - Tick limit) / 8, ticks below the tickmap
+    pub upper: [u8; 22182], // ticks above the tickmap
+}
+
+impl Default for TickmapExtension {
+    fn default() -> Self {
+        TickmapExtension {
+            lower: [0; 22182],
+            upper: [0; 22182],
+        }
+    }
+}
+
+// index of the tick in a bitmap holding 2 * tick_limit ticks
+fn tick_to_index(tick: i32, tick_spacing: u16, tick_limit: i32) -> Result<usize> {
+    require!(tick_spacing != 0, InvalidTickSpacing);
+    require!(tick % tick_spacing as i32 == 0, InvalidTickIndex);
+
+    let bitmap_index = (tick / tick_spacing as i32)
+        .checked_add(tick_limit)
+        .ok_or(ErrorCode::InvalidTickIndex)?;
+    require!(
+        bitmap_index >= 0 && bitmap_index < 2 * tick_limit,
+        InvalidTickIndex
     );
 
-    let bitmap_index = tick
-        .checked_div(tick_spacing.try_into().unwrap())
-        .unwrap()
-        .checked_add(TICK_LIMIT)
-        .unwrap();
+    Ok(bitmap_index as usize)
+}
 
-    let byte: usize = (bitmap_index.checked_div(8).unwrap()).try_into().unwrap();
-    let bit: u8 = (bitmap_index % 8).abs().try_into().unwrap();
+fn index_to_tick(index: usize, tick_spacing: u16, tick_limit: i32) -> Result<i32> {
+    let index: i32 = index.try_into().map_err(|_| ErrorCode::InvalidTickIndex)?;
 
-    (byte, bit)
+    (index - tick_limit)
+        .checked_mul(tick_spacing as i32)
+        .ok_or_else(|| ErrorCode::InvalidTickIndex.into())
 }
 
-pub fn get_search_limit(tick: i32, tick_spacing: u16, up: bool) -> i32 {
+pub fn get_search_limit(tick: i32, tick_spacing: u16, up: bool) -> Result<i32> {
+    search_limit(tick, tick_spacing, up, TICK_LIMIT)
+}
+
+fn search_limit(tick: i32, tick_spacing: u16, up: bool, tick_limit: i32) -> Result<i32> {
+    require!(tick_spacing != 0, InvalidTickSpacing);
     let index = tick / tick_spacing as i32;
 
     let limit = if up {
         // ticks are limited by amount of space in the bitmap...
-        let array_limit = TICK_LIMIT.checked_sub(1).unwrap();
+        let array_limit = tick_limit - 1;
         // ...search range is limited to 256 at the time ...
-        let range_limit = index.checked_add(TICK_SEARCH_RANGE).unwrap();
+        let range_limit = index.saturating_add(TICK_SEARCH_RANGE);
         // ...also ticks for prices over 2^64 aren't needed
-        let price_limit = MAX_TICK.checked_div(tick_spacing as i32).unwrap();
+        let price_limit = MAX_TICK / tick_spacing as i32;
 
         array_limit.min(range_limit).min(price_limit)
     } else {
-        let array_limit = (-TICK_LIMIT).checked_add(1).unwrap();
-        let range_limit = index.checked_sub(TICK_SEARCH_RANGE).unwrap();
-        let price_limit = -MAX_TICK.checked_div(tick_spacing as i32).unwrap();
+        let array_limit = -tick_limit + 1;
+        let range_limit = index.saturating_sub(TICK_SEARCH_RANGE);
+        let price_limit = -MAX_TICK / tick_spacing as i32;
 
         array_limit.max(range_limit).max(price_limit)
     };
 
-    limit.checked_mul(tick_spacing as i32).unwrap()
+    // limits are bounded by the tickmap, so it can't overflow
+    Ok(limit * tick_spacing as i32)
+}
+
+// splits index of the extended bitmap into the part holding it and index inside of that part
+fn split_extended_index(index: usize) -> (ExtendedPart, usize) {
+    if index < EXTENSION_SIDE_BITS {
+        (ExtendedPart::Lower, index)
+    } else if index < EXTENSION_SIDE_BITS + 2 * TICK_LIMIT as usize {
+        (ExtendedPart::Tickmap, index - EXTENSION_SIDE_BITS)
+    } else {
+        (
+            ExtendedPart::Upper,
+            index - EXTENSION_SIDE_BITS - 2 * TICK_LIMIT as usize,
+        )
+    }
+}
+
+enum ExtendedPart {
+    Lower,
+    Tickmap,
+    Upper,
+}
+
+// 64 bits of the bitmap starting at the given bit (bits outside of the bitmap are zeros)
+fn read_word(bitmap: &[u8], start: isize) -> u64 {
+    let bits = (bitmap.len() * 8) as isize;
+    if start <= -(TICKMAP_WORD_BITS as isize) || start >= bits {
+        return 0;
+    }
+    if start < 0 {
+        return read_word(bitmap, 0) << -start;
+    }
+
+    let byte = start as usize / 8;
+    let end = bitmap.len().min(byte + TICKMAP_WORD_BYTES + 1);
+
+    let mut bytes = [0u8; 2 * TICKMAP_WORD_BYTES];
+    bytes[..end - byte].copy_from_slice(&bitmap[byte..end]);
+
+    (u128::from_le_bytes(bytes) >> (start as usize % 8)) as u64
+}
+
+// flips the tick in the tickmap or in its extension, depending on which of them holds the tick
+pub fn flip_tick(
+    tickmap: &mut Tickmap,
+    extension: Option<&mut TickmapExtension>,
+    value: bool,
+    tick: i32,
+    tick_spacing: u16,
+) -> Result<()> {
+    let view = TickmapView::new(tickmap, extension.as_deref());
+    require!(view.get(tick, tick_spacing)? != value, InvalidTickmapFlip);
+
+    let index = tick_to_index(tick, tick_spacing, view.tick_limit())?;
+    let (bitmap, index) = match extension {
+        None => (&mut tickmap.bitmap[..], index),
+        Some(extension) => match split_extended_index(index) {
+            (ExtendedPart::Lower, index) => (&mut extension.lower[..], index),
+            (ExtendedPart::Tickmap, index) => (&mut tickmap.bitmap[..], index),
+            (ExtendedPart::Upper, index) => (&mut extension.upper[..], index),
+        },
+    };
+
+    bitmap[index / 8] ^= 1 << (index % 8);
+    Ok(())
 }
 
 impl Tickmap {
-    pub fn flip(&mut self, value: bool, tick: i32, tick_spacing: u16) {
-        assert!(
-            self.get(tick, tick_spacing) != value,
-            "tick initialize tick again"
-        );
+    pub fn flip(&mut self, value: bool, tick: i32, tick_spacing: u16) -> Result<()> {
+        flip_tick(self, None, value, tick, tick_spacing)
+    }
+
+    pub fn get(&self, tick: i32, tick_spacing: u16) -> Result<bool> {
+        TickmapView::new(self, None).get(tick, tick_spacing)
+    }
+
+    pub fn next_initialized(&self, tick: i32, tick_spacing: u16) -> Result<Option<i32>> {
+        TickmapView::new(self, None).next_initialized(tick, tick_spacing)
+    }
+
+    pub fn prev_initialized(&self, tick: i32, tick_spacing: u16) -> Result<Option<i32>> {
+        TickmapView::new(self, None).prev_initialized(tick, tick_spacing)
+    }
+}
 
-        let (byte, bit) = tick_to_position(tick, tick_spacing);
+// Tickmap of a pool together with its extension, if the pool has one
+#[derive(Clone, Copy)]
+pub struct TickmapView<'a> {
+    pub tickmap: &'a Tickmap,
+    pub extension: Option<&'a TickmapExtension>,
+}
 
-        self.bitmap[byte] ^= 1 << bit;
+impl<'a> TickmapView<'a> {
+    pub fn new(tickmap: &'a Tickmap, extension: Option<&'a TickmapExtension>) -> Self {
+        TickmapView { tickmap, extension }
     }
 
-    pub fn get(&self, tick: i32, tick_spacing: u16) -> bool {
-        let (byte, bit) = tick_to_position(tick, tick_spacing);
-        let value = (self.bitmap[byte] >> bit) % 2;
+    pub fn tick_limit(&self) -> i32 {
+        match self.extension {
+            Some(_) => EXTENDED_TICK_LIMIT,
+            None => TICK_LIMIT,
+        }
+    }
+
+    pub fn search_limit(&self, tick: i32, tick_spacing: u16, up: bool) -> Result<i32> {
+        search_limit(tick, tick_spacing, up, self.tick_limit())
+    }
 
-        (value) == 1
+    pub fn get(&self, tick: i32, tick_spacing: u16) -> Result<bool> {
+        let index = tick_to_index(tick, tick_spacing, self.tick_limit())?;
+        let (bitmap, index) = match self.extension {
+            None => (&self.tickmap.bitmap[..], index),
+            Some(extension) => match split_extended_index(index) {
+                (ExtendedPart::Lower, index) => (&extension.lower[..], index),
+                (ExtendedPart::Tickmap, index) => (&self.tickmap.bitmap[..], index),
+                (ExtendedPart::Upper, index) => (&extension.upper[..], index),
+            },
+        };
+
+        Ok((bitmap[index / 8] >> (index % 8)) % 2 == 1)
     }
 
-    pub fn next_initialized(&self, tick: i32, tick_spacing: u16) -> Option<i32> {
-        let limit = get_search_limit(tick, tick_spacing, true);
+    pub fn next_initialized(&self, tick: i32, tick_spacing: u16) -> Result<Option<i32>> {
+        let tick_limit = self.tick_limit();
+        let limit = self.search_limit(tick, tick_spacing, true)?;
 
         // add 1 to not check current tick
-        let (mut byte, mut bit) =
-            tick_to_position(tick.checked_add(tick_spacing as i32).unwrap(), tick_spacing);
-        let (limiting_byte, limiting_bit) = tick_to_position(limit, tick_spacing);
+        let next_tick = tick
+            .checked_add(tick_spacing as i32)
+            .ok_or(ErrorCode::InvalidTickIndex)?;
+        if next_tick > limit {
+            return Ok(None);
+        }
 
-        while byte < limiting_byte || (byte == limiting_byte && bit <= limiting_bit) {
-            // ignore some bits on first loop
-            let mut shifted = self.bitmap[byte] >> bit;
+        let start = tick_to_index(next_tick, tick_spacing, tick_limit)?;
+        let limiting_index = tick_to_index(limit, tick_spacing, tick_limit)?;
 
-            // go through all bits in byte until it is zero
-            if shifted != 0 {
-                while shifted.checked_rem(2).unwrap() == 0 {
-                    shifted >>= 1;
-                    bit = bit.checked_add(1).unwrap();
-                }
+        let mut word_index = start / TICKMAP_WORD_BITS;
+        // ignore bits below the start on first word
+        let mut word = self.get_word(word_index) & (u64::MAX << (start % TICKMAP_WORD_BITS));
 
-                return if byte < limiting_byte || (byte == limiting_byte && bit <= limiting_bit) {
-                    let index: i32 = byte
-                        .checked_mul(8)
-                        .unwrap()
-                        .checked_add(bit.into())
-                        .unwrap()
-                        .try_into()
-                        .unwrap();
-                    Some(
-                        index
-                            .checked_sub(TICK_LIMIT)
-                            .unwrap()
-                            .checked_mul(tick_spacing.try_into().unwrap())
-                            .unwrap(),
-                    )
-                } else {
-                    None
+        loop {
+            if word != 0 {
+                let index = word_index * TICKMAP_WORD_BITS + word.trailing_zeros() as usize;
+
+                // return first initalized tick if limiit is not exceeded, otherswise return None
+                return match index <= limiting_index {
+                    true => Ok(Some(index_to_tick(index, tick_spacing, tick_limit)?)),
+                    false => Ok(None),
                 };
             }
 
-            // go to the text byte
-            byte = byte.checked_add(1).unwrap();
-            bit = 0;
+            // go to the next word
+            word_index += 1;
+            if word_index * TICKMAP_WORD_BITS > limiting_index {
+                return Ok(None);
+            }
+            word = self.get_word(word_index);
         }
-
-        None
     }
 
-    pub fn prev_initialized(&self, tick: i32, tick_spacing: u16) -> Option<i32> {
+    pub fn prev_initialized(&self, tick: i32, tick_spacing: u16) -> Result<Option<i32>> {
+        let tick_limit = self.tick_limit();
         // don't subtract 1 to check the current tick
-        let limit = get_search_limit(tick, tick_spacing, false);
-        let (mut byte, mut bit) = tick_to_position(tick as i32, tick_spacing);
-        let (limiting_byte, limiting_bit) = tick_to_position(limit, tick_spacing);
+        let limit = self.search_limit(tick, tick_spacing, false)?;
+        if tick < limit {
+            return Ok(None);
+        }
 
-        while byte > limiting_byte || (byte == limiting_byte && bit >= limiting_bit) {
-            let mut mask = 1u16.checked_shl(bit.try_into().unwrap()).unwrap();
-            let value = self.bitmap[byte] as u16;
+        let start = tick_to_index(tick, tick_spacing, tick_limit)?;
+        let limiting_index = tick_to_index(limit, tick_spacing, tick_limit)?;
 
-            if value.checked_rem(mask.checked_shl(1).unwrap()).unwrap() > 0 {
-                while value & mask == 0 {
-                    mask >>= 1;
-                    bit = bit.checked_sub(1).unwrap();
-                }
+        let mut word_index = start / TICKMAP_WORD_BITS;
+        // ignore bits above the start on first word
+        let mut word = self.get_word(word_index)
+            & (u64::MAX >> (TICKMAP_WORD_BITS - 1 - start % TICKMAP_WORD_BITS));
 
-                return if byte > limiting_byte || (byte == limiting_byte && bit >= limiting_bit) {
-                    let index: i32 = byte
-                        .checked_mul(8)
-                        .unwrap()
-                        .checked_add(bit.into())
-                        .unwrap()
-                        .try_into()
-                        .unwrap();
+        loop {
+            if word != 0 {
+                let index = word_index * TICKMAP_WORD_BITS + TICKMAP_WORD_BITS
+                    - 1
+                    - word.leading_zeros() as usize;
 
-                    Some(
-                        index
-                            .checked_sub(TICK_LIMIT)
-                            .unwrap()
-                            .checked_mul(tick_spacing.try_into().unwrap())
-                            .unwrap(),
-                    )
-                } else {
-                    None
+                // return first initalized tick if limiit is not exceeded, otherswise return None
+                return match index >= limiting_index {
+                    true => Ok(Some(index_to_tick(index, tick_spacing, tick_limit)?)),
+                    false => Ok(None),
                 };
             }
 
-            // go to the text byte
-            byte = byte.checked_sub(1).unwrap();
-            bit = 7;
+            // limit is inside of the current word
+            if word_index * TICKMAP_WORD_BITS <= limiting_index {
+                return Ok(None);
+            }
+
+            // go to the previous word
+            word_index -= 1;
+            word = self.get_word(word_index);
         }
+    }
 
-        None
+    // 64 bits of the bitmap starting at word_index * 64, the tickmap sits between both parts of the extension
+    fn get_word(&self, word_index: usize) -> u64 {
+        let start = (word_index * TICKMAP_WORD_BITS) as isize;
+
+        match self.extension {
+            None => read_word(&self.tickmap.bitmap, start),
+            Some(extension) => {
+                let tickmap_start = EXTENSION_SIDE_BITS as isize;
+                let upper_start = tickmap_start + 2 * TICK_LIMIT as isize;
+
+                read_word(&extension.lower, start)
+                    | read_word(&self.tickmap.bitmap, start - tickmap_start)
+                    | read_word(&extension.upper, start - upper_start)
+            }
+        }
     }
 }
 
@@ -174,6 +313,13 @@ impl Tickmap {
 mod tests {
     use super::*;
 
+    // byte and bit of the tick in the tickmap
+    fn tick_to_position(tick: i32, tick_spacing: u16) -> Result<(usize, u8)> {
+        let index = tick_to_index(tick, tick_spacing, TICK_LIMIT)?;
+
+        Ok((index / 8, (index % 8) as u8))
+    }
+
     #[test]
     fn test_price_limit() {
         let map = Tickmap::default();
@@ -181,8 +327,10 @@ mod tests {
         // tick spacing equals 5 is threshold from which entire price range is available
         let tick_spacing = 5;
         let max_absolute_tick = (MAX_TICK / tick_spacing as i32) * tick_spacing as i32;
-        let (max_tick_byte, max_tick_bit) = tick_to_position(max_absolute_tick, tick_spacing);
-        let (min_tick_byte, min_tick_bit) = tick_to_position(-max_absolute_tick, tick_spacing);
+        let (max_tick_byte, max_tick_bit) =
+            tick_to_position(max_absolute_tick, tick_spacing).unwrap();
+        let (min_tick_byte, min_tick_bit) =
+            tick_to_position(-max_absolute_tick, tick_spacing).unwrap();
         let min_index = 8 * min_tick_byte + min_tick_bit as usize;
         let max_index = 8 * max_tick_byte + max_tick_bit as usize;
         let max_tick = (max_index as i32 - TICK_LIMIT) * tick_spacing as i32;
@@ -195,8 +343,8 @@ mod tests {
         assert_eq!(max_tick, 221_815);
         assert_eq!(min_tick, -221_815);
         // try to access price edges
-        map.get(max_absolute_tick, tick_spacing);
-        map.get(-max_absolute_tick, tick_spacing);
+        map.get(max_absolute_tick, tick_spacing).unwrap();
+        map.get(-max_absolute_tick, tick_spacing).unwrap();
     }
 
     #[test]
@@ -207,52 +355,68 @@ mod tests {
         {
             let index = 0;
 
-            assert_eq!(map.get(index, 1), false);
-            map.flip(true, index, 1);
-            assert_eq!(map.get(index, 1), true);
-            map.flip(false, index, 1);
-            assert_eq!(map.get(index, 1), false);
+            assert_eq!(map.get(index, 1).unwrap(), false);
+            map.flip(true, index, 1).unwrap();
+            assert_eq!(map.get(index, 1).unwrap(), true);
+            map.flip(false, index, 1).unwrap();
+            assert_eq!(map.get(index, 1).unwrap(), false);
         }
         // small
         {
             let index = 7;
 
-            assert_eq!(map.get(index, 1), false);
-            map.flip(true, index, 1);
-            assert_eq!(map.get(index, 1), true);
-            map.flip(false, index, 1);
-            assert_eq!(map.get(index, 1), false);
+            assert_eq!(map.get(index, 1).unwrap(), false);
+            map.flip(true, index, 1).unwrap();
+            assert_eq!(map.get(index, 1).unwrap(), true);
+            map.flip(false, index, 1).unwrap();
+            assert_eq!(map.get(index, 1).unwrap(), false);
         }
         // big
         {
             let index = TICK_LIMIT - 1;
 
-            assert_eq!(map.get(index, 1), false);
-            map.flip(true, index, 1);
-            assert_eq!(map.get(index, 1), true);
-            map.flip(false, index, 1);
-            assert_eq!(map.get(index, 1), false);
+            assert_eq!(map.get(index, 1).unwrap(), false);
+            map.flip(true, index, 1).unwrap();
+            assert_eq!(map.get(index, 1).unwrap(), true);
+            map.flip(false, index, 1).unwrap();
+            assert_eq!(map.get(index, 1).unwrap(), false);
         }
         // negative
         {
             let index = TICK_LIMIT - 40;
 
-            assert_eq!(map.get(index, 1), false);
-            map.flip(true, index, 1);
-            assert_eq!(map.get(index, 1), true);
-            map.flip(false, index, 1);
-            assert_eq!(map.get(index, 1), false);
+            assert_eq!(map.get(index, 1).unwrap(), false);
+            map.flip(true, index, 1).unwrap();
+            assert_eq!(map.get(index, 1).unwrap(), true);
+            map.flip(false, index, 1).unwrap();
+            assert_eq!(map.get(index, 1).unwrap(), false);
         }
         // tick spacing
         {
             let index = 20000;
             let tick_spacing = 1000;
 
-            assert_eq!(map.get(index, tick_spacing), false);
-            map.flip(true, index, tick_spacing);
-            assert_eq!(map.get(index, tick_spacing), true);
-            map.flip(false, index, tick_spacing);
-            assert_eq!(map.get(index, tick_spacing), false);
+            assert_eq!(map.get(index, tick_spacing).unwrap(), false);
+            map.flip(true, index, tick_spacing).unwrap();
+            assert_eq!(map.get(index, tick_spacing).unwrap(), true);
+            map.flip(false, index, tick_spacing).unwrap();
+            assert_eq!(map.get(index, tick_spacing).unwrap(), false);
+        }
+        // errors instead of panics
+        {
+            let mut map = Tickmap::default();
+
+            // already in the requested state
+            assert!(map.flip(false, 0, 1).is_err());
+            map.flip(true, 0, 1).unwrap();
+            assert!(map.flip(true, 0, 1).is_err());
+            // not aligned to the spacing
+            assert!(map.flip(true, 5, 10).is_err());
+            assert!(map.get(5, 10).is_err());
+            // outside of the bitmap
+            assert!(map.get(2 * TICK_LIMIT, 1).is_err());
+            assert!(map.get(-TICK_LIMIT - 1, 1).is_err());
+            assert!(map.get(0, 0).is_err());
         }
     }
 
@@ -261,56 +425,62 @@ mod tests {
         // Simple
         {
             let mut map = Tickmap::default();
-            map.flip(true, 5, 1);
-            assert_eq!(map.next_initialized(0, 1), Some(5));
+            map.flip(true, 5, 1).unwrap();
+            assert_eq!(map.next_initialized(0, 1).unwrap(), Some(5));
         }
         // Multiple
         {
             let mut map = Tickmap::default();
-            map.flip(true, 50, 10);
-            map.flip(true, 100, 10);
-            assert_eq!(map.next_initialized(0, 10), Some(50));
-            assert_eq!(map.next_initialized(50, 10), Some(100));
+            map.flip(true, 50, 10).unwrap();
+            map.flip(true, 100, 10).unwrap();
+            assert_eq!(map.next_initialized(0, 10).unwrap(), Some(50));
+            assert_eq!(map.next_initialized(50, 10).unwrap(), Some(100));
         }
         // Current is last
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, 0, 10);
-            assert_eq!(map.next_initialized(0, 10), None);
+            map.flip(true, 0, 10).unwrap();
+            assert_eq!(map.next_initialized(0, 10).unwrap(), None);
         }
         // Just below limit
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, 0, 1);
-            assert_eq!(map.next_initialized(-TICK_SEARCH_RANGE, 1), Some(0));
+            map.flip(true, 0, 1).unwrap();
+            assert_eq!(
+                map.next_initialized(-TICK_SEARCH_RANGE, 1).unwrap(),
+                Some(0)
+            );
         }
         // At limit
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, 0, 1);
-            assert_eq!(map.next_initialized(-TICK_SEARCH_RANGE - 1, 1), None);
+            map.flip(true, 0, 1).unwrap();
+            assert_eq!(
+                map.next_initialized(-TICK_SEARCH_RANGE - 1, 1).unwrap(),
+                None
+            );
         }
         // Further than limit
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, TICK_LIMIT - 10, 1);
-            assert_eq!(map.next_initialized(-TICK_LIMIT + 1, 1), None);
+            map.flip(true, TICK_LIMIT - 10, 1).unwrap();
+            assert_eq!(map.next_initialized(-TICK_LIMIT + 1, 1).unwrap(), None);
         }
         // Hitting the limit
         {
             let map = Tickmap::default();
 
-            assert_eq!(map.next_initialized(MAX_TICK - 22, 4), None);
+            assert_eq!(map.next_initialized(MAX_TICK - 22, 4).unwrap(), None);
         }
         // Already at limit
         {
             let map = Tickmap::default();
 
-            assert_eq!(map.next_initialized(MAX_TICK - 2, 4), None);
+            assert_eq!(map.next_initialized(MAX_TICK - 2, 4).unwrap(), None);
         }
     }
 
@@ -319,51 +489,54 @@ mod tests {
         // Simple
         {
             let mut map = Tickmap::default();
-            map.flip(true, -5, 1);
-            assert_eq!(map.prev_initialized(0, 1), Some(-5));
+            map.flip(true, -5, 1).unwrap();
+            assert_eq!(map.prev_initialized(0, 1).unwrap(), Some(-5));
         }
         // Multiple
         {
             let mut map = Tickmap::default();
-            map.flip(true, -50, 10);
-            map.flip(true, -100, 10);
-            assert_eq!(map.prev_initialized(0, 10), Some(-50));
-            assert_eq!(map.prev_initialized(-50, 10), Some(-50));
+            map.flip(true, -50, 10).unwrap();
+            map.flip(true, -100, 10).unwrap();
+            assert_eq!(map.prev_initialized(0, 10).unwrap(), Some(-50));
+            assert_eq!(map.prev_initialized(-50, 10).unwrap(), Some(-50));
         }
         // Current is last
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, 0, 10);
-            assert_eq!(map.prev_initialized(0, 10), Some(0));
+            map.flip(true, 0, 10).unwrap();
+            assert_eq!(map.prev_initialized(0, 10).unwrap(), Some(0));
         }
         // Next is last
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, 10, 10);
-            assert_eq!(map.prev_initialized(0, 10), None);
+            map.flip(true, 10, 10).unwrap();
+            assert_eq!(map.prev_initialized(0, 10).unwrap(), None);
         }
         // Just below limit
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, 0, 1);
-            assert_eq!(map.prev_initialized(TICK_SEARCH_RANGE, 1), Some(0));
+            map.flip(true, 0, 1).unwrap();
+            assert_eq!(map.prev_initialized(TICK_SEARCH_RANGE, 1).unwrap(), Some(0));
         }
         // At limit
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, 0, 1);
-            assert_eq!(map.prev_initialized(TICK_SEARCH_RANGE + 1, 1), None);
+            map.flip(true, 0, 1).unwrap();
+            assert_eq!(
+                map.prev_initialized(TICK_SEARCH_RANGE + 1, 1).unwrap(),
+                None
+            );
         }
         // Farther than limit
         {
             let mut map = Tickmap::default();
 
-            map.flip(true, -TICK_LIMIT + 1, 1);
-            assert_eq!(map.prev_initialized(TICK_LIMIT - 1, 1), None);
+            map.flip(true, -TICK_LIMIT + 1, 1).unwrap();
+            assert_eq!(map.prev_initialized(TICK_LIMIT - 1, 1).unwrap(), None);
         }
     }
 
@@ -371,19 +544,19 @@ mod tests {
     fn test_get_search_limit() {
         // Simple up
         {
-            let result = get_search_limit(0, 1, true);
+            let result = get_search_limit(0, 1, true).unwrap();
             assert_eq!(result, TICK_SEARCH_RANGE);
         }
         // Simple down
         {
-            let result = get_search_limit(0, 1, false);
+            let result = get_search_limit(0, 1, false).unwrap();
             assert_eq!(result, -TICK_SEARCH_RANGE);
         }
         // Less simple up
         {
             let start = 60;
             let step = 12;
-            let result = get_search_limit(start, step, true);
+            let result = get_search_limit(start, step, true).unwrap();
             let expected = start + TICK_SEARCH_RANGE * step as i32;
             assert_eq!(result, expected);
         }
@@ -391,37 +564,326 @@ mod tests {
         {
             let start = 60;
             let step = 12;
-            let result = get_search_limit(start, step, false);
+            let result = get_search_limit(start, step, false).unwrap();
             let expected = start - TICK_SEARCH_RANGE * step as i32;
             assert_eq!(result, expected);
         }
         // Up to array limit
         {
             let step = 2u16;
-            let result = get_search_limit(step as i32 * TICK_LIMIT - 10, step, true);
+            let result = get_search_limit(step as i32 * TICK_LIMIT - 10, step, true).unwrap();
             let expected = step as i32 * (TICK_LIMIT - 1);
             assert_eq!(result, expected);
         }
         // Down to array limit
         {
             let step = 2u16;
-            let result = get_search_limit(step as i32 * (-TICK_LIMIT + 1), step, false);
+            let result = get_search_limit(step as i32 * (-TICK_LIMIT + 1), step, false).unwrap();
             let expected = step as i32 * -(TICK_LIMIT - 1);
             assert_eq!(result, expected);
         }
         // Up to price limit
         {
             let step = 5u16;
-            let result = get_search_limit(MAX_TICK - 22, step, true);
+            let result = get_search_limit(MAX_TICK - 22, step, true).unwrap();
             let expected = MAX_TICK - 3;
             assert_eq!(result, expected);
         }
         // At the price limit
         {
             let step = 5u16;
-            let result = get_search_limit(MAX_TICK - 3, step, true);
+            let result = get_search_limit(MAX_TICK - 3, step, true).unwrap();
             let expected = MAX_TICK - 3;
             assert_eq!(result, expected);
         }
     }
+
+    // byte by byte search used before word level search, kept as a reference
+    fn next_initialized_bytewise(map: &Tickmap, tick: i32, tick_spacing: u16) -> Option<i32> {
+        let limit = get_search_limit(tick, tick_spacing, true).unwrap();
+
+        // add 1 to not check current tick
+        let next_tick = tick.checked_add(tick_spacing as i32).unwrap();
+        if next_tick > limit {
+            return None;
+        }
+        let (mut byte, mut bit) = tick_to_position(next_tick, tick_spacing).unwrap();
+        let (limiting_byte, limiting_bit) = tick_to_position(limit, tick_spacing).unwrap();
+
+        while byte < limiting_byte || (byte == limiting_byte && bit <= limiting_bit) {
+            // ignore some bits on first loop
+            let mut shifted = map.bitmap[byte] >> bit;
+
+            // go through all bits in byte until it is zero
+            if shifted != 0 {
+                while shifted.checked_rem(2).unwrap() == 0 {
+                    shifted >>= 1;
+                    bit = bit.checked_add(1).unwrap();
+                }
+
+                return if byte < limiting_byte || (byte == limiting_byte && bit <= limiting_bit) {
+                    let index: i32 = byte
+                        .checked_mul(8)
+                        .unwrap()
+                        .checked_add(bit.into())
+                        .unwrap()
+                        .try_into()
+                        .unwrap();
+                    Some(
+                        index
+                            .checked_sub(TICK_LIMIT)
+                            .unwrap()
+                            .checked_mul(tick_spacing.try_into().unwrap())
+                            .unwrap(),
+                    )
+                } else {
+                    None
+                };
+            }
+
+            // go to the text byte
+            if let Some(value) = byte.checked_add(1) {
+                byte = value;
+            } else {
+                return None;
+            }
+            bit = 0;
+        }
+
+        None
+    }
+
+    // tick_spacing - spacing already scaled by tick_spacing
+    fn prev_initialized_bytewise(map: &Tickmap, tick: i32, tick_spacing: u16) -> Option<i32> {
+        // don't subtract 1 to check the current tick
+        let limit = get_search_limit(tick, tick_spacing, false).unwrap(); // limit scaled by tick_spacing
+        let (mut byte, mut bit) = tick_to_position(tick as i32, tick_spacing).unwrap();
+        let (limiting_byte, limiting_bit) = tick_to_position(limit, tick_spacing).unwrap();
+
+        while byte > limiting_byte || (byte == limiting_byte && bit >= limiting_bit) {
+            // always safe due to limitated domain of bit variable
+            let mut mask = 1u16.checked_shl(bit.try_into().unwrap()).unwrap(); // left = MSB direction (increase value)
+            let value = map.bitmap[byte] as u16;
+
+            // enter if some of previous bits are initialized in current byte
+            if value.checked_rem(mask.checked_shl(1).unwrap()).unwrap() > 0 {
+                // skip uninitalized ticks
+                while value & mask == 0 {
+                    mask >>= 1;
+                    bit = bit.checked_sub(1).unwrap();
+                }
+
+                // return first initalized tick if limiit is not exceeded, otherswise return None
+                return if byte > limiting_byte || (byte == limiting_byte && bit >= limiting_bit) {
+                    // no possibility to overflow
+                    let index: i32 = byte
+                        .checked_mul(8)
+                        .unwrap()
+                        .checked_add(bit.into())
+                        .unwrap()
+                        .try_into()
+                        .unwrap();
+
+                    Some(
+                        index
+                            .checked_sub(TICK_LIMIT)
+                            .unwrap()
+                            .checked_mul(tick_spacing.try_into().unwrap())
+                            .unwrap(),
+                    )
+                } else {
+                    None
+                };
+            }
+
+            // go to the next byte
+            if let Some(value) = byte.checked_sub(1) {
+                byte = value;
+            } else {
+                return None;
+            }
+            bit = 7;
+        }
+
+        None
+    }
+
+    #[test]
+    fn test_word_search_matches_bytewise_search() {
+        // xorshift, deterministic pseudo random numbers
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut random = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for spacing in [1u16, 2, 3, 4, 5, 10, 60, 100, 1000] {
+            let max_index = match spacing < 5 {
+                true => TICK_LIMIT - spacing as i32,
+                false => (MAX_TICK / spacing as i32) * spacing as i32,
+            };
+            let max_position = max_index / spacing as i32;
+
+            // empty, sparse and dense tickmaps
+            for density in [0u64, 1, 16, 512] {
+                let mut map = Tickmap::default();
+                for _ in 0..density {
+                    let position = (random() % (2 * max_position as u64 + 1)) as i32 - max_position;
+                    let tick = position * spacing as i32;
+                    if !map.get(tick, spacing).unwrap() {
+                        map.flip(true, tick, spacing).unwrap();
+                    }
+                }
+
+                let mut ticks = vec![-max_index, max_index, 0];
+                ticks.extend((0..200).map(|_| {
+                    ((random() % (2 * max_position as u64 + 1)) as i32 - max_position)
+                        * spacing as i32
+                }));
+
+                for tick in ticks {
+                    assert_eq!(
+                        map.next_initialized(tick, spacing).unwrap(),
+                        next_initialized_bytewise(&map, tick, spacing)
+                    );
+                    assert_eq!(
+                        map.prev_initialized(tick, spacing).unwrap(),
+                        prev_initialized_bytewise(&map, tick, spacing)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_extension_flip() {
+        let mut tickmap = Tickmap::default();
+        let mut extension = TickmapExtension::default();
+
+        // ticks next to borders of the parts and at the edges of the price range
+        let ticks = [
+            -MAX_TICK,
+            -TICK_LIMIT - 1,
+            -TICK_LIMIT,
+            -1,
+            0,
+            TICK_LIMIT - 1,
+            TICK_LIMIT,
+            MAX_TICK,
+        ];
+        for tick in ticks {
+            flip_tick(&mut tickmap, Some(&mut extension), true, tick, 1).unwrap();
+        }
+        for tick in ticks {
+            let view = TickmapView::new(&tickmap, Some(&extension));
+            assert!(view.get(tick, 1).unwrap());
+        }
+
+        // ticks covered by the tickmap keep their place in it
+        assert!(tickmap.get(-TICK_LIMIT, 1).unwrap());
+        assert!(tickmap.get(TICK_LIMIT - 1, 1).unwrap());
+        assert!(tickmap.get(0, 1).unwrap());
+        let initialized = |bitmap: &[u8]| bitmap.iter().map(|byte| byte.count_ones()).sum::<u32>();
+        assert_eq!(initialized(&tickmap.bitmap), 4);
+        assert_eq!(initialized(&extension.lower), 2);
+        assert_eq!(initialized(&extension.upper), 2);
+
+        // extension is needed for ticks outside of the tickmap
+        assert!(tickmap.flip(true, TICK_LIMIT, 1).is_err());
+        assert!(flip_tick(&mut tickmap, None, true, -TICK_LIMIT - 1, 1).is_err());
+        // but the price range is still limited
+        assert!(flip_tick(&mut tickmap, Some(&mut extension), true, MAX_TICK + 1, 1).is_err());
+        // already in the requested state
+        assert!(flip_tick(&mut tickmap, Some(&mut extension), true, TICK_LIMIT, 1).is_err());
+
+        flip_tick(&mut tickmap, Some(&mut extension), false, MAX_TICK, 1).unwrap();
+        flip_tick(&mut tickmap, Some(&mut extension), false, 0, 1).unwrap();
+        let view = TickmapView::new(&tickmap, Some(&extension));
+        assert!(!view.get(MAX_TICK, 1).unwrap());
+        assert!(!view.get(0, 1).unwrap());
+    }
+
+    #[test]
+    fn test_extended_search() {
+        // xorshift, deterministic pseudo random numbers
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut random = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for spacing in [1u16, 2, 3, 4] {
+            let max_position = MAX_TICK / spacing as i32;
+            let borders = [
+                -max_position,
+                -TICK_LIMIT - 1,
+                -TICK_LIMIT,
+                TICK_LIMIT - 1,
+                TICK_LIMIT,
+                max_position,
+            ];
+
+            let mut tickmap = Tickmap::default();
+            let mut extension = TickmapExtension::default();
+            for position in borders {
+                flip_tick(
+                    &mut tickmap,
+                    Some(&mut extension),
+                    true,
+                    position * spacing as i32,
+                    spacing,
+                )
+                .unwrap();
+            }
+            for _ in 0..2048 {
+                let position = (random() % (2 * max_position as u64 + 1)) as i32 - max_position;
+                let tick = position * spacing as i32;
+                let view = TickmapView::new(&tickmap, Some(&extension));
+                if !view.get(tick, spacing).unwrap() {
+                    flip_tick(&mut tickmap, Some(&mut extension), true, tick, spacing).unwrap();
+                }
+            }
+
+            let view = TickmapView::new(&tickmap, Some(&extension));
+            // searches checking every tick in the range one by one
+            let next_tick_by_tick = |tick: i32| {
+                let limit = view.search_limit(tick, spacing, true).unwrap();
+                (tick + spacing as i32..=limit)
+                    .step_by(spacing as usize)
+                    .find(|tick| view.get(*tick, spacing).unwrap())
+            };
+            let prev_tick_by_tick = |tick: i32| {
+                let limit = view.search_limit(tick, spacing, false).unwrap();
+                (limit..=tick)
+                    .rev()
+                    .step_by(spacing as usize)
+                    .find(|tick| view.get(*tick, spacing).unwrap())
+            };
+
+            let mut ticks: Vec<i32> = borders
+                .iter()
+                .flat_map(|position| [position - 1, *position, position + 1])
+                .filter(|position| position.abs() <= max_position)
+                .map(|position| position * spacing as i32)
+                .collect();
+            ticks.extend((0..500).map(|_| {
+                ((random() % (2 * max_position as u64 + 1)) as i32 - max_position) * spacing as i32
+            }));
+
+            for tick in ticks {
+                assert_eq!(
+                    view.next_initialized(tick, spacing).unwrap(),
+                    next_tick_by_tick(tick)
+                );
+                assert_eq!(
+                    view.prev_initialized(tick, spacing).unwrap(),
+                    prev_tick_by_tick(tick)
+                );
+            }
+        }
+    }
 }
@@ -1,37 +1,48 @@
 use anchor_lang::__private::ErrorCode;
 use anchor_lang::__private::CLOSED_ACCOUNT_DISCRIMINATOR;
-use std::cell::RefMut;
 use std::convert::TryInto;
 use std::io::Write;
 
-use crate::math::calculate_price_sqrt;
+use crate::log::get_tick_at_sqrt_price;
+use crate::math::{
+    calculate_fee_growth_inside, calculate_price_sqrt, compute_swap_step, get_delta_x, get_delta_y,
+    is_enough_amount_to_push_price, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
 use crate::structs::pool::Pool;
+use crate::structs::position::Position;
+use crate::structs::position_list::PositionList;
 use crate::structs::tick::Tick;
-use crate::structs::tickmap::Tickmap;
-use crate::structs::tickmap::{get_search_limit, MAX_TICK, TICK_LIMIT};
+use crate::structs::tickmap::{TickmapExtension, TickmapView, MAX_TICK};
 use crate::*;
 
-pub fn check_ticks(tick_lower: i32, tick_upper: i32, tick_spacing: u16) -> Result<()> {
+// tick limit depends on the tickmap of the pool, see Pool::tick_limit
+pub fn check_ticks(
+    tick_lower: i32,
+    tick_upper: i32,
+    tick_spacing: u16,
+    tick_limit: i32,
+) -> Result<()> {
     // Check order
     require!(tick_lower < tick_upper, InvalidTickIndex);
 
-    check_tick(tick_lower, tick_spacing)?;
-    check_tick(tick_upper, tick_spacing)?;
+    check_tick(tick_lower, tick_spacing, tick_limit)?;
+    check_tick(tick_upper, tick_spacing, tick_limit)?;
 
     Ok(())
 }
 
-pub fn check_tick(tick_index: i32, tick_spacing: u16) -> Result<()> {
+pub fn check_tick(tick_index: i32, tick_spacing: u16, tick_limit: i32) -> Result<()> {
     // Check order
     require!(
         tick_index.checked_rem(tick_spacing.into()) == Some(0),
         InvalidTickIndex
     );
 
-    let tickmap_index = tick_index.checked_div(tick_spacing.into()).unwrap();
+    // spacing is not zero, as the remainder exists
+    let tickmap_index = tick_index / tick_spacing as i32;
 
-    require!(tickmap_index >= (-TICK_LIMIT), InvalidTickIndex);
-    require!(tickmap_index < TICK_LIMIT, InvalidTickIndex);
+    require!(tickmap_index >= (-tick_limit), InvalidTickIndex);
+    require!(tickmap_index < tick_limit, InvalidTickIndex);
     require!(tick_index >= (-MAX_TICK), InvalidTickIndex);
     require!(tick_index <= MAX_TICK, InvalidTickIndex);
 
@@ -45,17 +56,17 @@ pub fn get_closer_limit(
     x_to_y: bool,
     current_tick: i32,
     tick_spacing: u16,
-    tickmap: &Tickmap,
+    tickmap: &TickmapView,
 ) -> Result<(Price, Option<(i32, bool)>)> {
     let closes_tick_index = if x_to_y {
-        tickmap.prev_initialized(current_tick, tick_spacing)
+        tickmap.prev_initialized(current_tick, tick_spacing)?
     } else {
-        tickmap.next_initialized(current_tick, tick_spacing)
+        tickmap.next_initialized(current_tick, tick_spacing)?
     };
 
     match closes_tick_index {
         Some(index) => {
-            let price = calculate_price_sqrt(index);
+            let price = calculate_price_sqrt(index)?;
             // trunk-ignore(clippy/if_same_then_else)
             if x_to_y && price > sqrt_price_limit {
                 Ok((price, Some((index, true))))
@@ -66,10 +77,14 @@ pub fn get_closer_limit(
             }
         }
         None => {
-            let index = get_search_limit(current_tick, tick_spacing, !x_to_y);
-            let price = calculate_price_sqrt(index);
+            let index = tickmap.search_limit(current_tick, tick_spacing, !x_to_y)?;
+            let price = calculate_price_sqrt(index)?;
 
-            require!(current_tick != index, LimitReached);
+            // below the lowest tick aligned to spacing the limit would end up above the current tick
+            require!(
+                (x_to_y && index < current_tick) || (!x_to_y && index > current_tick),
+                LimitReached
+            );
 
             // trunk-ignore(clippy/if_same_then_else)
             if x_to_y && price > sqrt_price_limit {
@@ -83,7 +98,63 @@ pub fn get_closer_limit(
     }
 }
 
-pub fn cross_tick(tick: &mut RefMut<Tick>, pool: &mut Pool, current_timestamp: u64) -> Result<()> {
+// Once the pool has a tickmap extension it has to be passed among remaining accounts
+pub fn load_tickmap_extension<'info>(
+    pool: &Pool,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<Option<AccountLoader<'info, TickmapExtension>>> {
+    if !pool.has_tickmap_extension() {
+        return Ok(None);
+    }
+
+    let account = remaining_accounts
+        .iter()
+        .find(|account| *account.key == pool.tickmap_extension)
+        .ok_or(crate::ErrorCode::InvalidTickmapExtension)?;
+
+    Ok(Some(AccountLoader::try_from(account)?))
+}
+
+// Either the owner signs or the operator set on its position list does
+// the operator signs as a remaining account, along with the position list if the instruction doesn't take it
+// accepted only by claim_fee, compound_fees and remove_position, proceeds always go to accounts of the owner
+pub fn check_position_authority<'info>(
+    owner: &AccountInfo<'info>,
+    position_list: Option<&AccountLoader<'info, PositionList>>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if owner.is_signer {
+        return Ok(());
+    }
+
+    let operator = match position_list {
+        Some(position_list) => position_list.load()?.operator,
+        None => {
+            let (position_list_address, _) = Pubkey::find_program_address(
+                &[b"positionlistv1", owner.key.as_ref()],
+                &crate::ID,
+            );
+            let account = remaining_accounts
+                .iter()
+                .find(|account| *account.key == position_list_address)
+                .ok_or(crate::ErrorCode::InvalidSigner)?;
+            let position_list = AccountLoader::<PositionList>::try_from(account)?;
+            let operator = position_list.load()?.operator;
+            operator
+        }
+    };
+
+    require!(
+        operator != Pubkey::default()
+            && remaining_accounts
+                .iter()
+                .any(|account| account.is_signer && *account.key == operator),
+        InvalidSigner
+    );
+    Ok(())
+}
+
+pub fn cross_tick(tick: &mut Tick, pool: &mut Pool, current_timestamp: u64) -> Result<()> {
     tick.fee_growth_outside_x = pool
         .fee_growth_global_x
         .unchecked_sub(tick.fee_growth_outside_x);
@@ -91,11 +162,13 @@ pub fn cross_tick(tick: &mut RefMut<Tick>, pool: &mut Pool, current_timestamp: u
         .fee_growth_global_y
         .unchecked_sub(tick.fee_growth_outside_y);
 
-    let seconds_passed: u64 = current_timestamp.checked_sub(pool.start_timestamp).unwrap();
+    let seconds_passed: u64 = current_timestamp
+        .checked_sub(pool.start_timestamp)
+        .ok_or(crate::ErrorCode::NegativeTime)?;
     tick.seconds_outside = seconds_passed - tick.seconds_outside;
 
     if !pool.liquidity.is_zero() {
-        pool.update_seconds_per_liquidity_global(current_timestamp);
+        pool.update_seconds_per_liquidity_global(current_timestamp)?;
     } else {
         pool.last_timestamp = current_timestamp;
     }
@@ -103,6 +176,17 @@ pub fn cross_tick(tick: &mut RefMut<Tick>, pool: &mut Pool, current_timestamp: u
         .seconds_per_liquidity_global
         .unchecked_sub(tick.seconds_per_liquidity_outside);
 
+    pool.update_rewards_growth_global(current_timestamp)?;
+    let reward_growths_global = pool.reward_growths_global();
+    let mut reward_growths_outside = tick.reward_growths_outside;
+    for (outside, global) in reward_growths_outside
+        .iter_mut()
+        .zip(reward_growths_global.iter())
+    {
+        *outside = global.unchecked_sub(*outside);
+    }
+    tick.reward_growths_outside = reward_growths_outside;
+
     // When going to higher tick net_liquidity should be added and for going lower subtracted
     if (pool.current_tick_index >= tick.index) ^ tick.sign {
         // trunk-ignore(clippy/assign_op_pattern)
@@ -115,12 +199,199 @@ pub fn cross_tick(tick: &mut RefMut<Tick>, pool: &mut Pool, current_timestamp: u
     Ok(())
 }
 
-pub fn get_current_timestamp() -> u64 {
-    Clock::get().unwrap().unix_timestamp.try_into().unwrap()
+// Ticks a swap can cross, the instruction finds them among its remaining accounts
+pub trait SwapTicks {
+    type Handle;
+
+    // None if the tick was not provided
+    fn find(&mut self, index: i32) -> Result<Option<Self::Handle>>;
+    fn cross(&mut self, tick: Self::Handle, pool: &mut Pool, current_timestamp: u64) -> Result<()>;
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SwapTotals {
+    pub amount_in: TokenAmount, // including the fee
+    pub amount_out: TokenAmount,
+    pub amount_referral: TokenAmount, // part of the amount in
+    pub unfilled_amount: TokenAmount,
+}
+
+// Moves the price of the pool until the amount is swapped, crossing initialized ticks on the way
+// tokens are not transferred, the caller does it with the returned totals
+#[allow(clippy::too_many_arguments)]
+pub fn swap_loop<T: SwapTicks>(
+    pool: &mut Pool,
+    tickmap: &TickmapView,
+    ticks: &mut T,
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool, // whether amount specifies input or output
+    sqrt_price_limit: Price,
+    allow_partial_fill: bool, // stop at price limit or at the first missing tick instead of failing
+    ref_percentage: FixedPoint, // part of the fee going to the referral
+    current_timestamp: u64,
+) -> Result<SwapTotals> {
+    // limit is on the right side of price
+    if x_to_y {
+        require!(
+            { pool.sqrt_price } > sqrt_price_limit
+                && sqrt_price_limit <= Price::new(MAX_SQRT_PRICE),
+            WrongLimit
+        );
+    } else {
+        require!(
+            { pool.sqrt_price } < sqrt_price_limit
+                && sqrt_price_limit >= Price::new(MIN_SQRT_PRICE),
+            WrongLimit
+        );
+    }
+
+    let mut remaining_amount = amount;
+
+    let mut total_amount_in = TokenAmount(0);
+    let mut total_amount_out = TokenAmount(0);
+    let mut total_amount_referral = TokenAmount(0);
+    let mut unfilled_amount = TokenAmount(0);
+
+    while !remaining_amount.is_zero() {
+        let (swap_limit, limiting_tick) = get_closer_limit(
+            sqrt_price_limit,
+            x_to_y,
+            pool.current_tick_index,
+            pool.tick_spacing,
+            tickmap,
+        )?;
+
+        let result = compute_swap_step(
+            pool.sqrt_price,
+            swap_limit,
+            pool.liquidity,
+            remaining_amount,
+            by_amount_in,
+            pool.fee,
+        )?;
+        // make remaining amount smaller
+        if by_amount_in {
+            remaining_amount = remaining_amount
+                .checked_sub(result.amount_in)
+                .and_then(|amount| amount.checked_sub(result.fee_amount))
+                .map_err(|_| crate::ErrorCode::MathOverflow)?;
+        } else {
+            remaining_amount = remaining_amount
+                .checked_sub(result.amount_out)
+                .map_err(|_| crate::ErrorCode::MathOverflow)?;
+        }
+
+        let referral_fee = pool.add_fee(result.fee_amount, ref_percentage, x_to_y)?;
+        total_amount_referral = total_amount_referral
+            .checked_add(referral_fee)
+            .map_err(|_| crate::ErrorCode::MathOverflow)?;
+
+        pool.sqrt_price = result.next_price_sqrt;
+
+        total_amount_in = total_amount_in
+            .checked_add(result.amount_in)
+            .and_then(|amount| amount.checked_add(result.fee_amount))
+            .map_err(|_| crate::ErrorCode::MathOverflow)?;
+        total_amount_out = total_amount_out
+            .checked_add(result.amount_out)
+            .map_err(|_| crate::ErrorCode::MathOverflow)?;
+
+        // Fail if price would go over swap limit
+        if { pool.sqrt_price } == sqrt_price_limit && !remaining_amount.is_zero() {
+            require!(allow_partial_fill, PriceLimitReached);
+
+            // stop at the limit and leave the rest unfilled
+            unfilled_amount = remaining_amount;
+            remaining_amount = TokenAmount(0);
+        }
+
+        // crossing tick
+        if let (true, Some((tick_index, initialized))) =
+            (result.next_price_sqrt == swap_limit, limiting_tick)
+        {
+            let is_enough_amount_to_cross = is_enough_amount_to_push_price(
+                remaining_amount,
+                result.next_price_sqrt,
+                pool.liquidity,
+                pool.fee,
+                by_amount_in,
+                x_to_y,
+            )?;
+
+            if initialized {
+                let tick = match ticks.find(tick_index)? {
+                    Some(tick) => tick,
+                    None => {
+                        require!(allow_partial_fill, TickNotFound);
+
+                        // stop on the tick without crossing it (current tick stays below the price)
+                        // the price limit may have already moved the rest to unfilled_amount
+                        if !remaining_amount.is_zero() {
+                            unfilled_amount = remaining_amount;
+                        }
+                        pool.current_tick_index = match x_to_y {
+                            true => tick_index,
+                            false => tick_index
+                                .checked_sub(pool.tick_spacing as i32)
+                                .ok_or(crate::ErrorCode::InvalidTickIndex)?,
+                        };
+                        break;
+                    }
+                };
+
+                // crossing tick
+                if !x_to_y || is_enough_amount_to_cross {
+                    ticks.cross(tick, pool, current_timestamp)?;
+                } else if !remaining_amount.is_zero() {
+                    if by_amount_in {
+                        pool.add_fee(remaining_amount, FixedPoint::from_integer(0), x_to_y)?;
+                        total_amount_in = total_amount_in
+                            .checked_add(remaining_amount)
+                            .map_err(|_| crate::ErrorCode::MathOverflow)?;
+                    }
+                    remaining_amount = TokenAmount(0);
+                }
+            }
+            // set tick to limit (below if price is going down, because current tick should always be below price)
+            pool.current_tick_index = if x_to_y && is_enough_amount_to_cross {
+                tick_index
+                    .checked_sub(pool.tick_spacing as i32)
+                    .ok_or(crate::ErrorCode::InvalidTickIndex)?
+            } else {
+                tick_index
+            };
+        } else {
+            require!(
+                pool.current_tick_index
+                    .checked_rem(pool.tick_spacing.into())
+                    == Some(0),
+                InvalidTickIndex
+            );
+            pool.current_tick_index =
+                get_tick_at_sqrt_price(result.next_price_sqrt, pool.tick_spacing)?;
+        }
+    }
+
+    require!(!total_amount_out.is_zero(), NoGainSwap);
+
+    Ok(SwapTotals {
+        amount_in: total_amount_in,
+        amount_out: total_amount_out,
+        amount_referral: total_amount_referral,
+        unfilled_amount,
+    })
+}
+
+pub fn get_current_timestamp() -> Result<u64> {
+    Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .map_err(|_| crate::ErrorCode::NegativeTime.into())
 }
 
-pub fn get_current_slot() -> u64 {
-    Clock::get().unwrap().slot
+pub fn get_current_slot() -> Result<u64> {
+    Ok(Clock::get()?.slot)
 }
 
 pub fn close<'info>(
@@ -129,8 +400,9 @@ pub fn close<'info>(
 ) -> ProgramResult {
     // Transfer tokens from the account to the sol_destination.
     let dest_starting_lamports = sol_destination.lamports();
-    **sol_destination.lamports.borrow_mut() =
-        dest_starting_lamports.checked_add(info.lamports()).unwrap();
+    **sol_destination.lamports.borrow_mut() = dest_starting_lamports
+        .checked_add(info.lamports())
+        .ok_or(crate::ErrorCode::MathOverflow)?;
     **info.lamports.borrow_mut() = 0;
 
     // Mark the account discriminator as closed.
@@ -143,16 +415,250 @@ pub fn close<'info>(
     Ok(())
 }
 
+// Pool invariants checked against a page of its ticks and positions
+// pages are merged into the report of the whole pool, as all accounts of a pool don't fit into a transaction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub struct PoolReport {
+    pub positions: u32,
+    pub ticks: u32,
+    pub initialized_ticks: u32, // bits set in the checked range of the tickmap
+    // principal, tokens owed and uncollected fees of the positions
+    pub owed_x: u128,
+    pub owed_y: u128,
+    pub position_liquidity: u128,
+    pub liquidity_in_range: u128, // liquidity of positions containing the current price
+    pub tick_liquidity_gross: u128,
+    pub tick_liquidity_added: u128, // liquidity change of ticks with a positive sign
+    pub tick_liquidity_removed: u128,
+    pub tickmap_valid: bool, // initialized bits of the checked range are exactly the ticks
+    // copied from the pool and its reserves
+    pub pool_liquidity: u128,
+    pub protocol_fee_x: u64,
+    pub protocol_fee_y: u64,
+    pub reserve_x: u64,
+    pub reserve_y: u64,
+}
+
+impl PoolReport {
+    // pool values are taken from the later page
+    pub fn merge(&self, page: &PoolReport) -> Result<PoolReport> {
+        let add = |a: u128, b: u128| a.checked_add(b).ok_or(crate::ErrorCode::MathOverflow);
+        let add_count = |a: u32, b: u32| a.checked_add(b).ok_or(crate::ErrorCode::MathOverflow);
+
+        Ok(PoolReport {
+            positions: add_count(self.positions, page.positions)?,
+            ticks: add_count(self.ticks, page.ticks)?,
+            initialized_ticks: add_count(self.initialized_ticks, page.initialized_ticks)?,
+            owed_x: add(self.owed_x, page.owed_x)?,
+            owed_y: add(self.owed_y, page.owed_y)?,
+            position_liquidity: add(self.position_liquidity, page.position_liquidity)?,
+            liquidity_in_range: add(self.liquidity_in_range, page.liquidity_in_range)?,
+            tick_liquidity_gross: add(self.tick_liquidity_gross, page.tick_liquidity_gross)?,
+            tick_liquidity_added: add(self.tick_liquidity_added, page.tick_liquidity_added)?,
+            tick_liquidity_removed: add(self.tick_liquidity_removed, page.tick_liquidity_removed)?,
+            tickmap_valid: self.tickmap_valid && page.tickmap_valid,
+            ..*page
+        })
+    }
+
+    pub fn is_solvent(&self) -> bool {
+        // saturated sum can't be covered by a u64 reserve either
+        let covers = |owed: u128, protocol_fee: u64, reserve: u64| {
+            owed.saturating_add(protocol_fee as u128) <= reserve as u128
+        };
+        covers(self.owed_x, self.protocol_fee_x, self.reserve_x)
+            && covers(self.owed_y, self.protocol_fee_y, self.reserve_y)
+    }
+
+    // every position adds its liquidity to both of its ticks
+    pub fn ticks_valid(&self) -> bool {
+        self.position_liquidity.checked_mul(2) == Some(self.tick_liquidity_gross)
+            && self.tick_liquidity_added == self.tick_liquidity_removed
+    }
+
+    pub fn liquidity_valid(&self) -> bool {
+        self.liquidity_in_range == self.pool_liquidity
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.is_solvent() && self.tickmap_valid && self.ticks_valid() && self.liquidity_valid()
+    }
+}
+
+// ticks are the initialized ones between lower and upper tick, both inclusive, in ascending order
+// positions come with their lower and upper tick, reserves are left empty
+pub fn calculate_pool_report(
+    pool: &Pool,
+    tickmap: &TickmapView,
+    lower_tick: i32,
+    upper_tick: i32,
+    ticks: &[Tick],
+    positions: &[(Position, Tick, Tick)],
+) -> Result<PoolReport> {
+    let tick_spacing = pool.tick_spacing;
+    let current_tick_index = pool.current_tick_index;
+    let overflow = || crate::ErrorCode::MathOverflow;
+
+    let initialized_ticks = count_initialized(tickmap, tick_spacing, lower_tick, upper_tick)?;
+    let tickmap_valid = ticks.len() == initialized_ticks as usize
+        && ticks.windows(2).all(|pair| pair[0].index < pair[1].index)
+        && ticks.iter().all(|tick| {
+            (lower_tick..=upper_tick).contains(&{ tick.index })
+                && matches!(tickmap.get(tick.index, tick_spacing), Ok(true))
+        });
+
+    let mut tick_liquidity_gross: u128 = 0;
+    let mut tick_liquidity_added: u128 = 0;
+    let mut tick_liquidity_removed: u128 = 0;
+    for tick in ticks {
+        let liquidity_change = { tick.liquidity_change }.v;
+        tick_liquidity_gross = tick_liquidity_gross
+            .checked_add({ tick.liquidity_gross }.v)
+            .ok_or_else(overflow)?;
+        match tick.sign {
+            true => {
+                tick_liquidity_added = tick_liquidity_added
+                    .checked_add(liquidity_change)
+                    .ok_or_else(overflow)?
+            }
+            false => {
+                tick_liquidity_removed = tick_liquidity_removed
+                    .checked_add(liquidity_change)
+                    .ok_or_else(overflow)?
+            }
+        }
+    }
+
+    let mut owed_x: u128 = 0;
+    let mut owed_y: u128 = 0;
+    let mut position_liquidity: u128 = 0;
+    let mut liquidity_in_range: u128 = 0;
+    for (position, lower_tick, upper_tick) in positions {
+        let lower_tick_index = position.lower_tick_index;
+        let upper_tick_index = position.upper_tick_index;
+        require!(
+            lower_tick.index == lower_tick_index && upper_tick.index == upper_tick_index,
+            WrongTick
+        );
+        let liquidity = position.liquidity;
+        position_liquidity = position_liquidity
+            .checked_add(liquidity.v)
+            .ok_or_else(overflow)?;
+
+        // amounts the position would get on removal
+        let lower_sqrt_price = calculate_price_sqrt(lower_tick_index)?;
+        let upper_sqrt_price = calculate_price_sqrt(upper_tick_index)?;
+        let (amount_x, amount_y) = if current_tick_index < lower_tick_index {
+            (
+                get_delta_x(lower_sqrt_price, upper_sqrt_price, liquidity, false),
+                Some(TokenAmount(0)),
+            )
+        } else if current_tick_index < upper_tick_index {
+            liquidity_in_range = liquidity_in_range
+                .checked_add(liquidity.v)
+                .ok_or_else(overflow)?;
+            (
+                get_delta_x(pool.sqrt_price, upper_sqrt_price, liquidity, false),
+                get_delta_y(lower_sqrt_price, pool.sqrt_price, liquidity, false),
+            )
+        } else {
+            (
+                Some(TokenAmount(0)),
+                get_delta_y(lower_sqrt_price, upper_sqrt_price, liquidity, false),
+            )
+        };
+        let amount_x = amount_x.ok_or_else(overflow)?;
+        let amount_y = amount_y.ok_or_else(overflow)?;
+
+        let (fee_growth_inside_x, fee_growth_inside_y) = calculate_fee_growth_inside(
+            *lower_tick,
+            *upper_tick,
+            current_tick_index,
+            pool.fee_growth_global_x,
+            pool.fee_growth_global_y,
+        );
+        let tokens_owed_x = { position.tokens_owed_x }
+            .checked_add(
+                fee_growth_inside_x
+                    .unchecked_sub(position.fee_growth_inside_x)
+                    .to_fee(liquidity)?,
+            )
+            .map_err(|_| overflow())?;
+        let tokens_owed_y = { position.tokens_owed_y }
+            .checked_add(
+                fee_growth_inside_y
+                    .unchecked_sub(position.fee_growth_inside_y)
+                    .to_fee(liquidity)?,
+            )
+            .map_err(|_| overflow())?;
+
+        owed_x = owed_x
+            .checked_add(amount_x.0 as u128)
+            .and_then(|owed| owed.checked_add(TokenAmount::from_decimal(tokens_owed_x).0 as u128))
+            .ok_or_else(overflow)?;
+        owed_y = owed_y
+            .checked_add(amount_y.0 as u128)
+            .and_then(|owed| owed.checked_add(TokenAmount::from_decimal(tokens_owed_y).0 as u128))
+            .ok_or_else(overflow)?;
+    }
+
+    Ok(PoolReport {
+        positions: positions.len() as u32,
+        ticks: ticks.len() as u32,
+        initialized_ticks,
+        owed_x,
+        owed_y,
+        position_liquidity,
+        liquidity_in_range,
+        tick_liquidity_gross,
+        tick_liquidity_added,
+        tick_liquidity_removed,
+        tickmap_valid,
+        pool_liquidity: pool.liquidity.v,
+        protocol_fee_x: pool.fee_protocol_token_x,
+        protocol_fee_y: pool.fee_protocol_token_y,
+        reserve_x: 0,
+        reserve_y: 0,
+    })
+}
+
+// bits set between lower and upper tick, both inclusive, the range is clamped to the tickmap
+fn count_initialized(
+    tickmap: &TickmapView,
+    tick_spacing: u16,
+    lower_tick: i32,
+    upper_tick: i32,
+) -> Result<u32> {
+    require!(tick_spacing != 0, InvalidTickSpacing);
+    let spacing = tick_spacing as i64;
+    let tick_limit = tickmap.tick_limit() as i64;
+    let from = (lower_tick as i64 + spacing - 1)
+        .div_euclid(spacing)
+        .max(-tick_limit);
+    let to = (upper_tick as i64).div_euclid(spacing).min(tick_limit - 1);
+
+    let mut initialized = 0;
+    for step in from..=to {
+        // between lower and upper tick, so it fits
+        if tickmap.get((step * spacing) as i32, tick_spacing)? {
+            initialized += 1;
+        }
+    }
+    Ok(initialized)
+}
+
 #[cfg(test)]
 mod test {
     use std::cell::RefCell;
 
     use super::*;
+    use crate::structs::tickmap::{flip_tick, Tickmap, TICK_LIMIT};
 
     #[test]
     fn test_get_closer_limit() -> Result<()> {
-        let tickmap = &mut Tickmap::default();
-        tickmap.flip(true, 0, 1);
+        let mut map = Tickmap::default();
+        map.flip(true, 0, 1).unwrap();
+        let tickmap = &TickmapView::new(&map, None);
         // tick limit closer
         {
             let (result, from_tick) =
@@ -186,6 +692,40 @@ mod test {
             assert_eq!(result, expected);
             assert_eq!(from_tick, None);
         }
+        // current tick below the lowest tick aligned to spacing
+        {
+            let map = Tickmap::default();
+            let tickmap = &TickmapView::new(&map, None);
+            let result = get_closer_limit(Price::new(MIN_SQRT_PRICE), true, -221820, 10, tickmap);
+            assert!(result.is_err());
+            let result = get_closer_limit(Price::new(MAX_SQRT_PRICE), false, 221810, 10, tickmap);
+            assert!(result.is_err());
+        }
+        // ticks outside of the tickmap are found in the extension
+        {
+            let mut map = Tickmap::default();
+            let mut extension = TickmapExtension::default();
+            let tick = -TICK_LIMIT - 100;
+            flip_tick(&mut map, Some(&mut extension), true, tick, 1)?;
+            let tickmap = &TickmapView::new(&map, Some(&extension));
+
+            let (result, from_tick) =
+                get_closer_limit(Price::new(MIN_SQRT_PRICE), true, tick + 50, 1, tickmap)?;
+            assert_eq!(result, calculate_price_sqrt(tick)?);
+            assert_eq!(from_tick, Some((tick, true)));
+
+            // without the extension the search ends at the end of the tickmap
+            let tickmap = &TickmapView::new(&map, None);
+            let (result, from_tick) = get_closer_limit(
+                Price::new(MIN_SQRT_PRICE),
+                true,
+                -TICK_LIMIT + 50,
+                1,
+                tickmap,
+            )?;
+            assert_eq!(result, calculate_price_sqrt(-TICK_LIMIT + 1)?);
+            assert_eq!(from_tick, Some((-TICK_LIMIT + 1, false)));
+        }
         Ok(())
     }
 
@@ -219,6 +759,7 @@ mod test {
                 start_timestamp: 4,
                 seconds_per_liquidity_global: FixedPoint::new(4611686018434500000000000),
                 current_tick_index: 7,
+                rewards_last_timestamp: 18446744073709,
                 ..Default::default()
             };
             let result_tick = Tick {
@@ -267,6 +808,7 @@ mod test {
                 start_timestamp: 34,
                 seconds_per_liquidity_global: FixedPoint::new(32),
                 current_tick_index: 4,
+                rewards_last_timestamp: 1844674407370,
                 ..Default::default()
             };
             let result_tick = Tick {
@@ -314,6 +856,7 @@ mod test {
                 start_timestamp: 15,
                 seconds_per_liquidity_global: FixedPoint::new(131762457669353142857142857142879),
                 current_tick_index: 9,
+                rewards_last_timestamp: 1844674407370953,
                 ..Default::default()
             };
             let result_tick = Tick {
@@ -361,6 +904,7 @@ mod test {
                 start_timestamp: 15,
                 seconds_per_liquidity_global: FixedPoint::new(131762457669352642857142857143211),
                 current_tick_index: 9,
+                rewards_last_timestamp: 1844674407370953,
                 ..Default::default()
             };
             let result_tick = Tick {
@@ -381,4 +925,174 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_calculate_pool_report() {
+        let pool_liquidity = Liquidity::from_integer(100);
+        let pool = Pool {
+            tick_spacing: 1,
+            current_tick_index: 0,
+            sqrt_price: calculate_price_sqrt(0).unwrap(),
+            liquidity: pool_liquidity,
+            fee_growth_global_x: FeeGrowth::from_fee(pool_liquidity, TokenAmount(50)).unwrap(),
+            fee_protocol_token_x: 3,
+            fee_protocol_token_y: 4,
+            ..Default::default()
+        };
+        let tick = |index: i32, liquidity: u128, sign: bool| Tick {
+            index,
+            sign,
+            liquidity_change: Liquidity::from_integer(liquidity),
+            liquidity_gross: Liquidity::from_integer(liquidity),
+            ..Default::default()
+        };
+        let ticks = [
+            tick(-10, 100, true),
+            tick(5, 200, true),
+            tick(10, 100, false),
+            tick(20, 200, false),
+        ];
+        let mut tickmap = Tickmap::default();
+        for tick in ticks {
+            tickmap.flip(true, tick.index, 1).unwrap();
+        }
+        // in range, with fees accrued
+        let first_position = (
+            Position {
+                lower_tick_index: -10,
+                upper_tick_index: 10,
+                liquidity: Liquidity::from_integer(100),
+                tokens_owed_x: FixedPoint::from_integer(7),
+                ..Default::default()
+            },
+            ticks[0],
+            ticks[2],
+        );
+        // above current tick
+        let second_position = (
+            Position {
+                lower_tick_index: 5,
+                upper_tick_index: 20,
+                liquidity: Liquidity::from_integer(200),
+                ..Default::default()
+            },
+            ticks[1],
+            ticks[3],
+        );
+        let positions = [first_position, second_position];
+
+        let expected_x = 50
+            + 7
+            + get_delta_x(
+                calculate_price_sqrt(0).unwrap(),
+                calculate_price_sqrt(10).unwrap(),
+                Liquidity::from_integer(100),
+                false,
+            )
+            .unwrap()
+            .0 as u128
+            + get_delta_x(
+                calculate_price_sqrt(5).unwrap(),
+                calculate_price_sqrt(20).unwrap(),
+                Liquidity::from_integer(200),
+                false,
+            )
+            .unwrap()
+            .0 as u128;
+        let expected_y = get_delta_y(
+            calculate_price_sqrt(-10).unwrap(),
+            calculate_price_sqrt(0).unwrap(),
+            Liquidity::from_integer(100),
+            false,
+        )
+        .unwrap()
+        .0 as u128;
+
+        let report = |tickmap: &Tickmap, ticks: &[Tick], positions: &[(Position, Tick, Tick)]| {
+            let tickmap = TickmapView::new(tickmap, None);
+            calculate_pool_report(&pool, &tickmap, -MAX_TICK, MAX_TICK, ticks, positions)
+        };
+
+        // consistent pool
+        {
+            let mut report = report(&tickmap, &ticks, &positions).unwrap();
+            assert_eq!(report.positions, 2);
+            assert_eq!(report.ticks, 4);
+            assert_eq!(report.initialized_ticks, 4);
+            assert_eq!(report.owed_x, expected_x);
+            assert_eq!(report.owed_y, expected_y);
+            assert!(report.tickmap_valid);
+            assert!(report.ticks_valid());
+            assert!(report.liquidity_valid());
+
+            report.reserve_x = expected_x as u64 + 3;
+            report.reserve_y = expected_y as u64 + 4;
+            assert!(report.is_valid());
+
+            report.reserve_y -= 1;
+            assert!(!report.is_solvent());
+        }
+        // split into pages
+        {
+            let tickmap_view = TickmapView::new(&tickmap, None);
+            let first_page =
+                calculate_pool_report(&pool, &tickmap_view, -MAX_TICK, 5, &ticks[..2], &[])
+                    .unwrap();
+            let second_page =
+                calculate_pool_report(&pool, &tickmap_view, 6, MAX_TICK, &ticks[2..], &positions)
+                    .unwrap();
+            assert!(first_page.tickmap_valid);
+            assert!(second_page.tickmap_valid);
+            assert!(!first_page.ticks_valid());
+
+            let merged = first_page.merge(&second_page).unwrap();
+            assert_eq!(merged, report(&tickmap, &ticks, &positions).unwrap());
+        }
+        // missing position
+        {
+            let report = report(&tickmap, &ticks, &[first_position]).unwrap();
+            assert!(report.tickmap_valid);
+            assert!(!report.ticks_valid());
+            assert!(report.liquidity_valid());
+        }
+        // missing tick
+        {
+            let report = report(&tickmap, &ticks[..3], &positions).unwrap();
+            assert!(!report.tickmap_valid);
+            assert!(!report.ticks_valid());
+        }
+        // tick out of order
+        {
+            let ticks = [ticks[1], ticks[0], ticks[2], ticks[3]];
+            let report = report(&tickmap, &ticks, &positions).unwrap();
+            assert!(!report.tickmap_valid);
+        }
+        // bit without a tick
+        {
+            let mut tickmap = tickmap;
+            tickmap.flip(true, 30, 1).unwrap();
+            let report = report(&tickmap, &ticks, &positions).unwrap();
+            assert_eq!(report.initialized_ticks, 5);
+            assert!(!report.tickmap_valid);
+            assert!(report.ticks_valid());
+        }
+        // position with a wrong tick
+        {
+            let (position, lower_tick, _) = first_position;
+            let result = report(&tickmap, &ticks, &[(position, lower_tick, ticks[3])]);
+            assert!(result.is_err());
+        }
+        // pool liquidity out of sync
+        {
+            let pool = Pool {
+                liquidity: Liquidity::from_integer(300),
+                ..pool
+            };
+            let tickmap = TickmapView::new(&tickmap, None);
+            let report =
+                calculate_pool_report(&pool, &tickmap, -MAX_TICK, MAX_TICK, &ticks, &positions)
+                    .unwrap();
+            assert!(!report.liquidity_valid());
+        }
+    }
 }
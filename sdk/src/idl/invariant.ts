@@ -149,8 +149,153 @@ export type Invariant = {
       ],
       "args": [
         {
-          "name": "initTick",
+          "name": "initSqrtPrice",
+          "type": {
+            "defined": "Price"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createPoolWithPosition",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeTier",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenYReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "initSqrtPrice",
+          "type": {
+            "defined": "Price"
+          }
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
           "type": "i32"
+        },
+        {
+          "name": "positionIndex",
+          "type": "u32"
+        },
+        {
+          "name": "liquidityDelta",
+          "type": {
+            "defined": "Liquidity"
+          }
+        },
+        {
+          "name": "slippageLimitLower",
+          "type": {
+            "defined": "Price"
+          }
+        },
+        {
+          "name": "slippageLimitUpper",
+          "type": {
+            "defined": "Price"
+          }
         }
       ]
     },
@@ -227,6 +372,79 @@ export type Invariant = {
         }
       ]
     },
+    {
+      "name": "swapWithPartialFill",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "xToY",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "byAmountIn",
+          "type": "bool"
+        },
+        {
+          "name": "sqrtPriceLimit",
+          "type": "u128"
+        }
+      ]
+    },
     {
       "name": "initializeOracle",
       "accounts": [
@@ -319,6 +537,47 @@ export type Invariant = {
         }
       ]
     },
+    {
+      "name": "createTickmapExtension",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmapExtension",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "createPositionList",
       "accounts": [
@@ -524,7 +783,7 @@ export type Invariant = {
         {
           "name": "owner",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "tokenX",
@@ -583,7 +842,28 @@ export type Invariant = {
       ]
     },
     {
-      "name": "transferPositionOwnership",
+      "name": "setPositionOperator",
+      "accounts": [
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "operator",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "transferPositionOwnership",
       "accounts": [
         {
           "name": "ownerList",
@@ -669,7 +949,7 @@ export type Invariant = {
         {
           "name": "owner",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "tokenX",
@@ -728,27 +1008,32 @@ export type Invariant = {
       ]
     },
     {
-      "name": "updateSecondsPerLiquidity",
+      "name": "claimFeesBatch",
       "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "lowerTick",
+          "name": "positionList",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "upperTick",
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "position",
-          "isMut": true,
-          "isSigner": false
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
         },
         {
           "name": "tokenX",
@@ -761,43 +1046,94 @@ export type Invariant = {
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "compoundFees",
+      "accounts": [
+        {
+          "name": "pool",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "lowerTickIndex",
-          "type": "i32"
+          "name": "index",
+          "type": "u32"
         },
         {
-          "name": "upperTickIndex",
+          "name": "lowerTickIndex",
           "type": "i32"
         },
         {
-          "name": "index",
+          "name": "upperTickIndex",
           "type": "i32"
         }
       ]
     },
     {
-      "name": "withdrawProtocolFee",
+      "name": "claimRewards",
       "accounts": [
         {
           "name": "state",
@@ -810,39 +1146,54 @@ export type Invariant = {
           "isSigner": false
         },
         {
-          "name": "tokenX",
-          "isMut": false,
+          "name": "position",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenY",
+          "name": "positionList",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountX",
+          "name": "lowerTick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "accountY",
+          "name": "upperTick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveX",
-          "isMut": true,
+          "name": "owner",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveY",
-          "isMut": true,
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "tokenY",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
+        },
+        {
+          "name": "accountReward",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
         },
         {
           "name": "programAuthority",
@@ -855,10 +1206,27 @@ export type Invariant = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "rewardIndex",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "changeProtocolFee",
+      "name": "initializeReward",
       "accounts": [
         {
           "name": "state",
@@ -881,73 +1249,685 @@ export type Invariant = {
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "rewardMint",
           "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "programAuthority",
+          "name": "adminAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "protocolFee",
-          "type": {
-            "defined": "FixedPoint"
-          }
+          "name": "rewardIndex",
+          "type": "u8"
+        },
+        {
+          "name": "emissionsPerSecond",
+          "type": "u64"
+        },
+        {
+          "name": "endTimestamp",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "changeFeeReceiver",
+      "name": "migrateState",
       "accounts": [
         {
-          "name": "state",
-          "isMut": false,
+          "name": "account",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "pool",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenX",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateFeeTier",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "tokenY",
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migratePool",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "admin",
-          "isMut": false,
+          "name": "payer",
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "feeReceiver",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "feeTier",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "fee",
-            "type": {
-              "defined": "FixedPoint"
-            }
+      "name": "migrateTick",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migratePosition",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migratePositionList",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateOracle",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateSecondsPerLiquidity",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "index",
+          "type": "i32"
+        }
+      ]
+    },
+    {
+      "name": "withdrawProtocolFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "changeProtocolFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "protocolFee",
+          "type": {
+            "defined": "FixedPoint"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeDefaultProtocolFee",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feeTier",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "protocolFee",
+          "type": {
+            "defined": "FixedPoint"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeFeeReceiver",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "feeReceiver",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "propose",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "target",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "feeReceiver",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "action",
+          "type": "u8"
+        },
+        {
+          "name": "value",
+          "type": "u128"
+        },
+        {
+          "name": "tickSpacing",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "executeProposal",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "target",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeCreateFeeTier",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeTier",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelProposal",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "target",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "proposer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "verifyPool",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXReserve",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYReserve",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lowerTick",
+          "type": "i32"
+        },
+        {
+          "name": "upperTick",
+          "type": "i32"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "feeTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "defaultProtocolFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                2
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeTierV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
           },
           {
             "name": "tickSpacing",
@@ -961,7 +1941,51 @@ export type Invariant = {
       }
     },
     {
-      "name": "oracle",
+      "name": "oracle",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "data",
+            "type": {
+              "array": [
+                {
+                  "defined": "Record"
+                },
+                256
+              ]
+            }
+          },
+          {
+            "name": "head",
+            "type": "u16"
+          },
+          {
+            "name": "amount",
+            "type": "u16"
+          },
+          {
+            "name": "size",
+            "type": "u16"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "oracleV0",
       "type": {
         "kind": "struct",
         "fields": [
@@ -985,118 +2009,420 @@ export type Invariant = {
             "type": "u16"
           },
           {
-            "name": "size",
-            "type": "u16"
+            "name": "size",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenX",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenY",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenXReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenYReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "positionIterator",
+            "type": "u128"
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "protocolFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "sqrtPrice",
+            "type": {
+              "defined": "Price"
+            }
+          },
+          {
+            "name": "currentTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "tickmap",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeGrowthGlobalX",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeGrowthGlobalY",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeProtocolTokenX",
+            "type": "u64"
+          },
+          {
+            "name": "feeProtocolTokenY",
+            "type": "u64"
+          },
+          {
+            "name": "secondsPerLiquidityGlobal",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "startTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "lastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "feeReceiver",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleInitialized",
+            "type": "bool"
+          },
+          {
+            "name": "rewards",
+            "type": {
+              "array": [
+                {
+                  "defined": "PoolReward"
+                },
+                3
+              ]
+            }
+          },
+          {
+            "name": "rewardsLastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "tickmapExtension",
+            "type": "publicKey"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                12
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenX",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenY",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenXReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenYReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "positionIterator",
+            "type": "u128"
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "protocolFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "sqrtPrice",
+            "type": {
+              "defined": "Price"
+            }
+          },
+          {
+            "name": "currentTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "tickmap",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeGrowthGlobalX",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeGrowthGlobalY",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeProtocolTokenX",
+            "type": "u64"
+          },
+          {
+            "name": "feeProtocolTokenY",
+            "type": "u64"
+          },
+          {
+            "name": "secondsPerLiquidityGlobal",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "startTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "lastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "feeReceiver",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleInitialized",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "pool",
+      "name": "position",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "tokenX",
+            "name": "owner",
             "type": "publicKey"
           },
           {
-            "name": "tokenY",
+            "name": "pool",
             "type": "publicKey"
           },
           {
-            "name": "tokenXReserve",
-            "type": "publicKey"
+            "name": "id",
+            "type": "u128"
           },
           {
-            "name": "tokenYReserve",
-            "type": "publicKey"
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
           },
           {
-            "name": "positionIterator",
-            "type": "u128"
+            "name": "lowerTickIndex",
+            "type": "i32"
           },
           {
-            "name": "tickSpacing",
-            "type": "u16"
+            "name": "upperTickIndex",
+            "type": "i32"
           },
           {
-            "name": "fee",
+            "name": "feeGrowthInsideX",
             "type": {
-              "defined": "FixedPoint"
+              "defined": "FeeGrowth"
             }
           },
           {
-            "name": "protocolFee",
+            "name": "feeGrowthInsideY",
             "type": {
-              "defined": "FixedPoint"
+              "defined": "FeeGrowth"
             }
           },
           {
-            "name": "liquidity",
+            "name": "secondsPerLiquidityInside",
             "type": {
-              "defined": "Liquidity"
+              "defined": "FixedPoint"
             }
           },
           {
-            "name": "sqrtPrice",
-            "type": {
-              "defined": "Price"
-            }
+            "name": "lastSlot",
+            "type": "u64"
           },
           {
-            "name": "currentTickIndex",
-            "type": "i32"
+            "name": "tokensOwedX",
+            "type": {
+              "defined": "FixedPoint"
+            }
           },
           {
-            "name": "tickmap",
-            "type": "publicKey"
+            "name": "tokensOwedY",
+            "type": {
+              "defined": "FixedPoint"
+            }
           },
           {
-            "name": "feeGrowthGlobalX",
+            "name": "rewardGrowthsInside",
             "type": {
-              "defined": "FeeGrowth"
+              "array": [
+                {
+                  "defined": "FeeGrowth"
+                },
+                3
+              ]
             }
           },
           {
-            "name": "feeGrowthGlobalY",
+            "name": "rewardsOwed",
             "type": {
-              "defined": "FeeGrowth"
+              "array": [
+                {
+                  "defined": "FixedPoint"
+                },
+                3
+              ]
             }
           },
           {
-            "name": "feeProtocolTokenX",
-            "type": "u64"
+            "name": "bump",
+            "type": "u8"
           },
           {
-            "name": "feeProtocolTokenY",
-            "type": "u64"
+            "name": "version",
+            "type": "u8"
           },
           {
-            "name": "secondsPerLiquidityGlobal",
+            "name": "reserved",
             "type": {
-              "defined": "FixedPoint"
+              "array": [
+                "u64",
+                8
+              ]
             }
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionList",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "startTimestamp",
-            "type": "u64"
+            "name": "head",
+            "type": "u32"
           },
           {
-            "name": "lastTimestamp",
-            "type": "u64"
+            "name": "bump",
+            "type": "u8"
           },
           {
-            "name": "feeReceiver",
-            "type": "publicKey"
+            "name": "version",
+            "type": "u8"
           },
           {
-            "name": "oracleAddress",
+            "name": "operator",
             "type": "publicKey"
           },
           {
-            "name": "oracleInitialized",
-            "type": "bool"
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionListV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "head",
+            "type": "u32"
           },
           {
             "name": "bump",
@@ -1106,7 +2432,7 @@ export type Invariant = {
       }
     },
     {
-      "name": "position",
+      "name": "positionV0",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1178,17 +2504,54 @@ export type Invariant = {
       }
     },
     {
-      "name": "positionList",
+      "name": "proposal",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "head",
-            "type": "u32"
+            "name": "action",
+            "type": "u8"
+          },
+          {
+            "name": "target",
+            "type": "publicKey"
+          },
+          {
+            "name": "proposer",
+            "type": "publicKey"
+          },
+          {
+            "name": "value",
+            "type": "u128"
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "feeReceiver",
+            "type": "publicKey"
+          },
+          {
+            "name": "executeAfter",
+            "type": "u64"
           },
           {
             "name": "bump",
             "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
           }
         ]
       }
@@ -1203,22 +2566,151 @@ export type Invariant = {
             "type": "publicKey"
           },
           {
-            "name": "nonce",
-            "type": "u8"
+            "name": "nonce",
+            "type": "u8"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "governanceDelay",
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                7
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "stateV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "nonce",
+            "type": "u8"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "tick",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "index",
+            "type": "i32"
+          },
+          {
+            "name": "sign",
+            "type": "bool"
+          },
+          {
+            "name": "liquidityChange",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "liquidityGross",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "sqrtPrice",
+            "type": {
+              "defined": "Price"
+            }
+          },
+          {
+            "name": "feeGrowthOutsideX",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeGrowthOutsideY",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "secondsPerLiquidityOutside",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "secondsOutside",
+            "type": "u64"
           },
           {
-            "name": "authority",
-            "type": "publicKey"
+            "name": "rewardGrowthsOutside",
+            "type": {
+              "array": [
+                {
+                  "defined": "FeeGrowth"
+                },
+                3
+              ]
+            }
           },
           {
             "name": "bump",
             "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
           }
         ]
       }
     },
     {
-      "name": "tick",
+      "name": "tickV0",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1297,6 +2789,32 @@ export type Invariant = {
           }
         ]
       }
+    },
+    {
+      "name": "tickmapExtension",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lower",
+            "type": {
+              "array": [
+                "u8",
+                22182
+              ]
+            }
+          },
+          {
+            "name": "upper",
+            "type": {
+              "array": [
+                "u8",
+                22182
+              ]
+            }
+          }
+        ]
+      }
     }
   ],
   "types": [
@@ -1365,6 +2883,108 @@ export type Invariant = {
           }
         ]
       }
+    },
+    {
+      "name": "PoolReward",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "emissionsPerSecond",
+            "type": "u64"
+          },
+          {
+            "name": "endTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "growthGlobal",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PoolReport",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "positions",
+            "type": "u32"
+          },
+          {
+            "name": "ticks",
+            "type": "u32"
+          },
+          {
+            "name": "initializedTicks",
+            "type": "u32"
+          },
+          {
+            "name": "owedX",
+            "type": "u128"
+          },
+          {
+            "name": "owedY",
+            "type": "u128"
+          },
+          {
+            "name": "positionLiquidity",
+            "type": "u128"
+          },
+          {
+            "name": "liquidityInRange",
+            "type": "u128"
+          },
+          {
+            "name": "tickLiquidityGross",
+            "type": "u128"
+          },
+          {
+            "name": "tickLiquidityAdded",
+            "type": "u128"
+          },
+          {
+            "name": "tickLiquidityRemoved",
+            "type": "u128"
+          },
+          {
+            "name": "tickmapValid",
+            "type": "bool"
+          },
+          {
+            "name": "poolLiquidity",
+            "type": "u128"
+          },
+          {
+            "name": "protocolFeeX",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFeeY",
+            "type": "u64"
+          },
+          {
+            "name": "reserveX",
+            "type": "u64"
+          },
+          {
+            "name": "reserveY",
+            "type": "u64"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -1399,291 +3019,1349 @@ export type Invariant = {
       "msg": "Invalid tick_lower or tick_upper"
     },
     {
-      "code": 6006,
-      "name": "NoMoreTicks",
-      "msg": "There is no more tick in that direction"
+      "code": 6006,
+      "name": "NoMoreTicks",
+      "msg": "There is no more tick in that direction"
+    },
+    {
+      "code": 6007,
+      "name": "TickNotFound",
+      "msg": "Correct tick not found in context"
+    },
+    {
+      "code": 6008,
+      "name": "PriceLimitReached",
+      "msg": "Price would cross swap limit"
+    },
+    {
+      "code": 6009,
+      "name": "InvalidTickLiquidity",
+      "msg": "Invalid tick liquidity"
+    },
+    {
+      "code": 6010,
+      "name": "EmptyPositionPokes",
+      "msg": "Disable empty position pokes"
+    },
+    {
+      "code": 6011,
+      "name": "InvalidPositionLiquidity",
+      "msg": "Invalid tick liquidity"
+    },
+    {
+      "code": 6012,
+      "name": "InvalidPoolLiquidity",
+      "msg": "Invalid pool liquidity"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidPositionIndex",
+      "msg": "Invalid position index"
+    },
+    {
+      "code": 6014,
+      "name": "PositionWithoutLiquidity",
+      "msg": "Position liquidity would be zero"
+    },
+    {
+      "code": 6015,
+      "name": "Unauthorized",
+      "msg": "You are not admin"
+    },
+    {
+      "code": 6016,
+      "name": "InvalidPoolTokenAddresses",
+      "msg": "Invalid pool token addresses"
+    },
+    {
+      "code": 6017,
+      "name": "NegativeTime",
+      "msg": "Time cannot be negative"
+    },
+    {
+      "code": 6018,
+      "name": "OracleAlreadyInitialized",
+      "msg": "Oracle is already initialized"
+    },
+    {
+      "code": 6019,
+      "name": "LimitReached",
+      "msg": "Absolute price limit was reached"
+    },
+    {
+      "code": 6020,
+      "name": "InvalidProtocolFee",
+      "msg": "Invalid protocol fee"
+    },
+    {
+      "code": 6021,
+      "name": "NoGainSwap",
+      "msg": "Swap amount out is 0"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidTokenAccount",
+      "msg": "Provided token account is different than expected"
+    },
+    {
+      "code": 6023,
+      "name": "InvalidAdmin",
+      "msg": "Admin address is different than expected"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidAuthority",
+      "msg": "Provided authority is different than expected"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidOwner",
+      "msg": "Provided token owner is different than expected"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidMint",
+      "msg": "Provided token account mint is different than expected mint token"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidTickmap",
+      "msg": "Provided tickmap is different than expected"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidTickmapOwner",
+      "msg": "Provided tickmap owner is different than program ID"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidListOwner",
+      "msg": "Recipient list address and owner list address should be different"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidTickSpacing",
+      "msg": "Invalid tick spacing"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidRemainingAccounts",
+      "msg": "Remaining accounts should be triples of position, lower tick and upper tick"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidSigner",
+      "msg": "Signer is neither the owner nor the operator of the position"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidRewardIndex",
+      "msg": "Invalid reward index"
+    },
+    {
+      "code": 6034,
+      "name": "RewardAlreadyInitialized",
+      "msg": "Reward is already initialized"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidAccountVersion",
+      "msg": "Account is not in a layout that can be migrated"
     },
     {
-      "code": 6007,
-      "name": "TickNotFound",
-      "msg": "Correct tick not found in context"
+      "code": 6036,
+      "name": "InvalidAccountOwner",
+      "msg": "Provided account owner is different than program ID"
     },
     {
-      "code": 6008,
-      "name": "PriceLimitReached",
-      "msg": "Price would cross swap limit"
+      "code": 6037,
+      "name": "InvalidInitSqrtPrice",
+      "msg": "Initial sqrt price is out of range"
     },
     {
-      "code": 6009,
-      "name": "InvalidTickLiquidity",
-      "msg": "Invalid tick liquidity"
+      "code": 6038,
+      "name": "InvalidFeeTier",
+      "msg": "Pool does not belong to the fee tier"
     },
     {
-      "code": 6010,
-      "name": "EmptyPositionPokes",
-      "msg": "Disable empty position pokes"
+      "code": 6039,
+      "name": "TimelockActive",
+      "msg": "Action has to be proposed and wait out the governance delay"
     },
     {
-      "code": 6011,
-      "name": "InvalidPositionLiquidity",
-      "msg": "Invalid tick liquidity"
+      "code": 6040,
+      "name": "ProposalNotReady",
+      "msg": "Governance delay of the proposal has not passed yet"
     },
     {
-      "code": 6012,
-      "name": "InvalidPoolLiquidity",
-      "msg": "Invalid pool liquidity"
+      "code": 6041,
+      "name": "InvalidProposal",
+      "msg": "Proposal action is invalid"
     },
     {
-      "code": 6013,
-      "name": "InvalidPositionIndex",
-      "msg": "Invalid position index"
+      "code": 6042,
+      "name": "InvalidTickmapFlip",
+      "msg": "Tick is already set to this state in the tickmap"
     },
     {
-      "code": 6014,
-      "name": "PositionWithoutLiquidity",
-      "msg": "Position liquidity would be zero"
+      "code": 6043,
+      "name": "MathOverflow",
+      "msg": "Arithmetic overflow"
     },
     {
-      "code": 6015,
-      "name": "Unauthorized",
-      "msg": "You are not admin"
+      "code": 6044,
+      "name": "InvalidSqrtPrice",
+      "msg": "Sqrt price is zero or out of range"
     },
     {
-      "code": 6016,
-      "name": "InvalidPoolTokenAddresses",
-      "msg": "Invalid pool token addresses"
+      "code": 6045,
+      "name": "InvalidTickmapExtension",
+      "msg": "Provided tickmap extension is different than expected or missing"
     },
     {
-      "code": 6017,
-      "name": "NegativeTime",
-      "msg": "Time cannot be negative"
+      "code": 6046,
+      "name": "TickmapExtensionAlreadyCreated",
+      "msg": "Tickmap extension of the pool is already created"
+    }
+  ]
+};
+
+export const IDL: Invariant = {
+  "version": "0.1.0",
+  "name": "invariant",
+  "instructions": [
+    {
+      "name": "createState",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "nonce",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "code": 6018,
-      "name": "OracleAlreadyInitialized",
-      "msg": "Oracle is already initialized"
+      "name": "createFeeTier",
+      "accounts": [
+        {
+          "name": "feeTier",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "fee",
+          "type": "u128"
+        },
+        {
+          "name": "tickSpacing",
+          "type": "u16"
+        }
+      ]
     },
     {
-      "code": 6019,
-      "name": "LimitReached",
-      "msg": "Absolute price limit was reached"
+      "name": "createPool",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeTier",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenYReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "initSqrtPrice",
+          "type": {
+            "defined": "Price"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createPoolWithPosition",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeTier",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenYReserve",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "initSqrtPrice",
+          "type": {
+            "defined": "Price"
+          }
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "positionIndex",
+          "type": "u32"
+        },
+        {
+          "name": "liquidityDelta",
+          "type": {
+            "defined": "Liquidity"
+          }
+        },
+        {
+          "name": "slippageLimitLower",
+          "type": {
+            "defined": "Price"
+          }
+        },
+        {
+          "name": "slippageLimitUpper",
+          "type": {
+            "defined": "Price"
+          }
+        }
+      ]
     },
     {
-      "code": 6020,
-      "name": "InvalidProtocolFee",
-      "msg": "Invalid protocol fee"
+      "name": "swap",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "xToY",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "byAmountIn",
+          "type": "bool"
+        },
+        {
+          "name": "sqrtPriceLimit",
+          "type": "u128"
+        }
+      ]
     },
     {
-      "code": 6021,
-      "name": "NoGainSwap",
-      "msg": "Swap amount out is 0"
+      "name": "swapWithPartialFill",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "xToY",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "byAmountIn",
+          "type": "bool"
+        },
+        {
+          "name": "sqrtPriceLimit",
+          "type": "u128"
+        }
+      ]
     },
     {
-      "code": 6022,
-      "name": "InvalidTokenAccount",
-      "msg": "Provided token account is different than expected"
+      "name": "initializeOracle",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6023,
-      "name": "InvalidAdmin",
-      "msg": "Admin address is different than expected"
+      "name": "createTick",
+      "accounts": [
+        {
+          "name": "tick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "i32"
+        }
+      ]
     },
     {
-      "code": 6024,
-      "name": "InvalidAuthority",
-      "msg": "Provided authority is different than expected"
+      "name": "createTickmapExtension",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmapExtension",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6025,
-      "name": "InvalidOwner",
-      "msg": "Provided token owner is different than expected"
+      "name": "createPositionList",
+      "accounts": [
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "code": 6026,
-      "name": "InvalidMint",
-      "msg": "Provided token account mint is different than expected mint token"
+      "name": "createPosition",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "position",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "liquidityDelta",
+          "type": {
+            "defined": "Liquidity"
+          }
+        },
+        {
+          "name": "slippageLimitLower",
+          "type": {
+            "defined": "Price"
+          }
+        },
+        {
+          "name": "slippageLimitUpper",
+          "type": {
+            "defined": "Price"
+          }
+        }
+      ]
     },
     {
-      "code": 6027,
-      "name": "InvalidTickmap",
-      "msg": "Provided tickmap is different than expected"
+      "name": "removePosition",
+      "accounts": [
+        {
+          "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "removedPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tickmap",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
+        }
+      ]
     },
     {
-      "code": 6028,
-      "name": "InvalidTickmapOwner",
-      "msg": "Provided tickmap owner is different than program ID"
+      "name": "setPositionOperator",
+      "accounts": [
+        {
+          "name": "positionList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "operator",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "code": 6029,
-      "name": "InvalidListOwner",
-      "msg": "Recipient list address and owner list address should be different"
+      "name": "transferPositionOwnership",
+      "accounts": [
+        {
+          "name": "ownerList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipientList",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "removedPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "lastPosition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "recipient",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "index",
+          "type": "u32"
+        }
+      ]
     },
     {
-      "code": 6030,
-      "name": "InvalidTickSpacing",
-      "msg": "Invalid tick spacing"
-    }
-  ]
-};
-
-export const IDL: Invariant = {
-  "version": "0.1.0",
-  "name": "invariant",
-  "instructions": [
-    {
-      "name": "createState",
+      "name": "claimFee",
       "accounts": [
         {
           "name": "state",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "position",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
+        },
+        {
+          "name": "lowerTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "upperTick",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountX",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "accountY",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "reserveX",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "programAuthority",
-          "isMut": false,
+          "name": "reserveY",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "programAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "nonce",
-          "type": "u8"
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
+          "type": "i32"
         }
       ]
     },
     {
-      "name": "createFeeTier",
+      "name": "claimFeesBatch",
       "accounts": [
-        {
-          "name": "feeTier",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "pool",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "positionList",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "owner",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "fee",
-          "type": "u128"
+          "name": "signer",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "tickSpacing",
-          "type": "u16"
-        }
-      ]
-    },
-    {
-      "name": "createPool",
-      "accounts": [
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "state",
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "pool",
+          "name": "accountX",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "feeTier",
-          "isMut": false,
+          "name": "accountY",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tickmap",
+          "name": "reserveX",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenX",
-          "isMut": false,
+          "name": "reserveY",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenY",
+          "name": "programAuthority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenXReserve",
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "compoundFees",
+      "accounts": [
+        {
+          "name": "pool",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "tokenYReserve",
+          "name": "position",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "lowerTick",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "authority",
-          "isMut": false,
+          "name": "upperTick",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "tokenX",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "initTick",
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "lowerTickIndex",
+          "type": "i32"
+        },
+        {
+          "name": "upperTickIndex",
           "type": "i32"
         }
       ]
     },
     {
-      "name": "swap",
+      "name": "claimRewards",
       "accounts": [
         {
           "name": "state",
@@ -1696,35 +4374,55 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "tickmap",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "accountX",
-          "isMut": true,
+          "name": "positionList",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountY",
+          "name": "lowerTick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveX",
+          "name": "upperTick",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveY",
-          "isMut": true,
+          "name": "owner",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
         },
+        {
+          "name": "tokenX",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenY",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "accountReward",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "programAuthority",
           "isMut": false,
@@ -1738,33 +4436,33 @@ export const IDL: Invariant = {
       ],
       "args": [
         {
-          "name": "xToY",
-          "type": "bool"
+          "name": "index",
+          "type": "u32"
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "lowerTickIndex",
+          "type": "i32"
         },
         {
-          "name": "byAmountIn",
-          "type": "bool"
+          "name": "upperTickIndex",
+          "type": "i32"
         },
         {
-          "name": "sqrtPriceLimit",
-          "type": "u128"
+          "name": "rewardIndex",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "initializeOracle",
+      "name": "initializeReward",
       "accounts": [
         {
-          "name": "pool",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "oracle",
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
@@ -1779,10 +4477,35 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "rewardMint",
           "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rewardVault",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "adminAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "rent",
           "isMut": false,
@@ -1794,23 +4517,26 @@ export const IDL: Invariant = {
           "isSigner": false
         }
       ],
-      "args": []
-    },
-    {
-      "name": "createTick",
-      "accounts": [
+      "args": [
         {
-          "name": "tick",
-          "isMut": true,
-          "isSigner": false
+          "name": "rewardIndex",
+          "type": "u8"
         },
         {
-          "name": "pool",
-          "isMut": false,
-          "isSigner": false
+          "name": "emissionsPerSecond",
+          "type": "u64"
         },
         {
-          "name": "tickmap",
+          "name": "endTimestamp",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "migrateState",
+      "accounts": [
+        {
+          "name": "account",
           "isMut": true,
           "isSigner": false
         },
@@ -1820,19 +4546,25 @@ export const IDL: Invariant = {
           "isSigner": true
         },
         {
-          "name": "tokenX",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateFeeTier",
+      "accounts": [
         {
-          "name": "tokenY",
-          "isMut": false,
+          "name": "account",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
         },
         {
           "name": "systemProgram",
@@ -1840,36 +4572,42 @@ export const IDL: Invariant = {
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "index",
-          "type": "i32"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "createPositionList",
+      "name": "migratePool",
       "accounts": [
         {
-          "name": "positionList",
+          "name": "account",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateTick",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1879,25 +4617,31 @@ export const IDL: Invariant = {
       "args": []
     },
     {
-      "name": "createPosition",
+      "name": "migratePosition",
       "accounts": [
         {
-          "name": "state",
-          "isMut": false,
+          "name": "account",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "position",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "pool",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migratePositionList",
+      "accounts": [
         {
-          "name": "positionList",
+          "name": "account",
           "isMut": true,
           "isSigner": false
         },
@@ -1907,65 +4651,77 @@ export const IDL: Invariant = {
           "isSigner": true
         },
         {
-          "name": "owner",
+          "name": "systemProgram",
           "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "lowerTick",
-          "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateOracle",
+      "accounts": [
         {
-          "name": "upperTick",
+          "name": "account",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tickmap",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenX",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateSecondsPerLiquidity",
+      "accounts": [
         {
-          "name": "tokenY",
-          "isMut": false,
+          "name": "pool",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "accountX",
-          "isMut": true,
+          "name": "lowerTick",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountY",
-          "isMut": true,
+          "name": "upperTick",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveX",
+          "name": "position",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveY",
-          "isMut": true,
+          "name": "tokenX",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "programAuthority",
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "owner",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "signer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "rent",
           "isMut": false,
@@ -1987,73 +4743,24 @@ export const IDL: Invariant = {
           "type": "i32"
         },
         {
-          "name": "liquidityDelta",
-          "type": {
-            "defined": "Liquidity"
-          }
-        },
-        {
-          "name": "slippageLimitLower",
-          "type": {
-            "defined": "Price"
-          }
-        },
-        {
-          "name": "slippageLimitUpper",
-          "type": {
-            "defined": "Price"
-          }
+          "name": "index",
+          "type": "i32"
         }
       ]
     },
     {
-      "name": "removePosition",
+      "name": "withdrawProtocolFee",
       "accounts": [
         {
           "name": "state",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "removedPosition",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "positionList",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "lastPosition",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "pool",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "tickmap",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "lowerTick",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "upperTick",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
         {
           "name": "tokenX",
           "isMut": false,
@@ -2084,6 +4791,11 @@ export const IDL: Invariant = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
         {
           "name": "programAuthority",
           "isMut": false,
@@ -2095,79 +4807,53 @@ export const IDL: Invariant = {
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "index",
-          "type": "u32"
-        },
-        {
-          "name": "lowerTickIndex",
-          "type": "i32"
-        },
-        {
-          "name": "upperTickIndex",
-          "type": "i32"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "transferPositionOwnership",
+      "name": "changeProtocolFee",
       "accounts": [
         {
-          "name": "ownerList",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "recipientList",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "newPosition",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "removedPosition",
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "lastPosition",
-          "isMut": true,
+          "name": "tokenX",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "recipient",
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "systemProgram",
+          "name": "programAuthority",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "index",
-          "type": "u32"
+          "name": "protocolFee",
+          "type": {
+            "defined": "FixedPoint"
+          }
         }
       ]
     },
     {
-      "name": "claimFee",
+      "name": "changeDefaultProtocolFee",
       "accounts": [
         {
           "name": "state",
@@ -2175,126 +4861,86 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "position",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "lowerTick",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "upperTick",
+          "name": "feeTier",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
-        },
-        {
-          "name": "tokenX",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenY",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "accountX",
-          "isMut": true,
-          "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "accountY",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "protocolFee",
+          "type": {
+            "defined": "FixedPoint"
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeFeeReceiver",
+      "accounts": [
         {
-          "name": "reserveX",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "reserveY",
+          "name": "pool",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "programAuthority",
+          "name": "tokenX",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "tokenY",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "index",
-          "type": "u32"
         },
         {
-          "name": "lowerTickIndex",
-          "type": "i32"
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "upperTickIndex",
-          "type": "i32"
+          "name": "feeReceiver",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "updateSecondsPerLiquidity",
+      "name": "propose",
       "accounts": [
         {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "lowerTick",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "upperTick",
+          "name": "state",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "position",
+          "name": "proposal",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenX",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenY",
+          "name": "target",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "feeReceiver",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "signer",
+          "name": "proposer",
           "isMut": true,
           "isSigner": true
         },
@@ -2311,74 +4957,80 @@ export const IDL: Invariant = {
       ],
       "args": [
         {
-          "name": "lowerTickIndex",
-          "type": "i32"
+          "name": "action",
+          "type": "u8"
         },
         {
-          "name": "upperTickIndex",
-          "type": "i32"
+          "name": "value",
+          "type": "u128"
         },
         {
-          "name": "index",
-          "type": "i32"
+          "name": "tickSpacing",
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "withdrawProtocolFee",
+      "name": "executeProposal",
       "accounts": [
         {
           "name": "state",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "pool",
+          "name": "proposal",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenX",
-          "isMut": false,
+          "name": "target",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenY",
-          "isMut": false,
+          "name": "proposer",
+          "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeCreateFeeTier",
+      "accounts": [
         {
-          "name": "accountX",
-          "isMut": true,
+          "name": "state",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "accountY",
+          "name": "proposal",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveX",
+          "name": "feeTier",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "reserveY",
+          "name": "proposer",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
-          "isMut": false,
+          "name": "payer",
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "programAuthority",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
@@ -2386,7 +5038,7 @@ export const IDL: Invariant = {
       "args": []
     },
     {
-      "name": "changeProtocolFee",
+      "name": "cancelProposal",
       "accounts": [
         {
           "name": "state",
@@ -2394,92 +5046,455 @@ export const IDL: Invariant = {
           "isSigner": false
         },
         {
-          "name": "pool",
+          "name": "proposal",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenX",
+          "name": "target",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenY",
-          "isMut": false,
+          "name": "proposer",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "admin",
+          "name": "signer",
           "isMut": false,
           "isSigner": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "verifyPool",
+      "accounts": [
+        {
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "programAuthority",
+          "name": "tickmap",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenXReserve",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenYReserve",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "protocolFee",
-          "type": {
-            "defined": "FixedPoint"
+          "name": "lowerTick",
+          "type": "i32"
+        },
+        {
+          "name": "upperTick",
+          "type": "i32"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "feeTier",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "defaultProtocolFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                2
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeTierV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "oracle",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "data",
+            "type": {
+              "array": [
+                {
+                  "defined": "Record"
+                },
+                256
+              ]
+            }
+          },
+          {
+            "name": "head",
+            "type": "u16"
+          },
+          {
+            "name": "amount",
+            "type": "u16"
+          },
+          {
+            "name": "size",
+            "type": "u16"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "oracleV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "data",
+            "type": {
+              "array": [
+                {
+                  "defined": "Record"
+                },
+                256
+              ]
+            }
+          },
+          {
+            "name": "head",
+            "type": "u16"
+          },
+          {
+            "name": "amount",
+            "type": "u16"
+          },
+          {
+            "name": "size",
+            "type": "u16"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "changeFeeReceiver",
-      "accounts": [
-        {
-          "name": "state",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "pool",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenX",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenY",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "feeReceiver",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": []
-    }
-  ],
-  "accounts": [
+      "name": "pool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenX",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenY",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenXReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenYReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "positionIterator",
+            "type": "u128"
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "protocolFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "sqrtPrice",
+            "type": {
+              "defined": "Price"
+            }
+          },
+          {
+            "name": "currentTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "tickmap",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeGrowthGlobalX",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeGrowthGlobalY",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeProtocolTokenX",
+            "type": "u64"
+          },
+          {
+            "name": "feeProtocolTokenY",
+            "type": "u64"
+          },
+          {
+            "name": "secondsPerLiquidityGlobal",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "startTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "lastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "feeReceiver",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleInitialized",
+            "type": "bool"
+          },
+          {
+            "name": "rewards",
+            "type": {
+              "array": [
+                {
+                  "defined": "PoolReward"
+                },
+                3
+              ]
+            }
+          },
+          {
+            "name": "rewardsLastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "tickmapExtension",
+            "type": "publicKey"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                12
+              ]
+            }
+          }
+        ]
+      }
+    },
     {
-      "name": "feeTier",
+      "name": "poolV0",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "fee",
+            "name": "tokenX",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenY",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenXReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenYReserve",
+            "type": "publicKey"
+          },
+          {
+            "name": "positionIterator",
+            "type": "u128"
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "fee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "protocolFee",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "sqrtPrice",
+            "type": {
+              "defined": "Price"
+            }
+          },
+          {
+            "name": "currentTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "tickmap",
+            "type": "publicKey"
+          },
+          {
+            "name": "feeGrowthGlobalX",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeGrowthGlobalY",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeProtocolTokenX",
+            "type": "u64"
+          },
+          {
+            "name": "feeProtocolTokenY",
+            "type": "u64"
+          },
+          {
+            "name": "secondsPerLiquidityGlobal",
             "type": {
               "defined": "FixedPoint"
             }
           },
           {
-            "name": "tickSpacing",
-            "type": "u16"
+            "name": "startTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "lastTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "feeReceiver",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleAddress",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleInitialized",
+            "type": "bool"
           },
           {
             "name": "bump",
@@ -2489,142 +5504,343 @@ export const IDL: Invariant = {
       }
     },
     {
-      "name": "oracle",
+      "name": "position",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "data",
+            "name": "owner",
+            "type": "publicKey"
+          },
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "id",
+            "type": "u128"
+          },
+          {
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
+          },
+          {
+            "name": "lowerTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "upperTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "feeGrowthInsideX",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "feeGrowthInsideY",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "secondsPerLiquidityInside",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "lastSlot",
+            "type": "u64"
+          },
+          {
+            "name": "tokensOwedX",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "tokensOwedY",
+            "type": {
+              "defined": "FixedPoint"
+            }
+          },
+          {
+            "name": "rewardGrowthsInside",
             "type": {
               "array": [
                 {
-                  "defined": "Record"
+                  "defined": "FeeGrowth"
                 },
-                256
+                3
               ]
             }
           },
           {
-            "name": "head",
-            "type": "u16"
+            "name": "rewardsOwed",
+            "type": {
+              "array": [
+                {
+                  "defined": "FixedPoint"
+                },
+                3
+              ]
+            }
           },
           {
-            "name": "amount",
-            "type": "u16"
+            "name": "bump",
+            "type": "u8"
           },
           {
-            "name": "size",
-            "type": "u16"
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
           }
         ]
       }
     },
     {
-      "name": "pool",
+      "name": "positionList",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "tokenX",
-            "type": "publicKey"
+            "name": "head",
+            "type": "u32"
           },
           {
-            "name": "tokenY",
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "operator",
             "type": "publicKey"
           },
           {
-            "name": "tokenXReserve",
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionListV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "head",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
             "type": "publicKey"
           },
           {
-            "name": "tokenYReserve",
+            "name": "pool",
             "type": "publicKey"
           },
           {
-            "name": "positionIterator",
+            "name": "id",
             "type": "u128"
           },
           {
-            "name": "tickSpacing",
-            "type": "u16"
+            "name": "liquidity",
+            "type": {
+              "defined": "Liquidity"
+            }
           },
           {
-            "name": "fee",
+            "name": "lowerTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "upperTickIndex",
+            "type": "i32"
+          },
+          {
+            "name": "feeGrowthInsideX",
             "type": {
-              "defined": "FixedPoint"
+              "defined": "FeeGrowth"
             }
           },
           {
-            "name": "protocolFee",
+            "name": "feeGrowthInsideY",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          },
+          {
+            "name": "secondsPerLiquidityInside",
             "type": {
               "defined": "FixedPoint"
             }
           },
           {
-            "name": "liquidity",
+            "name": "lastSlot",
+            "type": "u64"
+          },
+          {
+            "name": "tokensOwedX",
             "type": {
-              "defined": "Liquidity"
+              "defined": "FixedPoint"
             }
           },
           {
-            "name": "sqrtPrice",
+            "name": "tokensOwedY",
             "type": {
-              "defined": "Price"
+              "defined": "FixedPoint"
             }
           },
           {
-            "name": "currentTickIndex",
-            "type": "i32"
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "proposal",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "action",
+            "type": "u8"
           },
           {
-            "name": "tickmap",
+            "name": "target",
             "type": "publicKey"
           },
           {
-            "name": "feeGrowthGlobalX",
-            "type": {
-              "defined": "FeeGrowth"
-            }
+            "name": "proposer",
+            "type": "publicKey"
           },
           {
-            "name": "feeGrowthGlobalY",
+            "name": "value",
+            "type": "u128"
+          },
+          {
+            "name": "tickSpacing",
+            "type": "u16"
+          },
+          {
+            "name": "feeReceiver",
+            "type": "publicKey"
+          },
+          {
+            "name": "executeAfter",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
             "type": {
-              "defined": "FeeGrowth"
+              "array": [
+                "u64",
+                4
+              ]
             }
+          }
+        ]
+      }
+    },
+    {
+      "name": "state",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
           },
           {
-            "name": "feeProtocolTokenX",
-            "type": "u64"
+            "name": "nonce",
+            "type": "u8"
           },
           {
-            "name": "feeProtocolTokenY",
-            "type": "u64"
+            "name": "authority",
+            "type": "publicKey"
           },
           {
-            "name": "secondsPerLiquidityGlobal",
-            "type": {
-              "defined": "FixedPoint"
-            }
+            "name": "bump",
+            "type": "u8"
           },
           {
-            "name": "startTimestamp",
-            "type": "u64"
+            "name": "version",
+            "type": "u8"
           },
           {
-            "name": "lastTimestamp",
+            "name": "governanceDelay",
             "type": "u64"
           },
           {
-            "name": "feeReceiver",
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                7
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "stateV0",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
             "type": "publicKey"
           },
           {
-            "name": "oracleAddress",
-            "type": "publicKey"
+            "name": "nonce",
+            "type": "u8"
           },
           {
-            "name": "oracleInitialized",
-            "type": "bool"
+            "name": "authority",
+            "type": "publicKey"
           },
           {
             "name": "bump",
@@ -2634,119 +5850,95 @@ export const IDL: Invariant = {
       }
     },
     {
-      "name": "position",
+      "name": "tick",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "owner",
+            "name": "pool",
             "type": "publicKey"
           },
           {
-            "name": "pool",
-            "type": "publicKey"
+            "name": "index",
+            "type": "i32"
           },
           {
-            "name": "id",
-            "type": "u128"
+            "name": "sign",
+            "type": "bool"
           },
           {
-            "name": "liquidity",
+            "name": "liquidityChange",
             "type": {
               "defined": "Liquidity"
             }
           },
           {
-            "name": "lowerTickIndex",
-            "type": "i32"
+            "name": "liquidityGross",
+            "type": {
+              "defined": "Liquidity"
+            }
           },
           {
-            "name": "upperTickIndex",
-            "type": "i32"
+            "name": "sqrtPrice",
+            "type": {
+              "defined": "Price"
+            }
           },
           {
-            "name": "feeGrowthInsideX",
+            "name": "feeGrowthOutsideX",
             "type": {
               "defined": "FeeGrowth"
             }
           },
           {
-            "name": "feeGrowthInsideY",
+            "name": "feeGrowthOutsideY",
             "type": {
               "defined": "FeeGrowth"
             }
           },
           {
-            "name": "secondsPerLiquidityInside",
+            "name": "secondsPerLiquidityOutside",
             "type": {
               "defined": "FixedPoint"
             }
           },
           {
-            "name": "lastSlot",
+            "name": "secondsOutside",
             "type": "u64"
           },
           {
-            "name": "tokensOwedX",
-            "type": {
-              "defined": "FixedPoint"
-            }
-          },
-          {
-            "name": "tokensOwedY",
+            "name": "rewardGrowthsOutside",
             "type": {
-              "defined": "FixedPoint"
+              "array": [
+                {
+                  "defined": "FeeGrowth"
+                },
+                3
+              ]
             }
           },
           {
             "name": "bump",
             "type": "u8"
-          }
-        ]
-      }
-    },
-    {
-      "name": "positionList",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "head",
-            "type": "u32"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          }
-        ]
-      }
-    },
-    {
-      "name": "state",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": "publicKey"
           },
           {
-            "name": "nonce",
+            "name": "version",
             "type": "u8"
           },
           {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
           }
         ]
       }
     },
     {
-      "name": "tick",
+      "name": "tickV0",
       "type": {
         "kind": "struct",
         "fields": [
@@ -2825,6 +6017,32 @@ export const IDL: Invariant = {
           }
         ]
       }
+    },
+    {
+      "name": "tickmapExtension",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lower",
+            "type": {
+              "array": [
+                "u8",
+                22182
+              ]
+            }
+          },
+          {
+            "name": "upper",
+            "type": {
+              "array": [
+                "u8",
+                22182
+              ]
+            }
+          }
+        ]
+      }
     }
   ],
   "types": [
@@ -2893,6 +6111,108 @@ export const IDL: Invariant = {
           }
         ]
       }
+    },
+    {
+      "name": "PoolReward",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "emissionsPerSecond",
+            "type": "u64"
+          },
+          {
+            "name": "endTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "growthGlobal",
+            "type": {
+              "defined": "FeeGrowth"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PoolReport",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "positions",
+            "type": "u32"
+          },
+          {
+            "name": "ticks",
+            "type": "u32"
+          },
+          {
+            "name": "initializedTicks",
+            "type": "u32"
+          },
+          {
+            "name": "owedX",
+            "type": "u128"
+          },
+          {
+            "name": "owedY",
+            "type": "u128"
+          },
+          {
+            "name": "positionLiquidity",
+            "type": "u128"
+          },
+          {
+            "name": "liquidityInRange",
+            "type": "u128"
+          },
+          {
+            "name": "tickLiquidityGross",
+            "type": "u128"
+          },
+          {
+            "name": "tickLiquidityAdded",
+            "type": "u128"
+          },
+          {
+            "name": "tickLiquidityRemoved",
+            "type": "u128"
+          },
+          {
+            "name": "tickmapValid",
+            "type": "bool"
+          },
+          {
+            "name": "poolLiquidity",
+            "type": "u128"
+          },
+          {
+            "name": "protocolFeeX",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFeeY",
+            "type": "u64"
+          },
+          {
+            "name": "reserveX",
+            "type": "u64"
+          },
+          {
+            "name": "reserveY",
+            "type": "u64"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -3050,6 +6370,86 @@ export const IDL: Invariant = {
       "code": 6030,
       "name": "InvalidTickSpacing",
       "msg": "Invalid tick spacing"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidRemainingAccounts",
+      "msg": "Remaining accounts should be triples of position, lower tick and upper tick"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidSigner",
+      "msg": "Signer is neither the owner nor the operator of the position"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidRewardIndex",
+      "msg": "Invalid reward index"
+    },
+    {
+      "code": 6034,
+      "name": "RewardAlreadyInitialized",
+      "msg": "Reward is already initialized"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidAccountVersion",
+      "msg": "Account is not in a layout that can be migrated"
+    },
+    {
+      "code": 6036,
+      "name": "InvalidAccountOwner",
+      "msg": "Provided account owner is different than program ID"
+    },
+    {
+      "code": 6037,
+      "name": "InvalidInitSqrtPrice",
+      "msg": "Initial sqrt price is out of range"
+    },
+    {
+      "code": 6038,
+      "name": "InvalidFeeTier",
+      "msg": "Pool does not belong to the fee tier"
+    },
+    {
+      "code": 6039,
+      "name": "TimelockActive",
+      "msg": "Action has to be proposed and wait out the governance delay"
+    },
+    {
+      "code": 6040,
+      "name": "ProposalNotReady",
+      "msg": "Governance delay of the proposal has not passed yet"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidProposal",
+      "msg": "Proposal action is invalid"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidTickmapFlip",
+      "msg": "Tick is already set to this state in the tickmap"
+    },
+    {
+      "code": 6043,
+      "name": "MathOverflow",
+      "msg": "Arithmetic overflow"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidSqrtPrice",
+      "msg": "Sqrt price is zero or out of range"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidTickmapExtension",
+      "msg": "Provided tickmap extension is different than expected or missing"
+    },
+    {
+      "code": 6046,
+      "name": "TickmapExtensionAlreadyCreated",
+      "msg": "Tickmap extension of the pool is already created"
     }
   ]
 };
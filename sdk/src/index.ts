@@ -11,6 +11,7 @@ import {
 } from './utils'
 import {
   TICK_LIMIT,
+  EXTENDED_TICK_LIMIT,
   calculatePriceSqrt,
   fromInteger,
   MAX_TICK,
@@ -20,7 +21,7 @@ import {
 import { PublicKey, Transaction } from '@solana/web3.js'
 import { Pair } from './pair'
 import { getMarketAddress, Network, MOCK_TOKENS } from './network'
-import { findTickmapChanges } from './tickmap'
+import { extendBitmap, findTickmapChanges } from './tickmap'
 
 export {
   Market,
@@ -31,6 +32,7 @@ export {
   sleep,
   calculatePriceSqrt,
   findTickmapChanges,
+  extendBitmap,
   fromInteger,
   SEED,
   INVARIANT_ERRORS,
@@ -38,6 +40,7 @@ export {
   PRICE_DENOMINATOR,
   LIQUIDITY_DENOMINATOR,
   TICK_LIMIT,
+  EXTENDED_TICK_LIMIT,
   MAX_TICK,
   MIN_TICK,
   MOCK_TOKENS,
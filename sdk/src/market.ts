@@ -1,6 +1,7 @@
 import { BN, Program, utils, Provider } from '@project-serum/anchor'
 import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token'
 import {
+  AccountMeta,
   ComputeBudgetProgram,
   Connection,
   Keypair,
@@ -13,11 +14,15 @@ import {
 import {
   calculatePriceAfterSlippage,
   calculatePriceSqrt,
+  getInitSqrtPrice,
+  EXTENDED_TICK_LIMIT,
   findClosestTicks,
   getX,
   getY,
-  isInitialized
+  isInitialized,
+  TICK_LIMIT
 } from './math'
+import { extendBitmap } from './tickmap'
 import {
   calculateClaimAmount,
   computeUnitsInstruction,
@@ -44,11 +49,30 @@ const POSITION_SEED = 'positionv1'
 const TICK_SEED = 'tickv1'
 const POSITION_LIST_SEED = 'positionlistv1'
 const STATE_SEED = 'statev1'
+const PROPOSAL_SEED = 'proposalv1'
 export const TICK_CROSSES_PER_IX = 19
 export const TICK_VIRTUAL_CROSSES_PER_IX = 10
 export const FEE_TIER = 'feetierv1'
 export const DEFAULT_PUBLIC_KEY = new PublicKey(0)
 
+export const hasTickmapExtension = (pool: PoolStructure) =>
+  !pool.tickmapExtension.equals(DEFAULT_PUBLIC_KEY)
+
+// extension has to be passed to instructions using the tickmap once the pool has one
+export const tickmapExtensionAccounts = (pool: PoolStructure) =>
+  hasTickmapExtension(pool)
+    ? [{ pubkey: pool.tickmapExtension, isWritable: true, isSigner: false }]
+    : []
+
+// operator signing on behalf of the owner, along with the position list if the instruction doesn't take it
+export const operatorAccounts = (owner: PublicKey, signer: PublicKey, positionList?: PublicKey) =>
+  signer.equals(owner)
+    ? []
+    : [
+        ...(positionList ? [{ pubkey: positionList, isWritable: false, isSigner: false }] : []),
+        { pubkey: signer, isWritable: false, isSigner: true }
+      ]
+
 export class Market {
   public connection: Connection
   public wallet: IWallet
@@ -104,19 +128,32 @@ export class Market {
     await signAndSend(transaction, [createPool.payer, ...signers], this.connection)
   }
 
-  async createPoolTx({ pair, payer, initTick }: CreatePoolTx) {
+  async createPoolTx({
+    pair,
+    payer,
+    initTick,
+    initSqrtPrice,
+    withTickmapExtension
+  }: CreatePoolTx) {
     const payerPubkey = payer?.publicKey ?? this.wallet.publicKey
     const bitmapKeypair = Keypair.generate()
     const tokenXReserve = Keypair.generate()
     const tokenYReserve = Keypair.generate()
-    const tick = initTick ?? 0
+    // the extension is created with the pool, so it can start at a price outside of the tickmap
+    const extensionKeypair = withTickmapExtension ? Keypair.generate() : undefined
+    const sqrtPrice = getInitSqrtPrice(
+      pair.tickSpacing,
+      initTick,
+      initSqrtPrice,
+      withTickmapExtension ? EXTENDED_TICK_LIMIT : TICK_LIMIT
+    )
 
     const { address: stateAddress } = await this.getStateAddress()
 
     const [poolAddress] = await pair.getAddressAndBump(this.program.programId)
     const { address: feeTierAddress } = await this.getFeeTierAddress(pair.feeTier)
 
-    const createIx = this.program.instruction.createPool(tick, {
+    const createIx = this.program.instruction.createPool(sqrtPrice, {
       accounts: {
         state: stateAddress,
         pool: poolAddress,
@@ -131,28 +168,40 @@ export class Market {
         payer: payerPubkey,
         rent: SYSVAR_RENT_PUBKEY,
         systemProgram: SystemProgram.programId
-      }
+      },
+      remainingAccounts: extensionKeypair
+        ? [{ pubkey: extensionKeypair.publicKey, isWritable: true, isSigner: false }]
+        : []
     })
 
     const transaction = new Transaction({
       feePayer: payerPubkey
-    })
-      .add(
-        SystemProgram.createAccount({
-          fromPubkey: payerPubkey,
-          newAccountPubkey: bitmapKeypair.publicKey,
-          space: this.program.account.tickmap.size,
-          lamports: await this.connection.getMinimumBalanceForRentExemption(
-            this.program.account.tickmap.size
-          ),
-          programId: this.program.programId
-        })
+    }).add(
+      SystemProgram.createAccount({
+        fromPubkey: payerPubkey,
+        newAccountPubkey: bitmapKeypair.publicKey,
+        space: this.program.account.tickmap.size,
+        lamports: await this.connection.getMinimumBalanceForRentExemption(
+          this.program.account.tickmap.size
+        ),
+        programId: this.program.programId
+      })
+    )
+    if (extensionKeypair) {
+      transaction.add(
+        await this.program.account.tickmapExtension.createInstruction(extensionKeypair)
       )
-      .add(createIx)
+    }
+    transaction.add(createIx)
 
     return {
       transaction,
-      signers: [bitmapKeypair, tokenXReserve, tokenYReserve]
+      signers: [
+        bitmapKeypair,
+        tokenXReserve,
+        tokenYReserve,
+        ...(extensionKeypair ? [extensionKeypair] : [])
+      ]
     }
   }
 
@@ -230,6 +279,27 @@ export class Market {
     return tickmap
   }
 
+  async getTickmapExtension(pair: Pair) {
+    const state = await this.getPool(pair)
+    if (!hasTickmapExtension(state)) {
+      return null
+    }
+    return (await this.program.account.tickmapExtension.fetch(
+      state.tickmapExtension
+    )) as TickmapExtension
+  }
+
+  // bitmap covering every tick the pool can use, together with the tick limit it was built for
+  async getFullTickmap(pair: Pair) {
+    const [tickmap, extension] = await Promise.all([
+      this.getTickmap(pair),
+      this.getTickmapExtension(pair)
+    ])
+    return extension === null
+      ? { bitmap: tickmap.bitmap, tickLimit: TICK_LIMIT }
+      : { bitmap: extendBitmap(tickmap, extension), tickLimit: EXTENDED_TICK_LIMIT }
+  }
+
   async isInitialized(pair: Pair, index: number) {
     const state = await this.getPool(pair)
     const tickmap = await this.getTickmap(pair)
@@ -592,6 +662,28 @@ export class Market {
     return (await this.program.account.state.fetch(address)) as State
   }
 
+  // one pending proposal per action and target
+  async getProposalAddress(target: PublicKey, action: ProposalAction) {
+    const [address, bump] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from(utils.bytes.utf8.encode(PROPOSAL_SEED)),
+        target.toBuffer(),
+        Buffer.from([action])
+      ],
+      this.program.programId
+    )
+
+    return {
+      address,
+      bump
+    }
+  }
+
+  async getProposal(target: PublicKey, action: ProposalAction) {
+    const { address } = await this.getProposalAddress(target, action)
+    return (await this.program.account.proposal.fetch(address)) as ProposalStructure
+  }
+
   async createTickInstruction({ pair, index, payer }: CreateTick) {
     payer = payer ?? this.wallet.publicKey
     const state = await this.getPool(pair)
@@ -648,6 +740,31 @@ export class Market {
     await signAndSend(tx, [signer], this.connection)
   }
 
+  async setPositionOperatorInstruction({ operator, owner }: SetPositionOperator) {
+    owner = owner ?? this.wallet.publicKey
+    const { positionListAddress } = await this.getPositionListAddress(owner)
+
+    return this.program.instruction.setPositionOperator(operator, {
+      accounts: {
+        positionList: positionListAddress,
+        owner
+      }
+    })
+  }
+
+  async setPositionOperatorTransaction(setPositionOperator: SetPositionOperator) {
+    const ix = await this.setPositionOperatorInstruction(setPositionOperator)
+    return new Transaction().add(ix)
+  }
+
+  // operator can claim fees, compound them and remove positions of the owner, DEFAULT_PUBLIC_KEY revokes it
+  // adding liquidity with initPosition still needs the owner to sign, tokens are taken from its accounts
+  async setPositionOperator(setPositionOperator: SetPositionOperator, signer: Keypair) {
+    const tx = await this.setPositionOperatorTransaction(setPositionOperator)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
   async initPositionInstruction(
     {
       pair,
@@ -688,6 +805,7 @@ export class Market {
       slippageLimitLower,
       slippageLimitUpper,
       {
+        remainingAccounts: tickmapExtensionAccounts(state),
         accounts: {
           state: this.stateAddress,
           pool: poolAddress,
@@ -784,6 +902,7 @@ export class Market {
       upperTick,
       liquidityDelta,
       initTick,
+      initSqrtPrice,
       knownPrice,
       slippage
     }: InitPoolAndPosition,
@@ -793,7 +912,7 @@ export class Market {
     const bitmapKeypair = Keypair.generate()
     const tokenXReserve = Keypair.generate()
     const tokenYReserve = Keypair.generate()
-    const tick = initTick ?? 0
+    const sqrtPrice = getInitSqrtPrice(pair.tickSpacing, initTick, initSqrtPrice)
 
     const setCuIx = computeUnitsInstruction(1_400_000, payerPubkey)
     const { address: stateAddress } = await this.getStateAddress()
@@ -828,7 +947,7 @@ export class Market {
         })
       )
       .add(
-        this.program.instruction.createPool(tick, {
+        this.program.instruction.createPool(sqrtPrice, {
           accounts: {
             state: stateAddress,
             pool: poolAddress,
@@ -924,6 +1043,106 @@ export class Market {
     await signAndSend(transaction, [signer, ...signers], this.connection)
   }
 
+  // creates the pool, its ticks and the first position in a single instruction
+  async createPoolWithPositionTx(
+    {
+      pair,
+      userTokenX,
+      userTokenY,
+      lowerTick,
+      upperTick,
+      liquidityDelta,
+      initTick,
+      initSqrtPrice,
+      knownPrice,
+      slippage
+    }: InitPoolAndPosition,
+    payer?: Keypair
+  ) {
+    const payerPubkey = payer?.publicKey ?? this.wallet.publicKey
+    const bitmapKeypair = Keypair.generate()
+    const tokenXReserve = Keypair.generate()
+    const tokenYReserve = Keypair.generate()
+    const sqrtPrice = getInitSqrtPrice(pair.tickSpacing, initTick, initSqrtPrice)
+
+    const [poolAddress] = await pair.getAddressAndBump(this.program.programId)
+    const { address: feeTierAddress } = await this.getFeeTierAddress(pair.feeTier)
+    const { positionListAddress } = await this.getPositionListAddress(payerPubkey)
+    const { tickAddress: lowerTickAddress } = await this.getTickAddress(pair, lowerTick)
+    const { tickAddress: upperTickAddress } = await this.getTickAddress(pair, upperTick)
+
+    const listExists = (await this.connection.getAccountInfo(positionListAddress)) !== null
+    const head = listExists ? (await this.getPositionList(payerPubkey)).head : 0
+    const { positionAddress } = await this.getPositionAddress(payerPubkey, head)
+
+    const slippageLimitLower = calculatePriceAfterSlippage(knownPrice, slippage, false)
+    const slippageLimitUpper = calculatePriceAfterSlippage(knownPrice, slippage, true)
+
+    const transaction = new Transaction({
+      feePayer: payerPubkey
+    })
+      .add(computeUnitsInstruction(1_400_000, payerPubkey))
+      .add(
+        SystemProgram.createAccount({
+          fromPubkey: payerPubkey,
+          newAccountPubkey: bitmapKeypair.publicKey,
+          space: this.program.account.tickmap.size,
+          lamports: await this.connection.getMinimumBalanceForRentExemption(
+            this.program.account.tickmap.size
+          ),
+          programId: this.program.programId
+        })
+      )
+    if (!listExists) transaction.add(await this.createPositionListInstruction(payerPubkey))
+
+    transaction.add(
+      this.program.instruction.createPoolWithPosition(
+        sqrtPrice,
+        lowerTick,
+        upperTick,
+        head,
+        liquidityDelta,
+        slippageLimitLower,
+        slippageLimitUpper,
+        {
+          accounts: {
+            state: this.stateAddress,
+            pool: poolAddress,
+            feeTier: feeTierAddress,
+            tickmap: bitmapKeypair.publicKey,
+            lowerTick: lowerTickAddress,
+            upperTick: upperTickAddress,
+            positionList: positionListAddress,
+            position: positionAddress,
+            tokenX: pair.tokenX,
+            tokenY: pair.tokenY,
+            tokenXReserve: tokenXReserve.publicKey,
+            tokenYReserve: tokenYReserve.publicKey,
+            accountX: userTokenX,
+            accountY: userTokenY,
+            payer: payerPubkey,
+            owner: payerPubkey,
+            authority: this.programAuthority,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            rent: SYSVAR_RENT_PUBKEY,
+            systemProgram: SystemProgram.programId
+          }
+        }
+      )
+    )
+
+    return {
+      transaction,
+      signers: [bitmapKeypair, tokenXReserve, tokenYReserve]
+    }
+  }
+
+  async createPoolWithPosition(createPool: InitPoolAndPosition, signer: Keypair) {
+    const { transaction, signers } = await this.createPoolWithPositionTx(createPool, signer)
+
+    await signAndSend(transaction, [signer, ...signers], this.connection)
+  }
+
   async swapInstruction(swap: Swap) {
     const {
       pair,
@@ -934,13 +1153,14 @@ export class Market {
       accountX,
       accountY,
       byAmountIn,
-      referralAccount
+      referralAccount,
+      partialFill
     } = swap
     const owner = swap.owner ?? this.wallet.publicKey
 
     const [pool, tickmap, poolAddress] = await Promise.all([
       this.getPool(pair),
-      this.getTickmap(pair),
+      this.getFullTickmap(pair),
       pair.getAddress(this.program.programId)
     ])
 
@@ -952,7 +1172,8 @@ export class Market {
       pool.tickSpacing,
       referralAccount ? TICK_CROSSES_PER_IX - 1 : TICK_CROSSES_PER_IX,
       Infinity,
-      xToY ? 'down' : 'up'
+      xToY ? 'down' : 'up',
+      tickmap.tickLimit
     )
 
     const indexesInReverse = findClosestTicks(
@@ -961,7 +1182,8 @@ export class Market {
       pool.tickSpacing,
       1,
       Infinity,
-      xToY ? 'up' : 'down'
+      xToY ? 'up' : 'down',
+      tickmap.tickLimit
     )
 
     const remainingAccounts = await Promise.all(
@@ -977,13 +1199,18 @@ export class Market {
 
     // trunk-ignore(eslint)
     const ra: Array<{ pubkey: PublicKey; isWritable: boolean; isSigner: boolean }> =
-      remainingAccounts.map(pubkey => {
-        return { pubkey, isWritable: true, isSigner: false }
-      })
+      remainingAccounts
+        .map(pubkey => {
+          return { pubkey, isWritable: true, isSigner: false }
+        })
+        .concat(tickmapExtensionAccounts(pool))
 
     const tx: Transaction = new Transaction()
 
-    const swapIx = this.program.instruction.swap(xToY, amount, byAmountIn, priceLimit, {
+    const swapMethod = partialFill
+      ? this.program.instruction.swapWithPartialFill
+      : this.program.instruction.swap
+    const swapIx = swapMethod(xToY, amount, byAmountIn, priceLimit, {
       remainingAccounts: ra,
       accounts: {
         state: this.stateAddress,
@@ -1014,6 +1241,19 @@ export class Market {
     return await signAndSend(tx, [signer], this.connection)
   }
 
+  // part of the amount left unfilled by a swap sent with partialFill, read from its return data
+  async getUnfilledAmount(signature: string) {
+    const tx = await this.connection.getTransaction(signature, {
+      commitment: 'confirmed',
+      maxSupportedTransactionVersion: 0
+    })
+    const returnData = tx?.meta?.returnData
+    if (!returnData || returnData.programId !== this.program.programId.toBase58()) {
+      throw new Error('Swap did not return the unfilled amount')
+    }
+    return new BN(Buffer.from(returnData.data[0], 'base64'), 'le')
+  }
+
   async getReserveBalances(pair: Pair, tokenX: Token, tokenY: Token) {
     const state = await this.getPool(pair)
 
@@ -1028,8 +1268,10 @@ export class Market {
   async claimFeeInstruction(claimFee: ClaimFee) {
     const { pair, userTokenX, userTokenY, index } = claimFee
     const owner = claimFee.owner ?? this.wallet.publicKey
+    const signer = claimFee.signer ?? owner
 
     const state = await this.getPool(pair)
+    const { positionListAddress } = await this.getPositionListAddress(owner)
     const { positionAddress } = await this.getPositionAddress(owner, index)
     const position = await this.getPosition(owner, index)
     const { tickAddress: lowerTickAddress } = await this.getTickAddress(
@@ -1046,6 +1288,7 @@ export class Market {
       position.lowerTickIndex,
       position.upperTickIndex,
       {
+        remainingAccounts: operatorAccounts(owner, signer, positionListAddress),
         accounts: {
           state: this.stateAddress,
           pool: await pair.getAddress(this.program.programId),
@@ -1077,6 +1320,217 @@ export class Market {
     await signAndSend(tx, [signer], this.connection)
   }
 
+  async claimFeesBatchInstruction(claimFeesBatch: ClaimFeesBatch) {
+    const { pair, userTokenX, userTokenY, indexes } = claimFeesBatch
+    const owner = claimFeesBatch.owner ?? this.wallet.publicKey
+    const signer = claimFeesBatch.signer ?? owner
+
+    const pool = await this.getPool(pair)
+    const { positionListAddress } = await this.getPositionListAddress(owner)
+
+    // each position is followed by its lower and upper tick
+    const positionAccounts = await Promise.all(
+      indexes.map(async index => {
+        const { positionAddress } = await this.getPositionAddress(owner, index)
+        const position = await this.getPosition(owner, index)
+        const { tickAddress: lowerTickAddress } = await this.getTickAddress(
+          pair,
+          position.lowerTickIndex
+        )
+        const { tickAddress: upperTickAddress } = await this.getTickAddress(
+          pair,
+          position.upperTickIndex
+        )
+        return [positionAddress, lowerTickAddress, upperTickAddress].map(pubkey => ({
+          pubkey,
+          isWritable: true,
+          isSigner: false
+        }))
+      })
+    )
+
+    return this.program.instruction.claimFeesBatch({
+      remainingAccounts: ([] as AccountMeta[]).concat(...positionAccounts),
+      accounts: {
+        state: this.stateAddress,
+        pool: await pair.getAddress(this.program.programId),
+        positionList: positionListAddress,
+        owner,
+        signer,
+        tokenX: pair.tokenX,
+        tokenY: pair.tokenY,
+        accountX: userTokenX,
+        accountY: userTokenY,
+        reserveX: pool.tokenXReserve,
+        reserveY: pool.tokenYReserve,
+        programAuthority: this.programAuthority,
+        tokenProgram: TOKEN_PROGRAM_ID
+      }
+    })
+  }
+
+  async claimFeesBatchTransaction(claimFeesBatch: ClaimFeesBatch) {
+    const ix = await this.claimFeesBatchInstruction(claimFeesBatch)
+    return new Transaction().add(ix)
+  }
+
+  async claimFeesBatch(claimFeesBatch: ClaimFeesBatch, signer: Keypair) {
+    const tx = await this.claimFeesBatchTransaction(claimFeesBatch)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  async compoundFeesInstruction(compoundFees: CompoundFees) {
+    const { pair, index } = compoundFees
+    const owner = compoundFees.owner ?? this.wallet.publicKey
+    const signer = compoundFees.signer ?? owner
+
+    const { positionListAddress } = await this.getPositionListAddress(owner)
+    const { positionAddress } = await this.getPositionAddress(owner, index)
+    const position = await this.getPosition(owner, index)
+    const { tickAddress: lowerTickAddress } = await this.getTickAddress(
+      pair,
+      position.lowerTickIndex
+    )
+    const { tickAddress: upperTickAddress } = await this.getTickAddress(
+      pair,
+      position.upperTickIndex
+    )
+
+    return this.program.instruction.compoundFees(
+      index,
+      position.lowerTickIndex,
+      position.upperTickIndex,
+      {
+        remainingAccounts: operatorAccounts(owner, signer, positionListAddress),
+        accounts: {
+          pool: await pair.getAddress(this.program.programId),
+          position: positionAddress,
+          lowerTick: lowerTickAddress,
+          upperTick: upperTickAddress,
+          owner,
+          tokenX: pair.tokenX,
+          tokenY: pair.tokenY
+        }
+      }
+    )
+  }
+
+  async compoundFeesTransaction(compoundFees: CompoundFees) {
+    const ix = await this.compoundFeesInstruction(compoundFees)
+    return new Transaction().add(ix)
+  }
+
+  async compoundFees(compoundFees: CompoundFees, signer: Keypair) {
+    const tx = await this.compoundFeesTransaction(compoundFees)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  async initializeRewardTx(initializeReward: InitializeReward) {
+    const { pair, rewardIndex, rewardMint, adminAccount, emissionsPerSecond, endTimestamp } =
+      initializeReward
+    const admin = initializeReward.admin ?? this.wallet.publicKey
+    const rewardVault = Keypair.generate()
+    const poolAddress = await pair.getAddress(this.program.programId)
+
+    // restarted reward moves what is left in the previous vault to the new one
+    const previous = (await this.getPool(pair)).rewards[rewardIndex]
+    const remainingAccounts = previous.mint.equals(PublicKey.default)
+      ? []
+      : [{ pubkey: previous.vault, isWritable: true, isSigner: false }]
+
+    const initializeIx = this.program.instruction.initializeReward(
+      rewardIndex,
+      emissionsPerSecond,
+      endTimestamp,
+      {
+        remainingAccounts,
+        accounts: {
+          state: this.stateAddress,
+          pool: poolAddress,
+          tokenX: pair.tokenX,
+          tokenY: pair.tokenY,
+          rewardMint,
+          rewardVault: rewardVault.publicKey,
+          adminAccount,
+          admin,
+          authority: this.programAuthority,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId
+        }
+      }
+    )
+
+    return {
+      transaction: new Transaction().add(initializeIx),
+      signers: [rewardVault]
+    }
+  }
+
+  // Admin function
+  async initializeReward(initializeReward: InitializeReward, signer: Keypair) {
+    const { transaction, signers } = await this.initializeRewardTx(initializeReward)
+
+    await signAndSend(transaction, [signer, ...signers], this.connection)
+  }
+
+  async claimRewardsInstruction(claimRewards: ClaimRewards) {
+    const { pair, index, rewardIndex, accountReward } = claimRewards
+    const owner = claimRewards.owner ?? this.wallet.publicKey
+    const signer = claimRewards.signer ?? owner
+
+    const pool = await this.getPool(pair)
+    const { positionListAddress } = await this.getPositionListAddress(owner)
+    const { positionAddress } = await this.getPositionAddress(owner, index)
+    const position = await this.getPosition(owner, index)
+    const { tickAddress: lowerTickAddress } = await this.getTickAddress(
+      pair,
+      position.lowerTickIndex
+    )
+    const { tickAddress: upperTickAddress } = await this.getTickAddress(
+      pair,
+      position.upperTickIndex
+    )
+
+    return this.program.instruction.claimRewards(
+      index,
+      position.lowerTickIndex,
+      position.upperTickIndex,
+      rewardIndex,
+      {
+        accounts: {
+          state: this.stateAddress,
+          pool: await pair.getAddress(this.program.programId),
+          position: positionAddress,
+          positionList: positionListAddress,
+          lowerTick: lowerTickAddress,
+          upperTick: upperTickAddress,
+          owner,
+          signer,
+          tokenX: pair.tokenX,
+          tokenY: pair.tokenY,
+          accountReward,
+          rewardVault: pool.rewards[rewardIndex].vault,
+          programAuthority: this.programAuthority,
+          tokenProgram: TOKEN_PROGRAM_ID
+        }
+      }
+    )
+  }
+
+  async claimRewardsTransaction(claimRewards: ClaimRewards) {
+    const ix = await this.claimRewardsInstruction(claimRewards)
+    return new Transaction().add(ix)
+  }
+
+  async claimRewards(claimRewards: ClaimRewards, signer: Keypair) {
+    const tx = await this.claimRewardsTransaction(claimRewards)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
   async withdrawProtocolFeeInstruction(withdrawProtocolFee: WithdrawProtocolFee) {
     const { pair, accountX, accountY } = withdrawProtocolFee
     const admin = withdrawProtocolFee.admin ?? this.wallet.publicKey
@@ -1115,6 +1569,7 @@ export class Market {
   async removePositionInstruction(removePosition: RemovePosition): Promise<TransactionInstruction> {
     const { pair, index, userTokenX, userTokenY } = removePosition
     const owner = removePosition.owner ?? this.wallet.publicKey
+    const signer = removePosition.signer ?? owner
 
     const positionList = await this.getPositionList(owner)
     const { positionListAddress } = await this.getPositionListAddress(owner)
@@ -1141,6 +1596,7 @@ export class Market {
       position.lowerTickIndex,
       position.upperTickIndex,
       {
+        remainingAccounts: [...tickmapExtensionAccounts(state), ...operatorAccounts(owner, signer)],
         accounts: {
           state: this.stateAddress,
           owner: owner,
@@ -1285,6 +1741,27 @@ export class Market {
     })
   }
 
+  async createTickmapExtension({ pair, payer }: CreateTickmapExtension) {
+    const extensionKeypair = Keypair.generate()
+    const poolAddress = await pair.getAddress(this.program.programId)
+
+    return await this.program.rpc.createTickmapExtension({
+      accounts: {
+        pool: poolAddress,
+        tickmapExtension: extensionKeypair.publicKey,
+        tokenX: pair.tokenX,
+        tokenY: pair.tokenY,
+        payer: payer.publicKey,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      },
+      signers: [payer, extensionKeypair],
+      instructions: [
+        await this.program.account.tickmapExtension.createInstruction(extensionKeypair)
+      ]
+    })
+  }
+
   async getOracle(pair: Pair) {
     const pool = await this.getPool(pair)
     return await this.program.account.oracle.fetch(pool.oracleAddress)
@@ -1320,6 +1797,220 @@ export class Market {
     await signAndSend(tx, [signer], this.connection)
   }
 
+  async changeDefaultProtocolFeeInstruction(changeDefaultProtocolFee: ChangeDefaultProtocolFee) {
+    let { feeTier, protocolFee, admin, pairs } = changeDefaultProtocolFee
+    admin = admin ?? this.wallet.publicKey
+
+    const { address: stateAddress } = await this.getStateAddress()
+    const { address: feeTierAddress } = await this.getFeeTierAddress(feeTier)
+    const pools = await Promise.all(
+      (pairs ?? []).map(async pair => ({
+        pubkey: await pair.getAddress(this.program.programId),
+        isSigner: false,
+        isWritable: true
+      }))
+    )
+
+    return this.program.instruction.changeDefaultProtocolFee(protocolFee, {
+      accounts: {
+        state: stateAddress,
+        feeTier: feeTierAddress,
+        admin
+      },
+      remainingAccounts: pools
+    })
+  }
+
+  async changeDefaultProtocolFeeTransaction(changeDefaultProtocolFee: ChangeDefaultProtocolFee) {
+    const ix = await this.changeDefaultProtocolFeeInstruction(changeDefaultProtocolFee)
+    return new Transaction().add(ix)
+  }
+
+  // Admin function
+  async changeDefaultProtocolFee(
+    changeDefaultProtocolFee: ChangeDefaultProtocolFee,
+    signer: Keypair
+  ) {
+    const tx = await this.changeDefaultProtocolFeeTransaction(changeDefaultProtocolFee)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  async proposeInstruction(propose: Propose) {
+    let { action, target, value, tickSpacing, feeReceiver, proposer } = propose
+    proposer = proposer ?? this.wallet.publicKey
+
+    const { address: stateAddress } = await this.getStateAddress()
+    const { address: proposalAddress } = await this.getProposalAddress(target, action)
+
+    return this.program.instruction.propose(action, value, tickSpacing ?? 0, {
+      accounts: {
+        state: stateAddress,
+        proposal: proposalAddress,
+        target,
+        feeReceiver: feeReceiver ?? DEFAULT_PUBLIC_KEY,
+        proposer,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
+  async proposeTransaction(propose: Propose) {
+    const ix = await this.proposeInstruction(propose)
+    return new Transaction().add(ix)
+  }
+
+  async propose(propose: Propose, signer: Keypair) {
+    const tx = await this.proposeTransaction(propose)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  async executeProposalInstruction({ target, action, pairs }: ExecuteProposal) {
+    const { address: stateAddress } = await this.getStateAddress()
+    const { address: proposalAddress } = await this.getProposalAddress(target, action)
+    const { proposer } = await this.getProposal(target, action)
+    const pools = await Promise.all(
+      (pairs ?? []).map(async pair => ({
+        pubkey: await pair.getAddress(this.program.programId),
+        isSigner: false,
+        isWritable: true
+      }))
+    )
+
+    return this.program.instruction.executeProposal({
+      accounts: {
+        state: stateAddress,
+        proposal: proposalAddress,
+        target,
+        proposer
+      },
+      remainingAccounts: pools
+    })
+  }
+
+  async executeProposalTransaction(executeProposal: ExecuteProposal) {
+    const ix = await this.executeProposalInstruction(executeProposal)
+    return new Transaction().add(ix)
+  }
+
+  async executeProposal(executeProposal: ExecuteProposal, signer: Keypair) {
+    const tx = await this.executeProposalTransaction(executeProposal)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  async executeCreateFeeTierInstruction({ feeTier, payer }: ExecuteCreateFeeTier) {
+    payer = payer ?? this.wallet.publicKey
+
+    const { address: stateAddress } = await this.getStateAddress()
+    const { address: feeTierAddress } = await this.getFeeTierAddress(feeTier)
+    const { address: proposalAddress } = await this.getProposalAddress(
+      feeTierAddress,
+      ProposalAction.CreateFeeTier
+    )
+    const { proposer } = await this.getProposal(feeTierAddress, ProposalAction.CreateFeeTier)
+
+    return this.program.instruction.executeCreateFeeTier({
+      accounts: {
+        state: stateAddress,
+        proposal: proposalAddress,
+        feeTier: feeTierAddress,
+        proposer,
+        payer,
+        rent: SYSVAR_RENT_PUBKEY,
+        systemProgram: SystemProgram.programId
+      }
+    })
+  }
+
+  async executeCreateFeeTierTransaction(executeCreateFeeTier: ExecuteCreateFeeTier) {
+    const ix = await this.executeCreateFeeTierInstruction(executeCreateFeeTier)
+    return new Transaction().add(ix)
+  }
+
+  async executeCreateFeeTier(executeCreateFeeTier: ExecuteCreateFeeTier, signer: Keypair) {
+    const tx = await this.executeCreateFeeTierTransaction(executeCreateFeeTier)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  async cancelProposalInstruction({ target, action, signer }: CancelProposal) {
+    signer = signer ?? this.wallet.publicKey
+
+    const { address: stateAddress } = await this.getStateAddress()
+    const { address: proposalAddress } = await this.getProposalAddress(target, action)
+    const { proposer } = await this.getProposal(target, action)
+
+    return this.program.instruction.cancelProposal({
+      accounts: {
+        state: stateAddress,
+        proposal: proposalAddress,
+        target,
+        proposer,
+        signer
+      }
+    })
+  }
+
+  async cancelProposalTransaction(cancelProposal: CancelProposal) {
+    const ix = await this.cancelProposalInstruction(cancelProposal)
+    return new Transaction().add(ix)
+  }
+
+  async cancelProposal(cancelProposal: CancelProposal, signer: Keypair) {
+    const tx = await this.cancelProposalTransaction(cancelProposal)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
+  // positions come with their ticks, so pages of a pool are verified separately
+  async verifyPoolInstruction({ pair, lowerTick, upperTick, ticks, positions }: VerifyPool) {
+    const pool = await this.getPool(pair)
+    const poolAddress = await pair.getAddress(this.program.programId)
+    const tickAccount = async (index: number) => ({
+      pubkey: (await this.getTickAddress(pair, index)).tickAddress,
+      isSigner: false,
+      isWritable: false
+    })
+
+    const positionAccounts = await Promise.all(
+      positions.map(async ({ address, lowerTickIndex, upperTickIndex }) => [
+        { pubkey: address, isSigner: false, isWritable: false },
+        await tickAccount(lowerTickIndex),
+        await tickAccount(upperTickIndex)
+      ])
+    )
+    const tickAccounts = await Promise.all(
+      [...ticks].sort((a, b) => a - b).map(index => tickAccount(index))
+    )
+
+    return this.program.instruction.verifyPool(lowerTick, upperTick, {
+      accounts: {
+        pool: poolAddress,
+        tickmap: pool.tickmap,
+        tokenXReserve: pool.tokenXReserve,
+        tokenYReserve: pool.tokenYReserve
+      },
+      remainingAccounts: ([] as AccountMeta[])
+        .concat(...positionAccounts)
+        .concat(tickAccounts)
+        .concat(tickmapExtensionAccounts(pool))
+    })
+  }
+
+  async verifyPoolTransaction(verifyPool: VerifyPool) {
+    const ix = await this.verifyPoolInstruction(verifyPool)
+    return new Transaction().add(ix)
+  }
+
+  async verifyPool(verifyPool: VerifyPool, signer: Keypair) {
+    const tx = await this.verifyPoolTransaction(verifyPool)
+
+    await signAndSend(tx, [signer], this.connection)
+  }
+
   async changeFeeReceiverInstruction(changeFeeReceiver: ChangeFeeReceiver) {
     const { pair, feeReceiver } = changeFeeReceiver
     const adminPubkey = changeFeeReceiver.admin ?? this.wallet.publicKey
@@ -1454,12 +2145,16 @@ export interface State {
   nonce: number
   authority: PublicKey
   bump: number
+  version: number
+  governanceDelay: BN
 }
 
 export interface FeeTierStructure {
   fee: Decimal
   tickSpacing: number
   bump: number
+  version: number
+  defaultProtocolFee: Decimal
 }
 
 export interface PoolStructure {
@@ -1485,7 +2180,19 @@ export interface PoolStructure {
   feeReceiver: PublicKey
   oracleAddress: PublicKey
   oracleInitialized: boolean
+  rewards: PoolReward[]
+  rewardsLastTimestamp: BN
   bump: number
+  version: number
+  tickmapExtension: PublicKey
+}
+
+export interface PoolReward {
+  mint: PublicKey
+  vault: PublicKey
+  emissionsPerSecond: BN
+  endTimestamp: BN
+  growthGlobal: FeeGrowth
 }
 
 export interface PoolData {
@@ -1498,6 +2205,10 @@ export interface PoolData {
 export interface Tickmap {
   bitmap: number[]
 }
+export interface TickmapExtension {
+  lower: number[]
+  upper: number[]
+}
 export interface TickPosition {
   byte: number
   bit: number
@@ -1505,6 +2216,12 @@ export interface TickPosition {
 export interface PositionList {
   head: number
   bump: number
+  version: number
+  operator: PublicKey
+}
+export interface SetPositionOperator {
+  operator: PublicKey
+  owner?: PublicKey
 }
 export interface Tick {
   pool: PublicKey
@@ -1516,7 +2233,9 @@ export interface Tick {
   feeGrowthOutsideX: Decimal
   feeGrowthOutsideY: Decimal
   secondsPerLiquidityOutside: Decimal
+  rewardGrowthsOutside: Decimal[]
   bump: number
+  version: number
 }
 
 export interface Position {
@@ -1532,7 +2251,10 @@ export interface Position {
   lastSlot: BN
   tokensOwedX: Decimal
   tokensOwedY: Decimal
+  rewardGrowthsInside: Decimal[]
+  rewardsOwed: Decimal[]
   bump: number
+  version: number
 }
 
 export interface PositionStructure {
@@ -1581,6 +2303,7 @@ export interface InitPosition {
 
 export interface InitPoolAndPosition extends InitPosition {
   initTick?: number
+  initSqrtPrice?: Decimal
 }
 
 export interface ModifyPosition {
@@ -1596,6 +2319,8 @@ export interface CreatePoolTx {
   pair: Pair
   payer?: Keypair
   initTick?: number
+  initSqrtPrice?: Decimal
+  withTickmapExtension?: boolean // needed to start outside of the tickmap at small spacings
 }
 export interface CreatePool extends CreatePoolTx {
   payer: Keypair
@@ -1603,10 +2328,42 @@ export interface CreatePool extends CreatePoolTx {
 export interface ClaimFee {
   pair: Pair
   owner?: PublicKey
+  signer?: PublicKey // operator of the owner, the owner signs if not set
   userTokenX: PublicKey
   userTokenY: PublicKey
   index: number
 }
+export interface ClaimFeesBatch {
+  pair: Pair
+  owner?: PublicKey
+  signer?: PublicKey // operator of the owner, the owner signs if not set
+  userTokenX: PublicKey
+  userTokenY: PublicKey
+  indexes: number[] // positions of the owner in the pair
+}
+export interface CompoundFees {
+  pair: Pair
+  owner?: PublicKey
+  signer?: PublicKey // operator of the owner, the owner signs if not set
+  index: number
+}
+export interface InitializeReward {
+  pair: Pair
+  admin?: PublicKey
+  rewardIndex: number
+  rewardMint: PublicKey
+  adminAccount: PublicKey // source of the whole emission, deposited upfront
+  emissionsPerSecond: BN
+  endTimestamp: BN
+}
+export interface ClaimRewards {
+  pair: Pair
+  owner?: PublicKey
+  signer?: PublicKey // operator of the owner, the owner signs if not set
+  index: number
+  rewardIndex: number
+  accountReward: PublicKey
+}
 export interface Swap {
   pair: Pair
   owner?: PublicKey
@@ -1618,6 +2375,7 @@ export interface Swap {
   accountY: PublicKey
   byAmountIn: boolean
   referralAccount?: PublicKey
+  partialFill?: boolean // stop at the price limit instead of failing, see getUnfilledAmount
 }
 export interface UpdateSecondsPerLiquidity {
   pair: Pair
@@ -1633,6 +2391,67 @@ export interface ChangeProtocolFee {
   admin?: PublicKey
   protocolFee: Decimal
 }
+
+export interface ChangeDefaultProtocolFee {
+  feeTier: FeeTier
+  admin?: PublicKey
+  protocolFee: Decimal
+  pairs?: Pair[]
+}
+
+export enum ProposalAction {
+  ChangeProtocolFee = 0,
+  ChangeFeeReceiver = 1,
+  CreateFeeTier = 2,
+  ChangeDefaultProtocolFee = 3,
+  ChangeGovernanceDelay = 4
+}
+
+export interface ProposalStructure {
+  action: number
+  target: PublicKey
+  proposer: PublicKey
+  value: BN
+  tickSpacing: number
+  feeReceiver: PublicKey
+  executeAfter: BN
+  bump: number
+  version: number
+}
+
+export interface Propose {
+  action: ProposalAction
+  target: PublicKey
+  value: BN
+  tickSpacing?: number
+  feeReceiver?: PublicKey
+  proposer?: PublicKey
+}
+
+export interface ExecuteProposal {
+  target: PublicKey
+  action: ProposalAction
+  pairs?: Pair[]
+}
+
+export interface ExecuteCreateFeeTier {
+  feeTier: FeeTier
+  payer?: PublicKey
+}
+
+export interface CancelProposal {
+  target: PublicKey
+  action: ProposalAction
+  signer?: PublicKey
+}
+
+export interface VerifyPool {
+  pair: Pair
+  lowerTick: number
+  upperTick: number
+  ticks: number[] // all initialized ticks between lower and upper tick
+  positions: PositionWithAddress[]
+}
 export interface CreateFeeTier {
   feeTier: FeeTier
   admin?: PublicKey
@@ -1651,6 +2470,7 @@ export interface WithdrawProtocolFee {
 export interface RemovePosition {
   pair: Pair
   owner?: PublicKey
+  signer?: PublicKey // operator of the owner, the owner signs if not set
   index: number
   userTokenX: PublicKey
   userTokenY: PublicKey
@@ -1666,6 +2486,11 @@ export interface InitializeOracle {
   payer: Keypair
 }
 
+export interface CreateTickmapExtension {
+  pair: Pair
+  payer: Keypair
+}
+
 export interface ChangeFeeReceiver {
   pair: Pair
   admin?: PublicKey
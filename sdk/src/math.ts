@@ -15,6 +15,7 @@ import {
 export const TICK_LIMIT = 44_364
 export const MAX_TICK = 221_818
 export const MIN_TICK = -MAX_TICK
+export const EXTENDED_TICK_LIMIT = MAX_TICK + 1 // tick limit of a tickmap with an extension
 export const TICK_SEARCH_RANGE = 256
 
 export const U64_MAX = new BN('18446744073709551615')
@@ -83,6 +84,35 @@ export const calculatePriceSqrt = (tickIndex: number): Decimal => {
   return { v: price.mul(new BN(10).pow(new BN(PRICE_SCALE - DECIMAL))) }
 }
 
+export const getMaxSqrtPrice = (tickSpacing: number, tickLimit: number = TICK_LIMIT): Decimal => {
+  return calculatePriceSqrt(getMaxTick(tickSpacing, tickLimit))
+}
+
+export const getMinSqrtPrice = (tickSpacing: number, tickLimit: number = TICK_LIMIT): Decimal => {
+  return calculatePriceSqrt(getMinTick(tickSpacing, tickLimit))
+}
+
+// create_pool takes an initial sqrt price, the tick is kept for callers written against the older interface
+export const getInitSqrtPrice = (
+  tickSpacing: number,
+  initTick?: number,
+  initSqrtPrice?: Decimal,
+  tickLimit: number = TICK_LIMIT
+): Decimal => {
+  if (initTick !== undefined && initSqrtPrice !== undefined) {
+    throw new Error('Pass either initTick or initSqrtPrice')
+  }
+  const sqrtPrice = initSqrtPrice ?? calculatePriceSqrt(initTick ?? 0)
+
+  if (
+    sqrtPrice.v.lt(getMinSqrtPrice(tickSpacing, tickLimit).v) ||
+    sqrtPrice.v.gt(getMaxSqrtPrice(tickSpacing, tickLimit).v)
+  ) {
+    throw new Error('Initial sqrt price out of range of the tick spacing')
+  }
+  return sqrtPrice
+}
+
 export const sqrt = (num: BN): BN => {
   if (num.lt(new BN(0))) {
     throw new Error('Sqrt only works on non-negative inputs')
@@ -353,13 +383,14 @@ export const findClosestTicks = (
   tickSpacing: number,
   limit: number,
   maxRange: number = Infinity,
-  oneWay: 'up' | 'down' | undefined = undefined
+  oneWay: 'up' | 'down' | undefined = undefined,
+  tickLimit: number = TICK_LIMIT // EXTENDED_TICK_LIMIT for a bitmap built with extendBitmap
 ) => {
   if (current % tickSpacing !== 0) {
     throw Error("invalid arguments can't find initialized ticks")
   }
 
-  const currentIndex = Math.floor(current / tickSpacing) + TICK_LIMIT
+  const currentIndex = Math.floor(current / tickSpacing) + tickLimit
 
   let above = currentIndex + 1
   let below = currentIndex
@@ -373,7 +404,7 @@ export const findClosestTicks = (
     if (!reachedTop) {
       const valueAbove = ticks[Math.floor(above / 8)] & (1 << above % 8)
       if (valueAbove) found.push(above)
-      reachedTop = above >= 2 * TICK_LIMIT
+      reachedTop = above >= 2 * tickLimit
       above++
     }
     if (!reachedBottom) {
@@ -391,7 +422,7 @@ export const findClosestTicks = (
   // two can be added in the last iteration
   if (found.length > limit) found.pop()
 
-  return found.map(i => (i - TICK_LIMIT) * tickSpacing)
+  return found.map(i => (i - tickLimit) * tickSpacing)
 }
 
 const mulUp = (a: BN, b: BN) => {
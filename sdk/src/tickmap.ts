@@ -1,6 +1,7 @@
 import { BN } from '@project-serum/anchor'
 import { MAX_TICK, TICK_LIMIT, TICK_SEARCH_RANGE } from '.'
-import { Tickmap, TickPosition } from './market'
+import { Tickmap, TickmapExtension, TickPosition } from './market'
+import { EXTENDED_TICK_LIMIT } from './math'
 
 export interface TickmapChange {
   [index: number]: 'added' | 'removed'
@@ -107,6 +108,26 @@ export const tickToPosition = (tick: BN, tickSpacing: BN): TickPosition => {
   return { byte, bit }
 }
 
+// joins the tickmap with its extension into a single bitmap indexed from -EXTENDED_TICK_LIMIT
+export const extendBitmap = (tickmap: Tickmap, extension: TickmapExtension): number[] => {
+  const sideBits = EXTENDED_TICK_LIMIT - TICK_LIMIT
+  const bitmap: number[] = new Array(Math.ceil((2 * EXTENDED_TICK_LIMIT) / 8)).fill(0)
+
+  const copyBits = (source: number[], offset: number, bits: number) => {
+    for (let i = 0; i < bits; i++) {
+      if ((source[i >> 3] & (1 << (i % 8))) !== 0) {
+        const index = offset + i
+        bitmap[index >> 3] |= 1 << (index % 8)
+      }
+    }
+  }
+  copyBits(extension.lower, 0, sideBits)
+  copyBits(tickmap.bitmap, sideBits, 2 * TICK_LIMIT)
+  copyBits(extension.upper, sideBits + 2 * TICK_LIMIT, sideBits)
+
+  return bitmap
+}
+
 export const findTickmapChanges = (
   currentTickmap: number[],
   nextTickmap: number[],
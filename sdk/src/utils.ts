@@ -86,7 +86,9 @@ export enum INVARIANT_ERRORS {
   INVALID_POOL_LIQUIDITY = '0x177c',
   INVALID_POSITION_INDEX = '0x177d',
   POSITION_WITHOUT_LIQUIDITY = '0x177e',
+  UNAUTHORIZED = '0x177f',
   INVALID_POOL_TOKEN_ADDRESSES = '0x1780',
+  INVALID_PROTOCOL_FEE = '0x1784',
   NO_GAIN_SWAP = '0x1785',
   INVALID_TOKEN_ACCOUNT = '0x1786',
   INVALID_ADMIN = '0x1787',
@@ -96,7 +98,23 @@ export enum INVARIANT_ERRORS {
   INVALID_TICKMAP = '0x178b',
   INVALID_TICKMAP_OWNER = '0x178c',
   INVALID_LIST_OWNER = '0x178d',
-  INVALID_TICK_SPACING = '0x178e'
+  INVALID_TICK_SPACING = '0x178e',
+  INVALID_REMAINING_ACCOUNTS = '0x178f',
+  INVALID_SIGNER = '0x1790',
+  INVALID_REWARD_INDEX = '0x1791',
+  REWARD_ALREADY_INITIALIZED = '0x1792',
+  INVALID_ACCOUNT_VERSION = '0x1793',
+  INVALID_ACCOUNT_OWNER = '0x1794',
+  INVALID_INIT_SQRT_PRICE = '0x1795',
+  INVALID_FEE_TIER = '0x1796',
+  TIMELOCK_ACTIVE = '0x1797',
+  PROPOSAL_NOT_READY = '0x1798',
+  INVALID_PROPOSAL = '0x1799',
+  INVALID_TICKMAP_FLIP = '0x179a',
+  MATH_OVERFLOW = '0x179b',
+  INVALID_SQRT_PRICE = '0x179c',
+  INVALID_TICKMAP_EXTENSION = '0x179d',
+  TICKMAP_EXTENSION_ALREADY_CREATED = '0x179e'
 }
 
 export interface SimulateSwapPrice {
@@ -870,15 +888,16 @@ export const bigNumberToBuffer = (n: BN, size: 16 | 32 | 64 | 128 | 256) => {
   return buffer
 }
 
-export const getMaxTick = (tickSpacing: number) => {
+// pass EXTENDED_TICK_LIMIT for pools with a tickmap extension
+export const getMaxTick = (tickSpacing: number, tickLimit: number = TICK_LIMIT) => {
   const limitedByPrice = MAX_TICK - (MAX_TICK % tickSpacing)
-  const limitedByTickmap = TICK_LIMIT * tickSpacing - tickSpacing
+  const limitedByTickmap = tickLimit * tickSpacing - tickSpacing
   return Math.min(limitedByPrice, limitedByTickmap)
 }
 
-export const getMinTick = (tickSpacing: number) => {
+export const getMinTick = (tickSpacing: number, tickLimit: number = TICK_LIMIT) => {
   const limitedByPrice = -MAX_TICK + (MAX_TICK % tickSpacing)
-  const limitedByTickmap = -TICK_LIMIT * tickSpacing
+  const limitedByTickmap = -tickLimit * tickSpacing
   return Math.max(limitedByPrice, limitedByTickmap)
 }
 
@@ -0,0 +1,97 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair } from '@solana/web3.js'
+import { assert } from 'chai'
+import { assertThrowsAsync, createTokensAndPool } from './testUtils'
+import { Market, Pair, Network, sleep, INVARIANT_ERRORS } from '@invariant-labs/sdk'
+import { fromFee } from '@invariant-labs/sdk/src/utils'
+import { ChangeDefaultProtocolFee, Decimal, FeeTier } from '@invariant-labs/sdk/src/market'
+
+describe('change default protocol fee', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  const admin = Keypair.generate()
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10
+  }
+  const otherFeeTier: FeeTier = {
+    fee: fromFee(new BN(3000)),
+    tickSpacing: 10
+  }
+  const protocolFee: Decimal = { v: fromFee(new BN(11000)) }
+  let market: Market
+  let pair: Pair
+  let otherPair: Pair
+
+  const changeDefaultProtocolFeeVars = (pairs?: Pair[]): ChangeDefaultProtocolFee => ({
+    feeTier,
+    protocolFee,
+    admin: admin.publicKey,
+    pairs
+  })
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await connection.requestAirdrop(admin.publicKey, 1e12)
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+    pair = (await createTokensAndPool(market, connection, admin, 0, feeTier)).pair
+    otherPair = (await createTokensAndPool(market, connection, admin, 0, otherFeeTier)).pair
+  })
+
+  it('#changeDefaultProtocolFee() other account', async () => {
+    const user = Keypair.generate()
+    await connection.requestAirdrop(user.publicKey, 1e10)
+    await sleep(500)
+
+    await assertThrowsAsync(
+      market.changeDefaultProtocolFee(
+        { ...changeDefaultProtocolFeeVars(), admin: user.publicKey },
+        user
+      ),
+      INVARIANT_ERRORS.INVALID_ADMIN
+    )
+  })
+
+  it('#changeDefaultProtocolFee() above one', async () => {
+    await assertThrowsAsync(
+      market.changeDefaultProtocolFee(
+        { ...changeDefaultProtocolFeeVars(), protocolFee: { v: fromFee(new BN(100001)) } },
+        admin
+      ),
+      INVARIANT_ERRORS.INVALID_PROTOCOL_FEE
+    )
+  })
+
+  it('#changeDefaultProtocolFee() with a pool of another fee tier', async () => {
+    await assertThrowsAsync(
+      market.changeDefaultProtocolFee(changeDefaultProtocolFeeVars([otherPair]), admin),
+      INVARIANT_ERRORS.INVALID_FEE_TIER
+    )
+  })
+
+  it('#changeDefaultProtocolFee()', async () => {
+    await market.changeDefaultProtocolFee(changeDefaultProtocolFeeVars([pair]), admin)
+
+    const { defaultProtocolFee } = await market.getFeeTier(feeTier)
+    const pool = await market.getPool(pair)
+    const otherPool = await market.getPool(otherPair)
+    assert.ok(defaultProtocolFee.v.eq(protocolFee.v))
+    assert.ok(pool.protocolFee.v.eq(protocolFee.v))
+    assert.ok(!otherPool.protocolFee.v.eq(protocolFee.v))
+  })
+
+  it('#createPool() after the change', async () => {
+    const { pair: newPair } = await createTokensAndPool(market, connection, admin, 0, feeTier)
+
+    const pool = await market.getPool(newPair)
+    assert.ok(pool.protocolFee.v.eq(protocolFee.v))
+  })
+})
@@ -0,0 +1,139 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair, PublicKey } from '@solana/web3.js'
+import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { assert } from 'chai'
+import { assertThrowsAsync, createTokensAndPool, createUserWithTokens } from './testUtils'
+import {
+  Market,
+  Pair,
+  Network,
+  sleep,
+  calculatePriceSqrt,
+  INVARIANT_ERRORS
+} from '@invariant-labs/sdk'
+import { toDecimal } from '@invariant-labs/sdk/src/utils'
+import { ClaimFeesBatch, Swap } from '@invariant-labs/sdk/src/market'
+
+describe('claim fees batch', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  const admin = Keypair.generate()
+  let market: Market
+  let pair: Pair
+  let tokenX: Token
+  let tokenY: Token
+  let owner: Keypair
+  let mintAuthority: Keypair
+  let userAccountX: PublicKey
+  let userAccountY: PublicKey
+
+  const claimFeesBatchVars = (indexes: number[]): ClaimFeesBatch => ({
+    pair,
+    owner: owner.publicKey,
+    userTokenX: userAccountX,
+    userTokenY: userAccountY,
+    indexes
+  })
+
+  const swap = async (xToY: boolean) => {
+    const swapper = await createUserWithTokens(pair, connection, mintAuthority)
+    const swapVars: Swap = {
+      pair,
+      xToY,
+      owner: swapper.owner.publicKey,
+      amount: new BN(1e7),
+      estimatedPriceAfterSwap: (await market.getPool(pair)).sqrtPrice,
+      slippage: toDecimal(5, 2),
+      accountX: swapper.userAccountX,
+      accountY: swapper.userAccountY,
+      byAmountIn: true
+    }
+    await market.swap(swapVars, swapper.owner)
+  }
+
+  const balances = async () => ({
+    x: (await tokenX.getAccountInfo(userAccountX)).amount,
+    y: (await tokenY.getAccountInfo(userAccountY)).amount
+  })
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await connection.requestAirdrop(admin.publicKey, 1e12)
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+
+    const createdPool = await createTokensAndPool(market, connection, admin)
+    pair = createdPool.pair
+    mintAuthority = createdPool.mintAuthority
+    tokenX = new Token(connection, pair.tokenX, TOKEN_PROGRAM_ID, admin)
+    tokenY = new Token(connection, pair.tokenY, TOKEN_PROGRAM_ID, admin)
+    const user = await createUserWithTokens(pair, connection, mintAuthority)
+    owner = user.owner
+    userAccountX = user.userAccountX
+    userAccountY = user.userAccountY
+
+    for (const [lowerTick, upperTick] of [
+      [-100, 100],
+      [-200, 50]
+    ]) {
+      await market.initPosition(
+        {
+          pair,
+          owner: owner.publicKey,
+          userTokenX: userAccountX,
+          userTokenY: userAccountY,
+          lowerTick,
+          upperTick,
+          liquidityDelta: { v: new BN(10).pow(new BN(16)) },
+          knownPrice: calculatePriceSqrt(0),
+          slippage: { v: new BN(0) }
+        },
+        owner
+      )
+    }
+  })
+
+  it('#claimFeesBatch() without positions', async () => {
+    await assertThrowsAsync(
+      market.claimFeesBatch(claimFeesBatchVars([]), owner),
+      INVARIANT_ERRORS.INVALID_REMAINING_ACCOUNTS
+    )
+  })
+
+  it('#claimFeesBatch()', async () => {
+    await swap(true)
+    await swap(false)
+    const before = await balances()
+
+    await market.claimFeesBatch(claimFeesBatchVars([0, 1]), owner)
+
+    const after = await balances()
+    assert.ok(after.x.gt(before.x))
+    assert.ok(after.y.gt(before.y))
+
+    // fees of both positions are collected
+    for (const index of [0, 1]) {
+      const position = await market.getPosition(owner.publicKey, index)
+      assert.ok(position.tokensOwedX.v.lt(new BN(10).pow(new BN(12))))
+      assert.ok(position.tokensOwedY.v.lt(new BN(10).pow(new BN(12))))
+    }
+  })
+
+  it('#claimFeesBatch() by a stranger', async () => {
+    const stranger = Keypair.generate()
+    await connection.requestAirdrop(stranger.publicKey, 1e9)
+    await sleep(500)
+
+    await assertThrowsAsync(
+      market.claimFeesBatch({ ...claimFeesBatchVars([0]), signer: stranger.publicKey }, stranger),
+      INVARIANT_ERRORS.INVALID_SIGNER
+    )
+  })
+})
@@ -0,0 +1,131 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair, PublicKey } from '@solana/web3.js'
+import { assert } from 'chai'
+import { assertThrowsAsync, createTokensAndPool, createUserWithTokens } from './testUtils'
+import {
+  Market,
+  Pair,
+  Network,
+  sleep,
+  calculatePriceSqrt,
+  INVARIANT_ERRORS
+} from '@invariant-labs/sdk'
+import { toDecimal } from '@invariant-labs/sdk/src/utils'
+import { CompoundFees, Swap } from '@invariant-labs/sdk/src/market'
+
+describe('compound fees', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  const admin = Keypair.generate()
+  const operator = Keypair.generate()
+  let market: Market
+  let pair: Pair
+  let owner: Keypair
+  let mintAuthority: Keypair
+
+  const compoundFeesVars = (signer?: PublicKey): CompoundFees => ({
+    pair,
+    owner: owner.publicKey,
+    signer,
+    index: 0
+  })
+
+  const swap = async (xToY: boolean) => {
+    const swapper = await createUserWithTokens(pair, connection, mintAuthority)
+    const swapVars: Swap = {
+      pair,
+      xToY,
+      owner: swapper.owner.publicKey,
+      amount: new BN(1e7),
+      estimatedPriceAfterSwap: (await market.getPool(pair)).sqrtPrice,
+      slippage: toDecimal(5, 2),
+      accountX: swapper.userAccountX,
+      accountY: swapper.userAccountY,
+      byAmountIn: true
+    }
+    await market.swap(swapVars, swapper.owner)
+  }
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await Promise.all([
+      connection.requestAirdrop(admin.publicKey, 1e12),
+      connection.requestAirdrop(operator.publicKey, 1e9)
+    ])
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+
+    const createdPool = await createTokensAndPool(market, connection, admin)
+    pair = createdPool.pair
+    mintAuthority = createdPool.mintAuthority
+    const user = await createUserWithTokens(pair, connection, mintAuthority)
+    owner = user.owner
+
+    await market.initPosition(
+      {
+        pair,
+        owner: owner.publicKey,
+        userTokenX: user.userAccountX,
+        userTokenY: user.userAccountY,
+        lowerTick: -100,
+        upperTick: 100,
+        liquidityDelta: { v: new BN(10).pow(new BN(16)) },
+        knownPrice: calculatePriceSqrt(0),
+        slippage: { v: new BN(0) }
+      },
+      owner
+    )
+  })
+
+  it('#compoundFees() without fees', async () => {
+    await assertThrowsAsync(
+      market.compoundFees(compoundFeesVars(), owner),
+      INVARIANT_ERRORS.ZERO_AMOUNT
+    )
+  })
+
+  it('#compoundFees()', async () => {
+    await swap(true)
+    await swap(false)
+    const positionBefore = await market.getPosition(owner.publicKey, 0)
+    const poolBefore = await market.getPool(pair)
+
+    await market.compoundFees(compoundFeesVars(), owner)
+
+    const positionAfter = await market.getPosition(owner.publicKey, 0)
+    const poolAfter = await market.getPool(pair)
+    const added = positionAfter.liquidity.v.sub(positionBefore.liquidity.v)
+    assert.ok(added.gtn(0))
+    assert.ok(poolAfter.liquidity.v.sub(poolBefore.liquidity.v).eq(added))
+  })
+
+  it('#compoundFees() by a stranger', async () => {
+    await swap(true)
+    await swap(false)
+
+    await assertThrowsAsync(
+      market.compoundFees(compoundFeesVars(operator.publicKey), operator),
+      INVARIANT_ERRORS.INVALID_SIGNER
+    )
+  })
+
+  it('#compoundFees() by operator', async () => {
+    await market.setPositionOperator(
+      { operator: operator.publicKey, owner: owner.publicKey },
+      owner
+    )
+    const positionBefore = await market.getPosition(owner.publicKey, 0)
+
+    await market.compoundFees(compoundFeesVars(operator.publicKey), operator)
+
+    const positionAfter = await market.getPosition(owner.publicKey, 0)
+    assert.ok(positionAfter.liquidity.v.gt(positionBefore.liquidity.v))
+  })
+})
@@ -0,0 +1,135 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { Keypair, PublicKey } from '@solana/web3.js'
+import { assert } from 'chai'
+import { assertThrowsAsync, createToken } from './testUtils'
+import { Market, Pair, Network, calculatePriceSqrt, sleep } from '@invariant-labs/sdk'
+import { FeeTier, InitPoolAndPosition } from '@invariant-labs/sdk/src/market'
+import { fromFee, tou64 } from '@invariant-labs/sdk/src/utils'
+import { isInitialized } from '@invariant-labs/sdk/src/math'
+
+describe('create pool with position', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  // @ts-expect-error
+  const wallet = provider.wallet.payer as Keypair
+  const mintAuthority = Keypair.generate()
+  const admin = Keypair.generate()
+  const owner = Keypair.generate()
+  const feeTier: FeeTier = {
+    fee: fromFee(new BN(600)),
+    tickSpacing: 10
+  }
+  const liquidity = new BN(1000)
+  let market: Market
+
+  const createPairWithAccounts = async () => {
+    const tokens = await Promise.all([
+      createToken(connection, wallet, mintAuthority),
+      createToken(connection, wallet, mintAuthority)
+    ])
+    const pair = new Pair(tokens[0].publicKey, tokens[1].publicKey, feeTier)
+    const tokenX = new Token(connection, pair.tokenX, TOKEN_PROGRAM_ID, wallet)
+    const tokenY = new Token(connection, pair.tokenY, TOKEN_PROGRAM_ID, wallet)
+
+    const [userTokenX, userTokenY] = await Promise.all([
+      tokenX.createAccount(owner.publicKey),
+      tokenY.createAccount(owner.publicKey)
+    ])
+    await Promise.all([
+      tokenX.mintTo(userTokenX, mintAuthority, [], tou64(new BN(1e9))),
+      tokenY.mintTo(userTokenY, mintAuthority, [], tou64(new BN(1e9)))
+    ])
+    return { pair, userTokenX, userTokenY }
+  }
+
+  const createPoolWithPositionVars = (
+    pair: Pair,
+    userTokenX: PublicKey,
+    userTokenY: PublicKey
+  ): InitPoolAndPosition => {
+    const initTick = pair.tickSpacing * 3
+    return {
+      pair,
+      owner: owner.publicKey,
+      userTokenX,
+      userTokenY,
+      lowerTick: pair.tickSpacing * 2,
+      upperTick: pair.tickSpacing * 4,
+      liquidityDelta: { v: liquidity },
+      initTick,
+      knownPrice: calculatePriceSqrt(initTick),
+      slippage: { v: new BN(0) }
+    }
+  }
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await Promise.all([
+      connection.requestAirdrop(mintAuthority.publicKey, 1e9),
+      connection.requestAirdrop(admin.publicKey, 1e9),
+      connection.requestAirdrop(owner.publicKey, 1e10)
+    ])
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+    await market.createFeeTier({ feeTier, admin: admin.publicKey }, admin)
+  })
+
+  it('#createPoolWithPosition() with a new position list', async () => {
+    const { pair, userTokenX, userTokenY } = await createPairWithAccounts()
+    const vars = createPoolWithPositionVars(pair, userTokenX, userTokenY)
+
+    await market.createPoolWithPosition(vars, owner)
+
+    const pool = await market.getPool(pair)
+    const position = await market.getPosition(owner.publicKey, 0)
+    const tickmap = await market.getTickmap(pair)
+    const positionList = await market.getPositionList(owner.publicKey)
+
+    assert.ok(pool.sqrtPrice.v.eq(calculatePriceSqrt(vars.initTick as number).v))
+    assert.ok(pool.liquidity.v.eq(liquidity))
+    assert.ok(position.liquidity.v.eq(liquidity))
+    assert.ok(position.pool.equals(await pair.getAddress(market.program.programId)))
+    assert.equal(positionList.head, 1)
+    assert.isTrue(isInitialized(tickmap, vars.lowerTick, pair.tickSpacing))
+    assert.isTrue(isInitialized(tickmap, vars.upperTick, pair.tickSpacing))
+  })
+
+  it('#createPoolWithPosition() with an existing position list', async () => {
+    const { pair, userTokenX, userTokenY } = await createPairWithAccounts()
+    const vars = createPoolWithPositionVars(pair, userTokenX, userTokenY)
+
+    await market.createPoolWithPosition(vars, owner)
+
+    const position = await market.getPosition(owner.publicKey, 1)
+    const positionList = await market.getPositionList(owner.publicKey)
+    assert.ok(position.liquidity.v.eq(liquidity))
+    assert.ok(position.pool.equals(await pair.getAddress(market.program.programId)))
+    assert.equal(positionList.head, 2)
+  })
+
+  it('#createPoolWithPosition() for an existing pool', async () => {
+    const { pair, userTokenX, userTokenY } = await createPairWithAccounts()
+    const vars = createPoolWithPositionVars(pair, userTokenX, userTokenY)
+    await market.createPoolWithPosition(vars, owner)
+
+    await assertThrowsAsync(market.createPoolWithPosition(vars, owner))
+  })
+
+  it('#createPoolWithPosition() out of slippage', async () => {
+    const { pair, userTokenX, userTokenY } = await createPairWithAccounts()
+    const vars = {
+      ...createPoolWithPositionVars(pair, userTokenX, userTokenY),
+      knownPrice: calculatePriceSqrt(pair.tickSpacing * 5)
+    }
+
+    await assertThrowsAsync(market.createPoolWithPosition(vars, owner))
+  })
+})
@@ -0,0 +1,214 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair, PublicKey } from '@solana/web3.js'
+import { assert } from 'chai'
+import { assertThrowsAsync, createTokensAndPool } from './testUtils'
+import { Market, Pair, Network, sleep, INVARIANT_ERRORS } from '@invariant-labs/sdk'
+import { fromFee } from '@invariant-labs/sdk/src/utils'
+import { FeeTier, ProposalAction } from '@invariant-labs/sdk/src/market'
+
+describe('governance', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  const admin = Keypair.generate()
+  const stranger = Keypair.generate()
+  const feeReceiver = Keypair.generate()
+  const governanceDelay = 2
+  let market: Market
+  let pair: Pair
+
+  const proposalExists = async (target: PublicKey, action: ProposalAction) => {
+    const { address } = await market.getProposalAddress(target, action)
+    return (await connection.getAccountInfo(address)) !== null
+  }
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await Promise.all([
+      connection.requestAirdrop(admin.publicKey, 1e12),
+      connection.requestAirdrop(stranger.publicKey, 1e10),
+      connection.requestAirdrop(feeReceiver.publicKey, 1e10)
+    ])
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+    pair = (await createTokensAndPool(market, connection, admin)).pair
+  })
+
+  it('#propose() governance delay above the limit', async () => {
+    await assertThrowsAsync(
+      market.propose(
+        {
+          action: ProposalAction.ChangeGovernanceDelay,
+          target: market.stateAddress,
+          value: new BN(30 * 24 * 60 * 60 + 1),
+          proposer: admin.publicKey
+        },
+        admin
+      ),
+      INVARIANT_ERRORS.INVALID_PROPOSAL
+    )
+  })
+
+  it('#propose() by a stranger', async () => {
+    await assertThrowsAsync(
+      market.propose(
+        {
+          action: ProposalAction.ChangeGovernanceDelay,
+          target: market.stateAddress,
+          value: new BN(governanceDelay),
+          proposer: stranger.publicKey
+        },
+        stranger
+      ),
+      INVARIANT_ERRORS.UNAUTHORIZED
+    )
+  })
+
+  it('#executeProposal() governance delay', async () => {
+    await market.propose(
+      {
+        action: ProposalAction.ChangeGovernanceDelay,
+        target: market.stateAddress,
+        value: new BN(governanceDelay),
+        proposer: admin.publicKey
+      },
+      admin
+    )
+
+    // without a delay the proposal is ready right away
+    await market.executeProposal(
+      { target: market.stateAddress, action: ProposalAction.ChangeGovernanceDelay },
+      stranger
+    )
+
+    const state = await market.getState()
+    assert.ok(state.governanceDelay.eqn(governanceDelay))
+    assert.isFalse(await proposalExists(market.stateAddress, ProposalAction.ChangeGovernanceDelay))
+  })
+
+  it('#changeFeeReceiver() with an active timelock', async () => {
+    await assertThrowsAsync(
+      market.changeFeeReceiver(
+        { pair, admin: admin.publicKey, feeReceiver: feeReceiver.publicKey },
+        admin
+      ),
+      INVARIANT_ERRORS.TIMELOCK_ACTIVE
+    )
+  })
+
+  it('#executeProposal() fee receiver', async () => {
+    const target = await pair.getAddress(market.program.programId)
+    await market.propose(
+      {
+        action: ProposalAction.ChangeFeeReceiver,
+        target,
+        value: new BN(0),
+        feeReceiver: feeReceiver.publicKey,
+        proposer: admin.publicKey
+      },
+      admin
+    )
+
+    const action = ProposalAction.ChangeFeeReceiver
+    await assertThrowsAsync(
+      market.executeProposal({ target, action }, stranger),
+      INVARIANT_ERRORS.PROPOSAL_NOT_READY
+    )
+
+    await sleep((governanceDelay + 1) * 1000)
+    await market.executeProposal({ target, action }, stranger)
+
+    const pool = await market.getPool(pair)
+    assert.ok(pool.feeReceiver.equals(feeReceiver.publicKey))
+    assert.isFalse(await proposalExists(target, action))
+  })
+
+  it('#cancelProposal()', async () => {
+    const target = await pair.getAddress(market.program.programId)
+    await market.propose(
+      {
+        action: ProposalAction.ChangeFeeReceiver,
+        target,
+        value: new BN(0),
+        feeReceiver: admin.publicKey,
+        proposer: admin.publicKey
+      },
+      admin
+    )
+
+    const action = ProposalAction.ChangeFeeReceiver
+    await assertThrowsAsync(
+      market.cancelProposal({ target, action, signer: stranger.publicKey }, stranger),
+      INVARIANT_ERRORS.INVALID_SIGNER
+    )
+
+    await market.cancelProposal({ target, action, signer: admin.publicKey }, admin)
+    assert.isFalse(await proposalExists(target, action))
+
+    const pool = await market.getPool(pair)
+    assert.ok(pool.feeReceiver.equals(feeReceiver.publicKey))
+  })
+
+  it('#propose() different actions on one target', async () => {
+    const target = await pair.getAddress(market.program.programId)
+    // the fee receiver proposes first, it must not block the admin
+    await market.propose(
+      {
+        action: ProposalAction.ChangeProtocolFee,
+        target,
+        value: new BN(0),
+        proposer: feeReceiver.publicKey
+      },
+      feeReceiver
+    )
+    await market.propose(
+      {
+        action: ProposalAction.ChangeFeeReceiver,
+        target,
+        value: new BN(0),
+        feeReceiver: admin.publicKey,
+        proposer: admin.publicKey
+      },
+      admin
+    )
+    assert.ok(await proposalExists(target, ProposalAction.ChangeProtocolFee))
+    assert.ok(await proposalExists(target, ProposalAction.ChangeFeeReceiver))
+
+    const proposal = await market.getProposal(target, ProposalAction.ChangeProtocolFee)
+    assert.ok(proposal.proposer.equals(feeReceiver.publicKey))
+
+    for (const action of [ProposalAction.ChangeProtocolFee, ProposalAction.ChangeFeeReceiver]) {
+      await market.cancelProposal({ target, action, signer: admin.publicKey }, admin)
+      assert.isFalse(await proposalExists(target, action))
+    }
+  })
+
+  it('#executeCreateFeeTier()', async () => {
+    const feeTier: FeeTier = { fee: fromFee(new BN(2000)), tickSpacing: 20 }
+    const { address: target } = await market.getFeeTierAddress(feeTier)
+    await market.propose(
+      {
+        action: ProposalAction.CreateFeeTier,
+        target,
+        value: feeTier.fee,
+        tickSpacing: feeTier.tickSpacing,
+        proposer: admin.publicKey
+      },
+      admin
+    )
+
+    await sleep((governanceDelay + 1) * 1000)
+    await market.executeCreateFeeTier({ feeTier, payer: stranger.publicKey }, stranger)
+
+    const created = await market.getFeeTier(feeTier)
+    assert.ok(created.fee.v.eq(feeTier.fee))
+    assert.equal(created.tickSpacing, feeTier.tickSpacing)
+    assert.isFalse(await proposalExists(target, ProposalAction.CreateFeeTier))
+  })
+})
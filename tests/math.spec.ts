@@ -25,7 +25,11 @@ import {
   isEnoughAmountToPushPrice,
   calculatePriceImpact,
   calculateMinReceivedTokensByAmountIn,
-  getXfromLiquidity
+  getXfromLiquidity,
+  getInitSqrtPrice,
+  getMaxSqrtPrice,
+  getMinSqrtPrice,
+  EXTENDED_TICK_LIMIT
 } from '@invariant-labs/sdk/src/math'
 import {
   bigNumberToBuffer,
@@ -2256,4 +2260,31 @@ describe('Math', () => {
       assert.equal(token.id, 'usd-coin')
     })
   })
+  describe('test getInitSqrtPrice', () => {
+    it('from tick', async () => {
+      assert.ok(getInitSqrtPrice(10, 20).v.eq(calculatePriceSqrt(20).v))
+      assert.ok(getInitSqrtPrice(10).v.eq(calculatePriceSqrt(0).v))
+    })
+    it('from sqrt price between ticks', async () => {
+      const sqrtPrice = { v: calculatePriceSqrt(20).v.addn(1) }
+      assert.ok(getInitSqrtPrice(10, undefined, sqrtPrice).v.eq(sqrtPrice.v))
+    })
+    it('both tick and sqrt price', async () => {
+      assert.throws(() => getInitSqrtPrice(10, 20, calculatePriceSqrt(20)))
+    })
+    it('out of range of the tick spacing', async () => {
+      assert.throws(() => getInitSqrtPrice(1, undefined, { v: getMaxSqrtPrice(1).v.addn(1) }))
+      assert.throws(() => getInitSqrtPrice(1, undefined, { v: getMinSqrtPrice(1).v.subn(1) }))
+      assert.throws(() => getInitSqrtPrice(1, TICK_LIMIT))
+      getInitSqrtPrice(1, undefined, getMaxSqrtPrice(1))
+      getInitSqrtPrice(1, undefined, getMinSqrtPrice(1))
+    })
+    it('out of the tickmap with an extension', async () => {
+      getInitSqrtPrice(1, TICK_LIMIT, undefined, EXTENDED_TICK_LIMIT)
+      getInitSqrtPrice(1, undefined, calculatePriceSqrt(MAX_TICK), EXTENDED_TICK_LIMIT)
+      getInitSqrtPrice(1, undefined, calculatePriceSqrt(-MAX_TICK), EXTENDED_TICK_LIMIT)
+      assert.ok(getMaxSqrtPrice(1, EXTENDED_TICK_LIMIT).v.eq(calculatePriceSqrt(MAX_TICK).v))
+      assert.ok(getMinSqrtPrice(1, EXTENDED_TICK_LIMIT).v.eq(calculatePriceSqrt(-MAX_TICK).v))
+    })
+  })
 })
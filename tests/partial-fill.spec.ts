@@ -0,0 +1,151 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair, Transaction } from '@solana/web3.js'
+import { Token, TOKEN_PROGRAM_ID } from '@solana/spl-token'
+import { assertThrowsAsync, createPoolWithLiquidity, createUserWithTokens } from './testUtils'
+import {
+  Market,
+  Network,
+  sleep,
+  INVARIANT_ERRORS,
+  calculatePriceSqrt,
+  signAndSend
+} from '@invariant-labs/sdk'
+import { toDecimal } from '@invariant-labs/sdk/src/utils'
+import { Swap } from '@invariant-labs/sdk/src/market'
+import { assert } from 'chai'
+
+describe('partial fill', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  // @ts-expect-error
+  const wallet = provider.wallet.payer as Keypair
+  const admin = Keypair.generate()
+  let market: Market
+  let expectedPrice: BN
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+
+    await connection.requestAirdrop(admin.publicKey, 1e12)
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+
+    // price reached by swapping the whole amount used below
+    const { pair, mintAuthority } = await createPoolWithLiquidity(market, connection, admin)
+    const { owner, userAccountX, userAccountY } = await createUserWithTokens(
+      pair,
+      connection,
+      mintAuthority
+    )
+    const swapVars: Swap = {
+      pair,
+      xToY: false,
+      owner: owner.publicKey,
+      amount: new BN(1e8),
+      estimatedPriceAfterSwap: (await market.getPool(pair)).sqrtPrice,
+      slippage: toDecimal(5, 2),
+      accountX: userAccountX,
+      accountY: userAccountY,
+      byAmountIn: true
+    }
+    await market.swap(swapVars, owner)
+
+    expectedPrice = (await market.getPool(pair)).sqrtPrice.v
+  })
+
+  const prepareSwap = async (priceLimit: BN) => {
+    const { pair, mintAuthority } = await createPoolWithLiquidity(market, connection, wallet)
+    const { owner, userAccountX, userAccountY } = await createUserWithTokens(
+      pair,
+      connection,
+      mintAuthority
+    )
+    const tokenY = new Token(connection, pair.tokenY, TOKEN_PROGRAM_ID, wallet)
+
+    const swapVars: Swap = {
+      pair,
+      xToY: false,
+      owner: owner.publicKey,
+      amount: new BN(1e8),
+      estimatedPriceAfterSwap: { v: priceLimit },
+      slippage: toDecimal(0, 0),
+      accountX: userAccountX,
+      accountY: userAccountY,
+      byAmountIn: true
+    }
+    return { pair, owner, userAccountY, tokenY, swapVars }
+  }
+
+  it('#swap without partial fill fails at the price limit', async () => {
+    const { owner, swapVars } = await prepareSwap(expectedPrice.subn(1))
+
+    await assertThrowsAsync(market.swap(swapVars, owner), INVARIANT_ERRORS.PRICE_LIMIT_REACHED)
+  })
+
+  it('#swap with partial fill stops at the price limit', async () => {
+    const priceLimit = expectedPrice.subn(1)
+    const { pair, owner, userAccountY, tokenY, swapVars } = await prepareSwap(priceLimit)
+    const balanceBefore = (await tokenY.getAccountInfo(userAccountY)).amount
+
+    const signature = await market.swap({ ...swapVars, partialFill: true }, owner)
+
+    const pool = await market.getPool(pair)
+    assert.ok(pool.sqrtPrice.v.eq(priceLimit))
+
+    const unfilled = await market.getUnfilledAmount(signature)
+    assert.ok(unfilled.gtn(0))
+    assert.ok(unfilled.lt(swapVars.amount))
+
+    const balanceAfter = (await tokenY.getAccountInfo(userAccountY)).amount
+    assert.ok(balanceBefore.sub(balanceAfter).eq(swapVars.amount.sub(unfilled)))
+  })
+
+  it('#swap with partial fill keeps the unfilled amount at a limit on a missing tick', async () => {
+    // upper tick of the position is initialized, the limit sits exactly on it
+    const upperTick = 1000
+    const priceLimit = calculatePriceSqrt(upperTick).v
+    const { pair, owner, userAccountY, tokenY, swapVars } = await prepareSwap(priceLimit)
+    const amount = new BN(1e9)
+    const { tickAddress } = await market.getTickAddress(pair, upperTick)
+    const balanceBefore = (await tokenY.getAccountInfo(userAccountY)).amount
+
+    // swap without the tick on the limit in remaining accounts
+    const swapTx = await market.swapTransaction({ ...swapVars, amount, partialFill: true })
+    const tx = new Transaction()
+    swapTx.instructions.forEach(ix => {
+      ix.keys = ix.keys.filter(key => !key.pubkey.equals(tickAddress))
+      tx.add(ix)
+    })
+    const signature = await signAndSend(tx, [owner], connection)
+
+    const pool = await market.getPool(pair)
+    assert.ok(pool.sqrtPrice.v.eq(priceLimit))
+
+    const unfilled = await market.getUnfilledAmount(signature)
+    assert.ok(unfilled.gtn(0))
+    assert.ok(unfilled.lt(amount))
+
+    const balanceAfter = (await tokenY.getAccountInfo(userAccountY)).amount
+    assert.ok(balanceBefore.sub(balanceAfter).eq(amount.sub(unfilled)))
+  })
+
+  it('#swap with partial fill fills the whole amount before the limit', async () => {
+    const { pair, owner, userAccountY, tokenY, swapVars } = await prepareSwap(expectedPrice.addn(1))
+    const balanceBefore = (await tokenY.getAccountInfo(userAccountY)).amount
+
+    const signature = await market.swap({ ...swapVars, partialFill: true }, owner)
+
+    assert.ok((await market.getPool(pair)).sqrtPrice.v.eq(expectedPrice))
+    assert.ok((await market.getUnfilledAmount(signature)).eqn(0))
+
+    const balanceAfter = (await tokenY.getAccountInfo(userAccountY)).amount
+    assert.ok(balanceBefore.sub(balanceAfter).eq(swapVars.amount))
+  })
+})
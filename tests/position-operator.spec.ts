@@ -0,0 +1,144 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair, PublicKey } from '@solana/web3.js'
+import { Token } from '@solana/spl-token'
+import { assert } from 'chai'
+import { assertThrowsAsync, createTokensAndPool, createUserWithTokens } from './testUtils'
+import {
+  Market,
+  Pair,
+  Network,
+  sleep,
+  calculatePriceSqrt,
+  INVARIANT_ERRORS
+} from '@invariant-labs/sdk'
+import { DEFAULT_PUBLIC_KEY, InitPosition, RemovePosition } from '@invariant-labs/sdk/src/market'
+
+describe('position operator', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  const admin = Keypair.generate()
+  const operator = Keypair.generate()
+  let market: Market
+  let pair: Pair
+  let tokenX: Token
+  let owner: Keypair
+  let userAccountX: PublicKey
+  let userAccountY: PublicKey
+
+  const initPosition = async () => {
+    const initPositionVars: InitPosition = {
+      pair,
+      owner: owner.publicKey,
+      userTokenX: userAccountX,
+      userTokenY: userAccountY,
+      lowerTick: -100,
+      upperTick: 100,
+      liquidityDelta: { v: new BN(10).pow(new BN(12)) },
+      knownPrice: calculatePriceSqrt(0),
+      slippage: { v: new BN(0) }
+    }
+    await market.initPosition(initPositionVars, owner)
+  }
+
+  const removePositionVars = (): RemovePosition => ({
+    pair,
+    owner: owner.publicKey,
+    signer: operator.publicKey,
+    index: 0,
+    userTokenX: userAccountX,
+    userTokenY: userAccountY
+  })
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await Promise.all([
+      connection.requestAirdrop(admin.publicKey, 1e12),
+      connection.requestAirdrop(operator.publicKey, 1e9)
+    ])
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+
+    const createdPool = await createTokensAndPool(market, connection, admin)
+    pair = createdPool.pair
+    tokenX = createdPool.tokenX
+    const user = await createUserWithTokens(pair, connection, createdPool.mintAuthority)
+    owner = user.owner
+    userAccountX = user.userAccountX
+    userAccountY = user.userAccountY
+    await initPosition()
+  })
+
+  it('#claimFee() by operator before approval', async () => {
+    await assertThrowsAsync(
+      market.claimFee(
+        {
+          pair,
+          owner: owner.publicKey,
+          signer: operator.publicKey,
+          userTokenX: userAccountX,
+          userTokenY: userAccountY,
+          index: 0
+        },
+        operator
+      ),
+      INVARIANT_ERRORS.INVALID_SIGNER
+    )
+  })
+
+  it('#setPositionOperator()', async () => {
+    await market.setPositionOperator(
+      { operator: operator.publicKey, owner: owner.publicKey },
+      owner
+    )
+
+    const positionList = await market.getPositionList(owner.publicKey)
+    assert.ok(positionList.operator.equals(operator.publicKey))
+  })
+
+  it('#claimFee() by operator', async () => {
+    await market.claimFee(
+      {
+        pair,
+        owner: owner.publicKey,
+        signer: operator.publicKey,
+        userTokenX: userAccountX,
+        userTokenY: userAccountY,
+        index: 0
+      },
+      operator
+    )
+  })
+
+  it('#removePosition() by operator', async () => {
+    const balanceBefore = (await tokenX.getAccountInfo(userAccountX)).amount
+
+    await market.removePosition(removePositionVars(), operator)
+
+    const positionList = await market.getPositionList(owner.publicKey)
+    assert.equal(positionList.head, 0)
+
+    // proceeds go to the owner
+    const balanceAfter = (await tokenX.getAccountInfo(userAccountX)).amount
+    assert.ok(balanceAfter.gt(balanceBefore))
+  })
+
+  it('#removePosition() by revoked operator', async () => {
+    await initPosition()
+    await market.setPositionOperator(
+      { operator: DEFAULT_PUBLIC_KEY, owner: owner.publicKey },
+      owner
+    )
+
+    await assertThrowsAsync(
+      market.removePosition(removePositionVars(), operator),
+      INVARIANT_ERRORS.INVALID_SIGNER
+    )
+  })
+})
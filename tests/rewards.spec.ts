@@ -0,0 +1,179 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair, PublicKey } from '@solana/web3.js'
+import { Token } from '@solana/spl-token'
+import { assert } from 'chai'
+import {
+  assertThrowsAsync,
+  createToken,
+  createTokensAndPool,
+  createUserWithTokens
+} from './testUtils'
+import {
+  Market,
+  Pair,
+  Network,
+  sleep,
+  calculatePriceSqrt,
+  INVARIANT_ERRORS
+} from '@invariant-labs/sdk'
+import { ClaimRewards, InitializeReward, RemovePosition } from '@invariant-labs/sdk/src/market'
+
+describe('rewards', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  const admin = Keypair.generate()
+  const emissionsPerSecond = new BN(1000)
+  let market: Market
+  let pair: Pair
+  let rewardToken: Token
+  let adminRewardAccount: PublicKey
+  let owner: Keypair
+  let ownerRewardAccount: PublicKey
+  let userAccountX: PublicKey
+  let userAccountY: PublicKey
+
+  const initializeRewardVars = (duration: number): InitializeReward => ({
+    pair,
+    admin: admin.publicKey,
+    rewardIndex: 0,
+    rewardMint: rewardToken.publicKey,
+    adminAccount: adminRewardAccount,
+    emissionsPerSecond,
+    endTimestamp: new BN(Math.floor(Date.now() / 1000) + duration)
+  })
+
+  const claimRewardsVars = (): ClaimRewards => ({
+    pair,
+    owner: owner.publicKey,
+    index: 0,
+    rewardIndex: 0,
+    accountReward: ownerRewardAccount
+  })
+
+  const removePositionVars = (): RemovePosition => ({
+    pair,
+    owner: owner.publicKey,
+    index: 0,
+    userTokenX: userAccountX,
+    userTokenY: userAccountY
+  })
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await connection.requestAirdrop(admin.publicKey, 1e12)
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+
+    const createdPool = await createTokensAndPool(market, connection, admin)
+    pair = createdPool.pair
+    const user = await createUserWithTokens(pair, connection, createdPool.mintAuthority)
+    owner = user.owner
+    userAccountX = user.userAccountX
+    userAccountY = user.userAccountY
+
+    rewardToken = await createToken(connection, admin, admin)
+    adminRewardAccount = await rewardToken.createAccount(admin.publicKey)
+    ownerRewardAccount = await rewardToken.createAccount(owner.publicKey)
+    await rewardToken.mintTo(adminRewardAccount, admin, [], 1e9)
+
+    await market.initPosition(
+      {
+        pair,
+        owner: owner.publicKey,
+        userTokenX: userAccountX,
+        userTokenY: userAccountY,
+        lowerTick: -100,
+        upperTick: 100,
+        liquidityDelta: { v: new BN(10).pow(new BN(12)) },
+        knownPrice: calculatePriceSqrt(0),
+        slippage: { v: new BN(0) }
+      },
+      owner
+    )
+  })
+
+  it('#initializeReward()', async () => {
+    const balanceBefore = (await rewardToken.getAccountInfo(adminRewardAccount)).amount
+    const vars = initializeRewardVars(4)
+    await market.initializeReward(vars, admin)
+
+    const { rewards } = await market.getPool(pair)
+    assert.ok(rewards[0].mint.equals(rewardToken.publicKey))
+    assert.ok(rewards[0].emissionsPerSecond.eq(emissionsPerSecond))
+
+    // whole emission is deposited upfront
+    const deposited = balanceBefore.sub(
+      (await rewardToken.getAccountInfo(adminRewardAccount)).amount
+    )
+    const vault = await rewardToken.getAccountInfo(rewards[0].vault)
+    assert.ok(vault.amount.eq(deposited))
+    assert.ok(deposited.gtn(0))
+  })
+
+  it('#initializeReward() while running', async () => {
+    await assertThrowsAsync(
+      market.initializeReward(initializeRewardVars(10), admin),
+      INVARIANT_ERRORS.REWARD_ALREADY_INITIALIZED
+    )
+  })
+
+  it('#claimRewards()', async () => {
+    await sleep(2000)
+    await market.claimRewards(claimRewardsVars(), owner)
+
+    const claimed = (await rewardToken.getAccountInfo(ownerRewardAccount)).amount
+    assert.ok(claimed.gtn(0))
+  })
+
+  it('#initializeReward() after the reward ended', async () => {
+    await sleep(4000)
+    const { rewards: rewardsBefore } = await market.getPool(pair)
+    const leftover = (await rewardToken.getAccountInfo(rewardsBefore[0].vault)).amount
+    const adminBalanceBefore = (await rewardToken.getAccountInfo(adminRewardAccount)).amount
+
+    await market.initializeReward(initializeRewardVars(4), admin)
+
+    // growth is carried over to the restarted reward
+    const { rewards } = await market.getPool(pair)
+    assert.ok(rewards[0].growthGlobal.v.gte(rewardsBefore[0].growthGlobal.v))
+    assert.ok(!rewards[0].vault.equals(rewardsBefore[0].vault))
+
+    // unclaimed rewards of the previous run are moved to the new vault
+    const deposited = adminBalanceBefore.sub(
+      (await rewardToken.getAccountInfo(adminRewardAccount)).amount
+    )
+    const previousVault = await rewardToken.getAccountInfo(rewardsBefore[0].vault)
+    const vault = await rewardToken.getAccountInfo(rewards[0].vault)
+    assert.ok(leftover.gtn(0))
+    assert.ok(previousVault.amount.eqn(0))
+    assert.ok(vault.amount.eq(leftover.add(deposited)))
+  })
+
+  it('#claimRewards() of the previous run from the new vault', async () => {
+    const balanceBefore = (await rewardToken.getAccountInfo(ownerRewardAccount)).amount
+    await market.claimRewards(claimRewardsVars(), owner)
+
+    const balanceAfter = (await rewardToken.getAccountInfo(ownerRewardAccount)).amount
+    assert.ok(balanceAfter.gt(balanceBefore))
+  })
+
+  it('#removePosition() with unclaimed rewards', async () => {
+    await sleep(2000)
+    const rewardBalanceBefore = (await rewardToken.getAccountInfo(ownerRewardAccount)).amount
+
+    // unclaimed rewards are forfeited, liquidity is withdrawn anyway
+    await market.removePosition(removePositionVars(), owner)
+
+    const positionList = await market.getPositionList(owner.publicKey)
+    assert.equal(positionList.head, 0)
+    const rewardBalanceAfter = (await rewardToken.getAccountInfo(ownerRewardAccount)).amount
+    assert.ok(rewardBalanceAfter.eq(rewardBalanceBefore))
+  })
+})
@@ -0,0 +1,147 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair } from '@solana/web3.js'
+import { assert } from 'chai'
+import {
+  assertThrowsAsync,
+  createPoolWithLiquidity,
+  createToken,
+  createUserWithTokens
+} from './testUtils'
+import { Market, Pair, TICK_LIMIT, Network, sleep, calculatePriceSqrt } from '@invariant-labs/sdk'
+import { DEFAULT_PUBLIC_KEY, InitPosition } from '@invariant-labs/sdk/src/market'
+import { EXTENDED_TICK_LIMIT } from '@invariant-labs/sdk/src/math'
+
+describe('tickmap extension', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  // @ts-expect-error
+  const wallet = provider.wallet.payer as Keypair
+  const admin = Keypair.generate()
+  const lowerTick = -TICK_LIMIT - 100
+  const upperTick = -TICK_LIMIT + 100
+  let market: Market
+  let pair: Pair
+  let mintAuthority: Keypair
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await connection.requestAirdrop(admin.publicKey, 1e12)
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+
+    const createdPool = await createPoolWithLiquidity(market, connection, admin)
+    pair = createdPool.pair
+    mintAuthority = createdPool.mintAuthority
+  })
+
+  const initPositionVars = async (): Promise<{ vars: InitPosition; owner: Keypair }> => {
+    const { owner, userAccountX, userAccountY } = await createUserWithTokens(
+      pair,
+      connection,
+      mintAuthority,
+      new BN(10).pow(new BN(14))
+    )
+    const vars: InitPosition = {
+      pair,
+      owner: owner.publicKey,
+      userTokenX: userAccountX,
+      userTokenY: userAccountY,
+      lowerTick,
+      upperTick,
+      liquidityDelta: { v: new BN(10).pow(new BN(12)) },
+      knownPrice: (await market.getPool(pair)).sqrtPrice,
+      slippage: { v: new BN(0) }
+    }
+    return { vars, owner }
+  }
+
+  it('#create()', async () => {
+    const pool = await market.getPool(pair)
+    assert.ok(pool.tickmapExtension.equals(DEFAULT_PUBLIC_KEY))
+    assert.isNull(await market.getTickmapExtension(pair))
+
+    const { bitmap, tickLimit } = await market.getFullTickmap(pair)
+    assert.equal(bitmap.length, TICK_LIMIT / 4)
+    assert.equal(tickLimit, TICK_LIMIT)
+  })
+
+  it('#initPosition() beyond the tickmap without an extension', async () => {
+    const { vars, owner } = await initPositionVars()
+    await assertThrowsAsync(market.initPosition(vars, owner))
+  })
+
+  it('#createTickmapExtension()', async () => {
+    await market.createTickmapExtension({ pair, payer: wallet })
+
+    const pool = await market.getPool(pair)
+    assert.isFalse(pool.tickmapExtension.equals(DEFAULT_PUBLIC_KEY))
+
+    const extension = await market.getTickmapExtension(pair)
+    assert.ok(extension !== null)
+    assert.ok(extension.lower.every(byte => byte === 0))
+    assert.ok(extension.upper.every(byte => byte === 0))
+
+    const { tickLimit } = await market.getFullTickmap(pair)
+    assert.equal(tickLimit, EXTENDED_TICK_LIMIT)
+  })
+
+  it('#createTickmapExtension() again', async () => {
+    await assertThrowsAsync(market.createTickmapExtension({ pair, payer: wallet }))
+  })
+
+  it('#initPosition() across the extension', async () => {
+    const { vars, owner } = await initPositionVars()
+    await market.initPosition(vars, owner)
+
+    const { bitmap } = await market.getFullTickmap(pair)
+    const isSet = (tick: number) => {
+      const index = tick + EXTENDED_TICK_LIMIT
+      return (bitmap[index >> 3] & (1 << index % 8)) !== 0
+    }
+    assert.ok(isSet(lowerTick))
+    assert.ok(isSet(upperTick))
+
+    // only the lower tick lands in the extension
+    const extension = await market.getTickmapExtension(pair)
+    assert.ok(extension !== null)
+    assert.equal(extension.lower.filter(byte => byte !== 0).length, 1)
+    assert.ok(extension.upper.every(byte => byte === 0))
+
+    const tickmap = await market.getTickmap(pair)
+    const index = upperTick + TICK_LIMIT
+    assert.notEqual(tickmap.bitmap[index >> 3] & (1 << index % 8), 0)
+  })
+
+  it('#createPool() outside of the tickmap with an extension', async () => {
+    const [tokenA, tokenB] = await Promise.all([
+      createToken(connection, wallet, mintAuthority),
+      createToken(connection, wallet, mintAuthority)
+    ])
+    const newPair = new Pair(tokenA.publicKey, tokenB.publicKey, pair.feeTier)
+    const initTick = -TICK_LIMIT - 100
+
+    await assertThrowsAsync(market.createPool({ pair: newPair, payer: wallet, initTick }))
+    await market.createPool({ pair: newPair, payer: wallet, initTick, withTickmapExtension: true })
+
+    const pool = await market.getPool(newPair)
+    assert.equal(pool.currentTickIndex, initTick)
+    assert.ok(pool.sqrtPrice.v.eq(calculatePriceSqrt(initTick).v))
+    assert.isFalse(pool.tickmapExtension.equals(DEFAULT_PUBLIC_KEY))
+
+    const extension = await market.getTickmapExtension(newPair)
+    assert.ok(extension !== null)
+    assert.ok(extension.lower.every(byte => byte === 0))
+    assert.ok(extension.upper.every(byte => byte === 0))
+
+    const { tickLimit } = await market.getFullTickmap(newPair)
+    assert.equal(tickLimit, EXTENDED_TICK_LIMIT)
+    await assertThrowsAsync(market.createTickmapExtension({ pair: newPair, payer: wallet }))
+  })
+})
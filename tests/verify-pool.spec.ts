@@ -0,0 +1,140 @@
+import * as anchor from '@project-serum/anchor'
+import { Provider, BN } from '@project-serum/anchor'
+import { Keypair } from '@solana/web3.js'
+import { assert } from 'chai'
+import { createTokensAndPool, createUserWithTokens } from './testUtils'
+import { Market, Pair, Network, sleep, calculatePriceSqrt } from '@invariant-labs/sdk'
+import { toDecimal } from '@invariant-labs/sdk/src/utils'
+import { PositionWithAddress, Swap, VerifyPool } from '@invariant-labs/sdk/src/market'
+
+describe('verify pool', () => {
+  const provider = Provider.local()
+  const connection = provider.connection
+  const admin = Keypair.generate()
+  let market: Market
+  let pair: Pair
+  let ticks: number[]
+  let positions: PositionWithAddress[]
+
+  // report of a page is set as return data of the instruction
+  const verifyPool = async (verifyPoolVars: VerifyPool) => {
+    const tx = await market.verifyPoolTransaction(verifyPoolVars)
+    tx.feePayer = admin.publicKey
+    const { value } = await connection.simulateTransaction(tx, [admin])
+    assert.isNull(value.err)
+
+    const returnData = value.returnData?.data[0] as string
+    return {
+      report: market.program.coder.types.decode('PoolReport', Buffer.from(returnData, 'base64')),
+      logs: value.logs as string[]
+    }
+  }
+
+  before(async () => {
+    market = await Market.build(
+      Network.LOCAL,
+      provider.wallet,
+      connection,
+      anchor.workspace.Invariant.programId
+    )
+    await connection.requestAirdrop(admin.publicKey, 1e12)
+    await sleep(500)
+
+    await market.createState(admin.publicKey, admin)
+    const createdPool = await createTokensAndPool(market, connection, admin)
+    pair = createdPool.pair
+    const user = await createUserWithTokens(pair, connection, createdPool.mintAuthority)
+
+    for (const [lowerTick, upperTick] of [
+      [-100, 100],
+      [-200, 50]
+    ]) {
+      await market.initPosition(
+        {
+          pair,
+          owner: user.owner.publicKey,
+          userTokenX: user.userAccountX,
+          userTokenY: user.userAccountY,
+          lowerTick,
+          upperTick,
+          liquidityDelta: { v: new BN(10).pow(new BN(16)) },
+          knownPrice: calculatePriceSqrt(0),
+          slippage: { v: new BN(0) }
+        },
+        user.owner
+      )
+    }
+
+    const swapVars: Swap = {
+      pair,
+      xToY: true,
+      owner: user.owner.publicKey,
+      amount: new BN(1e7),
+      estimatedPriceAfterSwap: (await market.getPool(pair)).sqrtPrice,
+      slippage: toDecimal(5, 2),
+      accountX: user.userAccountX,
+      accountY: user.userAccountY,
+      byAmountIn: true
+    }
+    await market.swap(swapVars, user.owner)
+
+    ticks = (await market.getAllTicks(pair)).map(({ index }) => index)
+    positions = await market.getPositionsForPool(await pair.getAddress(market.program.programId))
+  })
+
+  it('#verifyPool() in a single page', async () => {
+    const { report, logs } = await verifyPool({
+      pair,
+      lowerTick: -200,
+      upperTick: 100,
+      ticks,
+      positions
+    })
+
+    assert.equal(report.positions, 2)
+    assert.equal(report.ticks, 4)
+    assert.equal(report.initializedTicks, 4)
+    assert.isTrue(report.tickmapValid)
+    assert.ok(report.liquidityInRange.eq(report.poolLiquidity))
+    assert.ok(report.owedX.lte(report.reserveX))
+    assert.ok(report.owedY.lte(report.reserveY))
+    assert.include(logs, 'Program log: INVARIANT: POOL VALID true')
+  })
+
+  it('#verifyPool() in pages', async () => {
+    const whole = await verifyPool({ pair, lowerTick: -200, upperTick: 100, ticks, positions })
+    const first = await verifyPool({
+      pair,
+      lowerTick: -200,
+      upperTick: 0,
+      ticks: ticks.filter(index => index <= 0),
+      positions: positions.slice(0, 1)
+    })
+    const second = await verifyPool({
+      pair,
+      lowerTick: 1,
+      upperTick: 100,
+      ticks: ticks.filter(index => index > 0),
+      positions: positions.slice(1)
+    })
+
+    assert.isTrue(first.report.tickmapValid)
+    assert.isTrue(second.report.tickmapValid)
+    for (const field of ['owedX', 'owedY', 'positionLiquidity', 'tickLiquidityGross']) {
+      assert.ok(first.report[field].add(second.report[field]).eq(whole.report[field]))
+    }
+  })
+
+  it('#verifyPool() with a missing tick', async () => {
+    const { report, logs } = await verifyPool({
+      pair,
+      lowerTick: -200,
+      upperTick: 100,
+      ticks: ticks.slice(1),
+      positions
+    })
+
+    assert.isFalse(report.tickmapValid)
+    assert.include(logs, 'Program log: INVARIANT: POOL VALID false')
+  })
+})